@@ -0,0 +1,339 @@
+//! JSONL batch mode for `prove --inputs` and `verify --manifest`.
+//!
+//! The model is read once; each record then produces one JSON result line,
+//! followed by a summary line. Failed records do not stop the batch, but make
+//! the process exit nonzero.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use verifai_core::model_bin::InputV0;
+use verifai_runtime::{
+    artifact_version, verify_lr_bulk, verify_mlp_bulk, BulkRecord, FreshnessPolicy,
+};
+
+use crate::translog::append_artifact;
+use crate::{
+    artifact_summary, check_verifiable, hex_encode_32, path_string_ref, prove_model, read_artifact,
+    read_file, write_file_atomic, Cli, CliError, JsonOut, LoadedModel, ProveOptions,
+};
+
+const MANIFEST_FILE: &str = "manifest.jsonl";
+/// Manifest records verified per bulk call, which bounds how many artifacts
+/// are held in memory at once.
+const VERIFY_CHUNK: usize = 1024;
+
+#[derive(Debug, Deserialize)]
+struct InputRecordJson {
+    #[serde(default)]
+    id: Option<String>,
+    x: Vec<f64>,
+}
+
+/// One line of `manifest.jsonl`; `output` and `artifact` are relative to the
+/// manifest's directory.
+#[derive(Debug, Serialize, Deserialize)]
+struct ManifestRecordJson {
+    index: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    x: Vec<f64>,
+    output: PathBuf,
+    artifact: PathBuf,
+}
+
+/// Collects JSON lines for `--json-file`, or streams them to stdout.
+struct JsonLines<'a> {
+    cli: &'a Cli,
+    buf: Vec<u8>,
+}
+
+impl<'a> JsonLines<'a> {
+    fn new(cli: &'a Cli) -> Self {
+        Self {
+            cli,
+            buf: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, payload: &JsonOut) -> Result<(), CliError> {
+        let line = serde_json::to_string(payload).map_err(|e| CliError::Json(format!("{e}")))?;
+        if self.cli.json_file.is_some() {
+            self.buf.extend_from_slice(line.as_bytes());
+            self.buf.push(b'\n');
+        } else if !self.cli.quiet {
+            println!("{line}");
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), CliError> {
+        if let Some(path) = self.cli.json_file.as_ref() {
+            write_file_atomic(path, &self.buf)?;
+        }
+        Ok(())
+    }
+}
+
+fn jsonl_lines(path: &PathBuf) -> Result<Vec<String>, CliError> {
+    let bytes = read_file(path)?;
+    let text =
+        String::from_utf8(bytes).map_err(|e| CliError::Json(format!("{}: {e}", path.display())))?;
+    Ok(text
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(str::to_owned)
+        .collect())
+}
+
+pub(crate) fn prove_jsonl(
+    cli: &Cli,
+    loaded_model: &LoadedModel,
+    model_bin: &[u8],
+    opts: &ProveOptions,
+    log_dir: Option<&PathBuf>,
+    inputs: &PathBuf,
+    out_dir: &Path,
+) -> Result<(), CliError> {
+    let lines = jsonl_lines(inputs)?;
+    fs::create_dir_all(out_dir).map_err(|e| CliError::Io(format!("{}: {e}", out_dir.display())))?;
+
+    let mut out = JsonLines::new(cli);
+    let mut manifest = Vec::new();
+    let mut failed = 0usize;
+
+    for (index, line) in lines.iter().enumerate() {
+        let mut id = None;
+        let result = serde_json::from_str::<InputRecordJson>(line)
+            .map_err(|e| CliError::Json(format!("{e}")))
+            .and_then(|record| {
+                id = record.id.clone();
+                let input_bin = InputV0 {
+                    x: record.x.clone(),
+                }
+                .encode_bin();
+                let (output_bin, artifact_bin) =
+                    prove_model(loaded_model, opts, model_bin, &input_bin, &mut ())?;
+                let (trace_root, _, _) = artifact_summary(&artifact_bin)
+                    .map_err(|e| CliError::Runtime(e.to_string()))?;
+
+                let entry = ManifestRecordJson {
+                    index,
+                    id: record.id,
+                    x: record.x,
+                    output: PathBuf::from(format!("{index}.output.bin")),
+                    artifact: PathBuf::from(format!("{index}.artifact.bin")),
+                };
+                let out_output = out_dir.join(&entry.output);
+                let out_artifact = out_dir.join(&entry.artifact);
+                write_file_atomic(&out_output, &output_bin)?;
+                write_file_atomic(&out_artifact, &artifact_bin)?;
+                let log_index = log_dir
+                    .map(|dir| append_artifact(dir, &artifact_bin))
+                    .transpose()?;
+                Ok((
+                    entry,
+                    opts.hash_alg.unwrap_or_default().digest(&output_bin),
+                    trace_root,
+                    out_output,
+                    out_artifact,
+                    log_index,
+                ))
+            });
+
+        let payload = match result {
+            Ok((entry, output_hash, trace_root, out_output, out_artifact, log_index)) => {
+                let line =
+                    serde_json::to_string(&entry).map_err(|e| CliError::Json(format!("{e}")))?;
+                manifest.extend_from_slice(line.as_bytes());
+                manifest.push(b'\n');
+                JsonOut::ProveRecord {
+                    ok: true,
+                    index,
+                    id,
+                    output_hash: Some(hex_encode_32(output_hash)),
+                    trace_root: Some(hex_encode_32(trace_root)),
+                    out_output: Some(path_string_ref(&out_output)),
+                    out_artifact: Some(path_string_ref(&out_artifact)),
+                    log_index,
+                    error: None,
+                }
+            }
+            Err(e) => {
+                failed += 1;
+                JsonOut::ProveRecord {
+                    ok: false,
+                    index,
+                    id,
+                    output_hash: None,
+                    trace_root: None,
+                    out_output: None,
+                    out_artifact: None,
+                    log_index: None,
+                    error: Some(e.to_string()),
+                }
+            }
+        };
+        out.push(&payload)?;
+    }
+
+    let manifest_path = out_dir.join(MANIFEST_FILE);
+    write_file_atomic(&manifest_path, &manifest)?;
+
+    out.push(&JsonOut::ProveBatch {
+        ok: failed == 0,
+        total: lines.len(),
+        succeeded: lines.len() - failed,
+        failed,
+        manifest: path_string_ref(&manifest_path),
+    })?;
+    out.finish()?;
+
+    if failed > 0 {
+        return Err(CliError::Runtime(format!(
+            "{failed} of {} records failed",
+            lines.len()
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) fn verify_jsonl(
+    cli: &Cli,
+    loaded_model: &LoadedModel,
+    model_bin: &[u8],
+    policy: &FreshnessPolicy,
+    manifest: &PathBuf,
+) -> Result<(), CliError> {
+    let lines = jsonl_lines(manifest)?;
+    let base = manifest.parent().unwrap_or_else(|| Path::new("."));
+
+    let mut out = JsonLines::new(cli);
+    let mut failed = 0usize;
+    let started = Instant::now();
+
+    for (chunk_no, chunk) in lines.chunks(VERIFY_CHUNK).enumerate() {
+        let loaded: Vec<(usize, Option<String>, Result<VerifyInputs, CliError>)> = chunk
+            .iter()
+            .enumerate()
+            .map(|(k, line)| {
+                load_verify_record(
+                    base,
+                    chunk_no * VERIFY_CHUNK + k,
+                    line,
+                    loaded_model,
+                    policy,
+                )
+            })
+            .collect();
+
+        // every record that loaded goes through one bulk verification
+        let records: Vec<BulkRecord<'_>> = loaded
+            .iter()
+            .filter_map(|(_, _, r)| r.as_ref().ok())
+            .map(|r| BulkRecord {
+                artifact_bin: &r.artifact_bin,
+                input_bin: &r.input_bin,
+                output_bin: &r.output_bin,
+            })
+            .collect();
+        let verify_bulk = match loaded_model {
+            LoadedModel::Logistic(_) => verify_lr_bulk,
+            LoadedModel::Mlp(_) => verify_mlp_bulk,
+        };
+        let mut verified = verify_bulk(model_bin, &records, policy).into_iter();
+
+        for (index, id, inputs) in loaded {
+            let result = inputs.and_then(|inputs| {
+                verified
+                    .next()
+                    .expect("one bulk result per loaded record")
+                    .map_err(|e| CliError::VerifyFailed(format!("{e:?}")))?;
+                let (trace_root, _, _) = artifact_summary(&inputs.artifact_bin)
+                    .map_err(|e| CliError::VerifyFailed(e.to_string()))?;
+                Ok(trace_root)
+            });
+
+            let payload = match result {
+                Ok(trace_root) => JsonOut::VerifyRecord {
+                    ok: true,
+                    index,
+                    id,
+                    trace_root: Some(hex_encode_32(trace_root)),
+                    error: None,
+                },
+                Err(e) => {
+                    failed += 1;
+                    JsonOut::VerifyRecord {
+                        ok: false,
+                        index,
+                        id,
+                        trace_root: None,
+                        error: Some(e.to_string()),
+                    }
+                }
+            };
+            out.push(&payload)?;
+        }
+    }
+
+    let elapsed = started.elapsed();
+    out.push(&JsonOut::VerifyBatch {
+        ok: failed == 0,
+        total: lines.len(),
+        succeeded: lines.len() - failed,
+        failed,
+        manifest: path_string_ref(manifest),
+        elapsed_ms: elapsed.as_millis() as u64,
+        records_per_sec: lines.len() as f64 / elapsed.as_secs_f64().max(1e-9),
+    })?;
+    out.finish()?;
+
+    if failed > 0 {
+        return Err(CliError::VerifyFailed(format!(
+            "{failed} of {} records failed",
+            lines.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Files and canonical input of one manifest record.
+struct VerifyInputs {
+    artifact_bin: Vec<u8>,
+    input_bin: Vec<u8>,
+    output_bin: Vec<u8>,
+}
+
+/// Parses manifest line `line_no` and reads its files. Returns the record's
+/// index and id (the line number and none until parsed) with the inputs.
+fn load_verify_record(
+    base: &Path,
+    line_no: usize,
+    line: &str,
+    loaded_model: &LoadedModel,
+    policy: &FreshnessPolicy,
+) -> (usize, Option<String>, Result<VerifyInputs, CliError>) {
+    let record = match serde_json::from_str::<ManifestRecordJson>(line) {
+        Ok(record) => record,
+        Err(e) => return (line_no, None, Err(CliError::Json(format!("{e}")))),
+    };
+    let inputs = (|| {
+        let artifact_bin = read_artifact(&base.join(&record.artifact))?;
+        let version = artifact_version(&artifact_bin).unwrap_or(0);
+        check_verifiable(version, loaded_model, policy, false, false)?;
+        if !matches!(version, 0 | 1 | 3..=6) {
+            return Err(CliError::VerifyFailed(format!(
+                "--manifest does not take v{version} artifacts"
+            )));
+        }
+        Ok(VerifyInputs {
+            artifact_bin,
+            input_bin: InputV0 { x: record.x }.encode_bin(),
+            output_bin: read_file(&base.join(&record.output))?,
+        })
+    })();
+    (record.index, record.id, inputs)
+}
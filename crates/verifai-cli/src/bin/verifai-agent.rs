@@ -0,0 +1,115 @@
+//! `verifai-agent`: keeps an artifact signing key in its own process and signs
+//! for `verifai prove --agent-socket` / `verifai cosign --agent-socket` over a
+//! Unix socket that only the agent's user can connect to.
+
+use clap::Parser;
+use std::path::PathBuf;
+use std::process;
+
+#[derive(Parser)]
+#[command(name = "verifai-agent")]
+#[command(version)]
+#[command(about = "Signing agent for verifai artifacts")]
+struct Args {
+    /// Unix socket path to listen on (must not exist yet)
+    #[arg(long)]
+    socket: PathBuf,
+
+    /// File holding the secret key as 64 hex chars (32 bytes)
+    #[arg(long)]
+    key_file: PathBuf,
+
+    /// Algorithm of the key in --key-file
+    #[arg(long, value_parser = ["ed25519", "p256", "secp256k1"], default_value = "ed25519")]
+    sig_alg: String,
+}
+
+fn main() {
+    let args = Args::parse();
+    // `run` only returns once the agent can no longer serve
+    if let Err((message, code)) = run(&args) {
+        eprintln!("error: {message}");
+        process::exit(code);
+    }
+}
+
+#[cfg(unix)]
+fn run(args: &Args) -> Result<(), (String, i32)> {
+    use std::fs;
+    use verifai_core::sig_alg::{AlgSigningKey, SigAlg};
+    use verifai_runtime::SigningAgent;
+
+    let io_failed = |path: &PathBuf| {
+        let path = path.display().to_string();
+        move |e: std::io::Error| (format!("io: {path}: {e}"), 2)
+    };
+    let key_hex = fs::read_to_string(&args.key_file).map_err(io_failed(&args.key_file))?;
+    let key = parse_hex_32(key_hex.trim()).ok_or_else(|| {
+        (
+            "hex: key file must hold 64 hex chars (32 bytes)".to_string(),
+            4,
+        )
+    })?;
+    let alg = SigAlg::from_name(&args.sig_alg).expect("clap checks --sig-alg");
+    let key = AlgSigningKey::new(alg, key).map_err(|_| {
+        (
+            "hex: key file does not hold a valid secret key for --sig-alg".to_string(),
+            4,
+        )
+    })?;
+    let agent = SigningAgent::with_key(key);
+
+    let listener = bind_private(&args.socket).map_err(io_failed(&args.socket))?;
+    println!(
+        "{}",
+        agent
+            .public_key()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>()
+    );
+
+    agent.serve(&listener).map_err(io_failed(&args.socket))
+}
+
+/// Binds `socket` so that no other user can connect, not even briefly: the
+/// socket is bound inside a fresh `0700` directory, made `0600` there and
+/// only then linked to `socket`, which must not exist yet.
+#[cfg(unix)]
+fn bind_private(socket: &std::path::Path) -> std::io::Result<std::os::unix::net::UnixListener> {
+    use std::fs::{self, DirBuilder};
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    use std::os::unix::net::UnixListener;
+
+    let mut staging = socket.as_os_str().to_owned();
+    staging.push(format!(".{}.tmp", process::id()));
+    let staging = PathBuf::from(staging);
+    DirBuilder::new().mode(0o700).create(&staging)?;
+    let staged = staging.join("agent.sock");
+    let bound = UnixListener::bind(&staged).and_then(|listener| {
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o600))?;
+        // unlike a rename, linking refuses to replace an existing path
+        fs::hard_link(&staged, socket)?;
+        Ok(listener)
+    });
+    let _ = fs::remove_file(&staged);
+    let _ = fs::remove_dir(&staging);
+    bound
+}
+
+#[cfg(not(unix))]
+fn run(_args: &Args) -> Result<(), (String, i32)> {
+    Err(("verifai-agent needs Unix domain sockets".into(), 5))
+}
+
+#[cfg(unix)]
+fn parse_hex_32(s: &str) -> Option<[u8; 32]> {
+    if s.len() != 64 || !s.is_ascii() {
+        return None;
+    }
+    let mut out = [0u8; 32];
+    for (i, b) in out.iter_mut().enumerate() {
+        *b = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).ok()?;
+    }
+    Some(out)
+}
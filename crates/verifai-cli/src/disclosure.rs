@@ -0,0 +1,217 @@
+//! Selective trace disclosure: the `disclose` and `verify-disclosure`
+//! commands.
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+use verifai_core::disclosure::TraceDisclosureV0;
+use verifai_core::event_bin::{ActivationKind, TraceEventV0};
+use verifai_runtime::{
+    artifact_version, disclose_lr_trace, disclose_mlp_trace, verify_trace_disclosure,
+};
+
+use crate::{
+    emit_success, path_string_ref, read_artifact, read_file, read_input_json, read_model,
+    write_file_atomic, Cli, CliError, JsonOut, LoadedModel,
+};
+
+/// One disclosed event as `verify-disclosure --print-json` reports it, and
+/// one line of a `prove --trace-format jsonl` trace.
+#[derive(Serialize)]
+pub(crate) struct DisclosedEventJson {
+    index: u32,
+    kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    op_id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    activation: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    z: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    input: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    y: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    feature: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<f64>,
+}
+
+/// `--kind` name of an event.
+fn event_kind(event: &TraceEventV0) -> &'static str {
+    match event {
+        TraceEventV0::OpLinear { .. } => "linear",
+        TraceEventV0::OpActivation { .. } => "activation",
+        TraceEventV0::OpOutput { .. } => "output",
+        TraceEventV0::OpFeature { .. } => "feature",
+        TraceEventV0::OpLayer { .. } => "layer",
+        TraceEventV0::OpMac { .. } => "mac",
+    }
+}
+
+pub(crate) fn event_json(index: u32, event: &TraceEventV0) -> DisclosedEventJson {
+    let mut json = DisclosedEventJson {
+        index,
+        kind: event_kind(event),
+        op_id: None,
+        activation: None,
+        z: None,
+        input: None,
+        output: None,
+        y: None,
+        feature: None,
+        value: None,
+    };
+    match *event {
+        TraceEventV0::OpLinear { op_id, z } => {
+            json.op_id = Some(op_id);
+            json.z = Some(z);
+        }
+        TraceEventV0::OpActivation {
+            op_id,
+            kind,
+            input,
+            output,
+        } => {
+            json.op_id = Some(op_id);
+            json.activation = Some(match kind {
+                ActivationKind::Sigmoid => "sigmoid",
+                ActivationKind::Relu => "relu",
+            });
+            json.input = Some(input);
+            json.output = Some(output);
+        }
+        TraceEventV0::OpOutput { y } => json.y = Some(y),
+        TraceEventV0::OpFeature { index, value } => {
+            json.feature = Some(index);
+            json.value = Some(value);
+        }
+        // only v9 traces carry these, and no command takes a v9 trace
+        TraceEventV0::OpLayer { .. } | TraceEventV0::OpMac { .. } => {}
+    }
+    json
+}
+
+pub(crate) fn describe(event: &DisclosedEventJson) -> String {
+    let mut line = event.kind.to_string();
+    let fields = [
+        ("op_id", event.op_id.map(|v| v.to_string())),
+        ("activation", event.activation.map(str::to_string)),
+        ("z", event.z.map(|v| v.to_string())),
+        ("input", event.input.map(|v| v.to_string())),
+        ("output", event.output.map(|v| v.to_string())),
+        ("y", event.y.map(|v| v.to_string())),
+        ("feature", event.feature.map(|v| v.to_string())),
+        ("value", event.value.map(|v| v.to_string())),
+    ];
+    for (name, value) in fields {
+        if let Some(value) = value {
+            line.push_str(&format!(" {name}={value}"));
+        }
+    }
+    line
+}
+
+/// Replays the artifact's trace and writes the events picked by `indices`
+/// or `kinds` with their multi-proof to `out`.
+pub(crate) fn disclose(
+    cli: &Cli,
+    artifact_path: &PathBuf,
+    model_path: &PathBuf,
+    input_path: &PathBuf,
+    indices: &[usize],
+    kinds: &[String],
+    out: &PathBuf,
+) -> Result<(), CliError> {
+    let artifact_bin = read_artifact(artifact_path)?;
+    let version = artifact_version(&artifact_bin).unwrap_or(0);
+    if !matches!(version, 0 | 1 | 3..=6) {
+        return Err(CliError::Runtime(format!(
+            "disclose does not take v{version} artifacts"
+        )));
+    }
+    let model = read_model(model_path)?;
+    let model_bin = model.encode_bin();
+    let input_bin = read_input_json(input_path)?.encode_bin();
+
+    let select = |i: usize, e: &TraceEventV0| {
+        indices.contains(&i) || kinds.iter().any(|k| k == event_kind(e))
+    };
+    let disclosure = match model {
+        LoadedModel::Logistic(_) => {
+            disclose_lr_trace(&artifact_bin, &model_bin, &input_bin, select)
+        }
+        LoadedModel::Mlp(_) => disclose_mlp_trace(&artifact_bin, &model_bin, &input_bin, select),
+    }
+    .map_err(|e| CliError::Runtime(format!("disclose failed: {e:?}")))?;
+    if let Some(&missing) = indices
+        .iter()
+        .find(|&&i| i >= disclosure.leaf_count as usize)
+    {
+        return Err(CliError::Runtime(format!(
+            "disclose failed: the trace has {} events, no index {missing}",
+            disclosure.leaf_count
+        )));
+    }
+    write_file_atomic(out, &disclosure.encode_bin())?;
+
+    let disclosed: Vec<u32> = disclosure.events.iter().map(|d| d.index).collect();
+    let payload = JsonOut::Disclose {
+        ok: true,
+        leaf_count: disclosure.leaf_count,
+        indices: disclosed.clone(),
+        proof_hashes: disclosure.proof.len(),
+        out: path_string_ref(out),
+    };
+    emit_success(cli, payload, || {
+        println!("ok");
+        println!("trace events : {}", disclosure.leaf_count);
+        println!("disclosed    : {disclosed:?}");
+        println!("proof hashes : {}", disclosure.proof.len());
+    })
+}
+
+/// Checks a disclosure against the artifact's signed trace root and prints
+/// the disclosed events.
+pub(crate) fn verify_disclosure(
+    cli: &Cli,
+    artifact_path: &PathBuf,
+    disclosure_path: &PathBuf,
+) -> Result<(), CliError> {
+    let artifact_bin = read_artifact(artifact_path)?;
+    let version = artifact_version(&artifact_bin).unwrap_or(0);
+    if !matches!(version, 0 | 1 | 3..=6) {
+        return Err(CliError::VerifyFailed(format!(
+            "verify-disclosure does not take v{version} artifacts"
+        )));
+    }
+    let disclosure = TraceDisclosureV0::decode_bin(&read_file(disclosure_path)?)
+        .map_err(|e| CliError::VerifyFailed(format!("{}: {e:?}", disclosure_path.display())))?;
+    verify_trace_disclosure(&artifact_bin, &disclosure)
+        .map_err(|e| CliError::VerifyFailed(format!("{e:?}")))?;
+
+    let events: Vec<DisclosedEventJson> = disclosure
+        .events
+        .iter()
+        .map(|d| event_json(d.index, &d.event))
+        .collect();
+    let lines: Vec<String> = events
+        .iter()
+        .map(|e| format!("event[{}] : {}", e.index, describe(e)))
+        .collect();
+    let payload = JsonOut::VerifyDisclosure {
+        ok: true,
+        hash_alg: disclosure.hash_alg.name().to_string(),
+        leaf_count: disclosure.leaf_count,
+        events,
+    };
+    emit_success(cli, payload, || {
+        println!("ok");
+        println!("trace events : {}", disclosure.leaf_count);
+        for line in &lines {
+            println!("{line}");
+        }
+    })
+}
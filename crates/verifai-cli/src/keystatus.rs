@@ -0,0 +1,98 @@
+//! Key-status lists: the `key-status` command and the check behind
+//! `verify --key-status`.
+
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use verifai_core::key_status::{KeyStatusEntry, KeyStatusListV0};
+use verifai_runtime::{check_artifact_key_status, unix_nanos_now, verify_key_status_list};
+
+use crate::{
+    emit_success, hex_encode_32, parse_hex_32, path_string_ref, read_file, write_file_atomic, Cli,
+    CliError, JsonOut,
+};
+
+/// `keys.json`: every key the authority vouches for. Times are unix nanos.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct KeysJson {
+    keys: Vec<KeyJson>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct KeyJson {
+    pubkey: String,
+    #[serde(default)]
+    valid_from: u64,
+    valid_until: Option<u64>,
+    revoked_at: Option<u64>,
+}
+
+/// Signs the keys in `keys_path` into a key-status list at `out`.
+pub(crate) fn key_status(
+    cli: &Cli,
+    keys_path: &PathBuf,
+    signing_key: [u8; 32],
+    out: &PathBuf,
+) -> Result<(), CliError> {
+    let keys: KeysJson = serde_json::from_slice(&read_file(keys_path)?)
+        .map_err(|e| CliError::Json(format!("{}: {e}", keys_path.display())))?;
+    let entries = keys
+        .keys
+        .iter()
+        .map(|k| {
+            Ok(KeyStatusEntry {
+                pubkey: parse_hex_32(&k.pubkey).map_err(|_| {
+                    CliError::InvalidHex("key pubkey must be 64 hex chars (32 bytes)")
+                })?,
+                valid_from: k.valid_from,
+                valid_until: k.valid_until,
+                revoked_at: k.revoked_at,
+            })
+        })
+        .collect::<Result<Vec<_>, CliError>>()?;
+
+    let mut list = KeyStatusListV0 {
+        issued_at: unix_nanos_now(),
+        entries,
+        authority_pubkey: [0u8; 32],
+        signature: [0u8; 64],
+    };
+    list.sign_detached(signing_key)
+        .map_err(|e| CliError::Runtime(format!("key-status sign failed: {e:?}")))?;
+    let list_bin = list.encode_bin();
+    // catches duplicate keys before the list is published
+    KeyStatusListV0::decode_bin(&list_bin)
+        .map_err(|_| CliError::Runtime("key-status list lists a key twice".into()))?;
+    write_file_atomic(out, &list_bin)?;
+
+    let authority = hex_encode_32(list.authority_pubkey);
+    let payload = JsonOut::KeyStatus {
+        ok: true,
+        authority_pubkey: authority.clone(),
+        keys: list.entries.len(),
+        out: path_string_ref(out),
+    };
+    emit_success(cli, payload, || {
+        println!("ok");
+        println!("authority_pubkey : {authority}");
+        println!("keys             : {}", list.entries.len());
+    })
+}
+
+/// Rejects `artifact_bin` unless the list at `list_path`, signed by
+/// `authority`, vouches for every key that signed it. Timestamps count only
+/// when `trusted_tsa` signed them.
+pub(crate) fn require_key_status(
+    artifact_bin: &[u8],
+    list_path: &PathBuf,
+    authority: [u8; 32],
+    trusted_tsa: Option<[u8; 32]>,
+    now: u64,
+) -> Result<(), CliError> {
+    let list = verify_key_status_list(&read_file(list_path)?, authority)
+        .map_err(|e| CliError::VerifyFailed(format!("key status list: {e:?}")))?;
+    check_artifact_key_status(artifact_bin, &list, trusted_tsa, now)
+        .map_err(|e| CliError::VerifyFailed(format!("{e:?}")))
+}
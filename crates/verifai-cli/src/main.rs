@@ -4,12 +4,44 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::process;
 
-use verifai_core::artifact_bin::{ProofArtifactV0, ProofArtifactV1};
-use verifai_core::hash::sha256;
-use verifai_core::model_bin::{InputV0, LogisticModelV0};
+mod batch;
+mod disclosure;
+mod keystatus;
+mod pipeline;
+mod preprocess;
+mod serve;
+mod trace_export;
+mod tracecheck;
+mod translog;
+
+use verifai_core::artifact_bin::{
+    ProofArtifactV0, ProofArtifactV1, ProofArtifactV3, ProofArtifactV4, ProofArtifactV5,
+    ProofArtifactV6, ProofArtifactV7, ProofArtifactV8, ProofArtifactV9,
+};
+use verifai_core::attestation::AttestationBundle;
+use verifai_core::event_bin::TraceLevel;
+use verifai_core::hash::{sha256, HashAlg};
+use verifai_core::model_bin::{InputV0, LogisticModelV0, MlpModelV1};
+use verifai_core::pipeline::StageLink;
+use verifai_core::salts::CommitmentSaltsV0;
+use verifai_core::sig_alg::{AlgSigningKey, SigAlg};
+use verifai_core::signer::{Signer, SignerError};
+use verifai_core::signer_bundle::{is_signer_bundle, SignerBundleV0};
+use verifai_core::trace_sink::TraceSink;
+#[cfg(unix)]
+use verifai_runtime::AgentSigner;
 use verifai_runtime::{
-    artifact_version, prove_lr_v0, prove_lr_v1_with_attester, verify_lr_v0, verify_lr_v1,
-    NoopAttester,
+    artifact_version, cosign_lr_v4, cosign_mlp_v4, encode_cose_v1, encode_intoto_envelope,
+    load_npy, load_npz, load_safetensors, native_artifact_bin, pem_certificates,
+    prove_lr_v0_with_sink, prove_lr_v1_with_sink, prove_lr_v3_with_sink, prove_lr_v4_with_sink,
+    prove_lr_v5_with_sink, prove_lr_v6_with_sink, prove_lr_v7_with_sink, prove_lr_v8_with_attester,
+    prove_lr_v9_with_attester, prove_mlp_v1_with_sink, prove_mlp_v3_with_sink,
+    prove_mlp_v4_with_sink, prove_mlp_v5_with_sink, prove_mlp_v6_with_sink, prove_mlp_v7_with_sink,
+    prove_mlp_v8, prove_mlp_v9, random_salts, unix_nanos_now, verify_lr_v0, verify_lr_v1,
+    verify_lr_v3, verify_lr_v4, verify_lr_v5, verify_lr_v6, verify_lr_v7, verify_lr_v9,
+    verify_mlp_v1, verify_mlp_v3, verify_mlp_v4, verify_mlp_v5, verify_mlp_v6, verify_mlp_v7,
+    verify_mlp_v9, verify_signer_chain, FreshnessPolicy, IssuanceV3, LocalTsa, NoopAttester,
+    TensorSet, ThresholdPolicy,
 };
 
 #[derive(Parser)]
@@ -37,7 +69,7 @@ struct Cli {
 enum Command {
     /// Read model.json -> canonical model.bin, print SHA-256 hash of model.bin
     HashModel {
-        /// Path to model.json
+        /// Path to model.json, model.safetensors, model.npz or a directory of .npy files
         #[arg(long)]
         model: PathBuf,
 
@@ -48,21 +80,49 @@ enum Command {
 
     /// Read model.json + input.json -> run inference -> write output.bin + artifact.bin
     Prove {
-        /// Path to model.json
+        /// Path to model.json, model.safetensors, model.npz or a directory of .npy files
         #[arg(long)]
         model: PathBuf,
 
         /// Path to input.json
-        #[arg(long)]
-        input: PathBuf,
+        #[arg(long, required_unless_present_any = ["inputs", "input_from", "raw_input"])]
+        input: Option<PathBuf>,
+
+        /// Pipeline stage: use the previous stage's output.bin as the only input feature
+        #[arg(long, conflicts_with_all = ["input", "inputs"])]
+        input_from: Option<PathBuf>,
 
         /// Output path for output.bin
-        #[arg(long)]
-        out_output: PathBuf,
+        #[arg(long, required_unless_present = "inputs")]
+        out_output: Option<PathBuf>,
 
         /// Output path for artifact.bin
-        #[arg(long)]
-        out_artifact: PathBuf,
+        #[arg(long, required_unless_present = "inputs")]
+        out_artifact: Option<PathBuf>,
+
+        /// Optional: also write the v1 artifact as a COSE_Sign1 message (needs --attest or an MLP model)
+        #[arg(long, conflicts_with_all = ["inputs", "fresh"])]
+        out_cose: Option<PathBuf>,
+
+        /// Optional: also write an in-toto Statement about output.bin as a signed DSSE envelope (v0/v1 artifacts)
+        #[arg(long, conflicts_with_all = ["inputs", "fresh"])]
+        out_intoto: Option<PathBuf>,
+
+        /// Optional: write the artifact and --cert-chain together as a signer bundle
+        #[arg(long, requires = "cert_chain", conflicts_with = "inputs")]
+        out_bundle: Option<PathBuf>,
+
+        /// PEM X.509 chain (leaf first) certifying the signing key, packaged by --out-bundle
+        #[arg(long, requires = "out_bundle")]
+        cert_chain: Option<PathBuf>,
+
+        /// Batch mode: JSONL file with one {"x": [...]} record per line (optional "id")
+        #[arg(long, requires = "out_dir", conflicts_with_all = ["input", "out_output", "out_artifact", "out_input_bin"])]
+        inputs: Option<PathBuf>,
+
+        /// Batch mode: directory for per-record output/artifact files and manifest.jsonl
+        #[arg(long, requires = "inputs")]
+        out_dir: Option<PathBuf>,
 
         /// Optional: write canonical model.bin
         #[arg(long)]
@@ -73,8 +133,12 @@ enum Command {
         out_input_bin: Option<PathBuf>,
 
         /// Signing key (Ed25519 secret key) as 64 hex chars (32 bytes)
-        #[arg(long)]
-        key_hex: String,
+        #[arg(long, required_unless_present = "agent_socket")]
+        key_hex: Option<String>,
+
+        /// Sign through the `verifai-agent` listening on this Unix socket instead of --key-hex
+        #[arg(long, conflicts_with_all = ["key_hex", "out_cose", "out_intoto"])]
+        agent_socket: Option<PathBuf>,
 
         /// Produce attested ProofArtifactV1 instead of V0
         #[arg(long)]
@@ -83,15 +147,310 @@ enum Command {
         /// Runtime id as 64 hex chars (32 bytes). If omitted, uses sha256("verifai-cli-default-runtime")
         #[arg(long)]
         runtime_id_hex: Option<String>,
+
+        /// Append every produced artifact to the transparency log in this directory
+        #[arg(long)]
+        log_dir: Option<PathBuf>,
+
+        /// Produce ProofArtifactV3 with signed issued_at, optional expiry and nonce
+        #[arg(long)]
+        fresh: bool,
+
+        /// Challenge nonce to sign into the artifact as 64 hex chars (32 bytes); defaults to zeros
+        #[arg(long, requires = "fresh")]
+        nonce_hex: Option<String>,
+
+        /// Artifact expires this many seconds after issuance
+        #[arg(long, requires = "fresh")]
+        expires_in_secs: Option<u64>,
+
+        /// Timestamp each artifact with a local TSA using this key (64 hex chars)
+        #[arg(long, requires = "fresh")]
+        tsa_key_hex: Option<String>,
+
+        /// Produce ProofArtifactV4, which other parties can co-sign with `cosign`
+        #[arg(long, conflicts_with_all = ["fresh", "out_cose", "out_intoto"])]
+        multisig: bool,
+
+        /// Produce ProofArtifactV5 signed with this algorithm; --key-hex is its 32-byte secret key,
+        /// or the --agent-socket agent must hold a key for it
+        #[arg(
+            long,
+            value_parser = ["ed25519", "p256", "secp256k1"],
+            conflicts_with_all = ["fresh", "multisig", "out_cose", "out_intoto"]
+        )]
+        sig_alg: Option<String>,
+
+        /// Produce ProofArtifactV6 using this hash algorithm for every hash and the trace root;
+        /// signs with --sig-alg, or Ed25519 without it
+        #[arg(
+            long,
+            value_parser = ["sha256", "blake3", "sha3-256"],
+            conflicts_with_all = ["fresh", "multisig", "out_cose", "out_intoto"]
+        )]
+        hash_alg: Option<String>,
+
+        /// Produce ProofArtifactV7 with salted commitments and write its fresh random salts here;
+        /// only verifiers given this file can check the artifact
+        #[arg(
+            long,
+            conflicts_with_all = ["inputs", "fresh", "multisig", "sig_alg", "hash_alg", "out_cose", "out_intoto"]
+        )]
+        out_salts: Option<PathBuf>,
+
+        /// Optional: write the trace events, for `check-trace` by verifiers who may not see the model
+        #[arg(long, conflicts_with = "inputs")]
+        out_trace: Option<PathBuf>,
+
+        /// Format of --out-trace: bin (default, what check-trace reads) or jsonl (one event per line)
+        #[arg(long, requires = "out_trace", value_parser = ["bin", "jsonl"])]
+        trace_format: Option<String>,
+
+        /// Produce ProofArtifactV8 from --raw-input, preprocessed by this preprocessor.json
+        #[arg(
+            long,
+            requires = "raw_input",
+            conflicts_with_all = [
+                "input", "input_from", "inputs", "fresh", "multisig", "sig_alg", "hash_alg",
+                "out_salts", "out_cose", "out_intoto", "out_trace"
+            ]
+        )]
+        preprocessor: Option<PathBuf>,
+
+        /// Path to raw_input.json ({"values": [number, "category" or null, ...]})
+        #[arg(long, requires = "preprocessor")]
+        raw_input: Option<PathBuf>,
+
+        /// Produce ProofArtifactV9 with its trace recorded at this level: summary (output only),
+        /// layer (per-layer digests), op (every linear op and activation) or fine (every MAC too)
+        #[arg(
+            long,
+            value_parser = ["summary", "layer", "op", "fine"],
+            conflicts_with_all = [
+                "inputs", "fresh", "multisig", "sig_alg", "hash_alg", "out_salts", "preprocessor",
+                "out_cose", "out_intoto", "out_trace"
+            ]
+        )]
+        trace_level: Option<String>,
+    },
+
+    /// Replay a v4 artifact and add a co-signature to it
+    Cosign {
+        /// Path to the v4 artifact.bin
+        #[arg(long)]
+        artifact: PathBuf,
+
+        /// Path to model.json, model.safetensors, model.npz or a directory of .npy files
+        #[arg(long)]
+        model: PathBuf,
+
+        /// Path to input.json
+        #[arg(long)]
+        input: PathBuf,
+
+        /// Path to output.bin
+        #[arg(long)]
+        output: PathBuf,
+
+        /// Co-signer key (Ed25519 secret key) as 64 hex chars (32 bytes)
+        #[arg(long, required_unless_present = "agent_socket")]
+        key_hex: Option<String>,
+
+        /// Sign through the `verifai-agent` listening on this Unix socket instead of --key-hex
+        #[arg(long, conflicts_with = "key_hex")]
+        agent_socket: Option<PathBuf>,
+
+        /// Output path for the co-signed artifact (may be --artifact itself)
+        #[arg(long)]
+        out: PathBuf,
     },
 
     /// Verify artifact.bin against model.json + input.json + output.bin
     Verify {
+        /// Path to artifact.bin, its COSE_Sign1 encoding or an in-toto DSSE envelope
+        #[arg(long, required_unless_present = "manifest")]
+        artifact: Option<PathBuf>,
+
+        /// Path to model.json, model.safetensors, model.npz or a directory of .npy files
+        #[arg(long)]
+        model: PathBuf,
+
+        /// Path to input.json
+        #[arg(long, required_unless_present_any = ["manifest", "raw_input"])]
+        input: Option<PathBuf>,
+
+        /// Path to output.bin
+        #[arg(long, required_unless_present = "manifest")]
+        output: Option<PathBuf>,
+
+        /// Batch mode: manifest.jsonl as written by `prove --out-dir`
+        #[arg(long, conflicts_with_all = ["artifact", "input", "output"])]
+        manifest: Option<PathBuf>,
+
+        /// Also require the artifact to be included in the log at --tree-head
+        #[arg(
+            long,
+            requires_all = ["tree_head", "log_source", "log_pubkey_hex"],
+            conflicts_with = "manifest"
+        )]
+        require_log_inclusion: bool,
+
+        /// Pinned signed tree head (from `log-head --out`)
+        #[arg(long, requires = "require_log_inclusion")]
+        tree_head: Option<PathBuf>,
+
+        /// Inclusion proof file (from `log-inclusion --out`)
+        #[arg(long, group = "log_source", requires = "require_log_inclusion")]
+        log_proof: Option<PathBuf>,
+
+        /// Compute the inclusion proof from a local log instead of --log-proof
+        #[arg(long, group = "log_source", requires = "require_log_inclusion")]
+        log_dir: Option<PathBuf>,
+
+        /// Pinned tree head signer as 64 hex chars (32 bytes); a head signed
+        /// by any other key is rejected
+        #[arg(long, requires = "require_log_inclusion")]
+        log_pubkey_hex: Option<String>,
+
+        /// Reject v3 artifacts issued more than this many seconds ago (or any older version)
+        #[arg(long)]
+        max_age_secs: Option<u64>,
+
+        /// Require this challenge nonce (64 hex chars) in a v3 artifact
+        #[arg(long)]
+        expect_nonce_hex: Option<String>,
+
+        /// Require a timestamp token from the --tsa-pubkey-hex TSA in a v3 artifact
+        /// (implied by --tsa-pubkey-hex)
+        #[arg(long, requires = "tsa_pubkey_hex")]
+        require_timestamp: bool,
+
+        /// Require the timestamp token to be signed by this TSA key (64 hex chars);
+        /// --key-status honours timestamps from no other TSA
+        #[arg(long)]
+        tsa_pubkey_hex: Option<String>,
+
+        /// Validate the signer bundle's certificate chain against these PEM root certificates
+        #[arg(long, conflicts_with = "manifest")]
+        trusted_root: Vec<PathBuf>,
+
+        /// Evaluate freshness and certificate validity at this unix time in nanoseconds instead of the system clock
+        #[arg(long)]
+        now_nanos: Option<u64>,
+
+        /// Trusted signer public key as 64 hex chars (32 bytes); repeat for several
+        #[arg(long, conflicts_with = "manifest")]
+        trusted_key_hex: Vec<String>,
+
+        /// Require signatures by at least this many distinct --trusted-key-hex keys (default 1)
+        #[arg(long, requires = "trusted_key_hex", value_parser = clap::value_parser!(u64).range(1..))]
+        threshold: Option<u64>,
+
+        /// Reject signing keys that this key-status list (from `key-status`) revokes or does not vouch for
+        #[arg(long, requires = "key_status_pubkey_hex", conflicts_with = "manifest")]
+        key_status: Option<PathBuf>,
+
+        /// Expected key-status list signer as 64 hex chars (32 bytes)
+        #[arg(long, requires = "key_status")]
+        key_status_pubkey_hex: Option<String>,
+
+        /// Salts file written by `prove --out-salts`, needed to verify v7 artifacts
+        #[arg(long, conflicts_with = "manifest")]
+        salts: Option<PathBuf>,
+
+        /// preprocessor.json the v8 artifact commits to, needed to verify v8 artifacts
+        #[arg(long, requires = "raw_input", conflicts_with_all = ["manifest", "salts"])]
+        preprocessor: Option<PathBuf>,
+
+        /// raw_input.json the v8 artifact was proven from, instead of --input
+        #[arg(long, requires = "preprocessor", conflicts_with = "input")]
+        raw_input: Option<PathBuf>,
+
+        /// Trace the prover claims (from `prove --out-trace`); replay stops at the first event that
+        /// differs and reports it. v0, v1 and v3-v6 artifacts only; v4 needs every co-signature valid
+        #[arg(long, conflicts_with_all = ["manifest", "salts", "preprocessor"])]
+        trace: Option<PathBuf>,
+    },
+
+    /// Sign a key-status list of artifact signing keys, their validity windows and revocations
+    KeyStatus {
+        /// JSON file: {"keys": [{"pubkey": hex, "valid_from": nanos, "valid_until": nanos, "revoked_at": nanos}]}
+        #[arg(long)]
+        keys: PathBuf,
+
+        /// Authority signing key (Ed25519 secret key) as 64 hex chars (32 bytes)
+        #[arg(long)]
+        key_hex: String,
+
+        /// Output path for the signed list
+        #[arg(long)]
+        out: PathBuf,
+    },
+
+    /// Sign the current root of a transparency log
+    LogHead {
+        /// Log directory
+        #[arg(long)]
+        log_dir: PathBuf,
+
+        /// Log operator signing key (Ed25519 secret key) as 64 hex chars (32 bytes)
+        #[arg(long)]
+        key_hex: String,
+
+        /// Write the signed tree head to this path (optional)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+
+    /// Write an inclusion proof for artifact.bin against a signed tree head
+    LogInclusion {
+        /// Log directory
+        #[arg(long)]
+        log_dir: PathBuf,
+
         /// Path to artifact.bin
         #[arg(long)]
         artifact: PathBuf,
 
-        /// Path to model.json
+        /// Signed tree head the proof is computed for
+        #[arg(long)]
+        tree_head: PathBuf,
+
+        /// Output path for the inclusion proof
+        #[arg(long)]
+        out: PathBuf,
+    },
+
+    /// Check that a newer signed tree head extends an older one
+    LogConsistency {
+        /// Log directory
+        #[arg(long)]
+        log_dir: PathBuf,
+
+        /// Older signed tree head
+        #[arg(long)]
+        old_head: PathBuf,
+
+        /// Newer signed tree head
+        #[arg(long)]
+        new_head: PathBuf,
+
+        /// Expected tree head signer as 64 hex chars (32 bytes)
+        #[arg(long)]
+        log_pubkey_hex: Option<String>,
+
+        /// Write the consistency proof to this path (optional)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+
+    /// Disclose chosen trace events of an artifact with a Merkle multi-proof of their inclusion
+    Disclose {
+        /// Path to artifact.bin
+        #[arg(long)]
+        artifact: PathBuf,
+
+        /// Path to model.json, model.safetensors, model.npz or a directory of .npy files
         #[arg(long)]
         model: PathBuf,
 
@@ -99,9 +458,112 @@ enum Command {
         #[arg(long)]
         input: PathBuf,
 
+        /// Disclose the trace event at this index; repeat for several
+        #[arg(long, required_unless_present = "kind")]
+        index: Vec<usize>,
+
+        /// Disclose every trace event of this kind; repeat for several
+        #[arg(long, value_parser = ["linear", "activation", "output", "feature"])]
+        kind: Vec<String>,
+
+        /// Output path for the disclosure
+        #[arg(long)]
+        out: PathBuf,
+    },
+
+    /// Check disclosed trace events against an artifact, without its model or input
+    VerifyDisclosure {
+        /// Path to artifact.bin
+        #[arg(long)]
+        artifact: PathBuf,
+
+        /// Disclosure written by `disclose`
+        #[arg(long)]
+        disclosure: PathBuf,
+    },
+
+    /// Check an artifact against its trace (from `prove --out-trace`) and output, without the model or input
+    CheckTrace {
+        /// Path to artifact.bin
+        #[arg(long)]
+        artifact: PathBuf,
+
         /// Path to output.bin
         #[arg(long)]
         output: PathBuf,
+
+        /// Trace written by `prove --out-trace`
+        #[arg(long)]
+        trace: PathBuf,
+    },
+
+    /// Link stage artifacts into a signed pipeline proof; each stage's input is the previous stage's output
+    Pipeline {
+        /// Stage artifact.bin, in pipeline order; repeat once per stage
+        #[arg(long, required = true)]
+        stage_artifact: Vec<PathBuf>,
+
+        /// Stage output.bin, in the same order as --stage-artifact
+        #[arg(long, required = true)]
+        stage_output: Vec<PathBuf>,
+
+        /// Pipeline operator signing key (Ed25519 secret key) as 64 hex chars (32 bytes)
+        #[arg(long)]
+        key_hex: String,
+
+        /// Output path for the pipeline proof
+        #[arg(long)]
+        out: PathBuf,
+    },
+
+    /// Verify a pipeline proof's signatures and links, and replay it end to end given every stage's model
+    VerifyPipeline {
+        /// Pipeline proof written by `pipeline`
+        #[arg(long)]
+        pipeline: PathBuf,
+
+        /// Expected pipeline operator key as 64 hex chars (32 bytes)
+        #[arg(long)]
+        pipeline_pubkey_hex: Option<String>,
+
+        /// Stage model, in pipeline order; repeat once per stage to replay (needs --input)
+        #[arg(long, requires = "input")]
+        model: Vec<PathBuf>,
+
+        /// First stage's input.json
+        #[arg(long, requires = "model")]
+        input: Option<PathBuf>,
+
+        /// Evaluate freshness of v3 stages at this unix time in nanoseconds instead of the system clock
+        #[arg(long)]
+        now_nanos: Option<u64>,
+    },
+
+    /// Serve prove/verify over HTTP for every model in a directory
+    Serve {
+        /// Directory of models (model.json, .safetensors, .npz or .npy subdirectories)
+        #[arg(long)]
+        models_dir: PathBuf,
+
+        /// Signing key (Ed25519 secret key) as 64 hex chars (32 bytes)
+        #[arg(long)]
+        key_hex: String,
+
+        /// Address to listen on (port 0 picks a free port)
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        bind: String,
+
+        /// Number of worker threads
+        #[arg(long, default_value_t = 4)]
+        threads: usize,
+
+        /// Produce attested ProofArtifactV1 for logistic models
+        #[arg(long)]
+        attest: bool,
+
+        /// Runtime id as 64 hex chars (32 bytes). If omitted, uses sha256("verifai-cli-default-runtime")
+        #[arg(long)]
+        runtime_id_hex: Option<String>,
     },
 }
 
@@ -122,8 +584,7 @@ fn main() {
 fn run(cli: Cli) -> Result<(), CliError> {
     match cli.cmd {
         Command::HashModel { ref model, ref out } => {
-            let model_v0 = read_model_json(&model)?;
-            let model_bin = model_v0.encode_bin();
+            let model_bin = read_model(model)?.encode_bin();
             let model_hash = sha256(&model_bin);
 
             if let Some(out_path) = &out {
@@ -144,19 +605,120 @@ fn run(cli: Cli) -> Result<(), CliError> {
         Command::Prove {
             ref model,
             ref input,
+            ref input_from,
             ref out_output,
             ref out_artifact,
+            ref out_cose,
+            ref out_intoto,
+            ref out_bundle,
+            ref cert_chain,
             ref out_model_bin,
             ref out_input_bin,
             ref key_hex,
+            ref agent_socket,
             attest,
             ref runtime_id_hex,
+            ref inputs,
+            ref out_dir,
+            ref log_dir,
+            fresh,
+            ref nonce_hex,
+            expires_in_secs,
+            ref tsa_key_hex,
+            multisig,
+            ref sig_alg,
+            ref hash_alg,
+            ref out_salts,
+            ref out_trace,
+            ref trace_format,
+            ref preprocessor,
+            ref raw_input,
+            ref trace_level,
         } => {
-            let model_v0 = read_model_json(&model)?;
-            let input_v0 = read_input_json(&input)?;
+            let loaded_model = read_model(model)?;
+            let sig_alg = sig_alg
+                .as_deref()
+                .map(|name| SigAlg::from_name(name).expect("clap checks --sig-alg"));
+            let signer = prove_signer(key_hex.as_deref(), agent_socket.as_deref(), sig_alg)?;
+
+            let fresh = if fresh {
+                let nonce = match nonce_hex {
+                    Some(s) => parse_hex_32(s).map_err(|_| {
+                        CliError::InvalidHex("nonce_hex must be 64 hex chars (32 bytes)")
+                    })?,
+                    None => [0u8; 32],
+                };
+                let tsa = match tsa_key_hex {
+                    Some(s) => Some(LocalTsa::new(parse_hex_32(s).map_err(|_| {
+                        CliError::InvalidHex("tsa_key_hex must be 64 hex chars (32 bytes)")
+                    })?)),
+                    None => None,
+                };
+                Some(FreshOptions {
+                    nonce,
+                    expires_in_nanos: expires_in_secs.map(|s| s.saturating_mul(1_000_000_000)),
+                    tsa,
+                })
+            } else {
+                None
+            };
+            let opts = ProveOptions {
+                attest,
+                runtime_id: parse_runtime_id(runtime_id_hex.as_deref())?,
+                signer,
+                fresh,
+                multisig,
+                sig_alg,
+                hash_alg: hash_alg
+                    .as_deref()
+                    .map(|name| HashAlg::from_name(name).expect("clap checks --hash-alg")),
+                salts: out_salts
+                    .as_ref()
+                    .map(|_| random_salts())
+                    .transpose()
+                    .map_err(|e| CliError::Runtime(format!("salt generation failed: {e:?}")))?,
+                preprocessor: preprocessor
+                    .as_ref()
+                    .map(preprocess::read_preprocessor_json)
+                    .transpose()?,
+                trace_level: trace_level
+                    .as_deref()
+                    .map(|name| TraceLevel::from_name(name).expect("clap checks --trace-level")),
+            };
 
-            let model_bin = model_v0.encode_bin();
-            let input_bin = input_v0.encode_bin();
+            if let (Some(inputs), Some(out_dir)) = (inputs, out_dir) {
+                let model_bin = loaded_model.encode_bin();
+                if let Some(p) = &out_model_bin {
+                    write_file_atomic(p, &model_bin)?;
+                }
+                return batch::prove_jsonl(
+                    &cli,
+                    &loaded_model,
+                    &model_bin,
+                    &opts,
+                    log_dir.as_ref(),
+                    inputs,
+                    out_dir,
+                );
+            }
+
+            let out_output = out_output
+                .as_ref()
+                .expect("clap requires --out-output without --inputs");
+            let out_artifact = out_artifact
+                .as_ref()
+                .expect("clap requires --out-artifact without --inputs");
+            let input_bin = match (input_from, raw_input) {
+                (Some(p), _) => StageLink::OutputAsInput
+                    .apply(&read_file(p)?)
+                    .map_err(|e| CliError::Runtime(format!("{}: {e:?}", p.display())))?,
+                (None, Some(p)) => preprocess::read_raw_input_json(p)?,
+                (None, None) => read_input_json(input.as_ref().expect(
+                    "clap requires --input without --inputs, --input-from or --raw-input",
+                ))?
+                .encode_bin(),
+            };
+            let model_bin = loaded_model.encode_bin();
 
             if let Some(p) = &out_model_bin {
                 write_file_atomic(p, &model_bin)?;
@@ -165,61 +727,84 @@ fn run(cli: Cli) -> Result<(), CliError> {
                 write_file_atomic(p, &input_bin)?;
             }
 
-            let signing_key = parse_hex_32(&key_hex)
-                .map_err(|_| CliError::InvalidHex("key_hex must be 64 hex chars (32 bytes)"))?;
-
-            let runtime_id = match runtime_id_hex {
-                Some(s) => parse_hex_32(s).map_err(|_| {
-                    CliError::InvalidHex("runtime_id_hex must be 64 hex chars (32 bytes)")
-                })?,
-                None => sha256(b"verifai-cli-default-runtime"),
-            };
-
-            let (output_bin, artifact_bin) = if attest {
-                prove_lr_v1_with_attester::<NoopAttester>(
-                    runtime_id,
-                    signing_key,
-                    &model_bin,
-                    &input_bin,
-                )
-                .map_err(|e| CliError::Runtime(format!("prove failed (v1): {e:?}")))?
-            } else {
-                prove_lr_v0(runtime_id, signing_key, &model_bin, &input_bin)
-                    .map_err(|e| CliError::Runtime(format!("prove failed: {e:?}")))?
+            let mut trace_export = out_trace
+                .as_ref()
+                .map(|p| {
+                    trace_export::TraceExport::create(p, trace_format.as_deref().unwrap_or("bin"))
+                })
+                .transpose()?;
+            let sink: &mut dyn TraceSink = match &mut trace_export {
+                Some(export) => export,
+                None => &mut (),
             };
+            let (output_bin, artifact_bin) =
+                prove_model(&loaded_model, &opts, &model_bin, &input_bin, sink)?;
 
-            write_file_atomic(&out_output, &output_bin)?;
-            write_file_atomic(&out_artifact, &artifact_bin)?;
-
-            let artifact_version = artifact_version(&artifact_bin).unwrap_or(0);
-            let (trace_root, sig_pubkey, attestation_bundle) = match artifact_version {
-                0 => {
-                    let art = ProofArtifactV0::decode_bin(&artifact_bin)
-                        .map_err(|_| CliError::Runtime("artifact decode failed".into()))?;
-                    (art.trace_root, art.sig_pubkey, None)
-                }
-                1 => {
-                    let art = ProofArtifactV1::decode_bin(&artifact_bin)
-                        .map_err(|_| CliError::Runtime("artifact decode failed".into()))?;
-                    (art.trace_root, art.sig_pubkey, Some(art.attestation))
-                }
-                _ => {
-                    return Err(CliError::Runtime(format!(
-                        "artifact version {artifact_version} not supported"
-                    )));
+            write_file_atomic(out_output, &output_bin)?;
+            write_file_atomic(out_artifact, &artifact_bin)?;
+            if let (Some(p), Some(salts)) = (out_salts, &opts.salts) {
+                write_file_atomic(p, &salts.encode_bin())?;
+            }
+            if let Some(export) = trace_export {
+                export.finish()?;
+            }
+            if let Some(p) = out_cose {
+                if artifact_version(&artifact_bin) != Some(1) {
+                    return Err(CliError::Runtime(
+                        "--out-cose needs a v1 artifact (use --attest)".into(),
+                    ));
                 }
-            };
-
-            let model_hash = sha256(&model_bin);
-            let input_hash = sha256(&input_bin);
-            let output_hash = sha256(&output_bin);
+                let key = opts
+                    .signer
+                    .secret_key()
+                    .expect("clap keeps --out-cose away from --agent-socket");
+                let cose = encode_cose_v1(&artifact_bin, key)
+                    .map_err(|e| CliError::Runtime(format!("{e:?}")))?;
+                write_file_atomic(p, &cose)?;
+            }
+            if let Some(p) = out_intoto {
+                let subject = out_output
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "output.bin".into());
+                let key = opts
+                    .signer
+                    .secret_key()
+                    .expect("clap keeps --out-intoto away from --agent-socket");
+                let envelope = encode_intoto_envelope(&artifact_bin, key, &subject)
+                    .map_err(|e| CliError::Runtime(format!("{e:?}")))?;
+                write_file_atomic(p, &envelope)?;
+            }
+            if let (Some(p), Some(chain)) = (out_bundle, cert_chain) {
+                let bundle = SignerBundleV0 {
+                    artifact: artifact_bin.clone(),
+                    cert_chain: read_cert_pem(chain)?,
+                };
+                write_file_atomic(p, &bundle.encode_bin())?;
+            }
+            let log_index = log_dir
+                .as_ref()
+                .map(|dir| translog::append_artifact(dir, &artifact_bin))
+                .transpose()?;
+
+            let (trace_root, sig_pubkey, attestation_bundle) =
+                artifact_summary(&artifact_bin).map_err(|e| CliError::Runtime(e.to_string()))?;
+            let freshness = artifact_freshness(&artifact_bin);
+
+            let hash_alg = artifact_hash_alg(&artifact_bin);
+            let trace_level = artifact_trace_level(&artifact_bin);
+            let digest = |bytes: &[u8]| hash_alg.unwrap_or_default().digest(bytes);
+            // v7 only carries salted commitments; report those, not plain hashes
+            let [model_hash, input_hash, output_hash] = artifact_salted_commitments(&artifact_bin)
+                .unwrap_or_else(|| [digest(&model_bin), digest(&input_bin), digest(&output_bin)]);
 
             let model_hash_hex = hex_encode_32(model_hash);
             let input_hash_hex = hex_encode_32(input_hash);
             let output_hash_hex = hex_encode_32(output_hash);
-            let runtime_id_hex = hex_encode_32(runtime_id);
+            let runtime_id_hex = hex_encode_32(opts.runtime_id);
             let trace_root_hex = hex_encode_32(trace_root);
-            let sig_pubkey_hex = hex_encode_32(sig_pubkey);
+            let sig_pubkey_hex = hex_encode_slice(&sig_pubkey);
+            let sig_alg = artifact_sig_alg(&artifact_bin);
 
             let payload = JsonOut::Prove {
                 ok: true,
@@ -229,10 +814,21 @@ fn run(cli: Cli) -> Result<(), CliError> {
                 output_hash: output_hash_hex.clone(),
                 trace_root: trace_root_hex.clone(),
                 sig_pubkey: sig_pubkey_hex.clone(),
+                sig_alg: sig_alg.map(|a| a.name().to_string()),
+                hash_alg: hash_alg.map(|a| a.name().to_string()),
                 out_model_bin: out_model_bin.as_ref().map(|p| path_string_ref(p)),
                 out_input_bin: out_input_bin.as_ref().map(|p| path_string_ref(p)),
-                out_output: path_string_ref(&out_output),
-                out_artifact: path_string_ref(&out_artifact),
+                out_output: path_string_ref(out_output),
+                out_artifact: path_string_ref(out_artifact),
+                out_cose: out_cose.as_ref().map(|p| path_string_ref(p)),
+                out_intoto: out_intoto.as_ref().map(|p| path_string_ref(p)),
+                out_salts: out_salts.as_ref().map(|p| path_string_ref(p)),
+                out_trace: out_trace.as_ref().map(|p| path_string_ref(p)),
+                preprocessor_hash: opts
+                    .preprocessor
+                    .as_deref()
+                    .map(|p| hex_encode_32(sha256(p))),
+                trace_level: trace_level.map(|l| l.name().to_string()),
                 attester_id: attestation_bundle
                     .as_ref()
                     .map(|a| hex_encode_32(a.attester_id)),
@@ -242,6 +838,11 @@ fn run(cli: Cli) -> Result<(), CliError> {
                 attestation: attestation_bundle
                     .as_ref()
                     .map(|a| hex_encode_slice(&a.attestation)),
+                log_index,
+                issued_at: freshness.as_ref().map(|f| f.issued_at),
+                expires_at: freshness.as_ref().and_then(|f| f.expires_at),
+                nonce: freshness.as_ref().map(|f| hex_encode_32(f.nonce)),
+                timestamp_gen_time: freshness.as_ref().and_then(|f| f.timestamp_gen_time),
             };
 
             emit_success(&cli, payload, || {
@@ -252,6 +853,15 @@ fn run(cli: Cli) -> Result<(), CliError> {
                 println!("runtime_id  : {}", runtime_id_hex);
                 println!("trace_root  : {}", trace_root_hex);
                 println!("sig_pubkey  : {}", sig_pubkey_hex);
+                if let Some(alg) = sig_alg {
+                    println!("sig_alg     : {}", alg.name());
+                }
+                if let Some(alg) = hash_alg {
+                    println!("hash_alg    : {}", alg.name());
+                }
+                if let Some(level) = trace_level {
+                    println!("trace_level : {}", level.name());
+                }
                 if let Some(att) = attestation_bundle.as_ref() {
                     println!("attester_id: {}", hex_encode_32(att.attester_id));
                     println!(
@@ -260,6 +870,12 @@ fn run(cli: Cli) -> Result<(), CliError> {
                     );
                     println!("attestation : {}", hex_encode_slice(&att.attestation));
                 }
+                if let Some(f) = freshness.as_ref() {
+                    println!("issued_at   : {}", f.issued_at);
+                }
+                if let Some(i) = log_index {
+                    println!("log_index   : {i}");
+                }
             })?;
 
             Ok(())
@@ -270,53 +886,209 @@ fn run(cli: Cli) -> Result<(), CliError> {
             ref model,
             ref input,
             ref output,
+            ref manifest,
+            require_log_inclusion,
+            ref tree_head,
+            ref log_proof,
+            ref log_dir,
+            ref log_pubkey_hex,
+            max_age_secs,
+            ref expect_nonce_hex,
+            require_timestamp: _,
+            ref tsa_pubkey_hex,
+            ref trusted_root,
+            now_nanos,
+            ref trusted_key_hex,
+            threshold,
+            ref key_status,
+            ref key_status_pubkey_hex,
+            ref salts,
+            ref preprocessor,
+            ref raw_input,
+            ref trace,
         } => {
-            let artifact_bin = read_file(&artifact)?;
-            let model_v0 = read_model_json(&model)?;
-            let input_v0 = read_input_json(&input)?;
-            let output_bin = read_file(&output)?;
-
-            let model_bin = model_v0.encode_bin();
-            let input_bin = input_v0.encode_bin();
-
-            let artifact_version = artifact_version(&artifact_bin).unwrap_or(0);
-            match artifact_version {
-                0 => {
-                    verify_lr_v0(&artifact_bin, &model_bin, &input_bin, &output_bin)
-                        .map_err(|e| CliError::VerifyFailed(format!("{e:?}")))?;
-                }
-                1 => {
-                    verify_lr_v1(&artifact_bin, &model_bin, &input_bin, &output_bin)
-                        .map_err(|e| CliError::VerifyFailed(format!("{e:?}")))?;
+            let loaded_model = read_model(model)?;
+            let model_bin = loaded_model.encode_bin();
+
+            let policy = FreshnessPolicy {
+                now: now_nanos.unwrap_or_else(unix_nanos_now),
+                max_age_nanos: max_age_secs.map(|s| s.saturating_mul(1_000_000_000)),
+                expected_nonce: expect_nonce_hex
+                    .as_deref()
+                    .map(|s| {
+                        parse_hex_32(s).map_err(|_| {
+                            CliError::InvalidHex("expect_nonce_hex must be 64 hex chars (32 bytes)")
+                        })
+                    })
+                    .transpose()?,
+                trusted_tsa: tsa_pubkey_hex
+                    .as_deref()
+                    .map(|s| {
+                        parse_hex_32(s).map_err(|_| {
+                            CliError::InvalidHex("tsa_pubkey_hex must be 64 hex chars (32 bytes)")
+                        })
+                    })
+                    .transpose()?,
+            };
+
+            if let Some(manifest) = manifest {
+                return batch::verify_jsonl(&cli, &loaded_model, &model_bin, &policy, manifest);
+            }
+
+            let artifact = artifact
+                .as_ref()
+                .expect("clap requires --artifact without --manifest");
+            let input = input
+                .as_ref()
+                .or(raw_input.as_ref())
+                .expect("clap requires --input or --raw-input without --manifest");
+            let output = output
+                .as_ref()
+                .expect("clap requires --output without --manifest");
+
+            let (artifact_bin, cert_chain) = read_artifact_with_chain(artifact)?;
+            let output_bin = read_file(output)?;
+            let input_bin = match raw_input {
+                Some(p) => preprocess::read_raw_input_json(p)?,
+                None => read_input_json(input)?.encode_bin(),
+            };
+            let salts = salts
+                .as_ref()
+                .map(|p| {
+                    CommitmentSaltsV0::decode_bin(&read_file(p)?)
+                        .map_err(|e| CliError::VerifyFailed(format!("{}: {e:?}", p.display())))
+                })
+                .transpose()?;
+
+            let mut trace_events = None;
+            match (preprocessor, trace) {
+                (Some(p), _) => preprocess::verify_preprocessed(
+                    &loaded_model,
+                    &artifact_bin,
+                    &preprocess::read_preprocessor_json(p)?,
+                    &model_bin,
+                    &input_bin,
+                    &output_bin,
+                    &policy,
+                )?,
+                (None, Some(t)) => {
+                    trace_events = Some(tracecheck::verify_with_trace(
+                        &loaded_model,
+                        &artifact_bin,
+                        &model_bin,
+                        &input_bin,
+                        &output_bin,
+                        t,
+                        &policy,
+                    )?);
                 }
-                _ => {
+                (None, None) => verify_model(
+                    &loaded_model,
+                    &artifact_bin,
+                    &model_bin,
+                    &input_bin,
+                    &output_bin,
+                    &policy,
+                    salts.as_ref(),
+                )?,
+            }
+
+            let log_index = if require_log_inclusion {
+                let tree_head = tree_head
+                    .as_ref()
+                    .expect("clap requires --tree-head with --require-log-inclusion");
+                let source = match (log_proof, log_dir) {
+                    (Some(p), _) => translog::InclusionSource::ProofFile(p),
+                    (None, Some(d)) => translog::InclusionSource::LogDir(d),
+                    (None, None) => unreachable!("clap requires --log-proof or --log-dir"),
+                };
+                let log_pubkey = parse_log_pubkey(log_pubkey_hex.as_deref())?
+                    .expect("clap requires --log-pubkey-hex with --require-log-inclusion");
+                Some(translog::require_inclusion(
+                    &artifact_bin,
+                    tree_head,
+                    source,
+                    log_pubkey,
+                )?)
+            } else {
+                None
+            };
+
+            let (trace_root, sig_pubkey, attestation_bundle) = artifact_summary(&artifact_bin)
+                .map_err(|e| CliError::VerifyFailed(e.to_string()))?;
+            let freshness = artifact_freshness(&artifact_bin);
+
+            let signer = if trusted_root.is_empty() {
+                None
+            } else {
+                let chain = cert_chain.ok_or_else(|| {
+                    CliError::VerifyFailed("artifact carries no certificate chain".into())
+                })?;
+                let roots = trusted_root
+                    .iter()
+                    .map(read_cert_pem)
+                    .collect::<Result<Vec<_>, _>>()?
+                    .concat();
+                let now_secs = (policy.now / 1_000_000_000) as i64;
+                let leaf_key = sig_pubkey.as_slice().try_into().map_err(|_| {
+                    CliError::VerifyFailed("certificate chains only certify Ed25519 keys".into())
+                })?;
+                Some(
+                    verify_signer_chain(&chain, &roots, leaf_key, now_secs)
+                        .map_err(|e| CliError::VerifyFailed(format!("{e:?}")))?,
+                )
+            };
+
+            let signers = artifact_signers(&artifact_bin);
+            if !trusted_key_hex.is_empty() {
+                let trusted = trusted_key_hex
+                    .iter()
+                    .map(|s| {
+                        parse_hex_32(s).map_err(|_| {
+                            CliError::InvalidHex("trusted_key_hex must be 64 hex chars (32 bytes)")
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let k = threshold.unwrap_or(1) as usize;
+                // ECDSA keys of v5 artifacts never match a trusted Ed25519 key
+                let single: Vec<[u8; 32]> =
+                    sig_pubkey.as_slice().try_into().ok().into_iter().collect();
+                let found = ThresholdPolicy::k_of_n(k, trusted)
+                    .count(signers.as_deref().unwrap_or(&single));
+                if found < k {
                     return Err(CliError::VerifyFailed(format!(
-                        "unsupported artifact version: {artifact_version}"
+                        "ThresholdNotMet: {found} of {k} required trusted signers"
                     )));
                 }
-            };
+            }
 
-            let (trace_root, sig_pubkey, attestation_bundle) = match artifact_version {
-                0 => {
-                    let art = ProofArtifactV0::decode_bin(&artifact_bin)
-                        .map_err(|_| CliError::VerifyFailed("artifact decode failed".into()))?;
-                    (art.trace_root, art.sig_pubkey, None)
-                }
-                1 => {
-                    let art = ProofArtifactV1::decode_bin(&artifact_bin)
-                        .map_err(|_| CliError::VerifyFailed("artifact decode failed".into()))?;
-                    (art.trace_root, art.sig_pubkey, Some(art.attestation))
-                }
-                _ => unreachable!(),
-            };
+            if let (Some(list), Some(authority)) = (key_status, key_status_pubkey_hex) {
+                let authority = parse_hex_32(authority).map_err(|_| {
+                    CliError::InvalidHex("key_status_pubkey_hex must be 64 hex chars (32 bytes)")
+                })?;
+                keystatus::require_key_status(
+                    &artifact_bin,
+                    list,
+                    authority,
+                    policy.trusted_tsa,
+                    policy.now,
+                )?;
+            }
 
             let trace_root_hex = hex_encode_32(trace_root);
-            let sig_pubkey_hex = hex_encode_32(sig_pubkey);
+            let sig_pubkey_hex = hex_encode_slice(&sig_pubkey);
+            let sig_alg = artifact_sig_alg(&artifact_bin);
+            let hash_alg = artifact_hash_alg(&artifact_bin);
+            let trace_level = artifact_trace_level(&artifact_bin);
 
             let payload = JsonOut::Verify {
                 ok: true,
                 trace_root: trace_root_hex.clone(),
                 sig_pubkey: sig_pubkey_hex.clone(),
+                sig_alg: sig_alg.map(|a| a.name().to_string()),
+                hash_alg: hash_alg.map(|a| a.name().to_string()),
+                trace_level: trace_level.map(|l| l.name().to_string()),
+                trace_events,
                 artifact: path_string_ref(artifact),
                 model: path_string_ref(model),
                 input: path_string_ref(input),
@@ -330,12 +1102,31 @@ fn run(cli: Cli) -> Result<(), CliError> {
                 attestation: attestation_bundle
                     .as_ref()
                     .map(|a| hex_encode_slice(&a.attestation)),
+                log_index,
+                issued_at: freshness.as_ref().map(|f| f.issued_at),
+                expires_at: freshness.as_ref().and_then(|f| f.expires_at),
+                nonce: freshness.as_ref().map(|f| hex_encode_32(f.nonce)),
+                timestamp_gen_time: freshness.as_ref().and_then(|f| f.timestamp_gen_time),
+                signer_subject: signer.as_ref().map(|s| s.subject.clone()),
+                signer_root: signer.as_ref().map(|s| s.root_subject.clone()),
+                signers: signers
+                    .as_ref()
+                    .map(|s| s.iter().copied().map(hex_encode_32).collect()),
             };
 
             emit_success(&cli, payload, || {
                 println!("ok");
                 println!("trace_root : {}", trace_root_hex);
                 println!("sig_pubkey : {}", sig_pubkey_hex);
+                if let Some(alg) = sig_alg {
+                    println!("sig_alg    : {}", alg.name());
+                }
+                if let Some(alg) = hash_alg {
+                    println!("hash_alg   : {}", alg.name());
+                }
+                if let Some(level) = trace_level {
+                    println!("trace_level: {}", level.name());
+                }
                 if let Some(att) = attestation_bundle.as_ref() {
                     println!("attester_id: {}", hex_encode_32(att.attester_id));
                     println!(
@@ -344,13 +1135,716 @@ fn run(cli: Cli) -> Result<(), CliError> {
                     );
                     println!("attestation : {}", hex_encode_slice(&att.attestation));
                 }
+                if let Some(f) = freshness.as_ref() {
+                    println!("issued_at  : {}", f.issued_at);
+                }
+                if let Some(n) = trace_events {
+                    println!("trace_events: {n}");
+                }
+                if let Some(i) = log_index {
+                    println!("log_index  : {i}");
+                }
+                if let Some(s) = signer.as_ref() {
+                    println!("signer     : {}", s.subject);
+                }
+                for key in signers.iter().flatten() {
+                    println!("cosigner   : {}", hex_encode_32(*key));
+                }
             })?;
 
             Ok(())
         }
+
+        Command::Cosign {
+            ref artifact,
+            ref model,
+            ref input,
+            ref output,
+            ref key_hex,
+            ref agent_socket,
+            ref out,
+        } => {
+            let signer = prove_signer(key_hex.as_deref(), agent_socket.as_deref(), None)?;
+            let loaded_model = read_model(model)?;
+            let model_bin = loaded_model.encode_bin();
+            let input_bin = read_input_json(input)?.encode_bin();
+            let output_bin = read_file(output)?;
+            let artifact_bin = read_artifact(artifact)?;
+
+            let cosign = match loaded_model {
+                LoadedModel::Logistic(_) => cosign_lr_v4,
+                LoadedModel::Mlp(_) => cosign_mlp_v4,
+            };
+            let cosigned = cosign(&artifact_bin, &model_bin, &input_bin, &output_bin, &signer)
+                .map_err(|e| CliError::VerifyFailed(format!("{e:?}")))?;
+            write_file_atomic(out, &cosigned)?;
+
+            let signers: Vec<String> = artifact_signers(&cosigned)
+                .unwrap_or_default()
+                .into_iter()
+                .map(hex_encode_32)
+                .collect();
+            let payload = JsonOut::Cosign {
+                ok: true,
+                out: path_string_ref(out),
+                signers: signers.clone(),
+            };
+            emit_success(&cli, payload, || {
+                println!("ok");
+                for key in &signers {
+                    println!("cosigner : {key}");
+                }
+            })
+        }
+
+        Command::KeyStatus {
+            ref keys,
+            ref key_hex,
+            ref out,
+        } => {
+            let signing_key = parse_hex_32(key_hex)
+                .map_err(|_| CliError::InvalidHex("key_hex must be 64 hex chars (32 bytes)"))?;
+            keystatus::key_status(&cli, keys, signing_key, out)
+        }
+
+        Command::LogHead {
+            ref log_dir,
+            ref key_hex,
+            ref out,
+        } => {
+            let signing_key = parse_hex_32(key_hex)
+                .map_err(|_| CliError::InvalidHex("key_hex must be 64 hex chars (32 bytes)"))?;
+            translog::log_head(&cli, log_dir, signing_key, out.as_ref())
+        }
+
+        Command::LogInclusion {
+            ref log_dir,
+            ref artifact,
+            ref tree_head,
+            ref out,
+        } => translog::log_inclusion(&cli, log_dir, artifact, tree_head, out),
+
+        Command::LogConsistency {
+            ref log_dir,
+            ref old_head,
+            ref new_head,
+            ref log_pubkey_hex,
+            ref out,
+        } => {
+            let log_pubkey = parse_log_pubkey(log_pubkey_hex.as_deref())?;
+            translog::log_consistency(&cli, log_dir, old_head, new_head, log_pubkey, out.as_ref())
+        }
+
+        Command::Disclose {
+            ref artifact,
+            ref model,
+            ref input,
+            ref index,
+            ref kind,
+            ref out,
+        } => disclosure::disclose(&cli, artifact, model, input, index, kind, out),
+
+        Command::VerifyDisclosure {
+            ref artifact,
+            ref disclosure,
+        } => disclosure::verify_disclosure(&cli, artifact, disclosure),
+
+        Command::CheckTrace {
+            ref artifact,
+            ref output,
+            ref trace,
+        } => tracecheck::check_trace(&cli, artifact, output, trace),
+
+        Command::Pipeline {
+            ref stage_artifact,
+            ref stage_output,
+            ref key_hex,
+            ref out,
+        } => {
+            let signing_key = parse_hex_32(key_hex)
+                .map_err(|_| CliError::InvalidHex("key_hex must be 64 hex chars (32 bytes)"))?;
+            pipeline::pipeline(&cli, stage_artifact, stage_output, signing_key, out)
+        }
+
+        Command::VerifyPipeline {
+            ref pipeline,
+            ref pipeline_pubkey_hex,
+            ref model,
+            ref input,
+            now_nanos,
+        } => {
+            let expected_pubkey = pipeline_pubkey_hex
+                .as_deref()
+                .map(|s| {
+                    parse_hex_32(s).map_err(|_| {
+                        CliError::InvalidHex("pipeline_pubkey_hex must be 64 hex chars (32 bytes)")
+                    })
+                })
+                .transpose()?;
+            let policy = FreshnessPolicy::at(now_nanos.unwrap_or_else(unix_nanos_now));
+            pipeline::verify_pipeline(
+                &cli,
+                pipeline,
+                expected_pubkey,
+                model,
+                input.as_ref(),
+                &policy,
+            )
+        }
+
+        Command::Serve {
+            ref models_dir,
+            ref key_hex,
+            ref bind,
+            threads,
+            attest,
+            ref runtime_id_hex,
+        } => {
+            let signing_key = parse_hex_32(key_hex)
+                .map_err(|_| CliError::InvalidHex("key_hex must be 64 hex chars (32 bytes)"))?;
+            let opts = serve::ServeOptions {
+                bind: bind.clone(),
+                threads,
+                prove: ProveOptions {
+                    attest,
+                    runtime_id: parse_runtime_id(runtime_id_hex.as_deref())?,
+                    signer: ProveSigner::Key(signing_key),
+                    fresh: None,
+                    multisig: false,
+                    sig_alg: None,
+                    hash_alg: None,
+                    salts: None,
+                    preprocessor: None,
+                    trace_level: None,
+                },
+            };
+            serve::serve(models_dir, opts)
+        }
+    }
+}
+
+fn parse_runtime_id(runtime_id_hex: Option<&str>) -> Result<[u8; 32], CliError> {
+    match runtime_id_hex {
+        Some(s) => parse_hex_32(s)
+            .map_err(|_| CliError::InvalidHex("runtime_id_hex must be 64 hex chars (32 bytes)")),
+        None => Ok(sha256(b"verifai-cli-default-runtime")),
+    }
+}
+
+fn parse_log_pubkey(log_pubkey_hex: Option<&str>) -> Result<Option<[u8; 32]>, CliError> {
+    log_pubkey_hex
+        .map(|s| {
+            parse_hex_32(s)
+                .map_err(|_| CliError::InvalidHex("log_pubkey_hex must be 64 hex chars (32 bytes)"))
+        })
+        .transpose()
+}
+
+/// The signer for `--key-hex` or `--agent-socket`; clap requires one of them.
+/// With `sig_alg`, `--key-hex` is a secret key for that algorithm and the
+/// agent must hold one.
+fn prove_signer(
+    key_hex: Option<&str>,
+    agent_socket: Option<&Path>,
+    sig_alg: Option<SigAlg>,
+) -> Result<ProveSigner, CliError> {
+    match (key_hex, agent_socket) {
+        (Some(s), _) => {
+            let secret = parse_hex_32(s)
+                .map_err(|_| CliError::InvalidHex("key_hex must be 64 hex chars (32 bytes)"))?;
+            match sig_alg {
+                Some(alg) => AlgSigningKey::new(alg, secret)
+                    .map(ProveSigner::Alg)
+                    .map_err(|_| {
+                        CliError::InvalidHex("key_hex is not a valid secret key for --sig-alg")
+                    }),
+                None => Ok(ProveSigner::Key(secret)),
+            }
+        }
+        #[cfg(unix)]
+        (None, Some(socket)) => {
+            let agent =
+                AgentSigner::connect(socket).map_err(|e| CliError::Runtime(format!("{e:?}")))?;
+            match sig_alg {
+                Some(alg) if agent.sig_alg() != alg => Err(CliError::Runtime(format!(
+                    "the agent signs with {}, not --sig-alg {}",
+                    agent.sig_alg().name(),
+                    alg.name()
+                ))),
+                _ => Ok(ProveSigner::Agent(agent)),
+            }
+        }
+        #[cfg(not(unix))]
+        (None, Some(_)) => Err(CliError::Runtime(
+            "--agent-socket needs Unix domain sockets".into(),
+        )),
+        (None, None) => unreachable!("clap requires --key-hex or --agent-socket"),
+    }
+}
+
+/* ------------------------------ Dispatch ------------------------------- */
+
+/// Where prove and cosign get their signatures from.
+pub(crate) enum ProveSigner {
+    Key([u8; 32]),
+    /// A `--key-hex` for `--sig-alg`.
+    Alg(AlgSigningKey),
+    #[cfg(unix)]
+    Agent(AgentSigner),
+}
+
+impl ProveSigner {
+    /// The raw key, for encodings that sign outside `Signer` (COSE, DSSE).
+    fn secret_key(&self) -> Option<[u8; 32]> {
+        match self {
+            ProveSigner::Key(k) => Some(*k),
+            ProveSigner::Alg(_) => None,
+            #[cfg(unix)]
+            ProveSigner::Agent(_) => None,
+        }
+    }
+}
+
+impl Signer for ProveSigner {
+    fn sig_alg(&self) -> SigAlg {
+        match self {
+            ProveSigner::Key(k) => k.sig_alg(),
+            ProveSigner::Alg(k) => k.alg(),
+            #[cfg(unix)]
+            ProveSigner::Agent(a) => a.sig_alg(),
+        }
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        match self {
+            ProveSigner::Key(k) => Signer::public_key(k),
+            ProveSigner::Alg(k) => k.public_key(),
+            #[cfg(unix)]
+            ProveSigner::Agent(a) => a.public_key(),
+        }
+    }
+
+    fn sign(&self, msg: &[u8]) -> Result<[u8; 64], SignerError> {
+        match self {
+            ProveSigner::Key(k) => k.sign(msg),
+            ProveSigner::Alg(k) => Signer::sign(k, msg),
+            #[cfg(unix)]
+            ProveSigner::Agent(a) => a.sign(msg),
+        }
+    }
+}
+
+/// Signing and artifact-version choices shared by single, batch and served proving.
+pub(crate) struct ProveOptions {
+    pub attest: bool,
+    pub runtime_id: [u8; 32],
+    pub signer: ProveSigner,
+    /// Produce `ProofArtifactV3` when set.
+    pub fresh: Option<FreshOptions>,
+    /// Produce `ProofArtifactV4`.
+    pub multisig: bool,
+    /// Produce `ProofArtifactV5` signed with this algorithm.
+    pub sig_alg: Option<SigAlg>,
+    /// Produce `ProofArtifactV6` with this hash algorithm, signed with
+    /// `sig_alg` (Ed25519 when unset).
+    pub hash_alg: Option<HashAlg>,
+    /// Produce `ProofArtifactV7` with commitments salted by these.
+    pub salts: Option<CommitmentSaltsV0>,
+    /// Produce `ProofArtifactV8` with this encoded `PreprocessorV1`; the
+    /// input is then an encoded `RawInputV1`.
+    pub preprocessor: Option<Vec<u8>>,
+    /// Produce `ProofArtifactV9` with its trace recorded at this level.
+    pub trace_level: Option<TraceLevel>,
+}
+
+pub(crate) struct FreshOptions {
+    pub nonce: [u8; 32],
+    pub expires_in_nanos: Option<u64>,
+    pub tsa: Option<LocalTsa>,
+}
+
+/// Proves one input, handing its trace events to `sink` as the run produces
+/// them. v8 and v9 runs, which clap keeps away from `--out-trace`, leave it
+/// untouched.
+fn prove_model(
+    loaded_model: &LoadedModel,
+    opts: &ProveOptions,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    sink: &mut dyn TraceSink,
+) -> Result<(Vec<u8>, Vec<u8>), CliError> {
+    let (runtime_id, signer) = (opts.runtime_id, &opts.signer);
+    if let Some(fresh) = &opts.fresh {
+        let issued_at = unix_nanos_now();
+        let issuance = IssuanceV3 {
+            issued_at,
+            expires_at: fresh.expires_in_nanos.map(|d| issued_at.saturating_add(d)),
+            nonce: fresh.nonce,
+        };
+        let tsa = fresh.tsa.as_ref();
+        let result = match loaded_model {
+            LoadedModel::Mlp(_) => prove_mlp_v3_with_sink(
+                runtime_id, signer, model_bin, input_bin, &issuance, tsa, sink,
+            ),
+            LoadedModel::Logistic(_) => prove_lr_v3_with_sink::<NoopAttester>(
+                runtime_id, signer, model_bin, input_bin, &issuance, tsa, sink,
+            ),
+        };
+        return result.map_err(|e| CliError::Runtime(format!("prove failed (v3): {e:?}")));
+    }
+    if opts.multisig {
+        let result = match loaded_model {
+            LoadedModel::Mlp(_) => {
+                prove_mlp_v4_with_sink(runtime_id, signer, model_bin, input_bin, sink)
+            }
+            LoadedModel::Logistic(_) => prove_lr_v4_with_sink::<NoopAttester>(
+                runtime_id, signer, model_bin, input_bin, sink,
+            ),
+        };
+        return result.map_err(|e| CliError::Runtime(format!("prove failed (v4): {e:?}")));
+    }
+    if let Some(hash_alg) = opts.hash_alg {
+        // signs with --sig-alg when given (see `prove_signer`), Ed25519 otherwise
+        let result = match loaded_model {
+            LoadedModel::Mlp(_) => {
+                prove_mlp_v6_with_sink(runtime_id, signer, hash_alg, model_bin, input_bin, sink)
+            }
+            LoadedModel::Logistic(_) => prove_lr_v6_with_sink::<NoopAttester>(
+                runtime_id, signer, hash_alg, model_bin, input_bin, sink,
+            ),
+        };
+        return result.map_err(|e| CliError::Runtime(format!("prove failed (v6): {e:?}")));
+    }
+    if let Some(level) = opts.trace_level {
+        let result = match loaded_model {
+            LoadedModel::Mlp(_) => prove_mlp_v9(runtime_id, signer, level, model_bin, input_bin),
+            LoadedModel::Logistic(_) => prove_lr_v9_with_attester::<NoopAttester>(
+                runtime_id, signer, level, model_bin, input_bin,
+            ),
+        };
+        return result.map_err(|e| CliError::Runtime(format!("prove failed (v9): {e:?}")));
+    }
+    if let Some(preprocessor_bin) = &opts.preprocessor {
+        let result = match loaded_model {
+            LoadedModel::Mlp(_) => {
+                prove_mlp_v8(runtime_id, signer, preprocessor_bin, model_bin, input_bin)
+            }
+            LoadedModel::Logistic(_) => prove_lr_v8_with_attester::<NoopAttester>(
+                runtime_id,
+                signer,
+                preprocessor_bin,
+                model_bin,
+                input_bin,
+            ),
+        };
+        return result.map_err(|e| CliError::Runtime(format!("prove failed (v8): {e:?}")));
+    }
+    if let Some(salts) = &opts.salts {
+        let result = match loaded_model {
+            LoadedModel::Mlp(_) => {
+                prove_mlp_v7_with_sink(runtime_id, signer, model_bin, input_bin, salts, sink)
+            }
+            LoadedModel::Logistic(_) => prove_lr_v7_with_sink::<NoopAttester>(
+                runtime_id, signer, model_bin, input_bin, salts, sink,
+            ),
+        };
+        return result.map_err(|e| CliError::Runtime(format!("prove failed (v7): {e:?}")));
+    }
+    if opts.sig_alg.is_some() {
+        // `prove_signer` made the signer one for --sig-alg
+        let result = match loaded_model {
+            LoadedModel::Mlp(_) => {
+                prove_mlp_v5_with_sink(runtime_id, signer, model_bin, input_bin, sink)
+            }
+            LoadedModel::Logistic(_) => prove_lr_v5_with_sink::<NoopAttester>(
+                runtime_id, signer, model_bin, input_bin, sink,
+            ),
+        };
+        return result.map_err(|e| CliError::Runtime(format!("prove failed (v5): {e:?}")));
+    }
+
+    match loaded_model {
+        LoadedModel::Mlp(_) => {
+            prove_mlp_v1_with_sink(runtime_id, signer, model_bin, input_bin, sink)
+                .map_err(|e| CliError::Runtime(format!("prove failed (mlp): {e:?}")))
+        }
+        LoadedModel::Logistic(_) if opts.attest => {
+            prove_lr_v1_with_sink::<NoopAttester>(runtime_id, signer, model_bin, input_bin, sink)
+                .map_err(|e| CliError::Runtime(format!("prove failed (v1): {e:?}")))
+        }
+        LoadedModel::Logistic(_) => {
+            prove_lr_v0_with_sink(runtime_id, signer, model_bin, input_bin, sink)
+                .map_err(|e| CliError::Runtime(format!("prove failed: {e:?}")))
+        }
+    }
+}
+
+/// Replays and checks an artifact of any single-input version. Freshness
+/// requirements beyond expiry fail for versions without freshness fields; v4
+/// artifacts need one valid signature here, trusted-key thresholds are
+/// checked by the caller. v7 artifacts need `salts`.
+fn verify_model(
+    loaded_model: &LoadedModel,
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+    policy: &FreshnessPolicy,
+    salts: Option<&CommitmentSaltsV0>,
+) -> Result<(), CliError> {
+    let artifact_version = artifact_version(artifact_bin).unwrap_or(0);
+    check_verifiable(
+        artifact_version,
+        loaded_model,
+        policy,
+        salts.is_some(),
+        false,
+    )?;
+
+    let result = match (artifact_version, loaded_model) {
+        (0, LoadedModel::Logistic(_)) => {
+            verify_lr_v0(artifact_bin, model_bin, input_bin, output_bin)
+        }
+        (1, LoadedModel::Logistic(_)) => {
+            verify_lr_v1(artifact_bin, model_bin, input_bin, output_bin)
+        }
+        (1, LoadedModel::Mlp(_)) => verify_mlp_v1(artifact_bin, model_bin, input_bin, output_bin),
+        (3, LoadedModel::Logistic(_)) => {
+            verify_lr_v3(artifact_bin, model_bin, input_bin, output_bin, policy)
+        }
+        (3, LoadedModel::Mlp(_)) => {
+            verify_mlp_v3(artifact_bin, model_bin, input_bin, output_bin, policy)
+        }
+        (4, LoadedModel::Logistic(_)) => verify_lr_v4(
+            artifact_bin,
+            model_bin,
+            input_bin,
+            output_bin,
+            &ThresholdPolicy::any_signer(),
+        )
+        .map(|_| ()),
+        (4, LoadedModel::Mlp(_)) => verify_mlp_v4(
+            artifact_bin,
+            model_bin,
+            input_bin,
+            output_bin,
+            &ThresholdPolicy::any_signer(),
+        )
+        .map(|_| ()),
+        (5, LoadedModel::Logistic(_)) => {
+            verify_lr_v5(artifact_bin, model_bin, input_bin, output_bin).map(|_| ())
+        }
+        (5, LoadedModel::Mlp(_)) => {
+            verify_mlp_v5(artifact_bin, model_bin, input_bin, output_bin).map(|_| ())
+        }
+        (6, LoadedModel::Logistic(_)) => {
+            verify_lr_v6(artifact_bin, model_bin, input_bin, output_bin).map(|_| ())
+        }
+        (6, LoadedModel::Mlp(_)) => {
+            verify_mlp_v6(artifact_bin, model_bin, input_bin, output_bin).map(|_| ())
+        }
+        (7, LoadedModel::Logistic(_)) => {
+            let salts = salts.expect("check_verifiable requires salts for v7");
+            verify_lr_v7(artifact_bin, model_bin, input_bin, output_bin, salts).map(|_| ())
+        }
+        (7, LoadedModel::Mlp(_)) => {
+            let salts = salts.expect("check_verifiable requires salts for v7");
+            verify_mlp_v7(artifact_bin, model_bin, input_bin, output_bin, salts).map(|_| ())
+        }
+        (9, LoadedModel::Logistic(_)) => {
+            verify_lr_v9(artifact_bin, model_bin, input_bin, output_bin).map(|_| ())
+        }
+        (9, LoadedModel::Mlp(_)) => {
+            verify_mlp_v9(artifact_bin, model_bin, input_bin, output_bin).map(|_| ())
+        }
+        _ => unreachable!("check_verifiable rejects other versions"),
+    };
+    result.map_err(|e| CliError::VerifyFailed(format!("{e:?}")))
+}
+
+/// Rejects artifact versions the caller cannot check for this model (v7
+/// without salts, v8 without a preprocessor), and freshness requirements on
+/// versions without freshness fields.
+fn check_verifiable(
+    artifact_version: u16,
+    loaded_model: &LoadedModel,
+    policy: &FreshnessPolicy,
+    has_salts: bool,
+    has_preprocessor: bool,
+) -> Result<(), CliError> {
+    let wants_freshness = policy.max_age_nanos.is_some()
+        || policy.expected_nonce.is_some()
+        || policy.trusted_tsa.is_some();
+    if wants_freshness && artifact_version != 3 {
+        return Err(CliError::VerifyFailed(format!(
+            "artifact version {artifact_version} carries no freshness fields"
+        )));
+    }
+    match (artifact_version, loaded_model) {
+        (0, LoadedModel::Mlp(_)) => Err(CliError::VerifyFailed(
+            "mlp models are only proven with v1 artifacts".into(),
+        )),
+        (0 | 1 | 3 | 4 | 5 | 6 | 9, _) => Ok(()),
+        (7, _) if has_salts => Ok(()),
+        (7, _) => Err(CliError::VerifyFailed(
+            "v7 artifacts have salted commitments; pass their --salts".into(),
+        )),
+        (8, _) if has_preprocessor => Ok(()),
+        (8, _) => Err(CliError::VerifyFailed(
+            "v8 artifacts commit to a raw input; pass --preprocessor and --raw-input".into(),
+        )),
+        _ => Err(CliError::VerifyFailed(format!(
+            "unsupported artifact version: {artifact_version}"
+        ))),
+    }
+}
+
+type ArtifactSummary = ([u8; 32], Vec<u8>, Option<AttestationBundle>);
+
+/// Trace root (the trace commitment for v7), signing key (the first co-signer
+/// for v4, any `SigAlg` key for v5 and v6) and attestation bundle (v1 and
+/// later) of an artifact.
+fn artifact_summary(artifact_bin: &[u8]) -> Result<ArtifactSummary, &'static str> {
+    match artifact_version(artifact_bin).unwrap_or(0) {
+        0 => {
+            let art =
+                ProofArtifactV0::decode_bin(artifact_bin).map_err(|_| "artifact decode failed")?;
+            Ok((art.trace_root, art.sig_pubkey.to_vec(), None))
+        }
+        1 => {
+            let art =
+                ProofArtifactV1::decode_bin(artifact_bin).map_err(|_| "artifact decode failed")?;
+            Ok((
+                art.trace_root,
+                art.sig_pubkey.to_vec(),
+                Some(art.attestation),
+            ))
+        }
+        3 => {
+            let art =
+                ProofArtifactV3::decode_bin(artifact_bin).map_err(|_| "artifact decode failed")?;
+            Ok((
+                art.trace_root,
+                art.sig_pubkey.to_vec(),
+                Some(art.attestation),
+            ))
+        }
+        4 => {
+            let art =
+                ProofArtifactV4::decode_bin(artifact_bin).map_err(|_| "artifact decode failed")?;
+            let first = art
+                .signatures
+                .first()
+                .ok_or("artifact carries no signature")?;
+            Ok((art.trace_root, first.pubkey.to_vec(), Some(art.attestation)))
+        }
+        5 => {
+            let art =
+                ProofArtifactV5::decode_bin(artifact_bin).map_err(|_| "artifact decode failed")?;
+            Ok((art.trace_root, art.sig_pubkey, Some(art.attestation)))
+        }
+        6 => {
+            let art =
+                ProofArtifactV6::decode_bin(artifact_bin).map_err(|_| "artifact decode failed")?;
+            Ok((art.trace_root, art.sig_pubkey, Some(art.attestation)))
+        }
+        7 => {
+            let art =
+                ProofArtifactV7::decode_bin(artifact_bin).map_err(|_| "artifact decode failed")?;
+            Ok((
+                art.trace_commitment,
+                art.sig_pubkey.to_vec(),
+                Some(art.attestation),
+            ))
+        }
+        8 => {
+            let art =
+                ProofArtifactV8::decode_bin(artifact_bin).map_err(|_| "artifact decode failed")?;
+            Ok((
+                art.trace_root,
+                art.sig_pubkey.to_vec(),
+                Some(art.attestation),
+            ))
+        }
+        9 => {
+            let art =
+                ProofArtifactV9::decode_bin(artifact_bin).map_err(|_| "artifact decode failed")?;
+            Ok((
+                art.trace_root,
+                art.sig_pubkey.to_vec(),
+                Some(art.attestation),
+            ))
+        }
+        _ => Err("artifact version not supported"),
+    }
+}
+
+struct Freshness {
+    issued_at: u64,
+    expires_at: Option<u64>,
+    nonce: [u8; 32],
+    timestamp_gen_time: Option<u64>,
+}
+
+/// Freshness fields of a v3 artifact; `None` for other versions.
+fn artifact_freshness(artifact_bin: &[u8]) -> Option<Freshness> {
+    let art = ProofArtifactV3::decode_bin(artifact_bin).ok()?;
+    Some(Freshness {
+        issued_at: art.issued_at,
+        expires_at: art.expires_at,
+        nonce: art.nonce,
+        timestamp_gen_time: art.timestamp.map(|t| t.gen_time),
+    })
+}
+
+/// Signature algorithm of a v5 or v6 artifact; `None` for other versions,
+/// which are all Ed25519.
+fn artifact_sig_alg(artifact_bin: &[u8]) -> Option<SigAlg> {
+    match artifact_version(artifact_bin) {
+        Some(5) => ProofArtifactV5::decode_bin(artifact_bin)
+            .ok()
+            .map(|art| art.sig_alg),
+        Some(6) => ProofArtifactV6::decode_bin(artifact_bin)
+            .ok()
+            .map(|art| art.sig_alg),
+        _ => None,
     }
 }
 
+/// Hash algorithm of a v6 artifact; `None` for other versions, which are all
+/// SHA-256.
+fn artifact_hash_alg(artifact_bin: &[u8]) -> Option<HashAlg> {
+    ProofArtifactV6::decode_bin(artifact_bin)
+        .ok()
+        .map(|art| art.hash_alg)
+}
+
+/// Trace level of a v9 artifact; `None` for other versions, which all
+/// record `TraceLevel::Op`.
+fn artifact_trace_level(artifact_bin: &[u8]) -> Option<TraceLevel> {
+    ProofArtifactV9::decode_bin(artifact_bin)
+        .ok()
+        .map(|art| art.trace_level)
+}
+
+/// Salted model, input and output commitments of a v7 artifact; `None` for
+/// other versions.
+fn artifact_salted_commitments(artifact_bin: &[u8]) -> Option<[[u8; 32]; 3]> {
+    let art = ProofArtifactV7::decode_bin(artifact_bin).ok()?;
+    Some([
+        art.model_commitment,
+        art.input_commitment,
+        art.output_commitment,
+    ])
+}
+
+/// Co-signer keys of a v4 artifact in artifact order; `None` for other
+/// versions.
+fn artifact_signers(artifact_bin: &[u8]) -> Option<Vec<[u8; 32]>> {
+    let art = ProofArtifactV4::decode_bin(artifact_bin).ok()?;
+    Some(art.signatures.iter().map(|s| s.pubkey).collect())
+}
+
 /* ----------------------------- JSON output ----------------------------- */
 
 #[derive(serde::Serialize)]
@@ -371,22 +1865,56 @@ enum JsonOut {
         trace_root: String,
         sig_pubkey: String,
         #[serde(skip_serializing_if = "Option::is_none")]
+        sig_alg: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        hash_alg: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         out_model_bin: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         out_input_bin: Option<String>,
         out_output: String,
         out_artifact: String,
         #[serde(skip_serializing_if = "Option::is_none")]
+        out_cose: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        out_intoto: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        out_salts: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        out_trace: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        preprocessor_hash: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        trace_level: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         attester_id: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         attestation_measurement: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         attestation: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        log_index: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        issued_at: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        expires_at: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        nonce: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        timestamp_gen_time: Option<u64>,
     },
     Verify {
         ok: bool,
         trace_root: String,
         sig_pubkey: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        sig_alg: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        hash_alg: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        trace_level: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        trace_events: Option<usize>,
         artifact: String,
         model: String,
         input: String,
@@ -397,6 +1925,130 @@ enum JsonOut {
         attestation_measurement: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         attestation: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        log_index: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        issued_at: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        expires_at: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        nonce: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        timestamp_gen_time: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        signer_subject: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        signer_root: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        signers: Option<Vec<String>>,
+    },
+    Cosign {
+        ok: bool,
+        out: String,
+        signers: Vec<String>,
+    },
+    KeyStatus {
+        ok: bool,
+        authority_pubkey: String,
+        keys: usize,
+        out: String,
+    },
+    ProveRecord {
+        ok: bool,
+        index: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        output_hash: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        trace_root: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        out_output: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        out_artifact: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        log_index: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    VerifyRecord {
+        ok: bool,
+        index: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        trace_root: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    ProveBatch {
+        ok: bool,
+        total: usize,
+        succeeded: usize,
+        failed: usize,
+        manifest: String,
+    },
+    VerifyBatch {
+        ok: bool,
+        total: usize,
+        succeeded: usize,
+        failed: usize,
+        manifest: String,
+        elapsed_ms: u64,
+        records_per_sec: f64,
+    },
+    LogHead {
+        ok: bool,
+        tree_size: u64,
+        root_hash: String,
+        log_pubkey: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        out: Option<String>,
+    },
+    LogInclusion {
+        ok: bool,
+        leaf_index: u64,
+        tree_size: u64,
+        out: String,
+    },
+    LogConsistency {
+        ok: bool,
+        old_size: u64,
+        new_size: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        out: Option<String>,
+    },
+    Disclose {
+        ok: bool,
+        leaf_count: u32,
+        indices: Vec<u32>,
+        proof_hashes: usize,
+        out: String,
+    },
+    VerifyDisclosure {
+        ok: bool,
+        hash_alg: String,
+        leaf_count: u32,
+        events: Vec<disclosure::DisclosedEventJson>,
+    },
+    Pipeline {
+        ok: bool,
+        stages: usize,
+        sig_pubkey: String,
+        out: String,
+    },
+    VerifyPipeline {
+        ok: bool,
+        stages: usize,
+        sig_pubkey: String,
+        replayed: bool,
+        y: f64,
+    },
+    CheckTrace {
+        ok: bool,
+        trace_root: String,
+        events: usize,
+        y: f64,
     },
 }
 
@@ -435,6 +2087,67 @@ fn path_string_ref(p: &Path) -> String {
     p.to_string_lossy().into_owned()
 }
 
+/* ----------------------------- Model loading ----------------------------- */
+
+enum LoadedModel {
+    Logistic(LogisticModelV0),
+    Mlp(MlpModelV1),
+}
+
+impl LoadedModel {
+    fn encode_bin(&self) -> Vec<u8> {
+        match self {
+            LoadedModel::Logistic(m) => m.encode_bin(),
+            LoadedModel::Mlp(m) => m.encode_bin(),
+        }
+    }
+}
+
+/// Loads a model from JSON (logistic only) or from a weights container.
+/// Weights containers map to `MlpModelV1` when they carry a `w1` tensor and
+/// to `LogisticModelV0` otherwise.
+fn read_model(path: &PathBuf) -> Result<LoadedModel, CliError> {
+    let tensors = if path.is_dir() {
+        read_npy_dir(path)?
+    } else {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("safetensors") => load_safetensors(&read_file(path)?)
+                .map_err(|e| CliError::Weights(format!("{}: {e:?}", path.display())))?,
+            Some("npz") => load_npz(&read_file(path)?)
+                .map_err(|e| CliError::Weights(format!("{}: {e:?}", path.display())))?,
+            _ => return Ok(LoadedModel::Logistic(read_model_json(path)?)),
+        }
+    };
+
+    let loaded = if tensors.contains("w1") {
+        tensors.to_mlp_v1().map(LoadedModel::Mlp)
+    } else {
+        tensors.to_logistic_v0().map(LoadedModel::Logistic)
+    };
+    loaded.map_err(|e| CliError::Weights(format!("{}: {e:?}", path.display())))
+}
+
+fn read_npy_dir(dir: &PathBuf) -> Result<TensorSet, CliError> {
+    let entries = fs::read_dir(dir).map_err(|e| CliError::Io(format!("{}: {e}", dir.display())))?;
+    let mut tensors = TensorSet::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| CliError::Io(format!("{}: {e}", dir.display())))?
+            .path();
+        if path.extension().and_then(|e| e.to_str()) != Some("npy") {
+            continue;
+        }
+        let name = match path.file_stem().and_then(|s| s.to_str()) {
+            Some(n) => n.to_string(),
+            None => continue,
+        };
+        let tensor = load_npy(&read_file(&path)?)
+            .map_err(|e| CliError::Weights(format!("{}: {e:?}", path.display())))?;
+        tensors.insert(name, tensor);
+    }
+    Ok(tensors)
+}
+
 /* ----------------------------- JSON parsing ----------------------------- */
 
 #[derive(Debug, Deserialize)]
@@ -471,7 +2184,37 @@ fn read_file(path: &PathBuf) -> Result<Vec<u8>, CliError> {
     fs::read(path).map_err(|e| CliError::Io(format!("{}: {e}", path.display())))
 }
 
-fn write_file_atomic(path: &PathBuf, data: &[u8]) -> Result<(), CliError> {
+/// Reads an artifact in any accepted encoding (native, COSE_Sign1, in-toto
+/// DSSE envelope or signer bundle) and returns the native bytes.
+pub(crate) fn read_artifact(path: &PathBuf) -> Result<Vec<u8>, CliError> {
+    read_artifact_with_chain(path).map(|(artifact, _)| artifact)
+}
+
+/// DER certificates, leaf first.
+type CertChain = Vec<Vec<u8>>;
+
+/// Like `read_artifact`, also returning a signer bundle's certificate chain.
+fn read_artifact_with_chain(path: &PathBuf) -> Result<(Vec<u8>, Option<CertChain>), CliError> {
+    let bytes = read_file(path)?;
+    let decode_failed = |e: String| CliError::VerifyFailed(format!("{}: {e}", path.display()));
+    let chain = if is_signer_bundle(&bytes) {
+        let bundle =
+            SignerBundleV0::decode_bin(&bytes).map_err(|e| decode_failed(format!("{e:?}")))?;
+        Some(bundle.cert_chain)
+    } else {
+        None
+    };
+    let artifact = native_artifact_bin(&bytes).map_err(|e| decode_failed(format!("{e:?}")))?;
+    Ok((artifact.into_owned(), chain))
+}
+
+fn read_cert_pem(path: &PathBuf) -> Result<Vec<Vec<u8>>, CliError> {
+    pem_certificates(&read_file(path)?)
+        .map_err(|e| CliError::Runtime(format!("{}: {e:?}", path.display())))
+}
+
+/// Where `path` is written before being renamed into place.
+fn tmp_path_for(path: &Path) -> Result<PathBuf, CliError> {
     let parent = path.parent().unwrap_or_else(|| Path::new("."));
     let file_name = path
         .file_name()
@@ -481,7 +2224,11 @@ fn write_file_atomic(path: &PathBuf, data: &[u8]) -> Result<(), CliError> {
         )))?
         .to_string_lossy();
 
-    let tmp_path = parent.join(format!(".{}.tmp", file_name));
+    Ok(parent.join(format!(".{}.tmp", file_name)))
+}
+
+fn write_file_atomic(path: &PathBuf, data: &[u8]) -> Result<(), CliError> {
+    let tmp_path = tmp_path_for(path)?;
     fs::write(&tmp_path, data).map_err(|e| CliError::Io(format!("{}: {e}", tmp_path.display())))?;
 
     fs::rename(&tmp_path, path).map_err(|e| CliError::Io(format!("{}: {e}", path.display())))?;
@@ -503,7 +2250,15 @@ fn hex_decode_exact(s: &str, expected_len: usize) -> Result<Vec<u8>, ()> {
     if s.len() != expected_len * 2 {
         return Err(());
     }
-    let mut out = Vec::with_capacity(expected_len);
+    hex_decode(s)
+}
+
+fn hex_decode(s: &str) -> Result<Vec<u8>, ()> {
+    let s = s.trim();
+    if s.len() % 2 == 1 {
+        return Err(());
+    }
+    let mut out = Vec::with_capacity(s.len() / 2);
     let b = s.as_bytes();
     let mut i = 0usize;
     while i < b.len() {
@@ -551,6 +2306,7 @@ enum CliError {
     InvalidHex(&'static str),
     Runtime(String),
     VerifyFailed(String),
+    Weights(String),
 }
 
 impl CliError {
@@ -561,6 +2317,7 @@ impl CliError {
             CliError::InvalidHex(_) => 4,
             CliError::Runtime(_) => 5,
             CliError::VerifyFailed(_) => 6,
+            CliError::Weights(_) => 7,
         }
     }
 }
@@ -573,6 +2330,7 @@ impl std::fmt::Display for CliError {
             CliError::InvalidHex(s) => write!(f, "hex: {s}"),
             CliError::Runtime(s) => write!(f, "{s}"),
             CliError::VerifyFailed(s) => write!(f, "verify failed: {s}"),
+            CliError::Weights(s) => write!(f, "weights: {s}"),
         }
     }
 }
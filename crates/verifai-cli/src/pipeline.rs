@@ -0,0 +1,123 @@
+//! Pipeline proofs: the `pipeline` and `verify-pipeline` commands.
+
+use std::path::PathBuf;
+
+use verifai_core::model_bin::OutputV0;
+use verifai_core::pipeline::{PipelineProofV0, PipelineStageV0, StageLink};
+use verifai_runtime::{
+    sign_pipeline_v0, unix_nanos_now, verify_pipeline_replay_v0, verify_pipeline_v0,
+    FreshnessPolicy, StageModel,
+};
+
+use crate::{
+    emit_success, hex_encode_32, path_string_ref, read_artifact, read_file, read_input_json,
+    read_model, write_file_atomic, Cli, CliError, JsonOut, LoadedModel,
+};
+
+/// Links the stage artifacts and outputs, in order, and signs the pipeline.
+pub(crate) fn pipeline(
+    cli: &Cli,
+    artifacts: &[PathBuf],
+    outputs: &[PathBuf],
+    signing_key: [u8; 32],
+    out: &PathBuf,
+) -> Result<(), CliError> {
+    if artifacts.len() != outputs.len() {
+        return Err(CliError::Runtime(
+            "pass one --stage-output per --stage-artifact".into(),
+        ));
+    }
+    let stages = artifacts
+        .iter()
+        .zip(outputs)
+        .enumerate()
+        .map(|(i, (artifact, output))| {
+            Ok(PipelineStageV0 {
+                link: (i > 0).then_some(StageLink::OutputAsInput),
+                artifact: read_artifact(artifact)?,
+                output: read_file(output)?,
+            })
+        })
+        .collect::<Result<Vec<_>, CliError>>()?;
+    let pipeline = sign_pipeline_v0(stages, signing_key, &FreshnessPolicy::at(unix_nanos_now()))
+        .map_err(|e| CliError::Runtime(format!("pipeline failed: {e:?}")))?;
+    let pipeline_bin = pipeline
+        .encode_bin()
+        .expect("sign_pipeline_v0 only signs encodable pipelines");
+    write_file_atomic(out, &pipeline_bin)?;
+
+    let sig_pubkey = hex_encode_32(pipeline.sig_pubkey);
+    let payload = JsonOut::Pipeline {
+        ok: true,
+        stages: pipeline.stages.len(),
+        sig_pubkey: sig_pubkey.clone(),
+        out: path_string_ref(out),
+    };
+    emit_success(cli, payload, || {
+        println!("ok");
+        println!("stages     : {}", pipeline.stages.len());
+        println!("sig_pubkey : {sig_pubkey}");
+    })
+}
+
+/// Verifies the pipeline at `path`, replaying every stage when `models` are
+/// given.
+pub(crate) fn verify_pipeline(
+    cli: &Cli,
+    path: &PathBuf,
+    expected_pubkey: Option<[u8; 32]>,
+    models: &[PathBuf],
+    input: Option<&PathBuf>,
+    policy: &FreshnessPolicy,
+) -> Result<(), CliError> {
+    let pipeline = PipelineProofV0::decode_bin(&read_file(path)?)
+        .map_err(|e| CliError::VerifyFailed(format!("{}: {e:?}", path.display())))?;
+    if expected_pubkey.is_some_and(|key| key != pipeline.sig_pubkey) {
+        return Err(CliError::VerifyFailed(
+            "pipeline signed by an unexpected key".into(),
+        ));
+    }
+
+    let result = match input {
+        Some(input) => {
+            let loaded = models
+                .iter()
+                .map(|m| read_model(m).map(|model| (model.encode_bin(), model)))
+                .collect::<Result<Vec<_>, CliError>>()?;
+            let stage_models: Vec<StageModel<'_>> = loaded
+                .iter()
+                .map(|(bin, model)| match model {
+                    LoadedModel::Logistic(_) => StageModel::Lr(bin),
+                    LoadedModel::Mlp(_) => StageModel::Mlp(bin),
+                })
+                .collect();
+            let input_bin = read_input_json(input)?.encode_bin();
+            verify_pipeline_replay_v0(&pipeline, &stage_models, &input_bin, policy)
+        }
+        None => verify_pipeline_v0(&pipeline, policy),
+    };
+    result.map_err(|e| CliError::VerifyFailed(format!("{e:?}")))?;
+
+    let last = pipeline
+        .stages
+        .last()
+        .expect("decode_bin rejects empty pipelines");
+    let y = OutputV0::decode_bin(&last.output)
+        .map_err(|e| CliError::VerifyFailed(format!("{e:?}")))?
+        .y;
+    let sig_pubkey = hex_encode_32(pipeline.sig_pubkey);
+    let payload = JsonOut::VerifyPipeline {
+        ok: true,
+        stages: pipeline.stages.len(),
+        sig_pubkey: sig_pubkey.clone(),
+        replayed: input.is_some(),
+        y,
+    };
+    emit_success(cli, payload, || {
+        println!("ok");
+        println!("stages     : {}", pipeline.stages.len());
+        println!("sig_pubkey : {sig_pubkey}");
+        println!("replayed   : {}", input.is_some());
+        println!("y          : {y}");
+    })
+}
@@ -0,0 +1,161 @@
+//! `--preprocessor` and `--raw-input`: JSON forms of `PreprocessorV1` and
+//! `RawInputV1`, and verification of the v8 artifacts proven from them.
+
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use verifai_core::preprocess::{FeatureSpec, PreprocessorV1, RawInputV1, RawValue};
+use verifai_runtime::{artifact_version, verify_lr_v8, verify_mlp_v8, FreshnessPolicy};
+
+use crate::{check_verifiable, read_file, CliError, LoadedModel};
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PreprocessorJson {
+    raw_len: u32,
+    features: Vec<FeatureJson>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase", deny_unknown_fields)]
+enum FeatureJson {
+    Numeric {
+        source: u32,
+        mean: f64,
+        std: f64,
+        #[serde(default)]
+        clip: Option<(f64, f64)>,
+        #[serde(default)]
+        default: f64,
+    },
+    Categorical {
+        source: u32,
+        categories: Vec<String>,
+        #[serde(default)]
+        default: Option<String>,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawInputJson {
+    values: Vec<Option<RawValueJson>>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawValueJson {
+    Number(f64),
+    Category(String),
+}
+
+/// Reads preprocessor.json and returns the canonical `PreprocessorV1`
+/// encoding, rejecting specs its decoder would reject.
+pub(crate) fn read_preprocessor_json(path: &PathBuf) -> Result<Vec<u8>, CliError> {
+    let invalid = |e: String| CliError::Json(format!("{}: {e}", path.display()));
+    let parsed: PreprocessorJson =
+        serde_json::from_slice(&read_file(path)?).map_err(|e| invalid(format!("{e}")))?;
+    let features = parsed
+        .features
+        .into_iter()
+        .map(|f| match f {
+            FeatureJson::Numeric {
+                source,
+                mean,
+                std,
+                clip,
+                default,
+            } => Ok(FeatureSpec::Numeric {
+                source,
+                default,
+                clip,
+                mean,
+                std,
+            }),
+            FeatureJson::Categorical {
+                source,
+                categories,
+                default,
+            } => {
+                let default = default
+                    .map(|d| {
+                        categories
+                            .iter()
+                            .position(|c| *c == d)
+                            .map(|i| i as u32)
+                            .ok_or_else(|| invalid(format!("default {d:?} is not a category")))
+                    })
+                    .transpose()?;
+                Ok(FeatureSpec::Categorical {
+                    source,
+                    categories,
+                    default,
+                })
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let preprocessor_bin = PreprocessorV1 {
+        raw_len: parsed.raw_len,
+        features,
+    }
+    .encode_bin();
+    PreprocessorV1::decode_bin(&preprocessor_bin)
+        .map_err(|e| invalid(format!("invalid preprocessor: {e:?}")))?;
+    Ok(preprocessor_bin)
+}
+
+/// Reads raw_input.json (`null`, numbers and category strings) and returns
+/// the canonical `RawInputV1` encoding.
+pub(crate) fn read_raw_input_json(path: &PathBuf) -> Result<Vec<u8>, CliError> {
+    let parsed: RawInputJson = serde_json::from_slice(&read_file(path)?)
+        .map_err(|e| CliError::Json(format!("{}: {e}", path.display())))?;
+    let values = parsed
+        .values
+        .into_iter()
+        .map(|v| match v {
+            None => RawValue::Missing,
+            Some(RawValueJson::Number(n)) => RawValue::Number(n),
+            Some(RawValueJson::Category(c)) => RawValue::Category(c),
+        })
+        .collect();
+    Ok(RawInputV1 { values }.encode_bin())
+}
+
+/// Replays a v8 artifact from the raw input. Other versions do not commit to
+/// a preprocessor and are rejected.
+pub(crate) fn verify_preprocessed(
+    loaded_model: &LoadedModel,
+    artifact_bin: &[u8],
+    preprocessor_bin: &[u8],
+    model_bin: &[u8],
+    raw_input_bin: &[u8],
+    output_bin: &[u8],
+    policy: &FreshnessPolicy,
+) -> Result<(), CliError> {
+    let version = artifact_version(artifact_bin).unwrap_or(0);
+    if version != 8 {
+        return Err(CliError::VerifyFailed(format!(
+            "artifact version {version} commits to no preprocessor; drop --preprocessor"
+        )));
+    }
+    check_verifiable(version, loaded_model, policy, false, true)?;
+    let result = match loaded_model {
+        LoadedModel::Logistic(_) => verify_lr_v8(
+            artifact_bin,
+            preprocessor_bin,
+            model_bin,
+            raw_input_bin,
+            output_bin,
+        ),
+        LoadedModel::Mlp(_) => verify_mlp_v8(
+            artifact_bin,
+            preprocessor_bin,
+            model_bin,
+            raw_input_bin,
+            output_bin,
+        ),
+    };
+    result
+        .map(|_| ())
+        .map_err(|e| CliError::VerifyFailed(format!("{e:?}")))
+}
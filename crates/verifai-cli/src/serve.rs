@@ -0,0 +1,385 @@
+//! `verifai serve`: a long-running HTTP front end for prove/verify.
+//!
+//! Models are loaded once from `--models-dir` and addressed by the hex SHA-256
+//! of their canonical encoding. The signing key stays in memory.
+//!
+//! Routes:
+//! - `POST /prove`  — JSON `{"model_hash", "x"}`, or `application/octet-stream`
+//!   with `input.bin` as the body and the model hash in `X-Verifai-Model-Hash`.
+//!   Binary replies are `u32_le(len(output.bin)) || output.bin || artifact.bin`.
+//! - `POST /verify` — JSON `{"model_hash", "x", "output", "artifact"}` (hex
+//!   bytes), or a binary body of `u32_le(len) || input.bin || u32_le(len) ||
+//!   output.bin || artifact.bin` with the model hash header.
+//! - `GET /health`, `GET /metrics` (Prometheus text format).
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use tiny_http::{Header, Method, Request, Response, Server};
+use verifai_core::bytes::Reader;
+use verifai_core::hash::sha256;
+use verifai_core::model_bin::InputV0;
+use verifai_runtime::{native_artifact_bin, unix_nanos_now, FreshnessPolicy};
+
+use crate::{
+    artifact_summary, hex_decode, hex_encode, hex_encode_32, parse_hex_32, prove_model, read_model,
+    verify_model, CliError, LoadedModel, ProveOptions,
+};
+
+const MODEL_HASH_HEADER: &str = "X-Verifai-Model-Hash";
+
+/// Largest request body read; bigger ones get 413 so that no client can
+/// exhaust the memory of the process holding the signing key.
+const MAX_BODY: usize = 4 << 20;
+
+pub(crate) struct ServeOptions {
+    pub bind: String,
+    pub threads: usize,
+    pub prove: ProveOptions,
+}
+
+struct ModelEntry {
+    loaded: LoadedModel,
+    model_bin: Vec<u8>,
+}
+
+#[derive(Default)]
+struct Metrics {
+    prove_ok: AtomicU64,
+    prove_failed: AtomicU64,
+    verify_ok: AtomicU64,
+    verify_failed: AtomicU64,
+    bad_requests: AtomicU64,
+}
+
+struct ServerState {
+    models: BTreeMap<[u8; 32], ModelEntry>,
+    opts: ServeOptions,
+    metrics: Metrics,
+}
+
+struct HttpError {
+    status: u16,
+    message: String,
+}
+
+impl HttpError {
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+enum Body {
+    Json(serde_json::Value),
+    Binary(Vec<u8>),
+    Text(String),
+}
+
+#[derive(Debug, Deserialize)]
+struct ProveRequestJson {
+    model_hash: String,
+    x: Vec<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VerifyRequestJson {
+    model_hash: String,
+    x: Vec<f64>,
+    output: String,
+    artifact: String,
+}
+
+pub(crate) fn serve(models_dir: &PathBuf, opts: ServeOptions) -> Result<(), CliError> {
+    let models = load_models(models_dir)?;
+    let server = Server::http(opts.bind.as_str())
+        .map_err(|e| CliError::Io(format!("{}: {e}", opts.bind)))?;
+    let addr = server
+        .server_addr()
+        .to_ip()
+        .map(|a| a.to_string())
+        .unwrap_or_else(|| opts.bind.clone());
+    println!("listening on {addr} ({} models)", models.len());
+
+    let threads = opts.threads.max(1);
+    let state = Arc::new(ServerState {
+        models,
+        opts,
+        metrics: Metrics::default(),
+    });
+    let server = Arc::new(server);
+
+    let workers: Vec<_> = (0..threads)
+        .map(|_| {
+            let server = Arc::clone(&server);
+            let state = Arc::clone(&state);
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    handle(&state, request);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        let _ = worker.join();
+    }
+    Ok(())
+}
+
+fn load_models(dir: &PathBuf) -> Result<BTreeMap<[u8; 32], ModelEntry>, CliError> {
+    let entries = fs::read_dir(dir).map_err(|e| CliError::Io(format!("{}: {e}", dir.display())))?;
+    let mut models = BTreeMap::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| CliError::Io(format!("{}: {e}", dir.display())))?
+            .path();
+        if !is_model_path(&path) {
+            continue;
+        }
+        let loaded = read_model(&path)?;
+        let model_bin = loaded.encode_bin();
+        models.insert(sha256(&model_bin), ModelEntry { loaded, model_bin });
+    }
+    Ok(models)
+}
+
+fn is_model_path(path: &Path) -> bool {
+    path.is_dir()
+        || matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("json") | Some("safetensors") | Some("npz")
+        )
+}
+
+fn handle(state: &ServerState, mut request: Request) {
+    let path = request.url().split('?').next().unwrap_or("").to_string();
+    let result = match (request.method(), path.as_str()) {
+        (Method::Get, "/health") => Ok(Body::Json(serde_json::json!({
+            "ok": true,
+            "models": state.models.len(),
+        }))),
+        (Method::Get, "/metrics") => Ok(Body::Text(metrics_text(state))),
+        (Method::Post, "/prove") => {
+            let r = handle_prove(state, &mut request);
+            count(&state.metrics.prove_ok, &state.metrics.prove_failed, &r);
+            r
+        }
+        (Method::Post, "/verify") => {
+            let r = handle_verify(state, &mut request);
+            count(&state.metrics.verify_ok, &state.metrics.verify_failed, &r);
+            r
+        }
+        _ => Err(HttpError::new(404, "not found")),
+    };
+
+    let response = match result {
+        Ok(Body::Json(v)) => reply(200, "application/json", v.to_string().into_bytes()),
+        Ok(Body::Binary(b)) => reply(200, "application/octet-stream", b),
+        Ok(Body::Text(t)) => reply(200, "text/plain; version=0.0.4", t.into_bytes()),
+        Err(e) => {
+            if e.status == 400 {
+                state.metrics.bad_requests.fetch_add(1, Ordering::Relaxed);
+            }
+            let body = serde_json::json!({ "ok": false, "error": e.message });
+            reply(e.status, "application/json", body.to_string().into_bytes())
+        }
+    };
+    let _ = request.respond(response);
+}
+
+fn count(ok: &AtomicU64, failed: &AtomicU64, r: &Result<Body, HttpError>) {
+    match r {
+        Ok(_) => ok.fetch_add(1, Ordering::Relaxed),
+        Err(_) => failed.fetch_add(1, Ordering::Relaxed),
+    };
+}
+
+fn reply(status: u16, content_type: &str, body: Vec<u8>) -> Response<std::io::Cursor<Vec<u8>>> {
+    let header = Header::from_bytes("Content-Type", content_type).expect("static header is valid");
+    Response::from_data(body)
+        .with_status_code(status)
+        .with_header(header)
+}
+
+fn metrics_text(state: &ServerState) -> String {
+    let m = &state.metrics;
+    let mut out = String::new();
+    for (name, value) in [
+        ("verifai_prove_ok_total", &m.prove_ok),
+        ("verifai_prove_failed_total", &m.prove_failed),
+        ("verifai_verify_ok_total", &m.verify_ok),
+        ("verifai_verify_failed_total", &m.verify_failed),
+        ("verifai_bad_requests_total", &m.bad_requests),
+    ] {
+        out.push_str(&format!("# TYPE {name} counter\n"));
+        out.push_str(&format!("{name} {}\n", value.load(Ordering::Relaxed)));
+    }
+    out.push_str("# TYPE verifai_models_loaded gauge\n");
+    out.push_str(&format!("verifai_models_loaded {}\n", state.models.len()));
+    out
+}
+
+fn is_binary(request: &Request) -> bool {
+    request.headers().iter().any(|h| {
+        h.field.equiv("Content-Type") && h.value.as_str().starts_with("application/octet-stream")
+    })
+}
+
+fn read_body(request: &mut Request) -> Result<Vec<u8>, HttpError> {
+    let too_large = || HttpError::new(413, format!("body exceeds {MAX_BODY} bytes"));
+    if request.body_length().is_some_and(|len| len > MAX_BODY) {
+        return Err(too_large());
+    }
+    let mut body = Vec::new();
+    Read::take(request.as_reader(), MAX_BODY as u64 + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| HttpError::new(400, format!("body: {e}")))?;
+    if body.len() > MAX_BODY {
+        return Err(too_large());
+    }
+    Ok(body)
+}
+
+fn header_model_hash(request: &Request) -> Result<String, HttpError> {
+    request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv(MODEL_HASH_HEADER))
+        .map(|h| h.value.as_str().to_string())
+        .ok_or_else(|| HttpError::new(400, format!("missing {MODEL_HASH_HEADER} header")))
+}
+
+fn lookup_model<'a>(state: &'a ServerState, hash_hex: &str) -> Result<&'a ModelEntry, HttpError> {
+    let hash = parse_hex_32(hash_hex)
+        .map_err(|_| HttpError::new(400, "model_hash must be 64 hex chars (32 bytes)"))?;
+    state
+        .models
+        .get(&hash)
+        .ok_or_else(|| HttpError::new(404, format!("unknown model_hash {hash_hex}")))
+}
+
+fn parse_json<T: for<'de> Deserialize<'de>>(body: &[u8]) -> Result<T, HttpError> {
+    serde_json::from_slice(body).map_err(|e| HttpError::new(400, format!("json: {e}")))
+}
+
+fn handle_prove(state: &ServerState, request: &mut Request) -> Result<Body, HttpError> {
+    let binary = is_binary(request);
+    let header_hash = if binary {
+        Some(header_model_hash(request)?)
+    } else {
+        None
+    };
+    let body = read_body(request)?;
+
+    let (model, input_bin) = match header_hash {
+        Some(hash) => {
+            InputV0::decode_bin(&body).map_err(|_| HttpError::new(400, "invalid input.bin"))?;
+            (lookup_model(state, &hash)?, body)
+        }
+        None => {
+            let req: ProveRequestJson = parse_json(&body)?;
+            let model = lookup_model(state, &req.model_hash)?;
+            (model, InputV0 { x: req.x }.encode_bin())
+        }
+    };
+
+    let (output_bin, artifact_bin) = prove_model(
+        &model.loaded,
+        &state.opts.prove,
+        &model.model_bin,
+        &input_bin,
+        &mut (),
+    )
+    .map_err(|e| HttpError::new(422, e.to_string()))?;
+
+    if binary {
+        let mut out = Vec::with_capacity(4 + output_bin.len() + artifact_bin.len());
+        out.extend_from_slice(&(output_bin.len() as u32).to_le_bytes());
+        out.extend_from_slice(&output_bin);
+        out.extend_from_slice(&artifact_bin);
+        return Ok(Body::Binary(out));
+    }
+
+    let (trace_root, sig_pubkey, _) =
+        artifact_summary(&artifact_bin).map_err(|e| HttpError::new(500, e))?;
+    Ok(Body::Json(serde_json::json!({
+        "ok": true,
+        "model_hash": hex_encode_32(sha256(&model.model_bin)),
+        "input_hash": hex_encode_32(sha256(&input_bin)),
+        "output_hash": hex_encode_32(sha256(&output_bin)),
+        "trace_root": hex_encode_32(trace_root),
+        "sig_pubkey": hex_encode(&sig_pubkey),
+        "output": hex_encode(&output_bin),
+        "artifact": hex_encode(&artifact_bin),
+    })))
+}
+
+fn handle_verify(state: &ServerState, request: &mut Request) -> Result<Body, HttpError> {
+    let header_hash = if is_binary(request) {
+        Some(header_model_hash(request)?)
+    } else {
+        None
+    };
+    let body = read_body(request)?;
+
+    let (model, input_bin, output_bin, artifact_bin) = match header_hash {
+        Some(hash) => {
+            let mut r = Reader::new(&body);
+            let bad = |_| HttpError::new(400, "invalid binary verify body");
+            let input_len = r.read_u32_le().map_err(bad)? as usize;
+            let input_bin = r.read_exact(input_len).map_err(bad)?.to_vec();
+            let output_len = r.read_u32_le().map_err(bad)? as usize;
+            let output_bin = r.read_exact(output_len).map_err(bad)?.to_vec();
+            let artifact_bin = r.read_exact(r.remaining()).map_err(bad)?.to_vec();
+            (
+                lookup_model(state, &hash)?,
+                input_bin,
+                output_bin,
+                artifact_bin,
+            )
+        }
+        None => {
+            let req: VerifyRequestJson = parse_json(&body)?;
+            let output_bin =
+                hex_decode(&req.output).map_err(|_| HttpError::new(400, "output must be hex"))?;
+            let artifact_bin = hex_decode(&req.artifact)
+                .map_err(|_| HttpError::new(400, "artifact must be hex"))?;
+            (
+                lookup_model(state, &req.model_hash)?,
+                InputV0 { x: req.x }.encode_bin(),
+                output_bin,
+                artifact_bin,
+            )
+        }
+    };
+    let artifact_bin = native_artifact_bin(&artifact_bin)
+        .map_err(|e| HttpError::new(422, format!("{e:?}")))?
+        .into_owned();
+
+    verify_model(
+        &model.loaded,
+        &artifact_bin,
+        &model.model_bin,
+        &input_bin,
+        &output_bin,
+        &FreshnessPolicy::at(unix_nanos_now()),
+        None,
+    )
+    .map_err(|e| HttpError::new(422, e.to_string()))?;
+
+    let (trace_root, sig_pubkey, _) =
+        artifact_summary(&artifact_bin).map_err(|e| HttpError::new(422, e))?;
+    Ok(Body::Json(serde_json::json!({
+        "ok": true,
+        "trace_root": hex_encode_32(trace_root),
+        "sig_pubkey": hex_encode(&sig_pubkey),
+    })))
+}
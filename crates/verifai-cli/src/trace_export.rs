@@ -0,0 +1,70 @@
+//! `prove --out-trace`: writing the trace while the proving run produces it.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+
+use verifai_core::event_bin::TraceEventV0;
+use verifai_core::trace_sink::{JsonlTraceWriter, TraceFileWriter, TraceSink};
+
+use crate::disclosure::event_json;
+use crate::{tmp_path_for, CliError};
+
+/// One line of a `jsonl` trace, as `verify-disclosure --print-json` reports
+/// the event.
+fn event_json_line(index: u32, event: &TraceEventV0) -> io::Result<String> {
+    serde_json::to_string(&event_json(index, event)).map_err(io::Error::from)
+}
+
+enum Writer {
+    Bin(TraceFileWriter<BufWriter<File>>),
+    Jsonl(JsonlTraceWriter<BufWriter<File>>),
+}
+
+/// A trace file being written next to its final path, renamed into place by
+/// `finish`.
+pub(crate) struct TraceExport {
+    path: PathBuf,
+    tmp_path: PathBuf,
+    writer: Writer,
+}
+
+impl TraceExport {
+    /// `format` is `bin` (a `TraceV0` file, as `check-trace` reads) or
+    /// `jsonl`.
+    pub(crate) fn create(path: &Path, format: &str) -> Result<Self, CliError> {
+        let tmp_path = tmp_path_for(path)?;
+        let file = File::create(&tmp_path)
+            .map_err(|e| CliError::Io(format!("{}: {e}", tmp_path.display())))?;
+        let out = BufWriter::new(file);
+        let writer = match format {
+            "jsonl" => Writer::Jsonl(JsonlTraceWriter::new(out, event_json_line)),
+            _ => Writer::Bin(TraceFileWriter::new(out)),
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            tmp_path,
+            writer,
+        })
+    }
+
+    /// Completes the file.
+    pub(crate) fn finish(self) -> Result<(), CliError> {
+        let result = match self.writer {
+            Writer::Bin(w) => w.finish().map(drop),
+            Writer::Jsonl(w) => w.finish().map(drop),
+        };
+        result.map_err(|e| CliError::Io(format!("{}: {e}", self.tmp_path.display())))?;
+        fs::rename(&self.tmp_path, &self.path)
+            .map_err(|e| CliError::Io(format!("{}: {e}", self.path.display())))
+    }
+}
+
+impl TraceSink for TraceExport {
+    fn record(&mut self, event: &TraceEventV0) {
+        match &mut self.writer {
+            Writer::Bin(w) => w.record(event),
+            Writer::Jsonl(w) => w.record(event),
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! The `check-trace` command: trace-only consistency checks for verifiers
+//! who may not see the model, and `verify --trace`.
+
+use std::path::PathBuf;
+
+use verifai_core::event_bin::{TraceEventV0, TraceV0};
+use verifai_core::model_bin::OutputV0;
+use verifai_runtime::{
+    artifact_version, check_trace_consistency, verify_lr_with_trace, verify_mlp_with_trace,
+    BulkRecord, FreshnessPolicy,
+};
+
+use crate::disclosure::{describe, event_json};
+use crate::{
+    artifact_summary, check_verifiable, emit_success, hex_encode_32, read_artifact, read_file, Cli,
+    CliError, JsonOut, LoadedModel,
+};
+
+fn read_trace(trace_path: &PathBuf) -> Result<TraceV0, CliError> {
+    TraceV0::decode_bin(&read_file(trace_path)?)
+        .map_err(|e| CliError::VerifyFailed(format!("{}: {e:?}", trace_path.display())))
+}
+
+/// Checks the artifact at `artifact_path` against a trace file and output.
+pub(crate) fn check_trace(
+    cli: &Cli,
+    artifact_path: &PathBuf,
+    output_path: &PathBuf,
+    trace_path: &PathBuf,
+) -> Result<(), CliError> {
+    let artifact_bin = read_artifact(artifact_path)?;
+    let output_bin = read_file(output_path)?;
+    let trace = read_trace(trace_path)?;
+    check_trace_consistency(&trace.events, &artifact_bin, &output_bin)
+        .map_err(|e| CliError::VerifyFailed(format!("{e:?}")))?;
+
+    let (trace_root, _, _) =
+        artifact_summary(&artifact_bin).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let trace_root_hex = hex_encode_32(trace_root);
+    let y = OutputV0::decode_bin(&output_bin)
+        .expect("check_trace_consistency decodes the output")
+        .y;
+    let payload = JsonOut::CheckTrace {
+        ok: true,
+        trace_root: trace_root_hex.clone(),
+        events: trace.events.len(),
+        y,
+    };
+    emit_success(cli, payload, || {
+        println!("ok");
+        println!("trace_root : {trace_root_hex}");
+        println!("events     : {}", trace.events.len());
+        println!("y          : {y}");
+    })
+}
+
+fn describe_event(index: usize, event: Option<&TraceEventV0>) -> String {
+    match event {
+        Some(event) => describe(&event_json(index as u32, event)),
+        None => "end of trace".into(),
+    }
+}
+
+/// `verify --trace`: replays the artifact against the trace its prover
+/// claims, stopping at the first event that differs. Returns the number of
+/// events checked.
+pub(crate) fn verify_with_trace(
+    loaded_model: &LoadedModel,
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+    trace_path: &PathBuf,
+    policy: &FreshnessPolicy,
+) -> Result<usize, CliError> {
+    let version = artifact_version(artifact_bin).unwrap_or(0);
+    check_verifiable(version, loaded_model, policy, false, false)?;
+    if !matches!(version, 0 | 1 | 3..=6) {
+        return Err(CliError::VerifyFailed(format!(
+            "--trace does not take v{version} artifacts"
+        )));
+    }
+    let trace = read_trace(trace_path)?;
+    let record = BulkRecord {
+        artifact_bin,
+        input_bin,
+        output_bin,
+    };
+    let divergence = match loaded_model {
+        LoadedModel::Logistic(_) => verify_lr_with_trace(model_bin, &record, &trace.events, policy),
+        LoadedModel::Mlp(_) => verify_mlp_with_trace(model_bin, &record, &trace.events, policy),
+    }
+    .map_err(|e| CliError::VerifyFailed(format!("{e:?}")))?;
+    if let Some(d) = divergence {
+        let op_id = d
+            .op_id
+            .map(|id| format!(" (op_id {id})"))
+            .unwrap_or_default();
+        return Err(CliError::VerifyFailed(format!(
+            "TraceDiverged at event {}{op_id}: expected {}, actual {}",
+            d.index,
+            describe_event(d.index, d.expected.as_ref()),
+            describe_event(d.index, d.actual.as_ref()),
+        )));
+    }
+    Ok(trace.events.len())
+}
@@ -0,0 +1,143 @@
+//! Transparency log commands: `log-head`, `log-inclusion`, `log-consistency`,
+//! plus the helpers `prove --log-dir` and `verify --require-log-inclusion` use.
+
+use std::path::{Path, PathBuf};
+
+use verifai_core::translog::SignedTreeHeadV0;
+use verifai_runtime::{verify_log_consistency_v0, verify_log_inclusion_v0, TransparencyLog};
+
+use crate::{
+    emit_success, hex_encode_32, path_string_ref, read_file, write_file_atomic, Cli, CliError,
+    JsonOut,
+};
+
+fn open_log(log_dir: &Path) -> Result<TransparencyLog, CliError> {
+    TransparencyLog::open(log_dir)
+        .map_err(|e| CliError::Runtime(format!("log {}: {e:?}", log_dir.display())))
+}
+
+fn read_tree_head(path: &PathBuf) -> Result<SignedTreeHeadV0, CliError> {
+    SignedTreeHeadV0::decode_bin(&read_file(path)?)
+        .map_err(|_| CliError::Runtime(format!("{}: invalid tree head", path.display())))
+}
+
+/// Appends one artifact to the log in `log_dir` and returns its index.
+pub(crate) fn append_artifact(log_dir: &Path, artifact_bin: &[u8]) -> Result<u64, CliError> {
+    open_log(log_dir)?
+        .append(artifact_bin)
+        .map_err(|e| CliError::Runtime(format!("log append failed: {e:?}")))
+}
+
+/// Where `verify --require-log-inclusion` takes its inclusion proof from.
+pub(crate) enum InclusionSource<'a> {
+    ProofFile(&'a PathBuf),
+    LogDir(&'a Path),
+}
+
+/// Checks `artifact_bin` against the pinned tree head and returns its index.
+pub(crate) fn require_inclusion(
+    artifact_bin: &[u8],
+    tree_head: &PathBuf,
+    source: InclusionSource<'_>,
+    log_pubkey: [u8; 32],
+) -> Result<u64, CliError> {
+    let head_bin = read_file(tree_head)?;
+    let proof_bin = match source {
+        InclusionSource::ProofFile(path) => read_file(path)?,
+        InclusionSource::LogDir(dir) => {
+            let head = read_tree_head(tree_head)?;
+            open_log(dir)?
+                .inclusion_proof(artifact_bin, head.tree_size)
+                .map_err(|e| CliError::VerifyFailed(format!("log inclusion: {e:?}")))?
+                .encode_bin()
+        }
+    };
+    verify_log_inclusion_v0(artifact_bin, &proof_bin, &head_bin, log_pubkey)
+        .map_err(|e| CliError::VerifyFailed(format!("log inclusion: {e:?}")))
+}
+
+pub(crate) fn log_head(
+    cli: &Cli,
+    log_dir: &Path,
+    signing_key: [u8; 32],
+    out: Option<&PathBuf>,
+) -> Result<(), CliError> {
+    let head = open_log(log_dir)?
+        .tree_head(signing_key)
+        .map_err(|e| CliError::Runtime(format!("tree head: {e:?}")))?;
+    if let Some(p) = out {
+        write_file_atomic(p, &head.encode_bin())?;
+    }
+
+    let root_hex = hex_encode_32(head.root_hash);
+    let payload = JsonOut::LogHead {
+        ok: true,
+        tree_size: head.tree_size,
+        root_hash: root_hex.clone(),
+        log_pubkey: hex_encode_32(head.log_pubkey),
+        out: out.map(|p| path_string_ref(p)),
+    };
+    emit_success(cli, payload, || {
+        println!("tree_size : {}", head.tree_size);
+        println!("root_hash : {root_hex}");
+    })
+}
+
+pub(crate) fn log_inclusion(
+    cli: &Cli,
+    log_dir: &Path,
+    artifact: &PathBuf,
+    tree_head: &PathBuf,
+    out: &PathBuf,
+) -> Result<(), CliError> {
+    let artifact_bin = read_file(artifact)?;
+    let head = read_tree_head(tree_head)?;
+    let proof = open_log(log_dir)?
+        .inclusion_proof(&artifact_bin, head.tree_size)
+        .map_err(|e| CliError::Runtime(format!("log inclusion: {e:?}")))?;
+    write_file_atomic(out, &proof.encode_bin())?;
+
+    let payload = JsonOut::LogInclusion {
+        ok: true,
+        leaf_index: proof.leaf_index,
+        tree_size: proof.tree_size,
+        out: path_string_ref(out),
+    };
+    emit_success(cli, payload, || {
+        println!("leaf_index : {}", proof.leaf_index);
+        println!("tree_size  : {}", proof.tree_size);
+    })
+}
+
+pub(crate) fn log_consistency(
+    cli: &Cli,
+    log_dir: &Path,
+    old_head: &PathBuf,
+    new_head: &PathBuf,
+    log_pubkey: Option<[u8; 32]>,
+    out: Option<&PathBuf>,
+) -> Result<(), CliError> {
+    let old = read_tree_head(old_head)?;
+    let new = read_tree_head(new_head)?;
+    let proof = open_log(log_dir)?
+        .consistency_proof(old.tree_size, new.tree_size)
+        .map_err(|e| CliError::VerifyFailed(format!("log consistency: {e:?}")))?;
+    let proof_bin = proof.encode_bin();
+    verify_log_consistency_v0(&old.encode_bin(), &new.encode_bin(), &proof_bin, log_pubkey)
+        .map_err(|e| CliError::VerifyFailed(format!("log consistency: {e:?}")))?;
+    if let Some(p) = out {
+        write_file_atomic(p, &proof_bin)?;
+    }
+
+    let payload = JsonOut::LogConsistency {
+        ok: true,
+        old_size: old.tree_size,
+        new_size: new.tree_size,
+        out: out.map(|p| path_string_ref(p)),
+    };
+    emit_success(cli, payload, || {
+        println!("ok");
+        println!("old_size : {}", old.tree_size);
+        println!("new_size : {}", new.tree_size);
+    })
+}
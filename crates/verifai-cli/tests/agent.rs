@@ -0,0 +1,168 @@
+#![cfg(unix)]
+
+use assert_cmd::cargo::cargo_bin;
+use assert_cmd::Command;
+use assert_fs::fixture::{FileWriteStr, PathChild};
+use assert_fs::TempDir;
+use serde_json::Value;
+use std::error::Error;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Stdio};
+use std::thread;
+use std::time::Duration;
+
+const KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+fn p(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+/// Kills the agent when the test ends, pass or fail.
+struct Agent(Child);
+
+impl Drop for Agent {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+fn spawn_agent(temp: &TempDir) -> Result<(Agent, PathBuf), Box<dyn Error>> {
+    let key_file = temp.child("agent.key");
+    key_file.write_str(&format!("{KEY_HEX}\n"))?;
+    let socket = temp.child("agent.sock").path().to_path_buf();
+    let agent = Agent(
+        std::process::Command::new(cargo_bin("verifai-agent"))
+            .args(["--socket", p(&socket), "--key-file", p(key_file.path())])
+            .stdout(Stdio::null())
+            .spawn()?,
+    );
+    for _ in 0..100 {
+        if socket.exists() {
+            return Ok((agent, socket));
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+    Err("agent did not start".into())
+}
+
+fn prove(temp: &TempDir, name: &str, signer: &[&str]) -> Result<Value, Box<dyn Error>> {
+    let out = Command::cargo_bin("verifai-cli")?
+        .args([
+            "prove",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--out-output",
+            p(temp.child("output.bin").path()),
+            "--out-artifact",
+            p(temp.child(name).path()),
+            "--print-json",
+        ])
+        .args(signer)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    Ok(serde_json::from_slice(&out)?)
+}
+
+#[test]
+fn prove_through_agent_matches_in_process_key() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let (agent, socket) = spawn_agent(&temp)?;
+
+    let local = prove(&temp, "local.bin", &["--key-hex", KEY_HEX])?;
+    let remote = prove(&temp, "remote.bin", &["--agent-socket", p(&socket)])?;
+    assert_eq!(remote["sig_pubkey"], local["sig_pubkey"]);
+    assert_eq!(
+        fs::read(temp.child("remote.bin").path())?,
+        fs::read(temp.child("local.bin").path())?
+    );
+
+    // v5 signs through the agent as long as it holds a key for --sig-alg
+    let local = prove(
+        &temp,
+        "local-v5.bin",
+        &["--key-hex", KEY_HEX, "--sig-alg", "ed25519"],
+    )?;
+    let remote = prove(
+        &temp,
+        "remote-v5.bin",
+        &["--agent-socket", p(&socket), "--sig-alg", "ed25519"],
+    )?;
+    assert_eq!(remote["sig_alg"], "ed25519");
+    assert_eq!(remote["sig_pubkey"], local["sig_pubkey"]);
+    assert_eq!(
+        fs::read(temp.child("remote-v5.bin").path())?,
+        fs::read(temp.child("local-v5.bin").path())?
+    );
+    // and so does v6
+    prove(
+        &temp,
+        "local-v6.bin",
+        &["--key-hex", KEY_HEX, "--hash-alg", "blake3"],
+    )?;
+    prove(
+        &temp,
+        "remote-v6.bin",
+        &["--agent-socket", p(&socket), "--hash-alg", "blake3"],
+    )?;
+    assert_eq!(
+        fs::read(temp.child("remote-v6.bin").path())?,
+        fs::read(temp.child("local-v6.bin").path())?
+    );
+    Command::cargo_bin("verifai-cli")?
+        .args([
+            "prove",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--out-output",
+            p(temp.child("output.bin").path()),
+            "--out-artifact",
+            p(temp.child("p256.bin").path()),
+            "--agent-socket",
+            p(&socket),
+            "--sig-alg",
+            "p256",
+        ])
+        .assert()
+        .code(5);
+
+    drop(agent);
+    // only the agent's user can connect, and the staging directory is gone
+    assert_eq!(fs::metadata(&socket)?.permissions().mode() & 0o777, 0o600);
+    for entry in fs::read_dir(temp.path())? {
+        assert!(!entry?.file_name().to_string_lossy().ends_with(".tmp"));
+    }
+    Command::cargo_bin("verifai-cli")?
+        .args([
+            "prove",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--out-output",
+            p(temp.child("output.bin").path()),
+            "--out-artifact",
+            p(temp.child("gone.bin").path()),
+            "--agent-socket",
+            p(&socket),
+        ])
+        .assert()
+        .code(5);
+
+    Ok(())
+}
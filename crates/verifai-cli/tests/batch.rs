@@ -0,0 +1,190 @@
+use assert_cmd::Command;
+use assert_fs::fixture::PathChild;
+use assert_fs::TempDir;
+use serde_json::Value;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+const KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+fn json_lines(stdout: &[u8]) -> Result<Vec<Value>, Box<dyn Error>> {
+    let text = std::str::from_utf8(stdout)?;
+    let mut out = Vec::new();
+    for line in text.lines() {
+        out.push(serde_json::from_str(line)?);
+    }
+    Ok(out)
+}
+
+fn prove_batch(temp: &TempDir, inputs: &str) -> Result<(PathBuf, Vec<Value>, i32), Box<dyn Error>> {
+    let inputs_path = temp.child("inputs.jsonl");
+    fs::write(inputs_path.path(), inputs)?;
+    let out_dir = temp.child("out");
+
+    let output = Command::cargo_bin("verifai-cli")?
+        .args([
+            "prove",
+            "--model",
+            tv_path("case-1/model.json").to_str().unwrap(),
+            "--inputs",
+            inputs_path.path().to_str().unwrap(),
+            "--out-dir",
+            out_dir.path().to_str().unwrap(),
+            "--key-hex",
+            KEY_HEX,
+        ])
+        .output()?;
+
+    Ok((
+        out_dir.path().to_path_buf(),
+        json_lines(&output.stdout)?,
+        output.status.code().unwrap_or(-1),
+    ))
+}
+
+#[test]
+fn prove_jsonl_then_verify_manifest() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let (out_dir, lines, code) = prove_batch(
+        &temp,
+        "{\"id\": \"a\", \"x\": [1.0, 2.0, 3.0, 4.0]}\n{\"x\": [0.0, 0.0, 0.0, 0.0]}\n",
+    )?;
+
+    assert_eq!(code, 0);
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["cmd"], "prove-record");
+    assert_eq!(lines[0]["id"], "a");
+    assert_eq!(lines[0]["ok"], true);
+    assert_eq!(lines[2]["cmd"], "prove-batch");
+    assert_eq!(lines[2]["succeeded"], 2);
+
+    assert!(out_dir.join("0.artifact.bin").exists());
+    assert!(out_dir.join("1.output.bin").exists());
+
+    let output = Command::cargo_bin("verifai-cli")?
+        .args([
+            "verify",
+            "--model",
+            tv_path("case-1/model.json").to_str().unwrap(),
+            "--manifest",
+            out_dir.join("manifest.jsonl").to_str().unwrap(),
+        ])
+        .output()?;
+
+    assert!(output.status.success());
+    let lines = json_lines(&output.stdout)?;
+    assert_eq!(lines.len(), 3);
+    assert!(lines[..2]
+        .iter()
+        .all(|l| l["cmd"] == "verify-record" && l["ok"] == true));
+    assert_eq!(lines[2]["cmd"], "verify-batch");
+    assert_eq!(lines[2]["failed"], 0);
+    Ok(())
+}
+
+#[test]
+fn prove_jsonl_reports_bad_records_and_exits_nonzero() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let (out_dir, lines, code) = prove_batch(
+        &temp,
+        "{\"x\": [1.0, 2.0, 3.0, 4.0]}\n{\"x\": [1.0]}\nnot json\n",
+    )?;
+
+    assert_eq!(code, 5);
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0]["ok"], true);
+    assert_eq!(lines[1]["ok"], false);
+    assert_eq!(lines[2]["ok"], false);
+    assert_eq!(lines[3]["failed"], 2);
+
+    let manifest = fs::read_to_string(out_dir.join("manifest.jsonl"))?;
+    assert_eq!(manifest.lines().count(), 1);
+    Ok(())
+}
+
+#[test]
+fn verify_manifest_flags_tampered_record() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let (out_dir, _, _) = prove_batch(
+        &temp,
+        "{\"x\": [1.0, 2.0, 3.0, 4.0]}\n{\"x\": [0.5, 0.5, 0.5, 0.5]}\n",
+    )?;
+
+    let mut output_bin = fs::read(out_dir.join("1.output.bin"))?;
+    output_bin[8] ^= 0x01;
+    fs::write(out_dir.join("1.output.bin"), output_bin)?;
+
+    let json_file = temp.child("verify.jsonl");
+    let output = Command::cargo_bin("verifai-cli")?
+        .args([
+            "verify",
+            "--model",
+            tv_path("case-1/model.json").to_str().unwrap(),
+            "--manifest",
+            out_dir.join("manifest.jsonl").to_str().unwrap(),
+            "--json-file",
+            json_file.path().to_str().unwrap(),
+        ])
+        .output()?;
+
+    assert_eq!(output.status.code(), Some(6));
+    assert!(output.stdout.is_empty());
+    let lines = json_lines(&fs::read(json_file.path())?)?;
+    assert_eq!(lines[0]["ok"], true);
+    assert_eq!(lines[1]["ok"], false);
+    assert_eq!(lines[1]["index"], 1);
+    assert_eq!(lines[2]["failed"], 1);
+    Ok(())
+}
+
+#[test]
+fn verify_manifest_batches_signatures_and_reports_throughput() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let inputs: String = (0..12)
+        .map(|i| format!("{{\"x\": [{i}.0, 1.0, 2.0, 3.0]}}\n"))
+        .collect();
+    let (out_dir, _, code) = prove_batch(&temp, &inputs)?;
+    assert_eq!(code, 0);
+
+    // corrupt two v0 signatures (bytes 194..258)
+    for i in [3, 9] {
+        let path = out_dir.join(format!("{i}.artifact.bin"));
+        let mut artifact = fs::read(&path)?;
+        artifact[200] ^= 0x01;
+        fs::write(&path, artifact)?;
+    }
+
+    let output = Command::cargo_bin("verifai-cli")?
+        .args([
+            "verify",
+            "--model",
+            tv_path("case-1/model.json").to_str().unwrap(),
+            "--manifest",
+            out_dir.join("manifest.jsonl").to_str().unwrap(),
+        ])
+        .output()?;
+
+    assert_eq!(output.status.code(), Some(6));
+    let lines = json_lines(&output.stdout)?;
+    assert_eq!(lines.len(), 13);
+    for (i, line) in lines[..12].iter().enumerate() {
+        let bad = i == 3 || i == 9;
+        assert_eq!(line["ok"], !bad, "record {i}");
+        if bad {
+            assert_eq!(line["error"], "verify failed: SignatureInvalid");
+        }
+    }
+    let summary = &lines[12];
+    assert_eq!(summary["cmd"], "verify-batch");
+    assert_eq!(summary["failed"], 2);
+    assert!(summary["elapsed_ms"].is_u64());
+    assert!(summary["records_per_sec"].as_f64().unwrap() > 0.0);
+    Ok(())
+}
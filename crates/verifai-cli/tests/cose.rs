@@ -0,0 +1,115 @@
+use assert_cmd::Command;
+use assert_fs::fixture::PathChild;
+use assert_fs::TempDir;
+use serde_json::Value;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+fn p(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+fn verify(temp: &TempDir, artifact: &Path) -> Result<Command, Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("verifai-cli")?;
+    cmd.args([
+        "verify",
+        "--model",
+        p(&tv_path("case-1/model.json")),
+        "--input",
+        p(&tv_path("case-1/input.json")),
+        "--output",
+        p(temp.child("output.bin").path()),
+        "--artifact",
+        p(artifact),
+        "--print-json",
+    ]);
+    Ok(cmd)
+}
+
+#[test]
+fn prove_writes_cose_and_verify_accepts_both_encodings() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let artifact = temp.child("artifact.bin");
+    let cose = temp.child("artifact.cose");
+
+    let out = Command::cargo_bin("verifai-cli")?
+        .args([
+            "prove",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--out-output",
+            p(temp.child("output.bin").path()),
+            "--out-artifact",
+            p(artifact.path()),
+            "--out-cose",
+            p(cose.path()),
+            "--key-hex",
+            KEY_HEX,
+            "--attest",
+            "--print-json",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let proved: Value = serde_json::from_slice(&out)?;
+    assert_eq!(proved["out_cose"], p(cose.path()));
+
+    for path in [artifact.path(), cose.path()] {
+        let out = verify(&temp, path)?
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let v: Value = serde_json::from_slice(&out)?;
+        assert_eq!(v["trace_root"], proved["trace_root"]);
+        assert_eq!(v["sig_pubkey"], proved["sig_pubkey"]);
+    }
+
+    // flipping the last byte corrupts the COSE signature
+    let mut bytes = fs::read(cose.path())?;
+    let last = bytes.len() - 1;
+    bytes[last] ^= 1;
+    let tampered = temp.child("tampered.cose");
+    fs::write(tampered.path(), bytes)?;
+    verify(&temp, tampered.path())?.assert().code(6);
+
+    Ok(())
+}
+
+#[test]
+fn out_cose_requires_v1_artifact() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    Command::cargo_bin("verifai-cli")?
+        .args([
+            "prove",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--out-output",
+            p(temp.child("output.bin").path()),
+            "--out-artifact",
+            p(temp.child("artifact.bin").path()),
+            "--out-cose",
+            p(temp.child("artifact.cose").path()),
+            "--key-hex",
+            KEY_HEX,
+        ])
+        .assert()
+        .code(5);
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use assert_cmd::Command;
+use assert_fs::fixture::PathChild;
+use assert_fs::TempDir;
+use serde_json::Value;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+fn p(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+fn json(cmd: &mut Command) -> Result<Value, Box<dyn Error>> {
+    let out = cmd.assert().success().get_output().stdout.clone();
+    Ok(serde_json::from_slice(&out)?)
+}
+
+fn disclose(case: &str, out: &Path, selection: &[&str]) -> Result<Command, Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("verifai-cli")?;
+    cmd.args([
+        "disclose",
+        "--artifact",
+        p(&tv_path(&format!("{case}/expected_artifact.bin"))),
+        "--model",
+        p(&tv_path(&format!("{case}/model.json"))),
+        "--input",
+        p(&tv_path(&format!("{case}/input.json"))),
+        "--out",
+        p(out),
+        "--print-json",
+    ]);
+    cmd.args(selection);
+    Ok(cmd)
+}
+
+fn verify_disclosure(artifact: &Path, disclosure: &Path) -> Result<Command, Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("verifai-cli")?;
+    cmd.args([
+        "verify-disclosure",
+        "--artifact",
+        p(artifact),
+        "--disclosure",
+        p(disclosure),
+        "--print-json",
+    ]);
+    Ok(cmd)
+}
+
+#[test]
+fn disclose_score_and_decision_then_verify() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let out = temp.child("disclosure.bin");
+    let disclosed = json(&mut disclose(
+        "case-1",
+        out.path(),
+        &["--kind", "linear", "--kind", "output"],
+    )?)?;
+    assert_eq!(disclosed["cmd"], "disclose");
+    assert_eq!(disclosed["leaf_count"], 3);
+    assert_eq!(disclosed["indices"], serde_json::json!([0, 2]));
+
+    let verified = json(&mut verify_disclosure(
+        &tv_path("case-1/expected_artifact.bin"),
+        out.path(),
+    )?)?;
+    assert_eq!(verified["cmd"], "verify-disclosure");
+    assert_eq!(verified["hash_alg"], "sha256");
+    let events = verified["events"].as_array().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["kind"], "linear");
+    assert!(events[0]["z"].is_f64());
+    assert_eq!(events[1]["kind"], "output");
+    assert!(events[1].get("z").is_none());
+    Ok(())
+}
+
+#[test]
+fn disclose_by_index_keeps_the_v6_hash_alg() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let out = temp.child("disclosure.bin");
+    let disclosed = json(&mut disclose(
+        "hashalg-blake3",
+        out.path(),
+        &["--index", "1"],
+    )?)?;
+    assert_eq!(disclosed["indices"], serde_json::json!([1]));
+
+    let verified = json(&mut verify_disclosure(
+        &tv_path("hashalg-blake3/expected_artifact.bin"),
+        out.path(),
+    )?)?;
+    assert_eq!(verified["hash_alg"], "blake3");
+    assert_eq!(verified["events"][0]["kind"], "activation");
+    assert_eq!(verified["events"][0]["activation"], "sigmoid");
+    Ok(())
+}
+
+#[test]
+fn verify_disclosure_rejects_another_artifact() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let out = temp.child("disclosure.bin");
+    disclose("case-1", out.path(), &["--index", "0"])?
+        .assert()
+        .success();
+
+    let output = verify_disclosure(&tv_path("case-2/expected_artifact.bin"), out.path())?
+        .assert()
+        .code(6)
+        .get_output()
+        .clone();
+    assert!(String::from_utf8_lossy(&output.stderr).contains("InclusionProofInvalid"));
+    Ok(())
+}
+
+#[test]
+fn disclose_rejects_out_of_range_and_missing_selection() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let out = temp.child("disclosure.bin");
+    disclose("case-1", out.path(), &["--index", "3"])?
+        .assert()
+        .code(5);
+    disclose("case-1", out.path(), &[])?.assert().code(2);
+    Ok(())
+}
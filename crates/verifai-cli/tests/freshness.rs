@@ -0,0 +1,136 @@
+use assert_cmd::Command;
+use assert_fs::fixture::PathChild;
+use assert_fs::TempDir;
+use serde_json::Value;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+const KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+const TSA_KEY_HEX: &str = "0505050505050505050505050505050505050505050505050505050505050505";
+const TSA_PUBKEY_HEX: &str = "6e7a1cdd29b0b78fd13af4c5598feff4ef2a97166e3ca6f2e4fbfccd80505bf1";
+const NONCE_HEX: &str = "0404040404040404040404040404040404040404040404040404040404040404";
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+fn p(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+fn prove(temp: &TempDir, extra: &[&str]) -> Result<Value, Box<dyn Error>> {
+    let out = Command::cargo_bin("verifai-cli")?
+        .args([
+            "prove",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--out-output",
+            p(temp.child("output.bin").path()),
+            "--out-artifact",
+            p(temp.child("artifact.bin").path()),
+            "--key-hex",
+            KEY_HEX,
+            "--print-json",
+        ])
+        .args(extra)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    Ok(serde_json::from_slice(&out)?)
+}
+
+fn verify(temp: &TempDir, extra: &[&str]) -> Result<Command, Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("verifai-cli")?;
+    cmd.args([
+        "verify",
+        "--model",
+        p(&tv_path("case-1/model.json")),
+        "--input",
+        p(&tv_path("case-1/input.json")),
+        "--output",
+        p(temp.child("output.bin").path()),
+        "--artifact",
+        p(temp.child("artifact.bin").path()),
+        "--print-json",
+    ]);
+    cmd.args(extra);
+    Ok(cmd)
+}
+
+#[test]
+fn fresh_prove_and_verify_policy() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let proved = prove(
+        &temp,
+        &[
+            "--fresh",
+            "--nonce-hex",
+            NONCE_HEX,
+            "--expires-in-secs",
+            "60",
+            "--tsa-key-hex",
+            TSA_KEY_HEX,
+        ],
+    )?;
+    let issued_at = proved["issued_at"].as_u64().unwrap();
+    assert_eq!(proved["expires_at"], issued_at + 60_000_000_000);
+    assert_eq!(proved["nonce"], NONCE_HEX);
+    assert!(proved["timestamp_gen_time"].as_u64().unwrap() >= issued_at);
+
+    let out = verify(
+        &temp,
+        &[
+            "--expect-nonce-hex",
+            NONCE_HEX,
+            "--require-timestamp",
+            "--tsa-pubkey-hex",
+            TSA_PUBKEY_HEX,
+            "--max-age-secs",
+            "3600",
+        ],
+    )?
+    .assert()
+    .success()
+    .get_output()
+    .stdout
+    .clone();
+    let v: Value = serde_json::from_slice(&out)?;
+    assert_eq!(v["ok"], true);
+    assert_eq!(v["issued_at"], issued_at);
+
+    // a token is only worth something from a pinned TSA
+    verify(&temp, &["--require-timestamp"])?.assert().code(2);
+    let other_tsa = "07".repeat(32);
+    verify(&temp, &["--tsa-pubkey-hex", &other_tsa])?
+        .assert()
+        .code(6);
+
+    let wrong_nonce = "03".repeat(32);
+    verify(&temp, &["--expect-nonce-hex", &wrong_nonce])?
+        .assert()
+        .code(6);
+
+    let after_expiry = (issued_at + 61_000_000_000).to_string();
+    verify(&temp, &["--now-nanos", &after_expiry])?
+        .assert()
+        .code(6);
+
+    Ok(())
+}
+
+#[test]
+fn freshness_requirements_reject_v0_artifacts() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let proved = prove(&temp, &[])?;
+    assert!(proved["issued_at"].is_null());
+
+    verify(&temp, &[])?.assert().success();
+    verify(&temp, &["--max-age-secs", "60"])?.assert().code(6);
+    Ok(())
+}
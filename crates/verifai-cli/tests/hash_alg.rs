@@ -0,0 +1,136 @@
+use assert_cmd::Command;
+use assert_fs::fixture::PathChild;
+use assert_fs::TempDir;
+use serde_json::Value;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+const KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+fn p(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+fn json(cmd: &mut Command) -> Result<Value, Box<dyn Error>> {
+    let out = cmd.assert().success().get_output().stdout.clone();
+    Ok(serde_json::from_slice(&out)?)
+}
+
+#[test]
+fn prove_with_each_hash_alg_matches_vectors_and_verifies() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    for alg in ["sha256", "blake3", "sha3-256"] {
+        let output = temp.child(format!("{alg}-output.bin"));
+        let artifact = temp.child(format!("{alg}-artifact.bin"));
+        let proved = json(Command::cargo_bin("verifai-cli")?.args([
+            "prove",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--out-output",
+            p(output.path()),
+            "--out-artifact",
+            p(artifact.path()),
+            "--key-hex",
+            KEY_HEX,
+            "--runtime-id-hex",
+            &"07".repeat(32),
+            "--hash-alg",
+            alg,
+            "--print-json",
+        ]))?;
+        assert_eq!(proved["hash_alg"], alg);
+        assert_eq!(proved["sig_alg"], "ed25519");
+        assert_eq!(
+            std::fs::read(artifact.path())?,
+            std::fs::read(tv_path(&format!("hashalg-{alg}/expected_artifact.bin")))?
+        );
+
+        let verified = json(Command::cargo_bin("verifai-cli")?.args([
+            "verify",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--output",
+            p(output.path()),
+            "--artifact",
+            p(artifact.path()),
+            "--print-json",
+        ]))?;
+        assert_eq!(verified["hash_alg"], alg);
+        assert_eq!(verified["trace_root"], proved["trace_root"]);
+    }
+    Ok(())
+}
+
+#[test]
+fn hash_alg_combines_with_sig_alg_and_defaults_to_sha256() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let prove = |extra: &[&str]| -> Result<Value, Box<dyn Error>> {
+        let mut cmd = Command::cargo_bin("verifai-cli")?;
+        cmd.args([
+            "prove",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--out-output",
+            p(temp.child("output.bin").path()),
+            "--out-artifact",
+            p(temp.child("artifact.bin").path()),
+            "--key-hex",
+            KEY_HEX,
+            "--print-json",
+        ]);
+        cmd.args(extra);
+        json(&mut cmd)
+    };
+
+    let blake3 = prove(&["--hash-alg", "blake3", "--sig-alg", "secp256k1"])?;
+    assert_eq!(blake3["hash_alg"], "blake3");
+    assert_eq!(blake3["sig_alg"], "secp256k1");
+
+    // without --hash-alg nothing changes and no hash_alg is reported
+    let plain = prove(&[])?;
+    assert!(plain.get("hash_alg").is_none());
+    let sha256 = prove(&["--hash-alg", "sha256"])?;
+    assert_eq!(sha256["model_hash"], plain["model_hash"]);
+    assert_ne!(blake3["model_hash"], plain["model_hash"]);
+    Ok(())
+}
+
+#[test]
+fn hash_alg_rejects_unknown_names_and_fresh_artifacts() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    for extra in [
+        &["--hash-alg", "md5"][..],
+        &["--hash-alg", "blake3", "--fresh"],
+    ] {
+        Command::cargo_bin("verifai-cli")?
+            .args([
+                "prove",
+                "--model",
+                p(&tv_path("case-1/model.json")),
+                "--input",
+                p(&tv_path("case-1/input.json")),
+                "--out-output",
+                p(temp.child("output.bin").path()),
+                "--out-artifact",
+                p(temp.child("artifact.bin").path()),
+                "--key-hex",
+                KEY_HEX,
+            ])
+            .args(extra)
+            .assert()
+            .code(2);
+    }
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use assert_cmd::Command;
+use assert_fs::fixture::PathChild;
+use assert_fs::TempDir;
+use serde_json::Value;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+fn p(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+fn verify(temp: &TempDir, artifact: &Path) -> Result<Command, Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("verifai-cli")?;
+    cmd.args([
+        "verify",
+        "--model",
+        p(&tv_path("case-1/model.json")),
+        "--input",
+        p(&tv_path("case-1/input.json")),
+        "--output",
+        p(temp.child("output.bin").path()),
+        "--artifact",
+        p(artifact),
+        "--print-json",
+    ]);
+    Ok(cmd)
+}
+
+#[test]
+fn prove_writes_envelope_and_verify_accepts_it() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let envelope = temp.child("artifact.intoto.json");
+
+    let out = Command::cargo_bin("verifai-cli")?
+        .args([
+            "prove",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--out-output",
+            p(temp.child("output.bin").path()),
+            "--out-artifact",
+            p(temp.child("artifact.bin").path()),
+            "--out-intoto",
+            p(envelope.path()),
+            "--key-hex",
+            KEY_HEX,
+            "--print-json",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let proved: Value = serde_json::from_slice(&out)?;
+    assert_eq!(proved["out_intoto"], p(envelope.path()));
+
+    let env: Value = serde_json::from_slice(&fs::read(envelope.path())?)?;
+    assert_eq!(env["payloadType"], "application/vnd.in-toto+json");
+    assert_eq!(env["signatures"][0]["keyid"], proved["sig_pubkey"]);
+
+    let out = verify(&temp, envelope.path())?
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: Value = serde_json::from_slice(&out)?;
+    assert_eq!(v["trace_root"], proved["trace_root"]);
+
+    // a zeroed signature (base64 of 64 zero bytes) does not verify
+    let mut forged = env;
+    forged["signatures"][0]["sig"] = format!("{}==", "A".repeat(86)).into();
+    let path = temp.child("forged.intoto.json");
+    fs::write(path.path(), serde_json::to_vec(&forged)?)?;
+    verify(&temp, path.path())?.assert().code(6);
+
+    Ok(())
+}
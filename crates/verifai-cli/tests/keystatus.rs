@@ -0,0 +1,158 @@
+use assert_cmd::Command;
+use assert_fs::fixture::{FileWriteStr, PathChild};
+use assert_fs::TempDir;
+use serde_json::Value;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+const KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+const AUTHORITY_KEY_HEX: &str = "0303030303030303030303030303030303030303030303030303030303030303";
+const TSA_KEY_HEX: &str = "0505050505050505050505050505050505050505050505050505050505050505";
+const TSA_PUBKEY_HEX: &str = "6e7a1cdd29b0b78fd13af4c5598feff4ef2a97166e3ca6f2e4fbfccd80505bf1";
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+fn p(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+fn json(cmd: &mut Command) -> Result<Value, Box<dyn Error>> {
+    let out = cmd.assert().success().get_output().stdout.clone();
+    Ok(serde_json::from_slice(&out)?)
+}
+
+/// Signs `keys_json` into `name` and returns the authority public key.
+fn key_status(temp: &TempDir, name: &str, keys_json: &str) -> Result<String, Box<dyn Error>> {
+    let keys = temp.child(format!("{name}.json"));
+    keys.write_str(keys_json)?;
+    let out = json(Command::cargo_bin("verifai-cli")?.args([
+        "key-status",
+        "--keys",
+        p(keys.path()),
+        "--key-hex",
+        AUTHORITY_KEY_HEX,
+        "--out",
+        p(temp.child(name).path()),
+        "--print-json",
+    ]))?;
+    Ok(out["authority_pubkey"].as_str().unwrap().to_string())
+}
+
+fn verify(temp: &TempDir, list: &str, authority: &str) -> Result<Command, Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("verifai-cli")?;
+    cmd.args([
+        "verify",
+        "--model",
+        p(&tv_path("case-1/model.json")),
+        "--input",
+        p(&tv_path("case-1/input.json")),
+        "--output",
+        p(temp.child("output.bin").path()),
+        "--artifact",
+        p(temp.child("artifact.bin").path()),
+        "--key-status",
+        p(temp.child(list).path()),
+        "--key-status-pubkey-hex",
+        authority,
+    ]);
+    Ok(cmd)
+}
+
+#[test]
+fn verify_rejects_revoked_and_unlisted_keys() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let proved = json(Command::cargo_bin("verifai-cli")?.args([
+        "prove",
+        "--model",
+        p(&tv_path("case-1/model.json")),
+        "--input",
+        p(&tv_path("case-1/input.json")),
+        "--out-output",
+        p(temp.child("output.bin").path()),
+        "--out-artifact",
+        p(temp.child("artifact.bin").path()),
+        "--key-hex",
+        KEY_HEX,
+        "--print-json",
+    ]))?;
+    let signer = proved["sig_pubkey"].as_str().unwrap();
+
+    let authority = key_status(
+        &temp,
+        "good.bin",
+        &format!(r#"{{"keys": [{{"pubkey": "{signer}"}}]}}"#),
+    )?;
+    verify(&temp, "good.bin", &authority)?.assert().success();
+    verify(&temp, "good.bin", &"00".repeat(32))?
+        .assert()
+        .code(6);
+
+    key_status(
+        &temp,
+        "revoked.bin",
+        &format!(r#"{{"keys": [{{"pubkey": "{signer}", "revoked_at": 1}}]}}"#),
+    )?;
+    verify(&temp, "revoked.bin", &authority)?.assert().code(6);
+
+    // artifacts without a signed issuance time are judged at the verifier's clock
+    key_status(
+        &temp,
+        "retired.bin",
+        &format!(r#"{{"keys": [{{"pubkey": "{signer}", "valid_until": 1}}]}}"#),
+    )?;
+    verify(&temp, "retired.bin", &authority)?.assert().code(6);
+
+    key_status(
+        &temp,
+        "other.bin",
+        &format!(r#"{{"keys": [{{"pubkey": "{}"}}]}}"#, "11".repeat(32)),
+    )?;
+    verify(&temp, "other.bin", &authority)?.assert().code(6);
+
+    Ok(())
+}
+
+#[test]
+fn verify_trusts_only_timestamps_from_the_pinned_tsa() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let proved = json(Command::cargo_bin("verifai-cli")?.args([
+        "prove",
+        "--model",
+        p(&tv_path("case-1/model.json")),
+        "--input",
+        p(&tv_path("case-1/input.json")),
+        "--out-output",
+        p(temp.child("output.bin").path()),
+        "--out-artifact",
+        p(temp.child("artifact.bin").path()),
+        "--key-hex",
+        KEY_HEX,
+        "--fresh",
+        "--tsa-key-hex",
+        TSA_KEY_HEX,
+        "--print-json",
+    ]))?;
+    let signer = proved["sig_pubkey"].as_str().unwrap();
+    let revoked_at = proved["timestamp_gen_time"].as_u64().unwrap() + 1_000_000_000;
+
+    let authority = key_status(
+        &temp,
+        "revoked.bin",
+        &format!(r#"{{"keys": [{{"pubkey": "{signer}", "revoked_at": {revoked_at}}}]}}"#),
+    )?;
+    verify(&temp, "revoked.bin", &authority)?
+        .args(["--tsa-pubkey-hex", TSA_PUBKEY_HEX])
+        .assert()
+        .success();
+    // without the pin the token is the leaked key's word, like issued_at
+    verify(&temp, "revoked.bin", &authority)?.assert().code(6);
+    verify(&temp, "revoked.bin", &authority)?
+        .args(["--tsa-pubkey-hex", &"11".repeat(32)])
+        .assert()
+        .code(6);
+    Ok(())
+}
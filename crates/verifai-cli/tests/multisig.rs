@@ -0,0 +1,140 @@
+use assert_cmd::Command;
+use assert_fs::fixture::PathChild;
+use assert_fs::TempDir;
+use serde_json::Value;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+const KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+const AUDITOR_KEY_HEX: &str = "0808080808080808080808080808080808080808080808080808080808080808";
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+fn p(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+fn json(cmd: &mut Command) -> Result<Value, Box<dyn Error>> {
+    let out = cmd.assert().success().get_output().stdout.clone();
+    Ok(serde_json::from_slice(&out)?)
+}
+
+fn replay(temp: &TempDir, cmd: &str, artifact: &Path) -> Result<Command, Box<dyn Error>> {
+    let mut c = Command::cargo_bin("verifai-cli")?;
+    c.args([
+        cmd,
+        "--model",
+        p(&tv_path("case-1/model.json")),
+        "--input",
+        p(&tv_path("case-1/input.json")),
+        "--output",
+        p(temp.child("output.bin").path()),
+        "--artifact",
+        p(artifact),
+        "--print-json",
+    ]);
+    Ok(c)
+}
+
+#[test]
+fn cosigned_artifact_meets_threshold() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let artifact = temp.child("artifact.bin");
+
+    let proved = json(Command::cargo_bin("verifai-cli")?.args([
+        "prove",
+        "--model",
+        p(&tv_path("case-1/model.json")),
+        "--input",
+        p(&tv_path("case-1/input.json")),
+        "--out-output",
+        p(temp.child("output.bin").path()),
+        "--out-artifact",
+        p(artifact.path()),
+        "--key-hex",
+        KEY_HEX,
+        "--multisig",
+        "--print-json",
+    ]))?;
+    let prover = proved["sig_pubkey"].as_str().unwrap().to_string();
+
+    let v = json(&mut replay(&temp, "verify", artifact.path())?)?;
+    assert_eq!(v["signers"], serde_json::json!([prover]));
+
+    let cosigned = temp.child("cosigned.bin");
+    let c = json(replay(&temp, "cosign", artifact.path())?.args([
+        "--key-hex",
+        AUDITOR_KEY_HEX,
+        "--out",
+        p(cosigned.path()),
+    ]))?;
+    let signers = c["signers"].as_array().unwrap().clone();
+    assert_eq!(signers.len(), 2);
+    assert_eq!(signers[0], prover.as_str());
+    let auditor = signers[1].as_str().unwrap();
+
+    let two_of_two = [
+        "--trusted-key-hex",
+        &prover,
+        "--trusted-key-hex",
+        auditor,
+        "--threshold",
+        "2",
+    ];
+    replay(&temp, "verify", artifact.path())?
+        .args(two_of_two)
+        .assert()
+        .code(6);
+    let v = json(replay(&temp, "verify", cosigned.path())?.args(two_of_two))?;
+    assert_eq!(v["signers"].as_array().unwrap().len(), 2);
+
+    // a single-signature artifact counts its one signer
+    replay(&temp, "verify", artifact.path())?
+        .args(["--trusted-key-hex", auditor])
+        .assert()
+        .code(6);
+
+    Ok(())
+}
+
+#[test]
+fn cosign_rejects_non_matching_output() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let artifact = temp.child("artifact.bin");
+
+    Command::cargo_bin("verifai-cli")?
+        .args([
+            "prove",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--out-output",
+            p(temp.child("output.bin").path()),
+            "--out-artifact",
+            p(artifact.path()),
+            "--key-hex",
+            KEY_HEX,
+            "--multisig",
+        ])
+        .assert()
+        .success();
+    std::fs::write(temp.child("output.bin").path(), b"not the output")?;
+
+    replay(&temp, "cosign", artifact.path())?
+        .args([
+            "--key-hex",
+            AUDITOR_KEY_HEX,
+            "--out",
+            p(temp.child("cosigned.bin").path()),
+        ])
+        .assert()
+        .code(6);
+    assert!(!temp.child("cosigned.bin").path().exists());
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use assert_cmd::Command;
+use assert_fs::fixture::{FileWriteStr, PathChild};
+use assert_fs::TempDir;
+use serde_json::Value;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+const KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+const OPERATOR_KEY_HEX: &str = "0505050505050505050505050505050505050505050505050505050505050505";
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+fn p(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+fn json(cmd: &mut Command) -> Result<Value, Box<dyn Error>> {
+    let out = cmd.assert().success().get_output().stdout.clone();
+    Ok(serde_json::from_slice(&out)?)
+}
+
+/// Proves case-1 as stage 1 and a one-feature scoring model on its output
+/// as stage 2.
+fn prove_stages(temp: &TempDir) -> Result<(), Box<dyn Error>> {
+    temp.child("score.json")
+        .write_str(r#"{"weights": [2.0], "bias": -1.0}"#)?;
+    json(Command::cargo_bin("verifai-cli")?.args([
+        "prove",
+        "--model",
+        p(&tv_path("case-1/model.json")),
+        "--input",
+        p(&tv_path("case-1/input.json")),
+        "--out-output",
+        p(temp.child("s1-output.bin").path()),
+        "--out-artifact",
+        p(temp.child("s1-artifact.bin").path()),
+        "--key-hex",
+        KEY_HEX,
+        "--print-json",
+    ]))?;
+    json(Command::cargo_bin("verifai-cli")?.args([
+        "prove",
+        "--model",
+        p(temp.child("score.json").path()),
+        "--input-from",
+        p(temp.child("s1-output.bin").path()),
+        "--out-output",
+        p(temp.child("s2-output.bin").path()),
+        "--out-artifact",
+        p(temp.child("s2-artifact.bin").path()),
+        "--key-hex",
+        KEY_HEX,
+        "--attest",
+        "--print-json",
+    ]))?;
+    Ok(())
+}
+
+fn link(temp: &TempDir, stages: &[&str]) -> Command {
+    let mut cmd = Command::cargo_bin("verifai-cli").unwrap();
+    cmd.args(["pipeline", "--key-hex", OPERATOR_KEY_HEX, "--print-json"]);
+    cmd.args(["--out", p(temp.child("pipeline.bin").path())]);
+    for stage in stages {
+        cmd.args([
+            "--stage-artifact",
+            p(temp.child(format!("{stage}-artifact.bin")).path()),
+            "--stage-output",
+            p(temp.child(format!("{stage}-output.bin")).path()),
+        ]);
+    }
+    cmd
+}
+
+fn verify(temp: &TempDir, extra: &[&str]) -> Command {
+    let mut cmd = Command::cargo_bin("verifai-cli").unwrap();
+    cmd.args([
+        "verify-pipeline",
+        "--pipeline",
+        p(temp.child("pipeline.bin").path()),
+        "--print-json",
+    ]);
+    cmd.args(extra);
+    cmd
+}
+
+#[test]
+fn pipeline_links_and_verifies_end_to_end() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    prove_stages(&temp)?;
+    let linked = json(&mut link(&temp, &["s1", "s2"]))?;
+    assert_eq!(linked["cmd"], "pipeline");
+    assert_eq!(linked["stages"], 2);
+
+    let checked = json(&mut verify(&temp, &[]))?;
+    assert_eq!(checked["replayed"], false);
+    assert_eq!(checked["sig_pubkey"], linked["sig_pubkey"]);
+
+    let score = temp.child("score.json");
+    let replayed = json(&mut verify(
+        &temp,
+        &[
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--model",
+            p(score.path()),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--pipeline-pubkey-hex",
+            linked["sig_pubkey"].as_str().unwrap(),
+        ],
+    ))?;
+    assert_eq!(replayed["replayed"], true);
+    assert_eq!(replayed["y"], checked["y"]);
+
+    // a different first input does not replay
+    verify(
+        &temp,
+        &[
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--model",
+            p(score.path()),
+            "--input",
+            p(&tv_path("case-2/input.json")),
+        ],
+    )
+    .assert()
+    .code(6);
+    verify(&temp, &["--pipeline-pubkey-hex", KEY_HEX])
+        .assert()
+        .code(6);
+    Ok(())
+}
+
+#[test]
+fn pipeline_refuses_unlinked_stages() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    prove_stages(&temp)?;
+    // stage 2 did not take stage 1's output as input when reversed
+    let assert = link(&temp, &["s2", "s1"]).assert().code(5);
+    let stderr = String::from_utf8_lossy(&assert.get_output().stderr).into_owned();
+    assert!(stderr.contains("PipelineLinkBroken"), "{stderr}");
+    assert!(!temp.child("pipeline.bin").path().exists());
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use assert_cmd::Command;
+use assert_fs::fixture::{FileWriteStr, PathChild};
+use assert_fs::TempDir;
+use serde_json::Value;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+const KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+
+const PREPROCESSOR: &str = r#"{"raw_len": 3, "features": [
+    {"kind": "numeric", "source": 0, "mean": 1.0, "std": 2.0, "clip": [-5.0, 5.0], "default": 1.0},
+    {"kind": "categorical", "source": 2, "categories": ["red", "green", "blue"]}
+]}"#;
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+fn p(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+fn json(cmd: &mut Command) -> Result<Value, Box<dyn Error>> {
+    let out = cmd.assert().success().get_output().stdout.clone();
+    Ok(serde_json::from_slice(&out)?)
+}
+
+fn write(temp: &TempDir, name: &str, contents: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let child = temp.child(name);
+    child.write_str(contents)?;
+    Ok(child.path().to_path_buf())
+}
+
+fn prove(temp: &TempDir, preprocessor: &Path, raw_input: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("verifai-cli").unwrap();
+    cmd.args([
+        "prove",
+        "--model",
+        p(&tv_path("case-1/model.json")),
+        "--preprocessor",
+        p(preprocessor),
+        "--raw-input",
+        p(raw_input),
+        "--out-output",
+        p(temp.child("output.bin").path()),
+        "--out-artifact",
+        p(temp.child("artifact.bin").path()),
+        "--key-hex",
+        KEY_HEX,
+        "--print-json",
+    ]);
+    cmd
+}
+
+fn verify(temp: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("verifai-cli").unwrap();
+    cmd.args([
+        "verify",
+        "--model",
+        p(&tv_path("case-1/model.json")),
+        "--output",
+        p(temp.child("output.bin").path()),
+        "--artifact",
+        p(temp.child("artifact.bin").path()),
+        "--print-json",
+    ]);
+    cmd
+}
+
+#[test]
+fn preprocessed_prove_verifies_from_raw_input() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let pre = write(&temp, "pre.json", PREPROCESSOR)?;
+    let raw = write(&temp, "raw.json", r#"{"values": [3.0, null, "green"]}"#)?;
+    let other_raw = write(&temp, "other.json", r#"{"values": [3.0, null, "blue"]}"#)?;
+
+    let proved = json(&mut prove(&temp, &pre, &raw))?;
+    assert_eq!(proved["preprocessor_hash"].as_str().unwrap().len(), 64);
+
+    // same score as proving the preprocessed features directly
+    let features = write(&temp, "features.json", r#"{"x": [1.0, 0.0, 1.0, 0.0]}"#)?;
+    Command::cargo_bin("verifai-cli")?
+        .args([
+            "prove",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&features),
+            "--out-output",
+            p(temp.child("plain-output.bin").path()),
+            "--out-artifact",
+            p(temp.child("plain-artifact.bin").path()),
+            "--key-hex",
+            KEY_HEX,
+        ])
+        .assert()
+        .success();
+    assert_eq!(
+        std::fs::read(temp.child("output.bin").path())?,
+        std::fs::read(temp.child("plain-output.bin").path())?
+    );
+
+    let verified = json(verify(&temp).args(["--preprocessor", p(&pre), "--raw-input", p(&raw)]))?;
+    assert_eq!(verified["trace_root"], proved["trace_root"]);
+
+    let out = verify(&temp)
+        .args(["--input", p(&features)])
+        .assert()
+        .code(6)
+        .get_output()
+        .clone();
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--preprocessor"));
+    let out = verify(&temp)
+        .args(["--preprocessor", p(&pre), "--raw-input", p(&other_raw)])
+        .assert()
+        .code(6)
+        .get_output()
+        .clone();
+    assert!(String::from_utf8_lossy(&out.stderr).contains("HashMismatch"));
+    Ok(())
+}
+
+#[test]
+fn invalid_preprocessor_or_raw_input_is_rejected() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let pre = write(&temp, "pre.json", PREPROCESSOR)?;
+    let raw = write(&temp, "raw.json", r#"{"values": [3.0, null, "green"]}"#)?;
+
+    for (name, bad) in [
+        (
+            "zero-std.json",
+            PREPROCESSOR.replace("\"std\": 2.0", "\"std\": 0.0"),
+        ),
+        (
+            "bad-default.json",
+            PREPROCESSOR.replace("]}\n]}", "], \"default\": \"mauve\"}\n]}"),
+        ),
+    ] {
+        let bad = write(&temp, name, &bad)?;
+        prove(&temp, &bad, &raw).assert().code(3);
+    }
+
+    let swapped = write(&temp, "swapped.json", r#"{"values": ["green", null, 3.0]}"#)?;
+    let out = prove(&temp, &pre, &swapped)
+        .assert()
+        .code(5)
+        .get_output()
+        .clone();
+    assert!(String::from_utf8_lossy(&out.stderr).contains("RawInputInvalid"));
+
+    prove(&temp, &pre, &raw)
+        .args(["--input", p(&tv_path("case-1/input.json"))])
+        .assert()
+        .code(2);
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use assert_cmd::Command;
+use assert_fs::fixture::PathChild;
+use assert_fs::TempDir;
+use serde_json::Value;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+const KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+fn p(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+fn json(cmd: &mut Command) -> Result<Value, Box<dyn Error>> {
+    let out = cmd.assert().success().get_output().stdout.clone();
+    Ok(serde_json::from_slice(&out)?)
+}
+
+fn prove(temp: &TempDir, name: &str) -> Result<Value, Box<dyn Error>> {
+    json(Command::cargo_bin("verifai-cli")?.args([
+        "prove",
+        "--model",
+        p(&tv_path("case-1/model.json")),
+        "--input",
+        p(&tv_path("case-1/input.json")),
+        "--out-output",
+        p(temp.child(format!("{name}-output.bin")).path()),
+        "--out-artifact",
+        p(temp.child(format!("{name}-artifact.bin")).path()),
+        "--out-salts",
+        p(temp.child(format!("{name}-salts.bin")).path()),
+        "--key-hex",
+        KEY_HEX,
+        "--print-json",
+    ]))
+}
+
+fn verify(temp: &TempDir, name: &str, salts: Option<&str>) -> Result<Command, Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("verifai-cli")?;
+    cmd.args([
+        "verify",
+        "--model",
+        p(&tv_path("case-1/model.json")),
+        "--input",
+        p(&tv_path("case-1/input.json")),
+        "--output",
+        p(temp.child(format!("{name}-output.bin")).path()),
+        "--artifact",
+        p(temp.child(format!("{name}-artifact.bin")).path()),
+        "--print-json",
+    ]);
+    if let Some(salts) = salts {
+        cmd.args([
+            "--salts",
+            p(temp.child(format!("{salts}-salts.bin")).path()),
+        ]);
+    }
+    Ok(cmd)
+}
+
+#[test]
+fn salted_prove_verifies_with_its_salts_only() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let a = prove(&temp, "a")?;
+    let b = prove(&temp, "b")?;
+    assert!(a["out_salts"].as_str().unwrap().ends_with("a-salts.bin"));
+    assert_eq!(std::fs::read(temp.child("a-salts.bin").path())?.len(), 136);
+
+    // fresh salts per artifact: the same input commits differently
+    assert_ne!(a["input_hash"], b["input_hash"]);
+    assert_ne!(a["trace_root"], b["trace_root"]);
+
+    let verified = json(&mut verify(&temp, "a", Some("a"))?)?;
+    assert_eq!(verified["trace_root"], a["trace_root"]);
+
+    for (salts, reason) in [(None, "--salts"), (Some("b"), "HashMismatch")] {
+        let out = verify(&temp, "a", salts)?
+            .assert()
+            .code(6)
+            .get_output()
+            .clone();
+        assert!(String::from_utf8_lossy(&out.stderr).contains(reason));
+    }
+    Ok(())
+}
+
+#[test]
+fn out_salts_rejects_other_artifact_versions() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    for extra in [&["--multisig"][..], &["--sig-alg", "p256"], &["--fresh"]] {
+        Command::cargo_bin("verifai-cli")?
+            .args([
+                "prove",
+                "--model",
+                p(&tv_path("case-1/model.json")),
+                "--input",
+                p(&tv_path("case-1/input.json")),
+                "--out-output",
+                p(temp.child("output.bin").path()),
+                "--out-artifact",
+                p(temp.child("artifact.bin").path()),
+                "--out-salts",
+                p(temp.child("salts.bin").path()),
+                "--key-hex",
+                KEY_HEX,
+            ])
+            .args(extra)
+            .assert()
+            .code(2);
+    }
+    Ok(())
+}
@@ -0,0 +1,258 @@
+use assert_cmd::cargo::CommandCargoExt;
+use assert_fs::fixture::PathChild;
+use assert_fs::TempDir;
+use serde_json::Value;
+use std::error::Error;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+
+const KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+const RUNTIME_ID_HEX: &str = "0707070707070707070707070707070707070707070707070707070707070707";
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+/// Kills the server when the test ends, pass or fail.
+struct ServerGuard {
+    child: Child,
+    addr: String,
+}
+
+impl Drop for ServerGuard {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn start_server(models_dir: &TempDir) -> Result<ServerGuard, Box<dyn Error>> {
+    let mut child = Command::cargo_bin("verifai-cli")?
+        .args([
+            "serve",
+            "--models-dir",
+            models_dir.path().to_str().unwrap(),
+            "--key-hex",
+            KEY_HEX,
+            "--runtime-id-hex",
+            RUNTIME_ID_HEX,
+            "--bind",
+            "127.0.0.1:0",
+        ])
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut line = String::new();
+    BufReader::new(child.stdout.take().unwrap()).read_line(&mut line)?;
+    let addr = line
+        .strip_prefix("listening on ")
+        .and_then(|rest| rest.split_whitespace().next())
+        .ok_or("unexpected startup line")?
+        .to_string();
+    Ok(ServerGuard { child, addr })
+}
+
+fn request(
+    addr: &str,
+    method: &str,
+    path: &str,
+    headers: &[(&str, &str)],
+    body: &[u8],
+) -> Result<(u16, Vec<u8>), Box<dyn Error>> {
+    let mut stream = TcpStream::connect(addr)?;
+    let mut head = format!(
+        "{method} {path} HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\nContent-Length: {}\r\n",
+        body.len()
+    );
+    for (k, v) in headers {
+        head.push_str(&format!("{k}: {v}\r\n"));
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(body)?;
+
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw)?;
+    let split = raw
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or("no header terminator")?;
+    let status_line = std::str::from_utf8(&raw[..split])?
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .ok_or("no status")?
+        .parse()?;
+    Ok((status, raw[split + 4..].to_vec()))
+}
+
+fn post_json(addr: &str, path: &str, body: &Value) -> Result<(u16, Value), Box<dyn Error>> {
+    let (status, body) = request(
+        addr,
+        "POST",
+        path,
+        &[("Content-Type", "application/json")],
+        body.to_string().as_bytes(),
+    )?;
+    Ok((status, serde_json::from_slice(&body)?))
+}
+
+fn models_dir() -> Result<TempDir, Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    fs::copy(
+        tv_path("case-1/model.json"),
+        temp.child("model.json").path(),
+    )?;
+    Ok(temp)
+}
+
+fn model_hash(temp: &TempDir) -> Result<String, Box<dyn Error>> {
+    let output = Command::cargo_bin("verifai-cli")?
+        .args([
+            "hash-model",
+            "--model",
+            temp.child("model.json").path().to_str().unwrap(),
+        ])
+        .output()?;
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+#[test]
+fn serve_json_prove_then_verify() -> Result<(), Box<dyn Error>> {
+    let models = models_dir()?;
+    let hash = model_hash(&models)?;
+    let server = start_server(&models)?;
+
+    let (status, health) = request(&server.addr, "GET", "/health", &[], b"")?;
+    assert_eq!(status, 200);
+    let health: Value = serde_json::from_slice(&health)?;
+    assert_eq!(health["models"], 1);
+
+    let x = serde_json::json!([1.0, 2.0, 3.0, 4.0]);
+    let (status, proved) = post_json(
+        &server.addr,
+        "/prove",
+        &serde_json::json!({ "model_hash": hash, "x": x }),
+    )?;
+    assert_eq!(status, 200);
+    assert_eq!(proved["ok"], true);
+    assert_eq!(proved["model_hash"], hash.as_str());
+
+    let verify_body = serde_json::json!({
+        "model_hash": hash,
+        "x": x,
+        "output": proved["output"],
+        "artifact": proved["artifact"],
+    });
+    let (status, verified) = post_json(&server.addr, "/verify", &verify_body)?;
+    assert_eq!(status, 200);
+    assert_eq!(verified["trace_root"], proved["trace_root"]);
+
+    let tampered = serde_json::json!({
+        "model_hash": hash,
+        "x": [4.0, 3.0, 2.0, 1.0],
+        "output": proved["output"],
+        "artifact": proved["artifact"],
+    });
+    let (status, rejected) = post_json(&server.addr, "/verify", &tampered)?;
+    assert_eq!(status, 422);
+    assert_eq!(rejected["ok"], false);
+
+    let (status, metrics) = request(&server.addr, "GET", "/metrics", &[], b"")?;
+    assert_eq!(status, 200);
+    let metrics = String::from_utf8(metrics)?;
+    assert!(metrics.contains("verifai_prove_ok_total 1"));
+    assert!(metrics.contains("verifai_verify_ok_total 1"));
+    assert!(metrics.contains("verifai_verify_failed_total 1"));
+    Ok(())
+}
+
+#[test]
+fn serve_binary_prove_and_errors() -> Result<(), Box<dyn Error>> {
+    let models = models_dir()?;
+    let hash = model_hash(&models)?;
+    let server = start_server(&models)?;
+
+    let input_bin = fs::read(tv_path("case-1/input.bin"))?;
+    let (status, body) = request(
+        &server.addr,
+        "POST",
+        "/prove",
+        &[
+            ("Content-Type", "application/octet-stream"),
+            ("X-Verifai-Model-Hash", &hash),
+        ],
+        &input_bin,
+    )?;
+    assert_eq!(status, 200);
+    let output_len = u32::from_le_bytes(body[..4].try_into()?) as usize;
+    let output_bin = &body[4..4 + output_len];
+    let artifact_bin = &body[4 + output_len..];
+    assert_eq!(output_bin, fs::read(tv_path("case-1/expected_output.bin"))?);
+    assert_eq!(
+        artifact_bin,
+        fs::read(tv_path("case-1/expected_artifact.bin"))?
+    );
+
+    let mut verify_body = Vec::new();
+    verify_body.extend_from_slice(&(input_bin.len() as u32).to_le_bytes());
+    verify_body.extend_from_slice(&input_bin);
+    verify_body.extend_from_slice(&(output_len as u32).to_le_bytes());
+    verify_body.extend_from_slice(output_bin);
+    verify_body.extend_from_slice(artifact_bin);
+    let (status, _) = request(
+        &server.addr,
+        "POST",
+        "/verify",
+        &[
+            ("Content-Type", "application/octet-stream"),
+            ("X-Verifai-Model-Hash", &hash),
+        ],
+        &verify_body,
+    )?;
+    assert_eq!(status, 200);
+
+    let (status, _) = post_json(
+        &server.addr,
+        "/prove",
+        &serde_json::json!({ "model_hash": "00".repeat(32), "x": [1.0] }),
+    )?;
+    assert_eq!(status, 404);
+
+    let (status, _) = request(&server.addr, "POST", "/prove", &[], b"not json")?;
+    assert_eq!(status, 400);
+    Ok(())
+}
+
+#[test]
+fn serve_rejects_oversized_bodies_before_reading_them() -> Result<(), Box<dyn Error>> {
+    let models = models_dir()?;
+    let server = start_server(&models)?;
+
+    // headers only: the declared length alone must be refused
+    let mut stream = TcpStream::connect(&server.addr)?;
+    write!(
+        stream,
+        "POST /prove HTTP/1.1\r\nHost: {}\r\nConnection: close\r\nContent-Length: {}\r\n\r\n",
+        server.addr,
+        1u64 << 30
+    )?;
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw)?;
+    let head = String::from_utf8_lossy(&raw);
+    assert!(head.starts_with("HTTP/1.1 413"), "{head}");
+
+    // a small body still goes through
+    let (status, _) = request(&server.addr, "POST", "/prove", &[], b"not json")?;
+    assert_eq!(status, 400);
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use assert_cmd::Command;
+use assert_fs::fixture::PathChild;
+use assert_fs::TempDir;
+use serde_json::Value;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+const KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+fn p(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+fn json(cmd: &mut Command) -> Result<Value, Box<dyn Error>> {
+    let out = cmd.assert().success().get_output().stdout.clone();
+    Ok(serde_json::from_slice(&out)?)
+}
+
+#[test]
+fn prove_with_each_sig_alg_matches_vectors_and_verifies() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    for (alg, pubkey_len) in [("ed25519", 32), ("p256", 33), ("secp256k1", 33)] {
+        let output = temp.child(format!("{alg}-output.bin"));
+        let artifact = temp.child(format!("{alg}-artifact.bin"));
+        let proved = json(Command::cargo_bin("verifai-cli")?.args([
+            "prove",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--out-output",
+            p(output.path()),
+            "--out-artifact",
+            p(artifact.path()),
+            "--key-hex",
+            KEY_HEX,
+            "--runtime-id-hex",
+            &"07".repeat(32),
+            "--sig-alg",
+            alg,
+            "--print-json",
+        ]))?;
+        assert_eq!(proved["sig_alg"], alg);
+        assert_eq!(proved["sig_pubkey"].as_str().unwrap().len(), 2 * pubkey_len);
+        assert_eq!(
+            std::fs::read(artifact.path())?,
+            std::fs::read(tv_path(&format!("sigalg-{alg}/expected_artifact.bin")))?
+        );
+
+        let verified = json(Command::cargo_bin("verifai-cli")?.args([
+            "verify",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--output",
+            p(output.path()),
+            "--artifact",
+            p(artifact.path()),
+            "--print-json",
+        ]))?;
+        assert_eq!(verified["sig_alg"], alg);
+        assert_eq!(verified["sig_pubkey"], proved["sig_pubkey"]);
+    }
+    Ok(())
+}
+
+#[test]
+fn sig_alg_rejects_agents_and_out_of_range_keys() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let prove = |key_hex: &str, extra: &[&str]| -> Result<Command, Box<dyn Error>> {
+        let mut cmd = Command::cargo_bin("verifai-cli")?;
+        cmd.args([
+            "prove",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--out-output",
+            p(temp.child("output.bin").path()),
+            "--out-artifact",
+            p(temp.child("artifact.bin").path()),
+            "--key-hex",
+            key_hex,
+        ]);
+        cmd.args(extra);
+        Ok(cmd)
+    };
+
+    // the secp256k1 group order is below 2^256 - 1
+    prove(&"ff".repeat(32), &["--sig-alg", "secp256k1"])?
+        .assert()
+        .code(4);
+    prove(KEY_HEX, &["--sig-alg", "rsa"])?.assert().code(2);
+    prove(KEY_HEX, &["--sig-alg", "p256", "--multisig"])?
+        .assert()
+        .code(2);
+    Ok(())
+}
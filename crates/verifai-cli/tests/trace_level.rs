@@ -0,0 +1,146 @@
+use assert_cmd::Command;
+use assert_fs::fixture::PathChild;
+use assert_fs::TempDir;
+use serde_json::Value;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+const KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+fn p(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+fn json(cmd: &mut Command) -> Result<Value, Box<dyn Error>> {
+    let out = cmd.assert().success().get_output().stdout.clone();
+    Ok(serde_json::from_slice(&out)?)
+}
+
+fn prove(temp: &TempDir, level: &str) -> Result<Command, Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("verifai-cli")?;
+    cmd.args([
+        "prove",
+        "--model",
+        p(&tv_path("case-1/model.json")),
+        "--input",
+        p(&tv_path("case-1/input.json")),
+        "--out-output",
+        p(temp.child(format!("{level}-output.bin")).path()),
+        "--out-artifact",
+        p(temp.child(format!("{level}-artifact.bin")).path()),
+        "--key-hex",
+        KEY_HEX,
+        "--trace-level",
+        level,
+        "--print-json",
+    ]);
+    Ok(cmd)
+}
+
+#[test]
+fn trace_level_is_declared_and_verified() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let mut roots = Vec::new();
+    for level in ["summary", "layer", "op", "fine"] {
+        let proved = json(&mut prove(&temp, level)?)?;
+        assert_eq!(proved["trace_level"], level);
+
+        let verified = json(Command::cargo_bin("verifai-cli")?.args([
+            "verify",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--output",
+            p(temp.child(format!("{level}-output.bin")).path()),
+            "--artifact",
+            p(temp.child(format!("{level}-artifact.bin")).path()),
+            "--print-json",
+        ]))?;
+        assert_eq!(verified["trace_level"], level);
+        assert_eq!(verified["trace_root"], proved["trace_root"]);
+        roots.push(proved["trace_root"].clone());
+    }
+    roots.dedup();
+    assert_eq!(roots.len(), 4);
+    Ok(())
+}
+
+#[test]
+fn trace_level_rejects_unknown_levels_and_other_versions() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    prove(&temp, "mac")?.assert().code(2);
+    prove(&temp, "fine")?
+        .args(["--out-salts", p(temp.child("salts.bin").path())])
+        .assert()
+        .code(2);
+    prove(&temp, "fine")?
+        .args(["--out-trace", p(temp.child("trace.bin").path())])
+        .assert()
+        .code(2);
+    Ok(())
+}
+
+#[test]
+fn manifests_and_disclosures_reject_v9_artifacts() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    prove(&temp, "op")?.assert().success();
+    let manifest = temp.child("manifest.jsonl");
+    std::fs::write(
+        manifest.path(),
+        "{\"index\": 0, \"x\": [1.0, 2.0, 3.0, 4.0], \
+         \"output\": \"op-output.bin\", \"artifact\": \"op-artifact.bin\"}\n",
+    )?;
+
+    let json_file = temp.child("verify.jsonl");
+    Command::cargo_bin("verifai-cli")?
+        .args([
+            "verify",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--manifest",
+            p(manifest.path()),
+            "--json-file",
+            p(json_file.path()),
+        ])
+        .assert()
+        .code(6);
+    let lines: Vec<Value> = std::fs::read_to_string(json_file.path())?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    assert_eq!(lines[0]["ok"], false);
+    assert!(lines[0]["error"]
+        .as_str()
+        .unwrap()
+        .contains("--manifest does not take v9 artifacts"));
+    assert_eq!(lines[1]["failed"], 1);
+
+    let disclosed = Command::cargo_bin("verifai-cli")?
+        .args([
+            "disclose",
+            "--artifact",
+            p(temp.child("op-artifact.bin").path()),
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--out",
+            p(temp.child("disclosure.bin").path()),
+            "--index",
+            "0",
+        ])
+        .assert()
+        .code(5)
+        .get_output()
+        .stderr
+        .clone();
+    assert!(String::from_utf8(disclosed)?.contains("disclose does not take v9 artifacts"));
+    Ok(())
+}
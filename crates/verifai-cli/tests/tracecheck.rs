@@ -0,0 +1,285 @@
+use assert_cmd::Command;
+use assert_fs::fixture::PathChild;
+use assert_fs::TempDir;
+use serde_json::Value;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use verifai_core::artifact_bin::ProofArtifactV0;
+use verifai_core::event_bin::{TraceEventV0, TraceV0};
+use verifai_core::hash::sha256;
+use verifai_core::merkle::trace_root_from_event_bytes;
+
+const KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+fn p(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+fn json(cmd: &mut Command) -> Result<Value, Box<dyn Error>> {
+    let out = cmd.assert().success().get_output().stdout.clone();
+    Ok(serde_json::from_slice(&out)?)
+}
+
+fn prove(temp: &TempDir, case: &str) -> Result<Value, Box<dyn Error>> {
+    json(Command::cargo_bin("verifai-cli")?.args([
+        "prove",
+        "--model",
+        p(&tv_path(&format!("{case}/model.json"))),
+        "--input",
+        p(&tv_path(&format!("{case}/input.json"))),
+        "--out-output",
+        p(temp.child(format!("{case}-output.bin")).path()),
+        "--out-artifact",
+        p(temp.child(format!("{case}-artifact.bin")).path()),
+        "--out-trace",
+        p(temp.child(format!("{case}-trace.bin")).path()),
+        "--key-hex",
+        KEY_HEX,
+        "--print-json",
+    ]))
+}
+
+fn check_trace(temp: &TempDir, artifact: &str, output: &str, trace: &str) -> Command {
+    let mut cmd = Command::cargo_bin("verifai-cli").unwrap();
+    cmd.args([
+        "check-trace",
+        "--artifact",
+        p(temp.child(format!("{artifact}-artifact.bin")).path()),
+        "--output",
+        p(temp.child(format!("{output}-output.bin")).path()),
+        "--trace",
+        p(temp.child(format!("{trace}-trace.bin")).path()),
+        "--print-json",
+    ]);
+    cmd
+}
+
+#[test]
+fn check_trace_without_model_or_input() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let proved = prove(&temp, "case-1")?;
+    assert!(proved["out_trace"]
+        .as_str()
+        .unwrap()
+        .ends_with("case-1-trace.bin"));
+
+    let checked = json(&mut check_trace(&temp, "case-1", "case-1", "case-1"))?;
+    assert_eq!(checked["cmd"], "check-trace");
+    assert_eq!(checked["trace_root"], proved["trace_root"]);
+    assert_eq!(checked["events"], 3);
+    assert!(checked["y"].is_f64());
+    Ok(())
+}
+
+#[test]
+fn check_trace_rejects_another_trace_or_output() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    prove(&temp, "case-1")?;
+    prove(&temp, "case-2")?;
+
+    for (output, trace, error) in [
+        ("case-1", "case-2", "TraceMismatch"),
+        ("case-2", "case-1", "HashMismatch"),
+    ] {
+        let assert = check_trace(&temp, "case-1", output, trace).assert().code(6);
+        let stderr = String::from_utf8_lossy(&assert.get_output().stderr).into_owned();
+        assert!(stderr.contains(error), "{stderr}");
+    }
+
+    // not a trace file
+    Command::cargo_bin("verifai-cli")?
+        .args([
+            "check-trace",
+            "--artifact",
+            p(temp.child("case-1-artifact.bin").path()),
+            "--output",
+            p(temp.child("case-1-output.bin").path()),
+            "--trace",
+            p(temp.child("case-1-output.bin").path()),
+        ])
+        .assert()
+        .code(6);
+    Ok(())
+}
+
+fn prove_jsonl_trace(temp: &TempDir, extra: &[&str]) -> Result<Vec<Value>, Box<dyn Error>> {
+    let trace = temp.child("trace.jsonl");
+    Command::cargo_bin("verifai-cli")?
+        .args([
+            "prove",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--out-output",
+            p(temp.child("output.bin").path()),
+            "--out-artifact",
+            p(temp.child("artifact.bin").path()),
+            "--out-trace",
+            p(trace.path()),
+            "--trace-format",
+            "jsonl",
+            "--key-hex",
+            KEY_HEX,
+        ])
+        .args(extra)
+        .assert()
+        .success();
+    let text = std::fs::read_to_string(trace.path())?;
+    Ok(text
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?)
+}
+
+#[test]
+fn out_trace_writes_jsonl_one_event_per_line() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let lines = prove_jsonl_trace(&temp, &[])?;
+    let kinds: Vec<&str> = lines.iter().map(|l| l["kind"].as_str().unwrap()).collect();
+    assert_eq!(kinds, ["linear", "activation", "output"]);
+    assert_eq!(lines[0]["index"], 0);
+    assert_eq!(lines[1]["activation"], "sigmoid");
+    assert_eq!(lines[1]["input"], lines[0]["z"]);
+    assert_eq!(lines[2]["y"], lines[1]["output"]);
+    assert!(!temp.child(".trace.jsonl.tmp").path().exists());
+
+    // v3 to v7 proving streams the same trace
+    let salts = temp.child("salts.bin");
+    for extra in [
+        &["--fresh"][..],
+        &["--multisig"],
+        &["--sig-alg", "p256"],
+        &["--hash-alg", "blake3"],
+        &["--out-salts", p(salts.path())],
+    ] {
+        assert_eq!(prove_jsonl_trace(&temp, extra)?, lines, "{extra:?}");
+    }
+    Ok(())
+}
+
+#[test]
+fn trace_format_requires_out_trace() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    Command::cargo_bin("verifai-cli")?
+        .args([
+            "prove",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--out-output",
+            p(temp.child("output.bin").path()),
+            "--out-artifact",
+            p(temp.child("artifact.bin").path()),
+            "--trace-format",
+            "jsonl",
+            "--key-hex",
+            KEY_HEX,
+        ])
+        .assert()
+        .code(2);
+    Ok(())
+}
+
+fn verify_with_trace(temp: &TempDir, artifact: &str, trace: &str) -> Command {
+    let mut cmd = Command::cargo_bin("verifai-cli").unwrap();
+    cmd.args([
+        "verify",
+        "--model",
+        p(&tv_path("case-1/model.json")),
+        "--input",
+        p(&tv_path("case-1/input.json")),
+        "--output",
+        p(temp.child("case-1-output.bin").path()),
+        "--artifact",
+        p(temp.child(artifact).path()),
+        "--trace",
+        p(temp.child(trace).path()),
+        "--print-json",
+    ]);
+    cmd
+}
+
+#[test]
+fn verify_with_claimed_trace() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let proved = prove(&temp, "case-1")?;
+    let verified = json(&mut verify_with_trace(
+        &temp,
+        "case-1-artifact.bin",
+        "case-1-trace.bin",
+    ))?;
+    assert_eq!(verified["ok"], true);
+    assert_eq!(verified["trace_root"], proved["trace_root"]);
+    assert_eq!(verified["trace_events"], 3);
+
+    // a trace the artifact does not commit to
+    prove(&temp, "case-2")?;
+    let assert = verify_with_trace(&temp, "case-1-artifact.bin", "case-2-trace.bin")
+        .assert()
+        .code(6);
+    let stderr = String::from_utf8_lossy(&assert.get_output().stderr).into_owned();
+    assert!(stderr.contains("TraceMismatch"), "{stderr}");
+    Ok(())
+}
+
+#[test]
+fn verify_with_trace_reports_the_first_divergent_event() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    prove(&temp, "case-1")?;
+    let trace_bin = std::fs::read(temp.child("case-1-trace.bin").path())?;
+    let mut events = TraceV0::decode_bin(&trace_bin).unwrap().events;
+    let TraceEventV0::OpLinear { z, .. } = events[0] else {
+        panic!("LR traces start with a linear op");
+    };
+    events[0] = TraceEventV0::OpLinear {
+        op_id: 0,
+        z: z + 1.0,
+    };
+
+    // a validly signed artifact over the forged trace
+    let model_bin = std::fs::read(tv_path("case-1/model.bin"))?;
+    let input_bin = std::fs::read(tv_path("case-1/input.bin"))?;
+    let output_bin = std::fs::read(temp.child("case-1-output.bin").path())?;
+    let event_bytes: Vec<Vec<u8>> = events.iter().map(|e| e.encode_bin()).collect();
+    let mut artifact = ProofArtifactV0 {
+        version: 0,
+        runtime_id: [0u8; 32],
+        model_hash: sha256(&model_bin),
+        input_hash: sha256(&input_bin),
+        output_hash: sha256(&output_bin),
+        trace_root: trace_root_from_event_bytes(&event_bytes),
+        sig_pubkey: [0u8; 32],
+        signature: [0u8; 64],
+    };
+    artifact.sign_detached([9u8; 32]).unwrap();
+    std::fs::write(
+        temp.child("forged-artifact.bin").path(),
+        artifact.encode_bin(),
+    )?;
+    std::fs::write(
+        temp.child("forged-trace.bin").path(),
+        TraceV0 { events }.encode_bin(),
+    )?;
+
+    let assert = verify_with_trace(&temp, "forged-artifact.bin", "forged-trace.bin")
+        .assert()
+        .code(6);
+    let stderr = String::from_utf8_lossy(&assert.get_output().stderr).into_owned();
+    assert!(
+        stderr.contains("TraceDiverged at event 0 (op_id 0): expected linear op_id=0 z="),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains(&format!(", actual linear op_id=0 z={z}")),
+        "{stderr}"
+    );
+    Ok(())
+}
@@ -0,0 +1,237 @@
+use assert_cmd::Command;
+use assert_fs::fixture::PathChild;
+use assert_fs::TempDir;
+use serde_json::Value;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+const LOG_KEY_HEX: &str = "0303030303030303030303030303030303030303030303030303030303030303";
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+fn p(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+/// Proves `x` into `{name}.output.bin`/`{name}.artifact.bin`, appending to
+/// `log_dir` when given, and returns the prove JSON.
+fn prove(
+    temp: &TempDir,
+    name: &str,
+    x: &str,
+    log_dir: Option<&Path>,
+) -> Result<Value, Box<dyn Error>> {
+    let input = temp.child(format!("{name}.input.json"));
+    fs::write(input.path(), format!("{{\"x\": {x}}}"))?;
+    let output = temp.child(format!("{name}.output.bin"));
+    let artifact = temp.child(format!("{name}.artifact.bin"));
+
+    let mut cmd = Command::cargo_bin("verifai-cli")?;
+    cmd.args([
+        "prove",
+        "--model",
+        p(&tv_path("case-1/model.json")),
+        "--input",
+        p(input.path()),
+        "--out-output",
+        p(output.path()),
+        "--out-artifact",
+        p(artifact.path()),
+        "--key-hex",
+        KEY_HEX,
+        "--print-json",
+    ]);
+    if let Some(dir) = log_dir {
+        cmd.args(["--log-dir", p(dir)]);
+    }
+    let out = cmd.assert().success().get_output().stdout.clone();
+    Ok(serde_json::from_slice(&out)?)
+}
+
+fn log_head(log_dir: &Path, out: &Path) -> Result<Value, Box<dyn Error>> {
+    let out = Command::cargo_bin("verifai-cli")?
+        .args([
+            "log-head",
+            "--log-dir",
+            p(log_dir),
+            "--key-hex",
+            LOG_KEY_HEX,
+            "--out",
+            p(out),
+            "--print-json",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    Ok(serde_json::from_slice(&out)?)
+}
+
+fn verify_with_log(
+    temp: &TempDir,
+    name: &str,
+    log_pubkey: &str,
+    extra: &[&str],
+) -> Result<Command, Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("verifai-cli")?;
+    cmd.args([
+        "verify",
+        "--model",
+        p(&tv_path("case-1/model.json")),
+        "--input",
+        p(temp.child(format!("{name}.input.json")).path()),
+        "--output",
+        p(temp.child(format!("{name}.output.bin")).path()),
+        "--artifact",
+        p(temp.child(format!("{name}.artifact.bin")).path()),
+        "--require-log-inclusion",
+        "--tree-head",
+        p(temp.child("head.bin").path()),
+        "--log-pubkey-hex",
+        log_pubkey,
+        "--print-json",
+    ]);
+    cmd.args(extra);
+    Ok(cmd)
+}
+
+#[test]
+fn prove_appends_and_verify_requires_inclusion() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let log_dir = temp.child("log");
+
+    let a = prove(&temp, "a", "[1.0, 2.0, 3.0, 4.0]", Some(log_dir.path()))?;
+    let b = prove(&temp, "b", "[0.5, 0.5, 0.5, 0.5]", Some(log_dir.path()))?;
+    prove(&temp, "c", "[4.0, 3.0, 2.0, 1.0]", None)?;
+    assert_eq!(a["log_index"], 0);
+    assert_eq!(b["log_index"], 1);
+
+    let head = log_head(log_dir.path(), temp.child("head.bin").path())?;
+    assert_eq!(head["tree_size"], 2);
+    let log_pubkey = head["log_pubkey"].as_str().unwrap();
+
+    let out = verify_with_log(&temp, "b", log_pubkey, &["--log-dir", p(log_dir.path())])?
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: Value = serde_json::from_slice(&out)?;
+    assert_eq!(v["log_index"], 1);
+
+    let proof = temp.child("a.proof.bin");
+    Command::cargo_bin("verifai-cli")?
+        .args([
+            "log-inclusion",
+            "--log-dir",
+            p(log_dir.path()),
+            "--artifact",
+            p(temp.child("a.artifact.bin").path()),
+            "--tree-head",
+            p(temp.child("head.bin").path()),
+            "--out",
+            p(proof.path()),
+        ])
+        .assert()
+        .success();
+    verify_with_log(&temp, "a", log_pubkey, &["--log-proof", p(proof.path())])?
+        .assert()
+        .success();
+
+    // a head signed by anyone but the pinned log key is not trusted
+    verify_with_log(
+        &temp,
+        "a",
+        &"ab".repeat(32),
+        &["--log-proof", p(proof.path())],
+    )?
+    .assert()
+    .code(6);
+    // and inclusion cannot be required without pinning one
+    let mut unpinned = Command::cargo_bin("verifai-cli")?;
+    unpinned
+        .args([
+            "verify",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(temp.child("a.input.json").path()),
+            "--output",
+            p(temp.child("a.output.bin").path()),
+            "--artifact",
+            p(temp.child("a.artifact.bin").path()),
+            "--require-log-inclusion",
+            "--tree-head",
+            p(temp.child("head.bin").path()),
+            "--log-proof",
+            p(proof.path()),
+        ])
+        .assert()
+        .code(2);
+
+    // never logged
+    verify_with_log(&temp, "c", log_pubkey, &["--log-dir", p(log_dir.path())])?
+        .assert()
+        .code(6);
+    // proof for a different artifact
+    verify_with_log(&temp, "b", log_pubkey, &["--log-proof", p(proof.path())])?
+        .assert()
+        .code(6);
+    Ok(())
+}
+
+#[test]
+fn log_consistency_between_heads() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let log_dir = temp.child("log");
+    let old_head = temp.child("old.bin");
+    let new_head = temp.child("new.bin");
+
+    prove(&temp, "a", "[1.0, 2.0, 3.0, 4.0]", Some(log_dir.path()))?;
+    log_head(log_dir.path(), old_head.path())?;
+    prove(&temp, "b", "[0.5, 0.5, 0.5, 0.5]", Some(log_dir.path()))?;
+    prove(&temp, "c", "[4.0, 3.0, 2.0, 1.0]", Some(log_dir.path()))?;
+    log_head(log_dir.path(), new_head.path())?;
+
+    let out = Command::cargo_bin("verifai-cli")?
+        .args([
+            "log-consistency",
+            "--log-dir",
+            p(log_dir.path()),
+            "--old-head",
+            p(old_head.path()),
+            "--new-head",
+            p(new_head.path()),
+            "--print-json",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: Value = serde_json::from_slice(&out)?;
+    assert_eq!(v["old_size"], 1);
+    assert_eq!(v["new_size"], 3);
+
+    // heads swapped: the newer tree is not a prefix of the older one
+    Command::cargo_bin("verifai-cli")?
+        .args([
+            "log-consistency",
+            "--log-dir",
+            p(log_dir.path()),
+            "--old-head",
+            p(new_head.path()),
+            "--new-head",
+            p(old_head.path()),
+        ])
+        .assert()
+        .code(6);
+    Ok(())
+}
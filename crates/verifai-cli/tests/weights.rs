@@ -0,0 +1,158 @@
+use assert_cmd::Command;
+use assert_fs::fixture::PathChild;
+use assert_fs::TempDir;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use verifai_core::hash::sha256;
+
+const KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn npy_f64(shape: &str, values: &[f64]) -> Vec<u8> {
+    let mut header = format!("{{'descr': '<f8', 'fortran_order': False, 'shape': {shape}, }}");
+    while (10 + header.len() + 1) % 64 != 0 {
+        header.push(' ');
+    }
+    header.push('\n');
+    let mut out = b"\x93NUMPY\x01\x00".to_vec();
+    out.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    out.extend_from_slice(header.as_bytes());
+    for v in values {
+        out.extend_from_slice(&v.to_le_bytes());
+    }
+    out
+}
+
+fn safetensors_f64(tensors: &[(&str, &[usize], &[f64])]) -> Vec<u8> {
+    let mut header = serde_json::Map::new();
+    let mut data = Vec::new();
+    for (name, shape, values) in tensors {
+        let begin = data.len();
+        for v in values.iter() {
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+        header.insert(
+            name.to_string(),
+            serde_json::json!({ "dtype": "F64", "shape": shape, "data_offsets": [begin, data.len()] }),
+        );
+    }
+    let header = serde_json::to_vec(&header).unwrap();
+    let mut out = (header.len() as u64).to_le_bytes().to_vec();
+    out.extend_from_slice(&header);
+    out.extend_from_slice(&data);
+    out
+}
+
+#[test]
+fn hash_model_accepts_safetensors() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let weights = temp.child("model.safetensors");
+    fs::write(
+        weights.path(),
+        safetensors_f64(&[
+            ("weights", &[4], &[0.1, -0.2, 0.3, 0.4]),
+            ("bias", &[], &[-0.05]),
+        ]),
+    )?;
+
+    let output = Command::cargo_bin("verifai-cli")?
+        .args(["hash-model", "--model", weights.path().to_str().unwrap()])
+        .output()?;
+
+    assert!(output.status.success());
+    let expected = hex(&sha256(&fs::read(tv_path("case-1/model.bin"))?));
+    assert_eq!(String::from_utf8(output.stdout)?.trim(), expected);
+    Ok(())
+}
+
+#[test]
+fn prove_and_verify_mlp_from_npy_dir() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let dir = temp.child("weights");
+    fs::create_dir(dir.path())?;
+    fs::write(
+        dir.child("w1.npy").path(),
+        npy_f64("(2, 4)", &[0.1, -0.2, 0.3, 0.4, -0.1, 0.5, 0.2, -0.3]),
+    )?;
+    fs::write(dir.child("b1.npy").path(), npy_f64("(2,)", &[0.0, -0.1]))?;
+    fs::write(dir.child("w2.npy").path(), npy_f64("(2,)", &[0.2, -0.4]))?;
+    fs::write(dir.child("b2.npy").path(), npy_f64("()", &[0.05]))?;
+
+    let output = temp.child("output.bin");
+    let artifact = temp.child("artifact.bin");
+    let runtime_id_hex = hex(&[7u8; 32]);
+
+    Command::cargo_bin("verifai-cli")?
+        .args([
+            "--quiet",
+            "prove",
+            "--model",
+            dir.path().to_str().unwrap(),
+            "--input",
+            tv_path("mlp-case-1/input.json").to_str().unwrap(),
+            "--out-output",
+            output.path().to_str().unwrap(),
+            "--out-artifact",
+            artifact.path().to_str().unwrap(),
+            "--key-hex",
+            KEY_HEX,
+            "--runtime-id-hex",
+            &runtime_id_hex,
+        ])
+        .assert()
+        .success();
+
+    assert_eq!(
+        fs::read(artifact.path())?,
+        fs::read(tv_path("mlp-case-1/expected_artifact.bin"))?
+    );
+
+    Command::cargo_bin("verifai-cli")?
+        .args([
+            "--quiet",
+            "verify",
+            "--artifact",
+            artifact.path().to_str().unwrap(),
+            "--model",
+            dir.path().to_str().unwrap(),
+            "--input",
+            tv_path("mlp-case-1/input.json").to_str().unwrap(),
+            "--output",
+            output.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    Ok(())
+}
+
+#[test]
+fn prove_rejects_mismatched_weight_shapes() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    let weights = temp.child("model.safetensors");
+    fs::write(
+        weights.path(),
+        safetensors_f64(&[
+            ("w1", &[2, 2], &[0.1, 0.2, 0.3, 0.4]),
+            ("b1", &[3], &[0.0, 0.0, 0.0]),
+            ("w2", &[2], &[0.1, 0.2]),
+            ("b2", &[], &[0.0]),
+        ]),
+    )?;
+
+    Command::cargo_bin("verifai-cli")?
+        .args(["hash-model", "--model", weights.path().to_str().unwrap()])
+        .assert()
+        .code(7);
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use assert_cmd::Command;
+use assert_fs::fixture::PathChild;
+use assert_fs::TempDir;
+use serde_json::Value;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+const KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+
+fn tv_path(rel: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(rel)
+}
+
+fn p(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+fn prove_bundle(temp: &TempDir, chain: &str) -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("verifai-cli")?
+        .args([
+            "prove",
+            "--model",
+            p(&tv_path("case-1/model.json")),
+            "--input",
+            p(&tv_path("case-1/input.json")),
+            "--out-output",
+            p(temp.child("output.bin").path()),
+            "--out-artifact",
+            p(temp.child("artifact.bin").path()),
+            "--out-bundle",
+            p(temp.child("artifact.bundle").path()),
+            "--cert-chain",
+            p(&tv_path(chain)),
+            "--key-hex",
+            KEY_HEX,
+        ])
+        .assert()
+        .success();
+    Ok(())
+}
+
+fn verify(temp: &TempDir, artifact: &str, extra: &[&str]) -> Result<Command, Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("verifai-cli")?;
+    cmd.args([
+        "verify",
+        "--model",
+        p(&tv_path("case-1/model.json")),
+        "--input",
+        p(&tv_path("case-1/input.json")),
+        "--output",
+        p(temp.child("output.bin").path()),
+        "--artifact",
+        p(temp.child(artifact).path()),
+        "--print-json",
+    ]);
+    cmd.args(extra);
+    Ok(cmd)
+}
+
+#[test]
+fn bundle_chain_verifies_against_trusted_root() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    prove_bundle(&temp, "x509/chain.pem")?;
+    let root = tv_path("x509/root.pem");
+
+    let out = verify(&temp, "artifact.bundle", &["--trusted-root", p(&root)])?
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: Value = serde_json::from_slice(&out)?;
+    assert_eq!(v["signer_subject"], "O=VerifAI Test, CN=prover-1");
+    assert_eq!(v["signer_root"], "O=VerifAI Test, CN=Test Root CA");
+
+    // without roots the bundle still verifies natively, with no identity claimed
+    let out = verify(&temp, "artifact.bundle", &[])?
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: Value = serde_json::from_slice(&out)?;
+    assert!(v.get("signer_subject").is_none());
+
+    let other = tv_path("x509/other-root.pem");
+    verify(&temp, "artifact.bundle", &["--trusted-root", p(&other)])?
+        .assert()
+        .code(6);
+    // 2100-01-01, after every certificate has expired
+    verify(
+        &temp,
+        "artifact.bundle",
+        &[
+            "--trusted-root",
+            p(&root),
+            "--now-nanos",
+            "4102444800000000000",
+        ],
+    )?
+    .assert()
+    .code(6);
+    // a bare artifact has no chain to check
+    verify(&temp, "artifact.bin", &["--trusted-root", p(&root)])?
+        .assert()
+        .code(6);
+    Ok(())
+}
+
+#[test]
+fn bundle_rejects_leaf_without_digital_signature() -> Result<(), Box<dyn Error>> {
+    let temp = TempDir::new()?;
+    prove_bundle(&temp, "x509/chain-no-digital-signature.pem")?;
+    let root = tv_path("x509/root.pem");
+    verify(&temp, "artifact.bundle", &["--trusted-root", p(&root)])?
+        .assert()
+        .code(6);
+    Ok(())
+}
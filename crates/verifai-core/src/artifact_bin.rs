@@ -1,15 +1,48 @@
 use crate::attestation::AttestationBundle;
-use crate::bytes::{push_bytes, push_u16_le, BytesError, Reader};
-use crate::hash::sha256;
+use crate::bytes::{
+    push_bytes, push_u16_le, push_u32_le, push_u64_le, push_u8, BytesError, Reader,
+};
+use crate::event_bin::TraceLevel;
+use crate::hash::{sha256, HashAlg};
+use crate::sig_alg::{verify_alg, AlgSigningKey, SigAlg};
+use crate::signer::{ed25519_public_key, sign_checked, Signer, SignerError};
+use crate::timestamp::TimestampTokenV0;
 
-use ed25519_dalek::Signer;
 use ed25519_dalek::Verifier;
-use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
+use ed25519_dalek::{Signature, VerifyingKey};
 
 pub const PROOF_ARTIFACT_V0_LEN: usize = 258;
+pub const PROOF_ARTIFACT_V10_LEN: usize = 386;
+/// A compressed Groth16 proof over BN254: two G1 points and one G2 point.
+pub const ZK_PROOF_LEN: usize = 128;
 
 const SIGN_PREFIX: &[u8; 19] = b"VERIFAI\0ARTIFACT\0V0";
 const SIGN_PREFIX_V1: &[u8; 19] = b"VERIFAI\0ARTIFACT\0V1";
+const SIGN_PREFIX_V2: &[u8; 19] = b"VERIFAI\0ARTIFACT\0V2";
+const SIGN_PREFIX_V3: &[u8; 19] = b"VERIFAI\0ARTIFACT\0V3";
+const SIGN_PREFIX_V4: &[u8; 19] = b"VERIFAI\0ARTIFACT\0V4";
+const SIGN_PREFIX_V5: &[u8; 19] = b"VERIFAI\0ARTIFACT\0V5";
+const SIGN_PREFIX_V6: &[u8; 19] = b"VERIFAI\0ARTIFACT\0V6";
+const SIGN_PREFIX_V7: &[u8; 19] = b"VERIFAI\0ARTIFACT\0V7";
+const SIGN_PREFIX_V8: &[u8; 19] = b"VERIFAI\0ARTIFACT\0V8";
+const SIGN_PREFIX_V9: &[u8; 19] = b"VERIFAI\0ARTIFACT\0V9";
+const SIGN_PREFIX_V10: &[u8; 20] = b"VERIFAI\0ARTIFACT\0V10";
+
+/// The signing-message prefix of every artifact version, for signers that
+/// should sign artifacts and nothing else.
+pub const ARTIFACT_SIGN_PREFIXES: [&[u8]; 11] = [
+    SIGN_PREFIX,
+    SIGN_PREFIX_V1,
+    SIGN_PREFIX_V2,
+    SIGN_PREFIX_V3,
+    SIGN_PREFIX_V4,
+    SIGN_PREFIX_V5,
+    SIGN_PREFIX_V6,
+    SIGN_PREFIX_V7,
+    SIGN_PREFIX_V8,
+    SIGN_PREFIX_V9,
+    SIGN_PREFIX_V10,
+];
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProofArtifactV0 {
@@ -36,6 +69,185 @@ pub struct ProofArtifactV1 {
     pub attestation: AttestationBundle,
 }
 
+/// `ProofArtifactV1` plus signed freshness data.
+///
+/// `issued_at`/`expires_at` are unix nanos (`expires_at` is encoded as 0 when
+/// absent) and `nonce` is the caller's challenge. The optional timestamp token
+/// stamps `sha256(signature)` and is not itself covered by the signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofArtifactV3 {
+    pub version: u16,
+    pub runtime_id: [u8; 32],
+    pub model_hash: [u8; 32],
+    pub input_hash: [u8; 32],
+    pub output_hash: [u8; 32],
+    pub trace_root: [u8; 32],
+    pub issued_at: u64,
+    pub expires_at: Option<u64>,
+    pub nonce: [u8; 32],
+    pub sig_pubkey: [u8; 32],
+    pub signature: [u8; 64],
+    pub timestamp: Option<TimestampTokenV0>,
+    pub attestation: AttestationBundle,
+}
+
+/// One Ed25519 signature over a `ProofArtifactV4` message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoSignature {
+    pub pubkey: [u8; 32],
+    pub signature: [u8; 64],
+}
+
+/// `ProofArtifactV1` signed by any number of parties.
+///
+/// Every co-signature covers the same `message_to_sign`, which leaves out the
+/// signer keys, so signatures can be added in any order without invalidating
+/// earlier ones. Signer keys are unique within an artifact.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofArtifactV4 {
+    pub version: u16,
+    pub runtime_id: [u8; 32],
+    pub model_hash: [u8; 32],
+    pub input_hash: [u8; 32],
+    pub output_hash: [u8; 32],
+    pub trace_root: [u8; 32],
+    pub signatures: Vec<CoSignature>,
+    pub attestation: AttestationBundle,
+}
+
+/// `ProofArtifactV1` with a signature algorithm identifier and
+/// variable-length key and signature fields (see `sig_alg`).
+///
+/// The signed message covers the algorithm and public key, so a signature
+/// cannot be replayed under a different algorithm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofArtifactV5 {
+    pub version: u16,
+    pub runtime_id: [u8; 32],
+    pub model_hash: [u8; 32],
+    pub input_hash: [u8; 32],
+    pub output_hash: [u8; 32],
+    pub trace_root: [u8; 32],
+    pub sig_alg: SigAlg,
+    pub sig_pubkey: Vec<u8>,
+    pub signature: Vec<u8>,
+    pub attestation: AttestationBundle,
+}
+
+/// `ProofArtifactV5` whose hashes, trace root and signed message all use
+/// `hash_alg` instead of SHA-256.
+///
+/// The signature covers a `hash_alg` digest of the artifact body rather than
+/// the body itself, so no part of the artifact depends on SHA-256.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofArtifactV6 {
+    pub version: u16,
+    pub runtime_id: [u8; 32],
+    pub model_hash: [u8; 32],
+    pub input_hash: [u8; 32],
+    pub output_hash: [u8; 32],
+    pub trace_root: [u8; 32],
+    pub hash_alg: HashAlg,
+    pub sig_alg: SigAlg,
+    pub sig_pubkey: Vec<u8>,
+    pub signature: Vec<u8>,
+    pub attestation: AttestationBundle,
+}
+
+/// `ProofArtifactV1` with salted commitments in place of plain hashes.
+///
+/// Each commitment is `sha256(salt || value)` with its own salt from a
+/// `CommitmentSaltsV0`; the trace commitment covers the trace root, and the
+/// attestation measures the trace commitment. Replaying needs the salts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofArtifactV7 {
+    pub version: u16,
+    pub runtime_id: [u8; 32],
+    pub model_commitment: [u8; 32],
+    pub input_commitment: [u8; 32],
+    pub output_commitment: [u8; 32],
+    pub trace_commitment: [u8; 32],
+    pub sig_pubkey: [u8; 32],
+    pub signature: [u8; 64],
+    pub attestation: AttestationBundle,
+}
+
+/// `ProofArtifactV1` that also proves feature preprocessing.
+///
+/// `input_hash` commits to the `RawInputV1`, not the model input, and
+/// `preprocessor_hash` to the `PreprocessorV1` that turned one into the
+/// other. The trace starts with one `OpFeature` event per model feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofArtifactV8 {
+    pub version: u16,
+    pub runtime_id: [u8; 32],
+    pub model_hash: [u8; 32],
+    pub preprocessor_hash: [u8; 32],
+    pub input_hash: [u8; 32],
+    pub output_hash: [u8; 32],
+    pub trace_root: [u8; 32],
+    pub sig_pubkey: [u8; 32],
+    pub signature: [u8; 64],
+    pub attestation: AttestationBundle,
+}
+
+/// `ProofArtifactV1` with a signed trace granularity.
+///
+/// `trace_root` is the Merkle root of the trace recorded at `trace_level`,
+/// and verifiers replay at that level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofArtifactV9 {
+    pub version: u16,
+    pub runtime_id: [u8; 32],
+    pub model_hash: [u8; 32],
+    pub input_hash: [u8; 32],
+    pub output_hash: [u8; 32],
+    pub trace_level: TraceLevel,
+    pub trace_root: [u8; 32],
+    pub sig_pubkey: [u8; 32],
+    pub signature: [u8; 64],
+    pub attestation: AttestationBundle,
+}
+
+/// A zero-knowledge proof of fixed-point logistic regression.
+///
+/// The hashes are SHA-256 of the `QuantizedLogisticModelV0`,
+/// `QuantizedInputV0` and `QuantizedOutputV0` encodings. `proof` is a
+/// Groth16 proof that they hash a model and input whose logit is the output,
+/// checked against the verifying key whose SHA-256 is `circuit_hash`.
+/// Verifiers need neither the model nor the input, and there is no trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofArtifactV10 {
+    pub version: u16,
+    pub runtime_id: [u8; 32],
+    pub model_hash: [u8; 32],
+    pub input_hash: [u8; 32],
+    pub output_hash: [u8; 32],
+    pub circuit_hash: [u8; 32],
+    pub proof: [u8; ZK_PROOF_LEN],
+    pub sig_pubkey: [u8; 32],
+    pub signature: [u8; 64],
+}
+
+/// One signed artifact covering every row of an `InputBatchV1`.
+///
+/// `inputs_root` and `outputs_root` are Merkle roots over the canonical
+/// `InputV0`/`OutputV0` encoding of each row; `traces_root` is a Merkle root
+/// over each row's 32-byte trace root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchProofArtifactV2 {
+    pub version: u16,
+    pub runtime_id: [u8; 32],
+    pub model_hash: [u8; 32],
+    pub row_count: u32,
+    pub inputs_root: [u8; 32],
+    pub outputs_root: [u8; 32],
+    pub traces_root: [u8; 32],
+    pub sig_pubkey: [u8; 32],
+    pub signature: [u8; 64],
+    pub attestation: AttestationBundle,
+}
+
 impl ProofArtifactV0 {
     pub fn message_to_sign(&self) -> Vec<u8> {
         // prefix + (all fields except signature), including sig_pubkey
@@ -90,13 +302,13 @@ impl ProofArtifactV0 {
     }
 
     pub fn sign_detached(&mut self, signing_key_bytes: [u8; 32]) -> Result<(), BytesError> {
-        let sk = SigningKey::from_bytes(&signing_key_bytes);
-        let vk = VerifyingKey::from(&sk);
-        self.sig_pubkey = vk.to_bytes();
+        self.sign_with(&signing_key_bytes)
+            .map_err(|_| BytesError::InvalidLength)
+    }
 
-        let msg = self.message_to_sign();
-        let sig: Signature = sk.sign(&msg);
-        self.signature = sig.to_bytes();
+    pub fn sign_with<S: Signer + ?Sized>(&mut self, signer: &S) -> Result<(), SignerError> {
+        self.sig_pubkey = ed25519_public_key(signer)?;
+        self.signature = sign_checked(signer, &self.message_to_sign())?;
         Ok(())
     }
 
@@ -174,13 +386,212 @@ impl ProofArtifactV1 {
     }
 
     pub fn sign_detached(&mut self, signing_key_bytes: [u8; 32]) -> Result<(), BytesError> {
-        let sk = SigningKey::from_bytes(&signing_key_bytes);
-        let vk = VerifyingKey::from(&sk);
-        self.sig_pubkey = vk.to_bytes();
+        self.sign_with(&signing_key_bytes)
+            .map_err(|_| BytesError::InvalidLength)
+    }
+
+    pub fn sign_with<S: Signer + ?Sized>(&mut self, signer: &S) -> Result<(), SignerError> {
+        self.sig_pubkey = ed25519_public_key(signer)?;
+        self.signature = sign_checked(signer, &self.message_to_sign())?;
+        Ok(())
+    }
+
+    pub fn verify_signature(&self) -> Result<(), BytesError> {
+        let vk =
+            VerifyingKey::from_bytes(&self.sig_pubkey).map_err(|_| BytesError::InvalidLength)?;
+        let sig = Signature::from_bytes(&self.signature);
+        let msg = self.message_to_sign();
+        vk.verify(&msg, &sig)
+            .map_err(|_| BytesError::InvalidLength)?;
+        Ok(())
+    }
+}
+
+impl BatchProofArtifactV2 {
+    pub fn message_to_sign(&self) -> Vec<u8> {
+        let attestation = self.attestation.encode_bin();
+        let mut out = Vec::with_capacity(19 + 2 + 4 + 32 * 6 + attestation.len());
+        out.extend_from_slice(SIGN_PREFIX_V2);
+        out.extend_from_slice(&self.version.to_le_bytes());
+        out.extend_from_slice(&self.runtime_id);
+        out.extend_from_slice(&self.model_hash);
+        out.extend_from_slice(&self.row_count.to_le_bytes());
+        out.extend_from_slice(&self.inputs_root);
+        out.extend_from_slice(&self.outputs_root);
+        out.extend_from_slice(&self.traces_root);
+        out.extend_from_slice(&self.sig_pubkey);
+        out.extend_from_slice(&attestation);
+        out
+    }
+
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let attestation = self.attestation.encode_bin();
+        let mut out = Vec::with_capacity(2 + 4 + 32 * 6 + 64 + attestation.len());
+        push_u16_le(&mut out, self.version);
+        push_bytes(&mut out, &self.runtime_id);
+        push_bytes(&mut out, &self.model_hash);
+        push_u32_le(&mut out, self.row_count);
+        push_bytes(&mut out, &self.inputs_root);
+        push_bytes(&mut out, &self.outputs_root);
+        push_bytes(&mut out, &self.traces_root);
+        push_bytes(&mut out, &self.sig_pubkey);
+        push_bytes(&mut out, &self.signature);
+        push_bytes(&mut out, &attestation);
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        let version = r.read_u16_le()?;
+        if version != 2 {
+            return Err(BytesError::InvalidLength);
+        }
+        let runtime_id = read_32(&mut r)?;
+        let model_hash = read_32(&mut r)?;
+        let row_count = r.read_u32_le()?;
+        let inputs_root = read_32(&mut r)?;
+        let outputs_root = read_32(&mut r)?;
+        let traces_root = read_32(&mut r)?;
+        let sig_pubkey = read_32(&mut r)?;
+        let signature = read_64(&mut r)?;
+        let remaining = r.read_exact(r.remaining())?;
+        let attestation = AttestationBundle::decode_bin(remaining)?;
+        Ok(Self {
+            version,
+            runtime_id,
+            model_hash,
+            row_count,
+            inputs_root,
+            outputs_root,
+            traces_root,
+            sig_pubkey,
+            signature,
+            attestation,
+        })
+    }
+
+    pub fn sign_detached(&mut self, signing_key_bytes: [u8; 32]) -> Result<(), BytesError> {
+        self.sign_with(&signing_key_bytes)
+            .map_err(|_| BytesError::InvalidLength)
+    }
 
+    pub fn sign_with<S: Signer + ?Sized>(&mut self, signer: &S) -> Result<(), SignerError> {
+        self.sig_pubkey = ed25519_public_key(signer)?;
+        self.signature = sign_checked(signer, &self.message_to_sign())?;
+        Ok(())
+    }
+
+    pub fn verify_signature(&self) -> Result<(), BytesError> {
+        let vk =
+            VerifyingKey::from_bytes(&self.sig_pubkey).map_err(|_| BytesError::InvalidLength)?;
+        let sig = Signature::from_bytes(&self.signature);
         let msg = self.message_to_sign();
-        let sig: Signature = sk.sign(&msg);
-        self.signature = sig.to_bytes();
+        vk.verify(&msg, &sig)
+            .map_err(|_| BytesError::InvalidLength)?;
+        Ok(())
+    }
+}
+
+impl ProofArtifactV3 {
+    pub fn message_to_sign(&self) -> Vec<u8> {
+        let attestation = self.attestation.encode_bin();
+        let mut out = Vec::with_capacity(19 + 2 + 32 * 7 + 8 * 2 + attestation.len());
+        out.extend_from_slice(SIGN_PREFIX_V3);
+        out.extend_from_slice(&self.version.to_le_bytes());
+        out.extend_from_slice(&self.runtime_id);
+        out.extend_from_slice(&self.model_hash);
+        out.extend_from_slice(&self.input_hash);
+        out.extend_from_slice(&self.output_hash);
+        out.extend_from_slice(&self.trace_root);
+        out.extend_from_slice(&self.issued_at.to_le_bytes());
+        out.extend_from_slice(&self.expires_at.unwrap_or(0).to_le_bytes());
+        out.extend_from_slice(&self.nonce);
+        out.extend_from_slice(&self.sig_pubkey);
+        out.extend_from_slice(&attestation);
+        out
+    }
+
+    /// Message imprint a timestamp token over this artifact must carry.
+    pub fn timestamp_imprint(&self) -> [u8; 32] {
+        sha256(&self.signature)
+    }
+
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let attestation = self.attestation.encode_bin();
+        let token = self.timestamp.as_ref().map(|t| t.encode_bin());
+        let token_len = token.as_ref().map_or(0, |t| t.len());
+        let mut out =
+            Vec::with_capacity(2 + 32 * 7 + 8 * 2 + 64 + 4 + token_len + attestation.len());
+        push_u16_le(&mut out, self.version);
+        push_bytes(&mut out, &self.runtime_id);
+        push_bytes(&mut out, &self.model_hash);
+        push_bytes(&mut out, &self.input_hash);
+        push_bytes(&mut out, &self.output_hash);
+        push_bytes(&mut out, &self.trace_root);
+        push_u64_le(&mut out, self.issued_at);
+        push_u64_le(&mut out, self.expires_at.unwrap_or(0));
+        push_bytes(&mut out, &self.nonce);
+        push_bytes(&mut out, &self.sig_pubkey);
+        push_bytes(&mut out, &self.signature);
+        push_u32_le(&mut out, token_len as u32);
+        if let Some(token) = &token {
+            push_bytes(&mut out, token);
+        }
+        push_bytes(&mut out, &attestation);
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        let version = r.read_u16_le()?;
+        if version != 3 {
+            return Err(BytesError::InvalidLength);
+        }
+        let runtime_id = read_32(&mut r)?;
+        let model_hash = read_32(&mut r)?;
+        let input_hash = read_32(&mut r)?;
+        let output_hash = read_32(&mut r)?;
+        let trace_root = read_32(&mut r)?;
+        let issued_at = r.read_u64_le()?;
+        let expires_at = match r.read_u64_le()? {
+            0 => None,
+            t => Some(t),
+        };
+        let nonce = read_32(&mut r)?;
+        let sig_pubkey = read_32(&mut r)?;
+        let signature = read_64(&mut r)?;
+        let token_len = r.read_u32_le()? as usize;
+        let timestamp = match token_len {
+            0 => None,
+            n => Some(TimestampTokenV0::decode_bin(r.read_exact(n)?)?),
+        };
+        let remaining = r.read_exact(r.remaining())?;
+        let attestation = AttestationBundle::decode_bin(remaining)?;
+        Ok(Self {
+            version,
+            runtime_id,
+            model_hash,
+            input_hash,
+            output_hash,
+            trace_root,
+            issued_at,
+            expires_at,
+            nonce,
+            sig_pubkey,
+            signature,
+            timestamp,
+            attestation,
+        })
+    }
+
+    pub fn sign_detached(&mut self, signing_key_bytes: [u8; 32]) -> Result<(), BytesError> {
+        self.sign_with(&signing_key_bytes)
+            .map_err(|_| BytesError::InvalidLength)
+    }
+
+    pub fn sign_with<S: Signer + ?Sized>(&mut self, signer: &S) -> Result<(), SignerError> {
+        self.sig_pubkey = ed25519_public_key(signer)?;
+        self.signature = sign_checked(signer, &self.message_to_sign())?;
         Ok(())
     }
 
@@ -195,6 +606,673 @@ impl ProofArtifactV1 {
     }
 }
 
+impl ProofArtifactV4 {
+    pub fn message_to_sign(&self) -> Vec<u8> {
+        let attestation = self.attestation.encode_bin();
+        let mut out = Vec::with_capacity(19 + 2 + 32 * 6 + attestation.len());
+        out.extend_from_slice(SIGN_PREFIX_V4);
+        out.extend_from_slice(&self.version.to_le_bytes());
+        out.extend_from_slice(&self.runtime_id);
+        out.extend_from_slice(&self.model_hash);
+        out.extend_from_slice(&self.input_hash);
+        out.extend_from_slice(&self.output_hash);
+        out.extend_from_slice(&self.trace_root);
+        out.extend_from_slice(&attestation);
+        out
+    }
+
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let attestation = self.attestation.encode_bin();
+        let mut out =
+            Vec::with_capacity(2 + 32 * 6 + 4 + 96 * self.signatures.len() + attestation.len());
+        push_u16_le(&mut out, self.version);
+        push_bytes(&mut out, &self.runtime_id);
+        push_bytes(&mut out, &self.model_hash);
+        push_bytes(&mut out, &self.input_hash);
+        push_bytes(&mut out, &self.output_hash);
+        push_bytes(&mut out, &self.trace_root);
+        push_u32_le(&mut out, self.signatures.len() as u32);
+        for s in &self.signatures {
+            push_bytes(&mut out, &s.pubkey);
+            push_bytes(&mut out, &s.signature);
+        }
+        push_bytes(&mut out, &attestation);
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        let version = r.read_u16_le()?;
+        if version != 4 {
+            return Err(BytesError::InvalidLength);
+        }
+        let runtime_id = read_32(&mut r)?;
+        let model_hash = read_32(&mut r)?;
+        let input_hash = read_32(&mut r)?;
+        let output_hash = read_32(&mut r)?;
+        let trace_root = read_32(&mut r)?;
+        let count = r.read_u32_le()? as usize;
+        if count > r.remaining() / 96 {
+            return Err(BytesError::InvalidLength);
+        }
+        let mut signatures: Vec<CoSignature> = Vec::with_capacity(count);
+        for _ in 0..count {
+            let pubkey = read_32(&mut r)?;
+            let signature = read_64(&mut r)?;
+            if signatures.iter().any(|s| s.pubkey == pubkey) {
+                return Err(BytesError::InvalidLength);
+            }
+            signatures.push(CoSignature { pubkey, signature });
+        }
+        let remaining = r.read_exact(r.remaining())?;
+        let attestation = AttestationBundle::decode_bin(remaining)?;
+        Ok(Self {
+            version,
+            runtime_id,
+            model_hash,
+            input_hash,
+            output_hash,
+            trace_root,
+            signatures,
+            attestation,
+        })
+    }
+
+    /// Appends a signature by `signing_key_bytes`; a key that has already
+    /// signed is left as is.
+    pub fn add_signature(&mut self, signing_key_bytes: [u8; 32]) -> Result<(), BytesError> {
+        self.add_signature_with(&signing_key_bytes)
+            .map_err(|_| BytesError::InvalidLength)
+    }
+
+    pub fn add_signature_with<S: Signer + ?Sized>(
+        &mut self,
+        signer: &S,
+    ) -> Result<(), SignerError> {
+        let pubkey = ed25519_public_key(signer)?;
+        if self.signatures.iter().any(|s| s.pubkey == pubkey) {
+            return Ok(());
+        }
+
+        let signature = sign_checked(signer, &self.message_to_sign())?;
+        self.signatures.push(CoSignature { pubkey, signature });
+        Ok(())
+    }
+
+    /// Checks every co-signature and returns the signer keys in artifact
+    /// order. Fails if there are none or any one of them is invalid.
+    pub fn verify_signatures(&self) -> Result<Vec<[u8; 32]>, BytesError> {
+        if self.signatures.is_empty() {
+            return Err(BytesError::InvalidLength);
+        }
+        let msg = self.message_to_sign();
+        for s in &self.signatures {
+            let vk = VerifyingKey::from_bytes(&s.pubkey).map_err(|_| BytesError::InvalidLength)?;
+            let sig = Signature::from_bytes(&s.signature);
+            vk.verify(&msg, &sig)
+                .map_err(|_| BytesError::InvalidLength)?;
+        }
+        Ok(self.signatures.iter().map(|s| s.pubkey).collect())
+    }
+}
+
+impl ProofArtifactV5 {
+    pub fn message_to_sign(&self) -> Vec<u8> {
+        let attestation = self.attestation.encode_bin();
+        let mut out =
+            Vec::with_capacity(19 + 2 + 32 * 5 + 1 + 2 + self.sig_pubkey.len() + attestation.len());
+        out.extend_from_slice(SIGN_PREFIX_V5);
+        out.extend_from_slice(&self.version.to_le_bytes());
+        out.extend_from_slice(&self.runtime_id);
+        out.extend_from_slice(&self.model_hash);
+        out.extend_from_slice(&self.input_hash);
+        out.extend_from_slice(&self.output_hash);
+        out.extend_from_slice(&self.trace_root);
+        out.push(self.sig_alg.id());
+        out.extend_from_slice(&(self.sig_pubkey.len() as u16).to_le_bytes());
+        out.extend_from_slice(&self.sig_pubkey);
+        out.extend_from_slice(&attestation);
+        out
+    }
+
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let attestation = self.attestation.encode_bin();
+        let mut out = Vec::with_capacity(
+            2 + 32 * 5
+                + 1
+                + 2
+                + self.sig_pubkey.len()
+                + 2
+                + self.signature.len()
+                + attestation.len(),
+        );
+        push_u16_le(&mut out, self.version);
+        push_bytes(&mut out, &self.runtime_id);
+        push_bytes(&mut out, &self.model_hash);
+        push_bytes(&mut out, &self.input_hash);
+        push_bytes(&mut out, &self.output_hash);
+        push_bytes(&mut out, &self.trace_root);
+        push_u8(&mut out, self.sig_alg.id());
+        push_u16_le(&mut out, self.sig_pubkey.len() as u16);
+        push_bytes(&mut out, &self.sig_pubkey);
+        push_u16_le(&mut out, self.signature.len() as u16);
+        push_bytes(&mut out, &self.signature);
+        push_bytes(&mut out, &attestation);
+        out
+    }
+
+    /// Rejects unknown algorithms and key or signature lengths the
+    /// algorithm does not allow.
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        let version = r.read_u16_le()?;
+        if version != 5 {
+            return Err(BytesError::InvalidLength);
+        }
+        let runtime_id = read_32(&mut r)?;
+        let model_hash = read_32(&mut r)?;
+        let input_hash = read_32(&mut r)?;
+        let output_hash = read_32(&mut r)?;
+        let trace_root = read_32(&mut r)?;
+        let sig_alg = SigAlg::from_id(r.read_u8()?).ok_or(BytesError::InvalidLength)?;
+        let pubkey_len = r.read_u16_le()? as usize;
+        if !sig_alg.pubkey_len_ok(pubkey_len) {
+            return Err(BytesError::InvalidLength);
+        }
+        let sig_pubkey = r.read_exact(pubkey_len)?.to_vec();
+        let sig_len = r.read_u16_le()? as usize;
+        if sig_len != sig_alg.signature_len() {
+            return Err(BytesError::InvalidLength);
+        }
+        let signature = r.read_exact(sig_len)?.to_vec();
+        let remaining = r.read_exact(r.remaining())?;
+        let attestation = AttestationBundle::decode_bin(remaining)?;
+        Ok(Self {
+            version,
+            runtime_id,
+            model_hash,
+            input_hash,
+            output_hash,
+            trace_root,
+            sig_alg,
+            sig_pubkey,
+            signature,
+            attestation,
+        })
+    }
+
+    /// Sets the algorithm and public key from `key` and signs.
+    pub fn sign_detached(&mut self, key: &AlgSigningKey) {
+        self.sign_with(key)
+            .expect("an in-process key signs for its own public key");
+    }
+
+    /// Sets the algorithm and public key from `signer` and signs.
+    pub fn sign_with<S: Signer + ?Sized>(&mut self, signer: &S) -> Result<(), SignerError> {
+        self.sig_alg = signer.sig_alg();
+        self.sig_pubkey = signer.public_key();
+        self.signature = sign_checked(signer, &self.message_to_sign())?.to_vec();
+        Ok(())
+    }
+
+    pub fn verify_signature(&self) -> Result<(), BytesError> {
+        verify_alg(
+            self.sig_alg,
+            &self.sig_pubkey,
+            &self.message_to_sign(),
+            &self.signature,
+        )
+    }
+}
+
+impl ProofArtifactV6 {
+    /// `prefix || hash_alg || hash_alg(body)`, where the body is the
+    /// encoding without `sig_len` and `signature`.
+    pub fn message_to_sign(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(19 + 1 + 32);
+        out.extend_from_slice(SIGN_PREFIX_V6);
+        out.push(self.hash_alg.id());
+        out.extend_from_slice(&self.hash_alg.digest(&self.body()));
+        out
+    }
+
+    fn body(&self) -> Vec<u8> {
+        let attestation = self.attestation.encode_bin();
+        let mut out =
+            Vec::with_capacity(2 + 32 * 5 + 2 + 2 + self.sig_pubkey.len() + attestation.len());
+        push_u16_le(&mut out, self.version);
+        push_bytes(&mut out, &self.runtime_id);
+        push_bytes(&mut out, &self.model_hash);
+        push_bytes(&mut out, &self.input_hash);
+        push_bytes(&mut out, &self.output_hash);
+        push_bytes(&mut out, &self.trace_root);
+        push_u8(&mut out, self.hash_alg.id());
+        push_u8(&mut out, self.sig_alg.id());
+        push_u16_le(&mut out, self.sig_pubkey.len() as u16);
+        push_bytes(&mut out, &self.sig_pubkey);
+        push_bytes(&mut out, &attestation);
+        out
+    }
+
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let attestation = self.attestation.encode_bin();
+        let mut out = Vec::with_capacity(
+            2 + 32 * 5
+                + 2
+                + 2
+                + self.sig_pubkey.len()
+                + 2
+                + self.signature.len()
+                + attestation.len(),
+        );
+        push_u16_le(&mut out, self.version);
+        push_bytes(&mut out, &self.runtime_id);
+        push_bytes(&mut out, &self.model_hash);
+        push_bytes(&mut out, &self.input_hash);
+        push_bytes(&mut out, &self.output_hash);
+        push_bytes(&mut out, &self.trace_root);
+        push_u8(&mut out, self.hash_alg.id());
+        push_u8(&mut out, self.sig_alg.id());
+        push_u16_le(&mut out, self.sig_pubkey.len() as u16);
+        push_bytes(&mut out, &self.sig_pubkey);
+        push_u16_le(&mut out, self.signature.len() as u16);
+        push_bytes(&mut out, &self.signature);
+        push_bytes(&mut out, &attestation);
+        out
+    }
+
+    /// Rejects unknown hash or signature algorithms and key or signature
+    /// lengths the signature algorithm does not allow.
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        let version = r.read_u16_le()?;
+        if version != 6 {
+            return Err(BytesError::InvalidLength);
+        }
+        let runtime_id = read_32(&mut r)?;
+        let model_hash = read_32(&mut r)?;
+        let input_hash = read_32(&mut r)?;
+        let output_hash = read_32(&mut r)?;
+        let trace_root = read_32(&mut r)?;
+        let hash_alg = HashAlg::from_id(r.read_u8()?).ok_or(BytesError::InvalidLength)?;
+        let sig_alg = SigAlg::from_id(r.read_u8()?).ok_or(BytesError::InvalidLength)?;
+        let pubkey_len = r.read_u16_le()? as usize;
+        if !sig_alg.pubkey_len_ok(pubkey_len) {
+            return Err(BytesError::InvalidLength);
+        }
+        let sig_pubkey = r.read_exact(pubkey_len)?.to_vec();
+        let sig_len = r.read_u16_le()? as usize;
+        if sig_len != sig_alg.signature_len() {
+            return Err(BytesError::InvalidLength);
+        }
+        let signature = r.read_exact(sig_len)?.to_vec();
+        let remaining = r.read_exact(r.remaining())?;
+        let attestation = AttestationBundle::decode_bin(remaining)?;
+        Ok(Self {
+            version,
+            runtime_id,
+            model_hash,
+            input_hash,
+            output_hash,
+            trace_root,
+            hash_alg,
+            sig_alg,
+            sig_pubkey,
+            signature,
+            attestation,
+        })
+    }
+
+    /// Sets the signature algorithm and public key from `key` and signs.
+    pub fn sign_detached(&mut self, key: &AlgSigningKey) {
+        self.sign_with(key)
+            .expect("an in-process key signs for its own public key");
+    }
+
+    /// Sets the signature algorithm and public key from `signer` and signs.
+    pub fn sign_with<S: Signer + ?Sized>(&mut self, signer: &S) -> Result<(), SignerError> {
+        self.sig_alg = signer.sig_alg();
+        self.sig_pubkey = signer.public_key();
+        self.signature = sign_checked(signer, &self.message_to_sign())?.to_vec();
+        Ok(())
+    }
+
+    pub fn verify_signature(&self) -> Result<(), BytesError> {
+        verify_alg(
+            self.sig_alg,
+            &self.sig_pubkey,
+            &self.message_to_sign(),
+            &self.signature,
+        )
+    }
+}
+
+impl ProofArtifactV7 {
+    pub fn message_to_sign(&self) -> Vec<u8> {
+        let attestation = self.attestation.encode_bin();
+        let mut out = Vec::with_capacity(19 + 2 + 32 * 6 + attestation.len());
+        out.extend_from_slice(SIGN_PREFIX_V7);
+        out.extend_from_slice(&self.version.to_le_bytes());
+        out.extend_from_slice(&self.runtime_id);
+        out.extend_from_slice(&self.model_commitment);
+        out.extend_from_slice(&self.input_commitment);
+        out.extend_from_slice(&self.output_commitment);
+        out.extend_from_slice(&self.trace_commitment);
+        out.extend_from_slice(&self.sig_pubkey);
+        out.extend_from_slice(&attestation);
+        out
+    }
+
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let attestation = self.attestation.encode_bin();
+        let mut out = Vec::with_capacity(2 + 32 * 6 + 64 + attestation.len());
+        push_u16_le(&mut out, self.version);
+        push_bytes(&mut out, &self.runtime_id);
+        push_bytes(&mut out, &self.model_commitment);
+        push_bytes(&mut out, &self.input_commitment);
+        push_bytes(&mut out, &self.output_commitment);
+        push_bytes(&mut out, &self.trace_commitment);
+        push_bytes(&mut out, &self.sig_pubkey);
+        push_bytes(&mut out, &self.signature);
+        push_bytes(&mut out, &attestation);
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        let version = r.read_u16_le()?;
+        if version != 7 {
+            return Err(BytesError::InvalidLength);
+        }
+        let runtime_id = read_32(&mut r)?;
+        let model_commitment = read_32(&mut r)?;
+        let input_commitment = read_32(&mut r)?;
+        let output_commitment = read_32(&mut r)?;
+        let trace_commitment = read_32(&mut r)?;
+        let sig_pubkey = read_32(&mut r)?;
+        let signature = read_64(&mut r)?;
+        let remaining = r.read_exact(r.remaining())?;
+        let attestation = AttestationBundle::decode_bin(remaining)?;
+        Ok(Self {
+            version,
+            runtime_id,
+            model_commitment,
+            input_commitment,
+            output_commitment,
+            trace_commitment,
+            sig_pubkey,
+            signature,
+            attestation,
+        })
+    }
+
+    pub fn sign_detached(&mut self, signing_key_bytes: [u8; 32]) -> Result<(), BytesError> {
+        self.sign_with(&signing_key_bytes)
+            .map_err(|_| BytesError::InvalidLength)
+    }
+
+    pub fn sign_with<S: Signer + ?Sized>(&mut self, signer: &S) -> Result<(), SignerError> {
+        self.sig_pubkey = ed25519_public_key(signer)?;
+        self.signature = sign_checked(signer, &self.message_to_sign())?;
+        Ok(())
+    }
+
+    /// Needs no salts: anyone can check who signed the commitments.
+    pub fn verify_signature(&self) -> Result<(), BytesError> {
+        let vk =
+            VerifyingKey::from_bytes(&self.sig_pubkey).map_err(|_| BytesError::InvalidLength)?;
+        let sig = Signature::from_bytes(&self.signature);
+        vk.verify(&self.message_to_sign(), &sig)
+            .map_err(|_| BytesError::InvalidLength)
+    }
+}
+
+impl ProofArtifactV8 {
+    pub fn message_to_sign(&self) -> Vec<u8> {
+        let attestation = self.attestation.encode_bin();
+        let mut out = Vec::with_capacity(19 + 2 + 32 * 7 + attestation.len());
+        out.extend_from_slice(SIGN_PREFIX_V8);
+        out.extend_from_slice(&self.version.to_le_bytes());
+        out.extend_from_slice(&self.runtime_id);
+        out.extend_from_slice(&self.model_hash);
+        out.extend_from_slice(&self.preprocessor_hash);
+        out.extend_from_slice(&self.input_hash);
+        out.extend_from_slice(&self.output_hash);
+        out.extend_from_slice(&self.trace_root);
+        out.extend_from_slice(&self.sig_pubkey);
+        out.extend_from_slice(&attestation);
+        out
+    }
+
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let attestation = self.attestation.encode_bin();
+        let mut out = Vec::with_capacity(2 + 32 * 7 + 64 + attestation.len());
+        push_u16_le(&mut out, self.version);
+        push_bytes(&mut out, &self.runtime_id);
+        push_bytes(&mut out, &self.model_hash);
+        push_bytes(&mut out, &self.preprocessor_hash);
+        push_bytes(&mut out, &self.input_hash);
+        push_bytes(&mut out, &self.output_hash);
+        push_bytes(&mut out, &self.trace_root);
+        push_bytes(&mut out, &self.sig_pubkey);
+        push_bytes(&mut out, &self.signature);
+        push_bytes(&mut out, &attestation);
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        let version = r.read_u16_le()?;
+        if version != 8 {
+            return Err(BytesError::InvalidLength);
+        }
+        let runtime_id = read_32(&mut r)?;
+        let model_hash = read_32(&mut r)?;
+        let preprocessor_hash = read_32(&mut r)?;
+        let input_hash = read_32(&mut r)?;
+        let output_hash = read_32(&mut r)?;
+        let trace_root = read_32(&mut r)?;
+        let sig_pubkey = read_32(&mut r)?;
+        let signature = read_64(&mut r)?;
+        let remaining = r.read_exact(r.remaining())?;
+        let attestation = AttestationBundle::decode_bin(remaining)?;
+        Ok(Self {
+            version,
+            runtime_id,
+            model_hash,
+            preprocessor_hash,
+            input_hash,
+            output_hash,
+            trace_root,
+            sig_pubkey,
+            signature,
+            attestation,
+        })
+    }
+
+    pub fn sign_detached(&mut self, signing_key_bytes: [u8; 32]) -> Result<(), BytesError> {
+        self.sign_with(&signing_key_bytes)
+            .map_err(|_| BytesError::InvalidLength)
+    }
+
+    pub fn sign_with<S: Signer + ?Sized>(&mut self, signer: &S) -> Result<(), SignerError> {
+        self.sig_pubkey = ed25519_public_key(signer)?;
+        self.signature = sign_checked(signer, &self.message_to_sign())?;
+        Ok(())
+    }
+
+    pub fn verify_signature(&self) -> Result<(), BytesError> {
+        let vk =
+            VerifyingKey::from_bytes(&self.sig_pubkey).map_err(|_| BytesError::InvalidLength)?;
+        let sig = Signature::from_bytes(&self.signature);
+        vk.verify(&self.message_to_sign(), &sig)
+            .map_err(|_| BytesError::InvalidLength)
+    }
+}
+
+impl ProofArtifactV9 {
+    pub fn message_to_sign(&self) -> Vec<u8> {
+        let attestation = self.attestation.encode_bin();
+        let mut out = Vec::with_capacity(19 + 2 + 32 * 6 + 1 + attestation.len());
+        out.extend_from_slice(SIGN_PREFIX_V9);
+        out.extend_from_slice(&self.version.to_le_bytes());
+        out.extend_from_slice(&self.runtime_id);
+        out.extend_from_slice(&self.model_hash);
+        out.extend_from_slice(&self.input_hash);
+        out.extend_from_slice(&self.output_hash);
+        out.push(self.trace_level.id());
+        out.extend_from_slice(&self.trace_root);
+        out.extend_from_slice(&self.sig_pubkey);
+        out.extend_from_slice(&attestation);
+        out
+    }
+
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let attestation = self.attestation.encode_bin();
+        let mut out = Vec::with_capacity(2 + 32 * 6 + 1 + 64 + attestation.len());
+        push_u16_le(&mut out, self.version);
+        push_bytes(&mut out, &self.runtime_id);
+        push_bytes(&mut out, &self.model_hash);
+        push_bytes(&mut out, &self.input_hash);
+        push_bytes(&mut out, &self.output_hash);
+        push_u8(&mut out, self.trace_level.id());
+        push_bytes(&mut out, &self.trace_root);
+        push_bytes(&mut out, &self.sig_pubkey);
+        push_bytes(&mut out, &self.signature);
+        push_bytes(&mut out, &attestation);
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        let version = r.read_u16_le()?;
+        if version != 9 {
+            return Err(BytesError::InvalidLength);
+        }
+        let runtime_id = read_32(&mut r)?;
+        let model_hash = read_32(&mut r)?;
+        let input_hash = read_32(&mut r)?;
+        let output_hash = read_32(&mut r)?;
+        let trace_level = TraceLevel::from_id(r.read_u8()?).ok_or(BytesError::InvalidLength)?;
+        let trace_root = read_32(&mut r)?;
+        let sig_pubkey = read_32(&mut r)?;
+        let signature = read_64(&mut r)?;
+        let remaining = r.read_exact(r.remaining())?;
+        let attestation = AttestationBundle::decode_bin(remaining)?;
+        Ok(Self {
+            version,
+            runtime_id,
+            model_hash,
+            input_hash,
+            output_hash,
+            trace_level,
+            trace_root,
+            sig_pubkey,
+            signature,
+            attestation,
+        })
+    }
+
+    pub fn sign_detached(&mut self, signing_key_bytes: [u8; 32]) -> Result<(), BytesError> {
+        self.sign_with(&signing_key_bytes)
+            .map_err(|_| BytesError::InvalidLength)
+    }
+
+    pub fn sign_with<S: Signer + ?Sized>(&mut self, signer: &S) -> Result<(), SignerError> {
+        self.sig_pubkey = ed25519_public_key(signer)?;
+        self.signature = sign_checked(signer, &self.message_to_sign())?;
+        Ok(())
+    }
+
+    pub fn verify_signature(&self) -> Result<(), BytesError> {
+        let vk =
+            VerifyingKey::from_bytes(&self.sig_pubkey).map_err(|_| BytesError::InvalidLength)?;
+        let sig = Signature::from_bytes(&self.signature);
+        vk.verify(&self.message_to_sign(), &sig)
+            .map_err(|_| BytesError::InvalidLength)
+    }
+}
+
+impl ProofArtifactV10 {
+    pub fn message_to_sign(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(20 + 2 + 32 * 6 + ZK_PROOF_LEN);
+        out.extend_from_slice(SIGN_PREFIX_V10);
+        out.extend_from_slice(&self.version.to_le_bytes());
+        out.extend_from_slice(&self.runtime_id);
+        out.extend_from_slice(&self.model_hash);
+        out.extend_from_slice(&self.input_hash);
+        out.extend_from_slice(&self.output_hash);
+        out.extend_from_slice(&self.circuit_hash);
+        out.extend_from_slice(&self.proof);
+        out.extend_from_slice(&self.sig_pubkey);
+        out
+    }
+
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(PROOF_ARTIFACT_V10_LEN);
+        push_u16_le(&mut out, self.version);
+        push_bytes(&mut out, &self.runtime_id);
+        push_bytes(&mut out, &self.model_hash);
+        push_bytes(&mut out, &self.input_hash);
+        push_bytes(&mut out, &self.output_hash);
+        push_bytes(&mut out, &self.circuit_hash);
+        push_bytes(&mut out, &self.proof);
+        push_bytes(&mut out, &self.sig_pubkey);
+        push_bytes(&mut out, &self.signature);
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        if buf.len() != PROOF_ARTIFACT_V10_LEN {
+            return Err(BytesError::InvalidLength);
+        }
+        let mut r = Reader::new(buf);
+        let version = r.read_u16_le()?;
+        if version != 10 {
+            return Err(BytesError::InvalidLength);
+        }
+        let runtime_id = read_32(&mut r)?;
+        let model_hash = read_32(&mut r)?;
+        let input_hash = read_32(&mut r)?;
+        let output_hash = read_32(&mut r)?;
+        let circuit_hash = read_32(&mut r)?;
+        let mut proof = [0u8; ZK_PROOF_LEN];
+        proof.copy_from_slice(r.read_exact(ZK_PROOF_LEN)?);
+        let sig_pubkey = read_32(&mut r)?;
+        let signature = read_64(&mut r)?;
+        Ok(Self {
+            version,
+            runtime_id,
+            model_hash,
+            input_hash,
+            output_hash,
+            circuit_hash,
+            proof,
+            sig_pubkey,
+            signature,
+        })
+    }
+
+    pub fn sign_detached(&mut self, signing_key_bytes: [u8; 32]) -> Result<(), BytesError> {
+        self.sign_with(&signing_key_bytes)
+            .map_err(|_| BytesError::InvalidLength)
+    }
+
+    pub fn sign_with<S: Signer + ?Sized>(&mut self, signer: &S) -> Result<(), SignerError> {
+        self.sig_pubkey = ed25519_public_key(signer)?;
+        self.signature = sign_checked(signer, &self.message_to_sign())?;
+        Ok(())
+    }
+
+    pub fn verify_signature(&self) -> Result<(), BytesError> {
+        let vk =
+            VerifyingKey::from_bytes(&self.sig_pubkey).map_err(|_| BytesError::InvalidLength)?;
+        let sig = Signature::from_bytes(&self.signature);
+        vk.verify(&self.message_to_sign(), &sig)
+            .map_err(|_| BytesError::InvalidLength)
+    }
+}
+
 fn read_32(r: &mut Reader<'_>) -> Result<[u8; 32], BytesError> {
     let b = r.read_exact(32)?;
     let mut out = [0u8; 32];
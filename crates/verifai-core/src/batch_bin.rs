@@ -0,0 +1,76 @@
+use crate::bytes::{push_bytes, push_u32_le, BytesError, Reader};
+
+/// Everything needed to check one row of a `BatchProofArtifactV2` without the
+/// rest of the batch: the row's canonical input/output, its trace root and the
+/// three Merkle paths to `inputs_root`, `outputs_root` and `traces_root`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchRowProofV2 {
+    pub index: u32,
+    pub input_bin: Vec<u8>,
+    pub output_bin: Vec<u8>,
+    pub trace_root: [u8; 32],
+    pub input_path: Vec<[u8; 32]>,
+    pub output_path: Vec<[u8; 32]>,
+    pub trace_path: Vec<[u8; 32]>,
+}
+
+impl BatchRowProofV2 {
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        push_u32_le(&mut out, self.index);
+        push_u32_le(&mut out, self.input_bin.len() as u32);
+        push_bytes(&mut out, &self.input_bin);
+        push_u32_le(&mut out, self.output_bin.len() as u32);
+        push_bytes(&mut out, &self.output_bin);
+        push_bytes(&mut out, &self.trace_root);
+        // all three trees have row_count leaves, so the paths share a depth
+        push_u32_le(&mut out, self.input_path.len() as u32);
+        for path in [&self.input_path, &self.output_path, &self.trace_path] {
+            for node in path {
+                push_bytes(&mut out, node);
+            }
+        }
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        let index = r.read_u32_le()?;
+        let input_len = r.read_u32_le()? as usize;
+        let input_bin = r.read_exact(input_len)?.to_vec();
+        let output_len = r.read_u32_le()? as usize;
+        let output_bin = r.read_exact(output_len)?.to_vec();
+        let trace_root = read_32(&mut r)?;
+        let depth = r.read_u32_le()? as usize;
+        let input_path = read_path(&mut r, depth)?;
+        let output_path = read_path(&mut r, depth)?;
+        let trace_path = read_path(&mut r, depth)?;
+        if r.remaining() != 0 {
+            return Err(BytesError::InvalidLength);
+        }
+        Ok(Self {
+            index,
+            input_bin,
+            output_bin,
+            trace_root,
+            input_path,
+            output_path,
+            trace_path,
+        })
+    }
+}
+
+fn read_path(r: &mut Reader<'_>, depth: usize) -> Result<Vec<[u8; 32]>, BytesError> {
+    let mut path = Vec::with_capacity(depth.min(64));
+    for _ in 0..depth {
+        path.push(read_32(r)?);
+    }
+    Ok(path)
+}
+
+fn read_32(r: &mut Reader<'_>) -> Result<[u8; 32], BytesError> {
+    let b = r.read_exact(32)?;
+    let mut out = [0u8; 32];
+    out.copy_from_slice(b);
+    Ok(out)
+}
@@ -0,0 +1,255 @@
+//! Checking the signatures of many artifacts at once.
+//!
+//! Ed25519 signatures from every artifact go through one batch verification,
+//! which costs much less than verifying them one by one. A batch only says
+//! whether all signatures hold, so when it fails the artifacts are split in
+//! halves and each half is batch-checked again until the invalid ones are
+//! found. Artifacts signed with another algorithm (ECDSA `ProofArtifactV5`
+//! and `ProofArtifactV6`) are checked one at a time.
+//!
+//! The batch equation is cofactored while `verify_signature` is not; the two
+//! only disagree on deliberately malformed signatures involving small-order
+//! points, which no honest signer produces.
+
+use crate::artifact_bin::{
+    BatchProofArtifactV2, ProofArtifactV0, ProofArtifactV1, ProofArtifactV3, ProofArtifactV4,
+    ProofArtifactV5, ProofArtifactV6, ProofArtifactV7, ProofArtifactV8, ProofArtifactV9,
+};
+use crate::bytes::BytesError;
+use crate::sig_alg::SigAlg;
+
+use ed25519_dalek::{Signature, VerifyingKey};
+
+/// Groups at most this large are checked artifact by artifact when a batch
+/// fails, where splitting further no longer pays off.
+const BISECT_MIN: usize = 4;
+
+/// One Ed25519 signature an artifact needs to hold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ed25519Check {
+    pub pubkey: [u8; 32],
+    pub message: Vec<u8>,
+    pub signature: [u8; 64],
+}
+
+/// An artifact whose signatures `verify_batch` can check.
+pub trait BatchVerify {
+    /// Every Ed25519 signature the artifact needs, or `None` if it is signed
+    /// with another algorithm.
+    fn ed25519_checks(&self) -> Option<Vec<Ed25519Check>>;
+
+    /// The artifact's own signature check.
+    fn verify_alone(&self) -> Result<(), BytesError>;
+}
+
+/// Checks the signatures of all `artifacts` and returns the indices of the
+/// ones that do not verify, in ascending order.
+pub fn verify_batch(artifacts: &[&dyn BatchVerify]) -> Vec<usize> {
+    let mut invalid = Vec::new();
+    let mut group: Vec<(usize, Vec<Ed25519Check>)> = Vec::new();
+    for (i, artifact) in artifacts.iter().enumerate() {
+        match artifact.ed25519_checks() {
+            // an artifact without any signature never verifies
+            Some(checks) if checks.is_empty() => invalid.push(i),
+            Some(checks) => group.push((i, checks)),
+            None if artifact.verify_alone().is_err() => invalid.push(i),
+            None => {}
+        }
+    }
+
+    find_invalid(artifacts, &group, &mut invalid);
+    invalid.sort_unstable();
+    invalid
+}
+
+fn find_invalid(
+    artifacts: &[&dyn BatchVerify],
+    group: &[(usize, Vec<Ed25519Check>)],
+    invalid: &mut Vec<usize>,
+) {
+    if group.is_empty() || batch_ok(group) {
+        return;
+    }
+    if group.len() <= BISECT_MIN {
+        invalid.extend(
+            group
+                .iter()
+                .filter(|(i, _)| artifacts[*i].verify_alone().is_err())
+                .map(|(i, _)| *i),
+        );
+        return;
+    }
+    let (left, right) = group.split_at(group.len() / 2);
+    find_invalid(artifacts, left, invalid);
+    find_invalid(artifacts, right, invalid);
+}
+
+fn batch_ok(group: &[(usize, Vec<Ed25519Check>)]) -> bool {
+    let checks = || group.iter().flat_map(|(_, checks)| checks);
+    let Ok(keys) = checks()
+        .map(|c| VerifyingKey::from_bytes(&c.pubkey))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return false;
+    };
+    let messages: Vec<&[u8]> = checks().map(|c| c.message.as_slice()).collect();
+    let signatures: Vec<Signature> = checks()
+        .map(|c| Signature::from_bytes(&c.signature))
+        .collect();
+    ed25519_dalek::verify_batch(&messages, &signatures, &keys).is_ok()
+}
+
+impl BatchVerify for ProofArtifactV0 {
+    fn ed25519_checks(&self) -> Option<Vec<Ed25519Check>> {
+        Some(vec![Ed25519Check {
+            pubkey: self.sig_pubkey,
+            message: self.message_to_sign(),
+            signature: self.signature,
+        }])
+    }
+
+    fn verify_alone(&self) -> Result<(), BytesError> {
+        self.verify_signature()
+    }
+}
+
+impl BatchVerify for ProofArtifactV1 {
+    fn ed25519_checks(&self) -> Option<Vec<Ed25519Check>> {
+        Some(vec![Ed25519Check {
+            pubkey: self.sig_pubkey,
+            message: self.message_to_sign(),
+            signature: self.signature,
+        }])
+    }
+
+    fn verify_alone(&self) -> Result<(), BytesError> {
+        self.verify_signature()
+    }
+}
+
+impl BatchVerify for BatchProofArtifactV2 {
+    fn ed25519_checks(&self) -> Option<Vec<Ed25519Check>> {
+        Some(vec![Ed25519Check {
+            pubkey: self.sig_pubkey,
+            message: self.message_to_sign(),
+            signature: self.signature,
+        }])
+    }
+
+    fn verify_alone(&self) -> Result<(), BytesError> {
+        self.verify_signature()
+    }
+}
+
+/// Covers the artifact signature only; a timestamp token is checked with the
+/// rest of the freshness policy.
+impl BatchVerify for ProofArtifactV3 {
+    fn ed25519_checks(&self) -> Option<Vec<Ed25519Check>> {
+        Some(vec![Ed25519Check {
+            pubkey: self.sig_pubkey,
+            message: self.message_to_sign(),
+            signature: self.signature,
+        }])
+    }
+
+    fn verify_alone(&self) -> Result<(), BytesError> {
+        self.verify_signature()
+    }
+}
+
+impl BatchVerify for ProofArtifactV4 {
+    fn ed25519_checks(&self) -> Option<Vec<Ed25519Check>> {
+        let message = self.message_to_sign();
+        Some(
+            self.signatures
+                .iter()
+                .map(|s| Ed25519Check {
+                    pubkey: s.pubkey,
+                    message: message.clone(),
+                    signature: s.signature,
+                })
+                .collect(),
+        )
+    }
+
+    fn verify_alone(&self) -> Result<(), BytesError> {
+        self.verify_signatures().map(|_| ())
+    }
+}
+
+impl BatchVerify for ProofArtifactV5 {
+    fn ed25519_checks(&self) -> Option<Vec<Ed25519Check>> {
+        if self.sig_alg != SigAlg::Ed25519 {
+            return None;
+        }
+        // decode_bin enforces the lengths, but the fields are public
+        Some(vec![Ed25519Check {
+            pubkey: self.sig_pubkey.as_slice().try_into().ok()?,
+            message: self.message_to_sign(),
+            signature: self.signature.as_slice().try_into().ok()?,
+        }])
+    }
+
+    fn verify_alone(&self) -> Result<(), BytesError> {
+        self.verify_signature()
+    }
+}
+
+impl BatchVerify for ProofArtifactV6 {
+    fn ed25519_checks(&self) -> Option<Vec<Ed25519Check>> {
+        if self.sig_alg != SigAlg::Ed25519 {
+            return None;
+        }
+        Some(vec![Ed25519Check {
+            pubkey: self.sig_pubkey.as_slice().try_into().ok()?,
+            message: self.message_to_sign(),
+            signature: self.signature.as_slice().try_into().ok()?,
+        }])
+    }
+
+    fn verify_alone(&self) -> Result<(), BytesError> {
+        self.verify_signature()
+    }
+}
+
+impl BatchVerify for ProofArtifactV7 {
+    fn ed25519_checks(&self) -> Option<Vec<Ed25519Check>> {
+        Some(vec![Ed25519Check {
+            pubkey: self.sig_pubkey,
+            message: self.message_to_sign(),
+            signature: self.signature,
+        }])
+    }
+
+    fn verify_alone(&self) -> Result<(), BytesError> {
+        self.verify_signature()
+    }
+}
+
+impl BatchVerify for ProofArtifactV8 {
+    fn ed25519_checks(&self) -> Option<Vec<Ed25519Check>> {
+        Some(vec![Ed25519Check {
+            pubkey: self.sig_pubkey,
+            message: self.message_to_sign(),
+            signature: self.signature,
+        }])
+    }
+
+    fn verify_alone(&self) -> Result<(), BytesError> {
+        self.verify_signature()
+    }
+}
+
+impl BatchVerify for ProofArtifactV9 {
+    fn ed25519_checks(&self) -> Option<Vec<Ed25519Check>> {
+        Some(vec![Ed25519Check {
+            pubkey: self.sig_pubkey,
+            message: self.message_to_sign(),
+            signature: self.signature,
+        }])
+    }
+
+    fn verify_alone(&self) -> Result<(), BytesError> {
+        self.verify_signature()
+    }
+}
@@ -44,6 +44,13 @@ impl<'a> Reader<'a> {
         Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
     }
 
+    pub fn read_u64_le(&mut self) -> Result<u64, BytesError> {
+        let b = self.read_exact(8)?;
+        Ok(u64::from_le_bytes([
+            b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7],
+        ]))
+    }
+
     pub fn read_f64_le(&mut self) -> Result<f64, BytesError> {
         let b = self.read_exact(8)?;
         Ok(f64::from_le_bytes([
@@ -68,6 +75,10 @@ pub fn push_u32_le(out: &mut Vec<u8>, v: u32) {
     out.extend_from_slice(&v.to_le_bytes());
 }
 
+pub fn push_u64_le(out: &mut Vec<u8>, v: u64) {
+    out.extend_from_slice(&v.to_le_bytes());
+}
+
 pub fn push_f64_le(out: &mut Vec<u8>, v: f64) {
     out.extend_from_slice(&v.to_le_bytes());
 }
@@ -0,0 +1,206 @@
+//! COSE_Sign1 (RFC 9052) encoding of `ProofArtifactV1`.
+//!
+//! The payload is a CBOR map of the artifact claims, the protected header
+//! carries `alg: EdDSA` and `kid: sig_pubkey`, and the COSE signature is made
+//! with the artifact's own key so any COSE library can check it. The native
+//! signature travels in the unprotected header under `NATIVE_SIG_LABEL`, which
+//! is what makes the conversion back to `.bin` lossless.
+
+use coset::cbor::value::Value;
+use coset::{
+    iana, CborSerializable, CoseSign1, CoseSign1Builder, HeaderBuilder, Label,
+    TaggedCborSerializable,
+};
+use ed25519_dalek::Signer;
+use ed25519_dalek::Verifier;
+use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
+
+use crate::artifact_bin::ProofArtifactV1;
+use crate::attestation::AttestationBundle;
+use crate::bytes::BytesError;
+
+pub const COSE_CONTENT_TYPE: &str = "application/vnd.verifai.proof-artifact.v1+cbor";
+
+/// Unprotected header label holding the 64-byte native `ProofArtifactV1` signature.
+pub const NATIVE_SIG_LABEL: &str = "verifai-v1-sig";
+
+/// True when `buf` looks like a COSE_Sign1 message (tagged or untagged)
+/// rather than a native artifact, whose first byte is a small version number.
+pub fn is_cose_sign1(buf: &[u8]) -> bool {
+    matches!(buf.first(), Some(0xd2) | Some(0x84))
+}
+
+impl ProofArtifactV1 {
+    /// Encodes the artifact as a tagged COSE_Sign1 message.
+    ///
+    /// `signing_key_bytes` must be the key behind `sig_pubkey`; the COSE
+    /// signature is a second signature over the RFC 9052 `Sig_structure`.
+    pub fn encode_cose_sign1(&self, signing_key_bytes: [u8; 32]) -> Result<Vec<u8>, BytesError> {
+        let sk = SigningKey::from_bytes(&signing_key_bytes);
+        if VerifyingKey::from(&sk).to_bytes() != self.sig_pubkey {
+            return Err(BytesError::InvalidMagic);
+        }
+
+        let protected = HeaderBuilder::new()
+            .algorithm(iana::Algorithm::EdDSA)
+            .content_type(COSE_CONTENT_TYPE.to_string())
+            .key_id(self.sig_pubkey.to_vec())
+            .build();
+        let unprotected = HeaderBuilder::new()
+            .text_value(
+                NATIVE_SIG_LABEL.to_string(),
+                Value::Bytes(self.signature.to_vec()),
+            )
+            .build();
+
+        CoseSign1Builder::new()
+            .protected(protected)
+            .unprotected(unprotected)
+            .payload(self.cose_payload()?)
+            .create_signature(b"", |tbs| sk.sign(tbs).to_bytes().to_vec())
+            .build()
+            .to_tagged_vec()
+            .map_err(|_| BytesError::InvalidLength)
+    }
+
+    /// Decodes a COSE_Sign1 message produced by `encode_cose_sign1` without
+    /// checking either signature.
+    pub fn decode_cose_sign1(buf: &[u8]) -> Result<Self, BytesError> {
+        Ok(Self::parse_cose_sign1(buf)?.1)
+    }
+
+    /// Decodes the message and checks the COSE signature against `kid`.
+    /// The native signature is left to `verify_signature`.
+    pub fn verify_cose_sign1(buf: &[u8]) -> Result<Self, BytesError> {
+        let (sign1, artifact) = Self::parse_cose_sign1(buf)?;
+        let vk = VerifyingKey::from_bytes(&artifact.sig_pubkey)
+            .map_err(|_| BytesError::InvalidLength)?;
+        sign1.verify_signature(b"", |sig, tbs| {
+            let sig = Signature::from_slice(sig).map_err(|_| BytesError::InvalidLength)?;
+            vk.verify(tbs, &sig).map_err(|_| BytesError::InvalidLength)
+        })?;
+        Ok(artifact)
+    }
+
+    fn parse_cose_sign1(buf: &[u8]) -> Result<(CoseSign1, Self), BytesError> {
+        let sign1 = match buf.first() {
+            Some(0xd2) => CoseSign1::from_tagged_slice(buf),
+            _ => CoseSign1::from_slice(buf),
+        }
+        .map_err(|_| BytesError::InvalidMagic)?;
+
+        let header = &sign1.protected.header;
+        if header.alg != Some(coset::Algorithm::Assigned(iana::Algorithm::EdDSA)) {
+            return Err(BytesError::InvalidMagic);
+        }
+        let sig_pubkey = to_array::<32>(&header.key_id)?;
+        let native_sig = sign1
+            .unprotected
+            .rest
+            .iter()
+            .find(|(label, _)| *label == Label::Text(NATIVE_SIG_LABEL.to_string()))
+            .and_then(|(_, v)| v.as_bytes())
+            .ok_or(BytesError::InvalidLength)?;
+        let signature = to_array::<64>(native_sig)?;
+        let payload = sign1.payload.as_deref().ok_or(BytesError::InvalidLength)?;
+
+        let artifact = Self::from_cose_payload(payload, sig_pubkey, signature)?;
+        Ok((sign1, artifact))
+    }
+
+    fn cose_payload(&self) -> Result<Vec<u8>, BytesError> {
+        let attestation = Value::Map(vec![
+            claim("attester_id", &self.attestation.attester_id),
+            claim("measurement", &self.attestation.measurement),
+            claim("evidence", &self.attestation.attestation),
+        ]);
+        let claims = Value::Map(vec![
+            (
+                Value::Text("version".into()),
+                Value::Integer(self.version.into()),
+            ),
+            claim("runtime_id", &self.runtime_id),
+            claim("model_hash", &self.model_hash),
+            claim("input_hash", &self.input_hash),
+            claim("output_hash", &self.output_hash),
+            claim("trace_root", &self.trace_root),
+            (Value::Text("attestation".into()), attestation),
+        ]);
+        let mut out = Vec::new();
+        coset::cbor::ser::into_writer(&claims, &mut out).map_err(|_| BytesError::InvalidLength)?;
+        Ok(out)
+    }
+
+    fn from_cose_payload(
+        payload: &[u8],
+        sig_pubkey: [u8; 32],
+        signature: [u8; 64],
+    ) -> Result<Self, BytesError> {
+        let claims: Value =
+            coset::cbor::de::from_reader(payload).map_err(|_| BytesError::InvalidLength)?;
+        let claims = Claims::new(&claims, 7)?;
+        let version = claims
+            .get("version")?
+            .as_integer()
+            .and_then(|v| u16::try_from(v).ok())
+            .ok_or(BytesError::InvalidLength)?;
+        if version != 1 {
+            return Err(BytesError::InvalidMagic);
+        }
+        let attestation = Claims::new(claims.get("attestation")?, 3)?;
+        Ok(Self {
+            version,
+            runtime_id: claims.bytes32("runtime_id")?,
+            model_hash: claims.bytes32("model_hash")?,
+            input_hash: claims.bytes32("input_hash")?,
+            output_hash: claims.bytes32("output_hash")?,
+            trace_root: claims.bytes32("trace_root")?,
+            sig_pubkey,
+            signature,
+            attestation: AttestationBundle {
+                attester_id: attestation.bytes32("attester_id")?,
+                measurement: attestation.bytes32("measurement")?,
+                attestation: attestation.bytes("evidence")?.to_vec(),
+            },
+        })
+    }
+}
+
+fn claim(name: &str, bytes: &[u8]) -> (Value, Value) {
+    (Value::Text(name.into()), Value::Bytes(bytes.to_vec()))
+}
+
+/// Text-keyed CBOR map with exactly the expected number of entries.
+struct Claims<'a>(&'a [(Value, Value)]);
+
+impl<'a> Claims<'a> {
+    fn new(value: &'a Value, len: usize) -> Result<Self, BytesError> {
+        match value.as_map() {
+            Some(entries) if entries.len() == len => Ok(Self(entries)),
+            _ => Err(BytesError::InvalidLength),
+        }
+    }
+
+    fn get(&self, name: &str) -> Result<&'a Value, BytesError> {
+        self.0
+            .iter()
+            .find(|(k, _)| k.as_text() == Some(name))
+            .map(|(_, v)| v)
+            .ok_or(BytesError::InvalidLength)
+    }
+
+    fn bytes(&self, name: &str) -> Result<&'a [u8], BytesError> {
+        self.get(name)?
+            .as_bytes()
+            .map(Vec::as_slice)
+            .ok_or(BytesError::InvalidLength)
+    }
+
+    fn bytes32(&self, name: &str) -> Result<[u8; 32], BytesError> {
+        to_array::<32>(self.bytes(name)?)
+    }
+}
+
+fn to_array<const N: usize>(b: &[u8]) -> Result<[u8; N], BytesError> {
+    b.try_into().map_err(|_| BytesError::InvalidLength)
+}
@@ -0,0 +1,145 @@
+//! Selective disclosure of trace events.
+//!
+//! A `TraceDisclosureV0` reveals some leaves of an artifact's trace tree
+//! together with the sibling hashes needed to rebuild `trace_root` from them.
+//! An auditor can read the disclosed events (the final score and decision,
+//! say) and check them against the signed root without seeing the other
+//! events, the model or the input.
+
+use crate::bytes::{push_bytes, push_u16_le, push_u32_le, push_u8, BytesError, Reader};
+use crate::event_bin::TraceEventV0;
+use crate::hash::HashAlg;
+use crate::merkle::{multi_proof_with, verify_multi_proof_with};
+
+const TRACE_DISCLOSURE_MAGIC: &[u8; 8] = b"VFAITRD0";
+
+/// One disclosed trace event and its leaf index in the full trace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisclosedEvent {
+    pub index: u32,
+    pub event: TraceEventV0,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceDisclosureV0 {
+    /// Hash of the trace tree; SHA-256 except for `ProofArtifactV6`.
+    pub hash_alg: HashAlg,
+    /// Claimed length of the full trace. The root does not bind it: odd
+    /// levels duplicate their last node, so a 3-event trace also verifies
+    /// as 4 events whose last two are equal.
+    pub leaf_count: u32,
+    /// Ascending by `index`.
+    pub events: Vec<DisclosedEvent>,
+    /// `merkle::multi_proof` for the disclosed indices.
+    pub proof: Vec<[u8; 32]>,
+}
+
+impl TraceDisclosureV0 {
+    /// Discloses the events at `indices` (any order, duplicates ignored) of
+    /// the full `trace`. `None` if `indices` is empty or out of range.
+    pub fn from_trace(
+        hash_alg: HashAlg,
+        trace: &[TraceEventV0],
+        indices: &[usize],
+    ) -> Option<Self> {
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+        let leaf_count = u32::try_from(trace.len()).ok()?;
+        let leaves: Vec<Vec<u8>> = trace.iter().map(|e| e.encode_bin()).collect();
+        let proof = multi_proof_with(hash_alg, &leaves, &indices)?;
+        Some(Self {
+            hash_alg,
+            leaf_count,
+            events: indices
+                .iter()
+                .map(|&i| DisclosedEvent {
+                    index: i as u32,
+                    event: trace[i].clone(),
+                })
+                .collect(),
+            proof,
+        })
+    }
+
+    /// Whether the disclosed events belong to the trace with this root.
+    /// Only their contents are proven; `index` and `leaf_count` are not
+    /// (see `leaf_count`).
+    pub fn verify_root(&self, trace_root: [u8; 32]) -> bool {
+        let leaves: Vec<(usize, Vec<u8>)> = self
+            .events
+            .iter()
+            .map(|d| (d.index as usize, d.event.encode_bin()))
+            .collect();
+        let leaves: Vec<(usize, &[u8])> = leaves.iter().map(|(i, b)| (*i, b.as_slice())).collect();
+        verify_multi_proof_with(
+            self.hash_alg,
+            &leaves,
+            self.leaf_count as usize,
+            &self.proof,
+            trace_root,
+        )
+    }
+
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        push_bytes(&mut out, TRACE_DISCLOSURE_MAGIC);
+        push_u8(&mut out, self.hash_alg.id());
+        push_u32_le(&mut out, self.leaf_count);
+        push_u32_le(&mut out, self.events.len() as u32);
+        for disclosed in &self.events {
+            let event = disclosed.event.encode_bin();
+            push_u32_le(&mut out, disclosed.index);
+            push_u16_le(&mut out, event.len() as u16);
+            push_bytes(&mut out, &event);
+        }
+        push_u32_le(&mut out, self.proof.len() as u32);
+        for hash in &self.proof {
+            push_bytes(&mut out, hash);
+        }
+        out
+    }
+
+    /// Rejects unknown hash algorithms, events out of order or beyond
+    /// `leaf_count`, and trailing bytes.
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        if r.read_exact(8)? != TRACE_DISCLOSURE_MAGIC {
+            return Err(BytesError::InvalidMagic);
+        }
+        let hash_alg = HashAlg::from_id(r.read_u8()?).ok_or(BytesError::InvalidLength)?;
+        let leaf_count = r.read_u32_le()?;
+        let event_count = r.read_u32_le()? as usize;
+        // every event takes at least 7 bytes, which bounds the allocation
+        if event_count > r.remaining() / 7 {
+            return Err(BytesError::InvalidLength);
+        }
+        let mut events: Vec<DisclosedEvent> = Vec::with_capacity(event_count);
+        for _ in 0..event_count {
+            let index = r.read_u32_le()?;
+            if index >= leaf_count || events.last().is_some_and(|prev| prev.index >= index) {
+                return Err(BytesError::InvalidLength);
+            }
+            let len = r.read_u16_le()? as usize;
+            let event = TraceEventV0::decode_bin(r.read_exact(len)?)?;
+            events.push(DisclosedEvent { index, event });
+        }
+        let proof_count = r.read_u32_le()? as usize;
+        if proof_count.checked_mul(32) != Some(r.remaining()) {
+            return Err(BytesError::InvalidLength);
+        }
+        let proof = (0..proof_count)
+            .map(|_| {
+                let mut hash = [0u8; 32];
+                hash.copy_from_slice(r.read_exact(32)?);
+                Ok(hash)
+            })
+            .collect::<Result<Vec<_>, BytesError>>()?;
+        Ok(Self {
+            hash_alg,
+            leaf_count,
+            events,
+            proof,
+        })
+    }
+}
@@ -0,0 +1,47 @@
+//! Dead Simple Signing Envelope (DSSE v1) signatures.
+//!
+//! DSSE signs `PAE(payloadType, payload)` rather than the payload itself so
+//! the type cannot be swapped without breaking the signature. The envelope's
+//! JSON framing lives with the callers.
+
+use ed25519_dalek::Signer;
+use ed25519_dalek::Verifier;
+use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
+
+use crate::bytes::BytesError;
+
+/// `"DSSEv1" SP LEN(type) SP type SP LEN(body) SP body`, lengths in ASCII decimal.
+pub fn pae(payload_type: &str, payload: &[u8]) -> Vec<u8> {
+    let mut out = format!(
+        "DSSEv1 {} {} {} ",
+        payload_type.len(),
+        payload_type,
+        payload.len()
+    )
+    .into_bytes();
+    out.extend_from_slice(payload);
+    out
+}
+
+/// Returns `(public_key, signature)` over `PAE(payload_type, payload)`.
+pub fn sign_dsse(
+    signing_key_bytes: [u8; 32],
+    payload_type: &str,
+    payload: &[u8],
+) -> ([u8; 32], [u8; 64]) {
+    let sk = SigningKey::from_bytes(&signing_key_bytes);
+    let sig: Signature = sk.sign(&pae(payload_type, payload));
+    (VerifyingKey::from(&sk).to_bytes(), sig.to_bytes())
+}
+
+pub fn verify_dsse(
+    pubkey: [u8; 32],
+    payload_type: &str,
+    payload: &[u8],
+    signature: &[u8],
+) -> Result<(), BytesError> {
+    let vk = VerifyingKey::from_bytes(&pubkey).map_err(|_| BytesError::InvalidLength)?;
+    let sig = Signature::from_slice(signature).map_err(|_| BytesError::InvalidLength)?;
+    vk.verify(&pae(payload_type, payload), &sig)
+        .map_err(|_| BytesError::InvalidLength)
+}
@@ -1,4 +1,6 @@
-use crate::bytes::{push_bytes, push_f64_le, push_u32_le, push_u8, BytesError, Reader};
+use crate::bytes::{
+    push_bytes, push_f64_le, push_u16_le, push_u32_le, push_u8, BytesError, Reader,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ActivationKind {
@@ -31,9 +33,88 @@ pub enum TraceEventV0 {
     OpOutput {
         y: f64,
     },
+    /// Feature `index` of the model input, as a `PreprocessorV1` derived it
+    /// from the raw input.
+    OpFeature {
+        index: u32,
+        value: f64,
+    },
+    /// Digest of a layer's output vector, for `TraceLevel::Layer` traces.
+    OpLayer {
+        layer: u32,
+        digest: [u8; 32],
+    },
+    /// Running sum of linear op `op_id` after adding term `index`, for
+    /// `TraceLevel::Fine` traces.
+    OpMac {
+        op_id: u32,
+        index: u32,
+        acc: f64,
+    },
+}
+
+/// How much of a run the trace records. Each level is replayed as declared;
+/// artifacts before v9 are always `Op`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TraceLevel {
+    /// The `OpOutput` event only.
+    Summary,
+    /// One `OpLayer` digest per layer, then `OpOutput`.
+    Layer,
+    /// Every linear op and activation.
+    #[default]
+    Op,
+    /// `Op`, with each linear op preceded by an `OpMac` per term.
+    Fine,
+}
+
+impl TraceLevel {
+    pub const ALL: [TraceLevel; 4] = [
+        TraceLevel::Summary,
+        TraceLevel::Layer,
+        TraceLevel::Op,
+        TraceLevel::Fine,
+    ];
+
+    pub fn id(self) -> u8 {
+        match self {
+            TraceLevel::Summary => 1,
+            TraceLevel::Layer => 2,
+            TraceLevel::Op => 3,
+            TraceLevel::Fine => 4,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|level| level.id() == id)
+    }
+
+    /// Short name used by the CLI and in JSON output.
+    pub fn name(self) -> &'static str {
+        match self {
+            TraceLevel::Summary => "summary",
+            TraceLevel::Layer => "layer",
+            TraceLevel::Op => "op",
+            TraceLevel::Fine => "fine",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|level| level.name() == name)
+    }
 }
 
 impl TraceEventV0 {
+    /// The op this event belongs to, for the kinds that name one.
+    pub fn op_id(&self) -> Option<u32> {
+        match *self {
+            Self::OpLinear { op_id, .. }
+            | Self::OpActivation { op_id, .. }
+            | Self::OpMac { op_id, .. } => Some(op_id),
+            Self::OpOutput { .. } | Self::OpFeature { .. } | Self::OpLayer { .. } => None,
+        }
+    }
+
     pub fn encode_bin(&self) -> Vec<u8> {
         let mut out = Vec::new();
         match *self {
@@ -58,6 +139,22 @@ impl TraceEventV0 {
                 push_u8(&mut out, 0x03);
                 push_f64_le(&mut out, y);
             }
+            Self::OpFeature { index, value } => {
+                push_u8(&mut out, 0x04);
+                push_u32_le(&mut out, index);
+                push_f64_le(&mut out, value);
+            }
+            Self::OpLayer { layer, digest } => {
+                push_u8(&mut out, 0x05);
+                push_u32_le(&mut out, layer);
+                push_bytes(&mut out, &digest);
+            }
+            Self::OpMac { op_id, index, acc } => {
+                push_u8(&mut out, 0x06);
+                push_u32_le(&mut out, op_id);
+                push_u32_le(&mut out, index);
+                push_f64_le(&mut out, acc);
+            }
         }
         out
     }
@@ -87,6 +184,23 @@ impl TraceEventV0 {
                 let y = r.read_f64_le()?;
                 Self::OpOutput { y }
             }
+            0x04 => {
+                let index = r.read_u32_le()?;
+                let value = r.read_f64_le()?;
+                Self::OpFeature { index, value }
+            }
+            0x05 => {
+                let layer = r.read_u32_le()?;
+                let mut digest = [0u8; 32];
+                digest.copy_from_slice(r.read_exact(32)?);
+                Self::OpLayer { layer, digest }
+            }
+            0x06 => {
+                let op_id = r.read_u32_le()?;
+                let index = r.read_u32_le()?;
+                let acc = r.read_f64_le()?;
+                Self::OpMac { op_id, index, acc }
+            }
             _ => return Err(BytesError::InvalidLength),
         };
         if r.remaining() != 0 {
@@ -96,6 +210,48 @@ impl TraceEventV0 {
     }
 }
 
+pub(crate) const TRACE_MAGIC: &[u8; 8] = b"VFAITRC0";
+
+/// A full trace as a file: the magic, then each event as a u16 length and
+/// its v0 encoding, up to the end. There is no count, so the file can be
+/// written one event at a time.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TraceV0 {
+    pub events: Vec<TraceEventV0>,
+}
+
+impl TraceV0 {
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let mut out = encode_magic(TRACE_MAGIC);
+        for event in &self.events {
+            push_bytes(&mut out, &encode_trace_record(event));
+        }
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        if r.read_exact(8)? != TRACE_MAGIC {
+            return Err(BytesError::InvalidMagic);
+        }
+        let mut events = Vec::new();
+        while r.remaining() != 0 {
+            let len = r.read_u16_le()? as usize;
+            events.push(TraceEventV0::decode_bin(r.read_exact(len)?)?);
+        }
+        Ok(Self { events })
+    }
+}
+
+/// One event of a `TraceV0` file, length prefix included.
+pub fn encode_trace_record(event: &TraceEventV0) -> Vec<u8> {
+    let bytes = event.encode_bin();
+    let mut out = Vec::with_capacity(2 + bytes.len());
+    push_u16_le(&mut out, bytes.len() as u16);
+    push_bytes(&mut out, &bytes);
+    out
+}
+
 // Small helper for fixed-size magic writes (keeps unused warnings away)
 pub fn encode_magic(magic: &[u8; 8]) -> Vec<u8> {
     let mut out = Vec::with_capacity(8);
@@ -8,3 +8,58 @@ pub fn sha256(data: &[u8]) -> [u8; 32] {
     out32.copy_from_slice(&out);
     out32
 }
+
+/// Hash used for commitments and Merkle nodes. Every option has a 32-byte
+/// digest; artifacts before v6 always use SHA-256.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HashAlg {
+    #[default]
+    Sha256,
+    Blake3,
+    Sha3_256,
+}
+
+impl HashAlg {
+    pub const ALL: [HashAlg; 3] = [HashAlg::Sha256, HashAlg::Blake3, HashAlg::Sha3_256];
+
+    pub fn id(self) -> u8 {
+        match self {
+            HashAlg::Sha256 => 1,
+            HashAlg::Blake3 => 2,
+            HashAlg::Sha3_256 => 3,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|alg| alg.id() == id)
+    }
+
+    /// Short name used by the CLI and in JSON output.
+    pub fn name(self) -> &'static str {
+        match self {
+            HashAlg::Sha256 => "sha256",
+            HashAlg::Blake3 => "blake3",
+            HashAlg::Sha3_256 => "sha3-256",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|alg| alg.name() == name)
+    }
+
+    pub fn digest(self, data: &[u8]) -> [u8; 32] {
+        match self {
+            HashAlg::Sha256 => sha256(data),
+            HashAlg::Blake3 => *blake3::hash(data).as_bytes(),
+            HashAlg::Sha3_256 => sha3::Sha3_256::digest(data).into(),
+        }
+    }
+}
+
+/// `sha256(salt || data)`, the commitment used by `ProofArtifactV7`.
+pub fn salted_sha256(salt: &[u8; 32], data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update(data);
+    hasher.finalize().into()
+}
@@ -0,0 +1,134 @@
+//! Signed key-status lists.
+//!
+//! An authority lists the artifact signing keys it vouches for, the period
+//! each one may sign in and, once a key leaks, when it was revoked. Verifiers
+//! pin the authority key and load the list from a file.
+
+use crate::bytes::{push_bytes, push_u32_le, push_u64_le, BytesError, Reader};
+
+use ed25519_dalek::Signer;
+use ed25519_dalek::Verifier;
+use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
+
+const KEY_STATUS_MAGIC: &[u8; 8] = b"VFAIKSL0";
+const SIGN_PREFIX_KEY_STATUS: &[u8; 20] = b"VERIFAI\0KEYSTATUS\0V0";
+
+pub const KEY_STATUS_ENTRY_LEN: usize = 32 + 8 * 3;
+
+/// Status of one signing key. Times are unix nanos; absent times are encoded
+/// as 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyStatusEntry {
+    pub pubkey: [u8; 32],
+    pub valid_from: u64,
+    pub valid_until: Option<u64>,
+    pub revoked_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyStatusListV0 {
+    pub issued_at: u64, // unix nanos
+    pub entries: Vec<KeyStatusEntry>,
+    pub authority_pubkey: [u8; 32],
+    pub signature: [u8; 64],
+}
+
+impl KeyStatusListV0 {
+    pub fn message_to_sign(&self) -> Vec<u8> {
+        let mut out =
+            Vec::with_capacity(20 + 8 + 4 + KEY_STATUS_ENTRY_LEN * self.entries.len() + 32);
+        out.extend_from_slice(SIGN_PREFIX_KEY_STATUS);
+        push_entries(&mut out, self.issued_at, &self.entries);
+        out.extend_from_slice(&self.authority_pubkey);
+        out
+    }
+
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let mut out =
+            Vec::with_capacity(8 + 8 + 4 + KEY_STATUS_ENTRY_LEN * self.entries.len() + 32 + 64);
+        push_bytes(&mut out, KEY_STATUS_MAGIC);
+        push_entries(&mut out, self.issued_at, &self.entries);
+        push_bytes(&mut out, &self.authority_pubkey);
+        push_bytes(&mut out, &self.signature);
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        if r.read_exact(8)? != KEY_STATUS_MAGIC {
+            return Err(BytesError::InvalidMagic);
+        }
+        let issued_at = r.read_u64_le()?;
+        let count = r.read_u32_le()? as usize;
+        if r.remaining() != KEY_STATUS_ENTRY_LEN * count + 32 + 64 {
+            return Err(BytesError::InvalidLength);
+        }
+        let mut entries: Vec<KeyStatusEntry> = Vec::with_capacity(count);
+        for _ in 0..count {
+            let pubkey = read_32(&mut r)?;
+            if entries.iter().any(|e| e.pubkey == pubkey) {
+                return Err(BytesError::InvalidLength);
+            }
+            entries.push(KeyStatusEntry {
+                pubkey,
+                valid_from: r.read_u64_le()?,
+                valid_until: read_opt_u64(&mut r)?,
+                revoked_at: read_opt_u64(&mut r)?,
+            });
+        }
+        let authority_pubkey = read_32(&mut r)?;
+        let mut signature = [0u8; 64];
+        signature.copy_from_slice(r.read_exact(64)?);
+        Ok(Self {
+            issued_at,
+            entries,
+            authority_pubkey,
+            signature,
+        })
+    }
+
+    pub fn entry(&self, pubkey: &[u8; 32]) -> Option<&KeyStatusEntry> {
+        self.entries.iter().find(|e| &e.pubkey == pubkey)
+    }
+
+    pub fn sign_detached(&mut self, signing_key_bytes: [u8; 32]) -> Result<(), BytesError> {
+        let sk = SigningKey::from_bytes(&signing_key_bytes);
+        self.authority_pubkey = VerifyingKey::from(&sk).to_bytes();
+        let sig: Signature = sk.sign(&self.message_to_sign());
+        self.signature = sig.to_bytes();
+        Ok(())
+    }
+
+    pub fn verify_signature(&self) -> Result<(), BytesError> {
+        let vk = VerifyingKey::from_bytes(&self.authority_pubkey)
+            .map_err(|_| BytesError::InvalidLength)?;
+        let sig = Signature::from_bytes(&self.signature);
+        vk.verify(&self.message_to_sign(), &sig)
+            .map_err(|_| BytesError::InvalidLength)
+    }
+}
+
+fn push_entries(out: &mut Vec<u8>, issued_at: u64, entries: &[KeyStatusEntry]) {
+    push_u64_le(out, issued_at);
+    push_u32_le(out, entries.len() as u32);
+    for e in entries {
+        push_bytes(out, &e.pubkey);
+        push_u64_le(out, e.valid_from);
+        push_u64_le(out, e.valid_until.unwrap_or(0));
+        push_u64_le(out, e.revoked_at.unwrap_or(0));
+    }
+}
+
+fn read_opt_u64(r: &mut Reader<'_>) -> Result<Option<u64>, BytesError> {
+    Ok(match r.read_u64_le()? {
+        0 => None,
+        t => Some(t),
+    })
+}
+
+fn read_32(r: &mut Reader<'_>) -> Result<[u8; 32], BytesError> {
+    let b = r.read_exact(32)?;
+    let mut out = [0u8; 32];
+    out.copy_from_slice(b);
+    Ok(out)
+}
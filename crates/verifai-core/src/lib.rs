@@ -1,12 +1,49 @@
 pub mod artifact_bin;
 pub mod attestation;
+pub mod batch_bin;
+pub mod batch_verify;
 pub mod bytes;
+pub mod cose;
+pub mod disclosure;
+pub mod dsse;
 pub mod event_bin;
 pub mod hash;
+pub mod key_status;
 pub mod merkle;
 pub mod model_bin;
+pub mod pipeline;
+pub mod preprocess;
+pub mod salts;
+pub mod sig_alg;
+pub mod signer;
+pub mod signer_bundle;
+pub mod timestamp;
+pub mod trace_sink;
+pub mod translog;
 
-pub use artifact_bin::{ProofArtifactV0, ProofArtifactV1, PROOF_ARTIFACT_V0_LEN};
+pub use artifact_bin::{
+    BatchProofArtifactV2, CoSignature, ProofArtifactV0, ProofArtifactV1, ProofArtifactV10,
+    ProofArtifactV3, ProofArtifactV4, ProofArtifactV5, ProofArtifactV6, ProofArtifactV7,
+    ProofArtifactV8, ProofArtifactV9, PROOF_ARTIFACT_V0_LEN, PROOF_ARTIFACT_V10_LEN,
+};
 pub use attestation::AttestationBundle;
-pub use event_bin::{ActivationKind, TraceEventV0};
-pub use model_bin::{InputV0, LogisticModelV0, OutputV0};
+pub use batch_bin::BatchRowProofV2;
+pub use batch_verify::{verify_batch, BatchVerify, Ed25519Check};
+pub use disclosure::{DisclosedEvent, TraceDisclosureV0};
+pub use event_bin::{ActivationKind, TraceEventV0, TraceLevel, TraceV0};
+pub use hash::HashAlg;
+pub use key_status::{KeyStatusEntry, KeyStatusListV0};
+pub use merkle::MerkleBuilder;
+pub use model_bin::{
+    InputBatchV1, InputV0, LogisticModelV0, OutputBatchV1, OutputV0, QuantizedInputV0,
+    QuantizedLogisticModelV0, QuantizedOutputV0,
+};
+pub use pipeline::{PipelineProofV0, PipelineStageV0, StageLink};
+pub use preprocess::{FeatureSpec, PreprocessorV1, RawInputV1, RawValue};
+pub use salts::CommitmentSaltsV0;
+pub use sig_alg::{AlgSigningKey, SigAlg};
+pub use signer::{Signer, SignerError};
+pub use signer_bundle::SignerBundleV0;
+pub use timestamp::TimestampTokenV0;
+pub use trace_sink::{EventJson, JsonlTraceWriter, Tee, TraceFileWriter, TraceSink};
+pub use translog::{LogConsistencyProofV0, LogInclusionProofV0, SignedTreeHeadV0};
@@ -1,30 +1,47 @@
-use crate::hash::sha256;
+use crate::hash::HashAlg;
 
 pub fn leaf_hash(event_bytes: &[u8]) -> [u8; 32] {
+    leaf_hash_with(HashAlg::Sha256, event_bytes)
+}
+
+pub fn node_hash(left: [u8; 32], right: [u8; 32]) -> [u8; 32] {
+    node_hash_with(HashAlg::Sha256, left, right)
+}
+
+pub fn empty_root() -> [u8; 32] {
+    empty_root_with(HashAlg::Sha256)
+}
+
+pub fn trace_root_from_event_bytes(events: &[Vec<u8>]) -> [u8; 32] {
+    trace_root_from_event_bytes_with(HashAlg::Sha256, events)
+}
+
+pub fn leaf_hash_with(alg: HashAlg, event_bytes: &[u8]) -> [u8; 32] {
     let mut buf = Vec::with_capacity(1 + event_bytes.len());
     buf.push(0x00);
     buf.extend_from_slice(event_bytes);
-    sha256(&buf)
+    alg.digest(&buf)
 }
 
-pub fn node_hash(left: [u8; 32], right: [u8; 32]) -> [u8; 32] {
+pub fn node_hash_with(alg: HashAlg, left: [u8; 32], right: [u8; 32]) -> [u8; 32] {
     let mut buf = Vec::with_capacity(1 + 32 + 32);
     buf.push(0x01);
     buf.extend_from_slice(&left);
     buf.extend_from_slice(&right);
-    sha256(&buf)
+    alg.digest(&buf)
 }
 
-pub fn empty_root() -> [u8; 32] {
-    sha256(&[0x02])
+pub fn empty_root_with(alg: HashAlg) -> [u8; 32] {
+    alg.digest(&[0x02])
 }
 
-pub fn trace_root_from_event_bytes(events: &[Vec<u8>]) -> [u8; 32] {
+/// `trace_root_from_event_bytes` with every leaf and node hashed by `alg`.
+pub fn trace_root_from_event_bytes_with(alg: HashAlg, events: &[Vec<u8>]) -> [u8; 32] {
     if events.is_empty() {
-        return empty_root();
+        return empty_root_with(alg);
     }
 
-    let mut level: Vec<[u8; 32]> = events.iter().map(|e| leaf_hash(e)).collect();
+    let mut level: Vec<[u8; 32]> = events.iter().map(|e| leaf_hash_with(alg, e)).collect();
 
     while level.len() > 1 {
         if level.len() % 2 == 1 {
@@ -33,10 +50,286 @@ pub fn trace_root_from_event_bytes(events: &[Vec<u8>]) -> [u8; 32] {
         }
         let mut next = Vec::with_capacity(level.len() / 2);
         for pair in level.chunks_exact(2) {
-            next.push(node_hash(pair[0], pair[1]));
+            next.push(node_hash_with(alg, pair[0], pair[1]));
         }
         level = next;
     }
 
     level[0]
 }
+
+/// Builds the root of `trace_root_from_event_bytes_with` one leaf at a time,
+/// keeping only one pending node per tree level.
+#[derive(Debug, Clone)]
+pub struct MerkleBuilder {
+    alg: HashAlg,
+    /// `pending[h]` is a complete subtree of `2^h` leaves still waiting for
+    /// its right sibling.
+    pending: Vec<Option<[u8; 32]>>,
+    len: u64,
+}
+
+impl Default for MerkleBuilder {
+    fn default() -> Self {
+        Self::with_alg(HashAlg::Sha256)
+    }
+}
+
+impl MerkleBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_alg(alg: HashAlg) -> Self {
+        Self {
+            alg,
+            pending: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Adds the leaf for `leaf_bytes` (an encoded event).
+    pub fn push(&mut self, leaf_bytes: &[u8]) {
+        let mut carry = leaf_hash_with(self.alg, leaf_bytes);
+        for slot in self.pending.iter_mut() {
+            match slot.take() {
+                Some(left) => carry = node_hash_with(self.alg, left, carry),
+                None => {
+                    *slot = Some(carry);
+                    self.len += 1;
+                    return;
+                }
+            }
+        }
+        self.pending.push(Some(carry));
+        self.len += 1;
+    }
+
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The root, duplicating the last node of every odd level like the
+    /// batch function.
+    pub fn finalize(self) -> [u8; 32] {
+        let mut carry: Option<[u8; 32]> = None;
+        for (height, slot) in self.pending.iter().enumerate() {
+            let more_above = self.pending[height + 1..].iter().any(Option::is_some);
+            carry = match (*slot, carry) {
+                (Some(left), Some(right)) => Some(node_hash_with(self.alg, left, right)),
+                (Some(last), None) | (None, Some(last)) => {
+                    if !more_above {
+                        return last;
+                    }
+                    Some(node_hash_with(self.alg, last, last))
+                }
+                (None, None) => None,
+            };
+        }
+        carry.unwrap_or_else(|| empty_root_with(self.alg))
+    }
+}
+
+/// Number of node levels above the leaves for a tree of `leaf_count` leaves.
+pub fn tree_depth(leaf_count: usize) -> usize {
+    let mut depth = 0;
+    let mut width = leaf_count;
+    while width > 1 {
+        width = width.div_ceil(2);
+        depth += 1;
+    }
+    depth
+}
+
+/// Sibling hashes from the leaf at `index` up to the root, using the same
+/// odd-level duplication rule as `trace_root_from_event_bytes`.
+pub fn inclusion_proof(leaves: &[Vec<u8>], index: usize) -> Option<Vec<[u8; 32]>> {
+    inclusion_proof_with(HashAlg::Sha256, leaves, index)
+}
+
+pub fn inclusion_proof_with(
+    alg: HashAlg,
+    leaves: &[Vec<u8>],
+    index: usize,
+) -> Option<Vec<[u8; 32]>> {
+    if index >= leaves.len() {
+        return None;
+    }
+
+    let mut level: Vec<[u8; 32]> = leaves.iter().map(|e| leaf_hash_with(alg, e)).collect();
+    let mut idx = index;
+    let mut path = Vec::with_capacity(tree_depth(leaves.len()));
+
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            let last = *level.last().expect("non-empty");
+            level.push(last);
+        }
+        path.push(level[idx ^ 1]);
+        let mut next = Vec::with_capacity(level.len() / 2);
+        for pair in level.chunks_exact(2) {
+            next.push(node_hash_with(alg, pair[0], pair[1]));
+        }
+        level = next;
+        idx /= 2;
+    }
+
+    Some(path)
+}
+
+/// Checks that `leaf_bytes` sits at `index` in a tree of `leaf_count` leaves
+/// with the given `root`.
+pub fn verify_inclusion(
+    leaf_bytes: &[u8],
+    index: usize,
+    leaf_count: usize,
+    proof: &[[u8; 32]],
+    root: [u8; 32],
+) -> bool {
+    verify_inclusion_with(HashAlg::Sha256, leaf_bytes, index, leaf_count, proof, root)
+}
+
+pub fn verify_inclusion_with(
+    alg: HashAlg,
+    leaf_bytes: &[u8],
+    index: usize,
+    leaf_count: usize,
+    proof: &[[u8; 32]],
+    root: [u8; 32],
+) -> bool {
+    if index >= leaf_count || proof.len() != tree_depth(leaf_count) {
+        return false;
+    }
+
+    let mut acc = leaf_hash_with(alg, leaf_bytes);
+    let mut idx = index;
+    for sibling in proof {
+        acc = if idx % 2 == 1 {
+            node_hash_with(alg, *sibling, acc)
+        } else {
+            node_hash_with(alg, acc, *sibling)
+        };
+        idx /= 2;
+    }
+
+    acc == root
+}
+
+/// Sibling hashes needed to rebuild the root from the leaves at `indices`
+/// (ascending, distinct), level by level from the leaves up and left to right
+/// within a level. Siblings that are themselves disclosed, or that are the
+/// duplicate of an odd level's last node, are left out.
+pub fn multi_proof(leaves: &[Vec<u8>], indices: &[usize]) -> Option<Vec<[u8; 32]>> {
+    multi_proof_with(HashAlg::Sha256, leaves, indices)
+}
+
+pub fn multi_proof_with(
+    alg: HashAlg,
+    leaves: &[Vec<u8>],
+    indices: &[usize],
+) -> Option<Vec<[u8; 32]>> {
+    if indices.is_empty()
+        || indices.windows(2).any(|w| w[0] >= w[1])
+        || *indices.last()? >= leaves.len()
+    {
+        return None;
+    }
+
+    let mut level: Vec<[u8; 32]> = leaves.iter().map(|e| leaf_hash_with(alg, e)).collect();
+    let mut known = indices.to_vec();
+    let mut proof = Vec::new();
+
+    while level.len() > 1 {
+        let len = level.len();
+        let mut i = 0;
+        while i < known.len() {
+            let idx = known[i];
+            // sorted, so a disclosed sibling can only be the next entry
+            if known.get(i + 1) == Some(&(idx ^ 1)) {
+                i += 1;
+            } else if idx ^ 1 < len {
+                proof.push(level[idx ^ 1]);
+            }
+            i += 1;
+        }
+
+        if len % 2 == 1 {
+            level.push(level[len - 1]);
+        }
+        level = level
+            .chunks_exact(2)
+            .map(|pair| node_hash_with(alg, pair[0], pair[1]))
+            .collect();
+        known.dedup_by_key(|idx| *idx / 2);
+        known.iter_mut().for_each(|idx| *idx /= 2);
+    }
+
+    Some(proof)
+}
+
+/// Checks that `leaves` (index and bytes, ascending by index) sit in a tree
+/// of `leaf_count` leaves with the given `root`, using a `multi_proof`.
+pub fn verify_multi_proof(
+    leaves: &[(usize, &[u8])],
+    leaf_count: usize,
+    proof: &[[u8; 32]],
+    root: [u8; 32],
+) -> bool {
+    verify_multi_proof_with(HashAlg::Sha256, leaves, leaf_count, proof, root)
+}
+
+pub fn verify_multi_proof_with(
+    alg: HashAlg,
+    leaves: &[(usize, &[u8])],
+    leaf_count: usize,
+    proof: &[[u8; 32]],
+    root: [u8; 32],
+) -> bool {
+    if leaves.is_empty()
+        || leaves.windows(2).any(|w| w[0].0 >= w[1].0)
+        || leaves[leaves.len() - 1].0 >= leaf_count
+    {
+        return false;
+    }
+
+    let mut nodes: Vec<(usize, [u8; 32])> = leaves
+        .iter()
+        .map(|(idx, bytes)| (*idx, leaf_hash_with(alg, bytes)))
+        .collect();
+    let mut proof = proof.iter();
+    let mut len = leaf_count;
+
+    while len > 1 {
+        let mut next = Vec::with_capacity(nodes.len());
+        let mut i = 0;
+        while i < nodes.len() {
+            let (idx, hash) = nodes[i];
+            let (left, right) = if idx % 2 == 1 {
+                match proof.next() {
+                    Some(sibling) => (*sibling, hash),
+                    None => return false,
+                }
+            } else if nodes.get(i + 1).map(|n| n.0) == Some(idx + 1) {
+                i += 1;
+                (hash, nodes[i].1)
+            } else if idx + 1 == len {
+                (hash, hash)
+            } else {
+                match proof.next() {
+                    Some(sibling) => (hash, *sibling),
+                    None => return false,
+                }
+            };
+            next.push((idx / 2, node_hash_with(alg, left, right)));
+            i += 1;
+        }
+        nodes = next;
+        len = len.div_ceil(2);
+    }
+
+    proof.next().is_none() && nodes == [(0, root)]
+}
@@ -1,9 +1,18 @@
-use crate::bytes::{push_bytes, push_f64_le, push_u32_le, BytesError, Reader};
+use crate::bytes::{push_bytes, push_f64_le, push_u32_le, push_u64_le, BytesError, Reader};
 
 const MODEL_MAGIC: &[u8; 8] = b"VFAIMDL0";
 const INPUT_MAGIC: &[u8; 8] = b"VFAIINP0";
 const OUTPUT_MAGIC: &[u8; 8] = b"VFAIOUT0";
 const MLP_MAGIC: &[u8; 8] = b"VFAIMLP1";
+const INPUT_BATCH_MAGIC: &[u8; 8] = b"VFAIINB1";
+const OUTPUT_BATCH_MAGIC: &[u8; 8] = b"VFAIOUB1";
+const QUANTIZED_MODEL_MAGIC: &[u8; 8] = b"VFAIQMD0";
+const QUANTIZED_INPUT_MAGIC: &[u8; 8] = b"VFAIQIN0";
+const QUANTIZED_OUTPUT_MAGIC: &[u8; 8] = b"VFAIQOU0";
+
+/// Fractional bits of quantized weights and inputs. Biases and logits are
+/// products of the two, so they carry twice as many.
+pub const QUANTIZED_FRAC_BITS: u32 = 16;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct LogisticModelV0 {
@@ -31,6 +40,40 @@ pub struct MlpModelV1 {
     pub b2: f64,
 }
 
+/// Rows proven together under one batch artifact. Each row hashes into the
+/// batch Merkle tree as its canonical `InputV0` encoding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputBatchV1 {
+    pub rows: Vec<InputV0>,
+}
+
+/// Outputs for an `InputBatchV1`, one per row, in row order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputBatchV1 {
+    pub rows: Vec<OutputV0>,
+}
+
+/// `LogisticModelV0` in fixed point: `weights` scaled by 2^16 and `bias` by
+/// 2^32, so the logit needs no rescaling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuantizedLogisticModelV0 {
+    pub weights: Vec<i32>,
+    pub bias: i64,
+}
+
+/// `InputV0` in fixed point, scaled by 2^16.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuantizedInputV0 {
+    pub x: Vec<i32>,
+}
+
+/// The fixed-point logit `bias + Σ weights[i] * x[i]`, scaled by 2^32. The
+/// score is its sigmoid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuantizedOutputV0 {
+    pub logit: i64,
+}
+
 impl LogisticModelV0 {
     pub fn encode_bin(&self) -> Vec<u8> {
         let mut out = Vec::with_capacity(12 + self.weights.len() * 8 + 8);
@@ -173,3 +216,202 @@ impl MlpModelV1 {
         })
     }
 }
+
+impl InputBatchV1 {
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        push_bytes(&mut out, INPUT_BATCH_MAGIC);
+        push_u32_le(&mut out, self.rows.len() as u32);
+        for row in &self.rows {
+            push_u32_le(&mut out, row.x.len() as u32);
+            for &v in &row.x {
+                push_f64_le(&mut out, v);
+            }
+        }
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        let magic = r.read_exact(8)?;
+        if magic != INPUT_BATCH_MAGIC {
+            return Err(BytesError::InvalidMagic);
+        }
+        let count = r.read_u32_le()? as usize;
+        let mut rows = Vec::with_capacity(count.min(r.remaining() / 4));
+        for _ in 0..count {
+            let n = r.read_u32_le()? as usize;
+            let mut x = Vec::with_capacity(n.min(r.remaining() / 8));
+            for _ in 0..n {
+                x.push(r.read_f64_le()?);
+            }
+            rows.push(InputV0 { x });
+        }
+        if r.remaining() != 0 {
+            return Err(BytesError::InvalidLength);
+        }
+        Ok(Self { rows })
+    }
+}
+
+impl OutputBatchV1 {
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(12 + self.rows.len() * 8);
+        push_bytes(&mut out, OUTPUT_BATCH_MAGIC);
+        push_u32_le(&mut out, self.rows.len() as u32);
+        for row in &self.rows {
+            push_f64_le(&mut out, row.y);
+        }
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        let magic = r.read_exact(8)?;
+        if magic != OUTPUT_BATCH_MAGIC {
+            return Err(BytesError::InvalidMagic);
+        }
+        let count = r.read_u32_le()? as usize;
+        let mut rows = Vec::with_capacity(count.min(r.remaining() / 8));
+        for _ in 0..count {
+            rows.push(OutputV0 {
+                y: r.read_f64_le()?,
+            });
+        }
+        if r.remaining() != 0 {
+            return Err(BytesError::InvalidLength);
+        }
+        Ok(Self { rows })
+    }
+}
+
+/// `v * 2^frac_bits` rounded to the nearest integer, if it fits in `T`.
+fn to_fixed<T: TryFrom<i64>>(v: f64, frac_bits: u32) -> Option<T> {
+    let scaled = (v * 2f64.powi(frac_bits as i32)).round();
+    // i64::MAX is not representable as f64; 2^63 is the first value out
+    if !scaled.is_finite() || scaled.abs() >= 2f64.powi(63) {
+        return None;
+    }
+    T::try_from(scaled as i64).ok()
+}
+
+impl QuantizedLogisticModelV0 {
+    /// Rounds `model` to fixed point. `None` if a weight is not finite or
+    /// outside ±2^15, or the bias outside ±2^31.
+    pub fn quantize(model: &LogisticModelV0) -> Option<Self> {
+        let weights = model
+            .weights
+            .iter()
+            .map(|&w| to_fixed(w, QUANTIZED_FRAC_BITS))
+            .collect::<Option<_>>()?;
+        let bias = to_fixed(model.bias, 2 * QUANTIZED_FRAC_BITS)?;
+        Some(Self { weights, bias })
+    }
+
+    /// The fixed-point logit for `input`. `None` if the dimensions differ or
+    /// the logit overflows an i64.
+    pub fn logit(&self, input: &QuantizedInputV0) -> Option<i64> {
+        if input.x.len() != self.weights.len() {
+            return None;
+        }
+        let sum = self
+            .weights
+            .iter()
+            .zip(&input.x)
+            .fold(i128::from(self.bias), |acc, (&w, &x)| {
+                acc + i128::from(w) * i128::from(x)
+            });
+        i64::try_from(sum).ok()
+    }
+
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(12 + self.weights.len() * 4 + 8);
+        push_bytes(&mut out, QUANTIZED_MODEL_MAGIC);
+        push_u32_le(&mut out, self.weights.len() as u32);
+        for &w in &self.weights {
+            push_u32_le(&mut out, w as u32);
+        }
+        push_u64_le(&mut out, self.bias as u64);
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        let magic = r.read_exact(8)?;
+        if magic != QUANTIZED_MODEL_MAGIC {
+            return Err(BytesError::InvalidMagic);
+        }
+        let n = r.read_u32_le()? as usize;
+        let mut weights = Vec::with_capacity(n.min(r.remaining() / 4));
+        for _ in 0..n {
+            weights.push(r.read_u32_le()? as i32);
+        }
+        let bias = r.read_u64_le()? as i64;
+        if r.remaining() != 0 {
+            return Err(BytesError::InvalidLength);
+        }
+        Ok(Self { weights, bias })
+    }
+}
+
+impl QuantizedInputV0 {
+    /// Rounds `input` to fixed point. `None` if a value is not finite or
+    /// outside ±2^15.
+    pub fn quantize(input: &InputV0) -> Option<Self> {
+        let x = input
+            .x
+            .iter()
+            .map(|&v| to_fixed(v, QUANTIZED_FRAC_BITS))
+            .collect::<Option<_>>()?;
+        Some(Self { x })
+    }
+
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(12 + self.x.len() * 4);
+        push_bytes(&mut out, QUANTIZED_INPUT_MAGIC);
+        push_u32_le(&mut out, self.x.len() as u32);
+        for &v in &self.x {
+            push_u32_le(&mut out, v as u32);
+        }
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        let magic = r.read_exact(8)?;
+        if magic != QUANTIZED_INPUT_MAGIC {
+            return Err(BytesError::InvalidMagic);
+        }
+        let n = r.read_u32_le()? as usize;
+        let mut x = Vec::with_capacity(n.min(r.remaining() / 4));
+        for _ in 0..n {
+            x.push(r.read_u32_le()? as i32);
+        }
+        if r.remaining() != 0 {
+            return Err(BytesError::InvalidLength);
+        }
+        Ok(Self { x })
+    }
+}
+
+impl QuantizedOutputV0 {
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(16);
+        push_bytes(&mut out, QUANTIZED_OUTPUT_MAGIC);
+        push_u64_le(&mut out, self.logit as u64);
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        let magic = r.read_exact(8)?;
+        if magic != QUANTIZED_OUTPUT_MAGIC {
+            return Err(BytesError::InvalidMagic);
+        }
+        let logit = r.read_u64_le()? as i64;
+        if r.remaining() != 0 {
+            return Err(BytesError::InvalidLength);
+        }
+        Ok(Self { logit })
+    }
+}
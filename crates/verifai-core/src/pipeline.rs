@@ -0,0 +1,160 @@
+//! Signed pipelines of inference artifacts.
+//!
+//! A `PipelineProofV0` lists one artifact per stage, in order, together
+//! with each stage's output. Every stage after the first declares how the
+//! previous output became its input, which binds its `input_hash` to the
+//! previous stage's `output_hash`. The pipeline operator signs the whole
+//! chain.
+
+use ed25519_dalek::Verifier;
+use ed25519_dalek::{Signature, VerifyingKey};
+
+use crate::bytes::{push_bytes, push_u16_le, push_u32_le, push_u8, BytesError, Reader};
+use crate::model_bin::{InputV0, OutputV0};
+use crate::signer::{ed25519_public_key, sign_checked, Signer, SignerError};
+
+const PIPELINE_MAGIC: &[u8; 8] = b"VFAIPPL0";
+const SIGN_PREFIX_PIPELINE: &[u8; 19] = b"VERIFAI\0PIPELINE\0V0";
+
+/// How a stage's input is derived from the previous stage's output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageLink {
+    /// The previous score is the only feature: `InputV0 { x: vec![y] }`.
+    OutputAsInput = 1,
+}
+
+impl StageLink {
+    pub fn id(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(Self::OutputAsInput),
+            _ => None,
+        }
+    }
+
+    /// `input.bin` of the next stage, given `output.bin` of this one.
+    pub fn apply(self, output_bin: &[u8]) -> Result<Vec<u8>, BytesError> {
+        let output = OutputV0::decode_bin(output_bin)?;
+        match self {
+            Self::OutputAsInput => Ok(InputV0 { x: vec![output.y] }.encode_bin()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineStageV0 {
+    /// `None` for the first stage only.
+    pub link: Option<StageLink>,
+    /// Native artifact encoding; any single-input version.
+    pub artifact: Vec<u8>,
+    pub output: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineProofV0 {
+    pub stages: Vec<PipelineStageV0>,
+    pub sig_pubkey: [u8; 32],
+    pub signature: [u8; 64],
+}
+
+impl PipelineProofV0 {
+    /// Fails like `encode_bin`.
+    pub fn message_to_sign(&self) -> Result<Vec<u8>, BytesError> {
+        let mut out = Vec::new();
+        out.extend_from_slice(SIGN_PREFIX_PIPELINE);
+        push_stages(&mut out, &self.stages)?;
+        out.extend_from_slice(&self.sig_pubkey);
+        Ok(out)
+    }
+
+    /// Fails if there are more than `u16::MAX` stages, or a stage's output
+    /// or artifact is too long for its length field.
+    pub fn encode_bin(&self) -> Result<Vec<u8>, BytesError> {
+        let mut out = Vec::new();
+        push_bytes(&mut out, PIPELINE_MAGIC);
+        push_stages(&mut out, &self.stages)?;
+        push_bytes(&mut out, &self.sig_pubkey);
+        push_bytes(&mut out, &self.signature);
+        Ok(out)
+    }
+
+    /// Rejects empty pipelines, a link on the first stage, a missing or
+    /// unknown link on any later one, and trailing bytes.
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        if r.read_exact(8)? != PIPELINE_MAGIC {
+            return Err(BytesError::InvalidMagic);
+        }
+        let count = r.read_u16_le()? as usize;
+        if count == 0 {
+            return Err(BytesError::InvalidLength);
+        }
+        let mut stages = Vec::with_capacity(count);
+        for i in 0..count {
+            let link = match (i, r.read_u8()?) {
+                (0, 0) => None,
+                (0, _) | (_, 0) => return Err(BytesError::InvalidLength),
+                (_, id) => Some(StageLink::from_id(id).ok_or(BytesError::InvalidLength)?),
+            };
+            let artifact_len = r.read_u32_le()? as usize;
+            let artifact = r.read_exact(artifact_len)?.to_vec();
+            let output_len = r.read_u16_le()? as usize;
+            let output = r.read_exact(output_len)?.to_vec();
+            stages.push(PipelineStageV0 {
+                link,
+                artifact,
+                output,
+            });
+        }
+        if r.remaining() != 32 + 64 {
+            return Err(BytesError::InvalidLength);
+        }
+        let mut sig_pubkey = [0u8; 32];
+        sig_pubkey.copy_from_slice(r.read_exact(32)?);
+        let mut signature = [0u8; 64];
+        signature.copy_from_slice(r.read_exact(64)?);
+        Ok(Self {
+            stages,
+            sig_pubkey,
+            signature,
+        })
+    }
+
+    pub fn sign_detached(&mut self, signing_key_bytes: [u8; 32]) -> Result<(), BytesError> {
+        self.sign_with(&signing_key_bytes)
+            .map_err(|_| BytesError::InvalidLength)
+    }
+
+    pub fn sign_with<S: Signer + ?Sized>(&mut self, signer: &S) -> Result<(), SignerError> {
+        self.sig_pubkey = ed25519_public_key(signer)?;
+        let msg = self
+            .message_to_sign()
+            .map_err(|_| SignerError("pipeline is too large to encode".into()))?;
+        self.signature = sign_checked(signer, &msg)?;
+        Ok(())
+    }
+
+    pub fn verify_signature(&self) -> Result<(), BytesError> {
+        let vk =
+            VerifyingKey::from_bytes(&self.sig_pubkey).map_err(|_| BytesError::InvalidLength)?;
+        let sig = Signature::from_bytes(&self.signature);
+        vk.verify(&self.message_to_sign()?, &sig)
+            .map_err(|_| BytesError::InvalidLength)
+    }
+}
+
+fn push_stages(out: &mut Vec<u8>, stages: &[PipelineStageV0]) -> Result<(), BytesError> {
+    let too_long = |_| BytesError::InvalidLength;
+    push_u16_le(out, u16::try_from(stages.len()).map_err(too_long)?);
+    for stage in stages {
+        push_u8(out, stage.link.map_or(0, StageLink::id));
+        push_u32_le(out, u32::try_from(stage.artifact.len()).map_err(too_long)?);
+        push_bytes(out, &stage.artifact);
+        push_u16_le(out, u16::try_from(stage.output.len()).map_err(too_long)?);
+        push_bytes(out, &stage.output);
+    }
+    Ok(())
+}
@@ -0,0 +1,273 @@
+//! Feature preprocessing specs and raw inputs.
+//!
+//! A `PreprocessorV1` turns a `RawInputV1` (numbers, categories and missing
+//! values) into the `InputV0` a model reads: imputation, clipping and
+//! standardization for numeric fields, one-hot encoding for categorical
+//! ones. `ProofArtifactV8` commits to the spec and the raw input, so a proof
+//! covers the whole path from raw values to the score.
+
+use crate::bytes::{
+    push_bytes, push_f64_le, push_u16_le, push_u32_le, push_u8, BytesError, Reader,
+};
+
+const PREPROCESSOR_MAGIC: &[u8; 8] = b"VFAIPRE1";
+const RAW_INPUT_MAGIC: &[u8; 8] = b"VFAIRAW1";
+
+/// Encodes an absent categorical default.
+const NO_DEFAULT: u32 = u32::MAX;
+
+/// How one raw field becomes model features.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeatureSpec {
+    /// One feature: the raw number (`default` if missing or NaN), clamped to
+    /// `clip` if set, then `(value - mean) / std`.
+    Numeric {
+        source: u32,
+        default: f64,
+        clip: Option<(f64, f64)>,
+        mean: f64,
+        std: f64,
+    },
+    /// One feature per category, 1.0 for the raw category and 0.0 for the
+    /// rest. Missing or unknown categories take category `default`, or all
+    /// zeros without one.
+    Categorical {
+        source: u32,
+        categories: Vec<String>,
+        default: Option<u32>,
+    },
+}
+
+impl FeatureSpec {
+    /// Raw field this spec reads.
+    pub fn source(&self) -> u32 {
+        match *self {
+            Self::Numeric { source, .. } | Self::Categorical { source, .. } => source,
+        }
+    }
+
+    /// Number of model features it produces.
+    pub fn width(&self) -> usize {
+        match self {
+            Self::Numeric { .. } => 1,
+            Self::Categorical { categories, .. } => categories.len(),
+        }
+    }
+}
+
+/// A canonical preprocessing spec. Features are emitted in `features` order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreprocessorV1 {
+    /// Fields every raw input must have.
+    pub raw_len: u32,
+    pub features: Vec<FeatureSpec>,
+}
+
+impl PreprocessorV1 {
+    /// Length of the `InputV0` it produces.
+    pub fn output_len(&self) -> usize {
+        self.features.iter().map(FeatureSpec::width).sum()
+    }
+
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        push_bytes(&mut out, PREPROCESSOR_MAGIC);
+        push_u32_le(&mut out, self.raw_len);
+        push_u32_le(&mut out, self.features.len() as u32);
+        for feature in &self.features {
+            match feature {
+                FeatureSpec::Numeric {
+                    source,
+                    default,
+                    clip,
+                    mean,
+                    std,
+                } => {
+                    push_u8(&mut out, 1);
+                    push_u32_le(&mut out, *source);
+                    push_f64_le(&mut out, *default);
+                    let (has_clip, (lo, hi)) = match clip {
+                        Some(bounds) => (1, *bounds),
+                        None => (0, (0.0, 0.0)),
+                    };
+                    push_u8(&mut out, has_clip);
+                    push_f64_le(&mut out, lo);
+                    push_f64_le(&mut out, hi);
+                    push_f64_le(&mut out, *mean);
+                    push_f64_le(&mut out, *std);
+                }
+                FeatureSpec::Categorical {
+                    source,
+                    categories,
+                    default,
+                } => {
+                    push_u8(&mut out, 2);
+                    push_u32_le(&mut out, *source);
+                    push_u32_le(&mut out, default.unwrap_or(NO_DEFAULT));
+                    push_u32_le(&mut out, categories.len() as u32);
+                    for category in categories {
+                        push_str(&mut out, category);
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// Rejects specs that could not be applied deterministically: sources
+    /// beyond `raw_len`, non-finite parameters, `std <= 0`, inverted clip
+    /// bounds, empty or duplicate categories and defaults out of range. Unused clip
+    /// bounds must be zero so every spec has one encoding.
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        if r.read_exact(8)? != PREPROCESSOR_MAGIC {
+            return Err(BytesError::InvalidMagic);
+        }
+        let raw_len = r.read_u32_le()?;
+        let count = r.read_u32_le()? as usize;
+        // every feature takes at least 13 bytes
+        if count > r.remaining() / 13 {
+            return Err(BytesError::InvalidLength);
+        }
+        let mut features = Vec::with_capacity(count);
+        for _ in 0..count {
+            let tag = r.read_u8()?;
+            let source = r.read_u32_le()?;
+            if source >= raw_len {
+                return Err(BytesError::InvalidLength);
+            }
+            let feature = match tag {
+                1 => {
+                    let default = read_finite(&mut r)?;
+                    let has_clip = r.read_u8()?;
+                    let (lo, hi) = (read_finite(&mut r)?, read_finite(&mut r)?);
+                    let clip = match has_clip {
+                        0 if lo.to_bits() == 0 && hi.to_bits() == 0 => None,
+                        1 if lo <= hi => Some((lo, hi)),
+                        _ => return Err(BytesError::InvalidLength),
+                    };
+                    let mean = read_finite(&mut r)?;
+                    let std = read_finite(&mut r)?;
+                    if std <= 0.0 {
+                        return Err(BytesError::InvalidLength);
+                    }
+                    FeatureSpec::Numeric {
+                        source,
+                        default,
+                        clip,
+                        mean,
+                        std,
+                    }
+                }
+                2 => {
+                    let default = match r.read_u32_le()? {
+                        NO_DEFAULT => None,
+                        d => Some(d),
+                    };
+                    let n = r.read_u32_le()? as usize;
+                    if n == 0 || n > r.remaining() / 2 || default.is_some_and(|d| d as usize >= n) {
+                        return Err(BytesError::InvalidLength);
+                    }
+                    let mut categories: Vec<String> = Vec::with_capacity(n);
+                    for _ in 0..n {
+                        let category = read_str(&mut r)?;
+                        if categories.contains(&category) {
+                            return Err(BytesError::InvalidLength);
+                        }
+                        categories.push(category);
+                    }
+                    FeatureSpec::Categorical {
+                        source,
+                        categories,
+                        default,
+                    }
+                }
+                _ => return Err(BytesError::InvalidLength),
+            };
+            features.push(feature);
+        }
+        if r.remaining() != 0 {
+            return Err(BytesError::InvalidLength);
+        }
+        Ok(Self { raw_len, features })
+    }
+}
+
+/// One raw field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RawValue {
+    Missing,
+    Number(f64),
+    Category(String),
+}
+
+/// Raw input fields before preprocessing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawInputV1 {
+    pub values: Vec<RawValue>,
+}
+
+impl RawInputV1 {
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        push_bytes(&mut out, RAW_INPUT_MAGIC);
+        push_u32_le(&mut out, self.values.len() as u32);
+        for value in &self.values {
+            match value {
+                RawValue::Missing => push_u8(&mut out, 0),
+                RawValue::Number(v) => {
+                    push_u8(&mut out, 1);
+                    push_f64_le(&mut out, *v);
+                }
+                RawValue::Category(c) => {
+                    push_u8(&mut out, 2);
+                    push_str(&mut out, c);
+                }
+            }
+        }
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        if r.read_exact(8)? != RAW_INPUT_MAGIC {
+            return Err(BytesError::InvalidMagic);
+        }
+        let count = r.read_u32_le()? as usize;
+        if count > r.remaining() {
+            return Err(BytesError::InvalidLength);
+        }
+        let mut values = Vec::with_capacity(count);
+        for _ in 0..count {
+            values.push(match r.read_u8()? {
+                0 => RawValue::Missing,
+                1 => RawValue::Number(r.read_f64_le()?),
+                2 => RawValue::Category(read_str(&mut r)?),
+                _ => return Err(BytesError::InvalidLength),
+            });
+        }
+        if r.remaining() != 0 {
+            return Err(BytesError::InvalidLength);
+        }
+        Ok(Self { values })
+    }
+}
+
+fn push_str(out: &mut Vec<u8>, s: &str) {
+    push_u16_le(out, s.len() as u16);
+    push_bytes(out, s.as_bytes());
+}
+
+fn read_str(r: &mut Reader<'_>) -> Result<String, BytesError> {
+    let len = r.read_u16_le()? as usize;
+    String::from_utf8(r.read_exact(len)?.to_vec()).map_err(|_| BytesError::InvalidLength)
+}
+
+fn read_finite(r: &mut Reader<'_>) -> Result<f64, BytesError> {
+    let v = r.read_f64_le()?;
+    if v.is_finite() {
+        Ok(v)
+    } else {
+        Err(BytesError::InvalidLength)
+    }
+}
@@ -0,0 +1,78 @@
+//! Salts for the commitments in `ProofArtifactV7`.
+//!
+//! The prover draws four fresh 32-byte salts per artifact and hands them only
+//! to verifiers allowed to check it; without them the artifact's commitments
+//! reveal nothing about the model, input, output or trace.
+
+use crate::bytes::{push_bytes, BytesError, Reader};
+use crate::hash::salted_sha256;
+
+const COMMITMENT_SALTS_MAGIC: &[u8; 8] = b"VFAISLT0";
+
+pub const COMMITMENT_SALTS_V0_LEN: usize = 8 + 32 * 4;
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct CommitmentSaltsV0 {
+    pub model: [u8; 32],
+    pub input: [u8; 32],
+    pub output: [u8; 32],
+    pub trace: [u8; 32],
+}
+
+// salts are as sensitive as the inputs they hide
+impl std::fmt::Debug for CommitmentSaltsV0 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommitmentSaltsV0").finish_non_exhaustive()
+    }
+}
+
+impl CommitmentSaltsV0 {
+    pub fn commit_model(&self, model_bin: &[u8]) -> [u8; 32] {
+        salted_sha256(&self.model, model_bin)
+    }
+
+    pub fn commit_input(&self, input_bin: &[u8]) -> [u8; 32] {
+        salted_sha256(&self.input, input_bin)
+    }
+
+    pub fn commit_output(&self, output_bin: &[u8]) -> [u8; 32] {
+        salted_sha256(&self.output, output_bin)
+    }
+
+    pub fn commit_trace(&self, trace_root: [u8; 32]) -> [u8; 32] {
+        salted_sha256(&self.trace, &trace_root)
+    }
+
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(COMMITMENT_SALTS_V0_LEN);
+        push_bytes(&mut out, COMMITMENT_SALTS_MAGIC);
+        push_bytes(&mut out, &self.model);
+        push_bytes(&mut out, &self.input);
+        push_bytes(&mut out, &self.output);
+        push_bytes(&mut out, &self.trace);
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        if buf.len() != COMMITMENT_SALTS_V0_LEN {
+            return Err(BytesError::InvalidLength);
+        }
+        let mut r = Reader::new(buf);
+        if r.read_exact(8)? != COMMITMENT_SALTS_MAGIC {
+            return Err(BytesError::InvalidMagic);
+        }
+        Ok(Self {
+            model: read_32(&mut r)?,
+            input: read_32(&mut r)?,
+            output: read_32(&mut r)?,
+            trace: read_32(&mut r)?,
+        })
+    }
+}
+
+fn read_32(r: &mut Reader<'_>) -> Result<[u8; 32], BytesError> {
+    let bytes = r.read_exact(32)?;
+    let mut out = [0u8; 32];
+    out.copy_from_slice(bytes);
+    Ok(out)
+}
@@ -0,0 +1,201 @@
+//! Signature algorithms for `ProofArtifactV5`.
+//!
+//! Ed25519 keys are the raw 32-byte key. ECDSA keys are SEC1 points: signing
+//! always produces the 33-byte compressed form, while decoding also accepts
+//! the 65-byte uncompressed form. ECDSA signatures are fixed-size `r || s`
+//! over SHA-256 of the message, with deterministic nonces (RFC 6979).
+//! secp256k1 signatures must be low-S.
+
+use crate::bytes::BytesError;
+
+use ed25519_dalek::Signer as _;
+use ed25519_dalek::Verifier as _;
+
+/// Algorithm identifier carried in `ProofArtifactV5`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SigAlg {
+    Ed25519,
+    EcdsaP256Sha256,
+    EcdsaSecp256k1Sha256,
+}
+
+impl SigAlg {
+    pub const ALL: [SigAlg; 3] = [
+        SigAlg::Ed25519,
+        SigAlg::EcdsaP256Sha256,
+        SigAlg::EcdsaSecp256k1Sha256,
+    ];
+
+    pub fn id(self) -> u8 {
+        match self {
+            SigAlg::Ed25519 => 1,
+            SigAlg::EcdsaP256Sha256 => 2,
+            SigAlg::EcdsaSecp256k1Sha256 => 3,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|alg| alg.id() == id)
+    }
+
+    /// Short name used by the CLI and in JSON output.
+    pub fn name(self) -> &'static str {
+        match self {
+            SigAlg::Ed25519 => "ed25519",
+            SigAlg::EcdsaP256Sha256 => "p256",
+            SigAlg::EcdsaSecp256k1Sha256 => "secp256k1",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|alg| alg.name() == name)
+    }
+
+    /// Whether `len` is an acceptable encoded public key length.
+    pub fn pubkey_len_ok(self, len: usize) -> bool {
+        match self {
+            SigAlg::Ed25519 => len == 32,
+            SigAlg::EcdsaP256Sha256 | SigAlg::EcdsaSecp256k1Sha256 => len == 33 || len == 65,
+        }
+    }
+
+    /// Encoded signature length; the same for all three algorithms.
+    pub fn signature_len(self) -> usize {
+        64
+    }
+}
+
+/// A 32-byte secret key for one of the `SigAlg` algorithms.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AlgSigningKey {
+    alg: SigAlg,
+    secret: [u8; 32],
+}
+
+impl std::fmt::Debug for AlgSigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AlgSigningKey")
+            .field("alg", &self.alg)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AlgSigningKey {
+    /// Fails if `secret` is not a valid scalar for an ECDSA curve (zero or
+    /// not below the group order).
+    pub fn new(alg: SigAlg, secret: [u8; 32]) -> Result<Self, BytesError> {
+        let valid = match alg {
+            SigAlg::Ed25519 => true,
+            SigAlg::EcdsaP256Sha256 => p256::ecdsa::SigningKey::from_slice(&secret).is_ok(),
+            SigAlg::EcdsaSecp256k1Sha256 => k256::ecdsa::SigningKey::from_slice(&secret).is_ok(),
+        };
+        if !valid {
+            return Err(BytesError::InvalidLength);
+        }
+        Ok(Self { alg, secret })
+    }
+
+    pub fn alg(&self) -> SigAlg {
+        self.alg
+    }
+
+    pub fn public_key(&self) -> Vec<u8> {
+        match self.alg {
+            SigAlg::Ed25519 => ed25519_dalek::SigningKey::from_bytes(&self.secret)
+                .verifying_key()
+                .to_bytes()
+                .to_vec(),
+            SigAlg::EcdsaP256Sha256 => self
+                .p256()
+                .verifying_key()
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec(),
+            SigAlg::EcdsaSecp256k1Sha256 => self
+                .k256()
+                .verifying_key()
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec(),
+        }
+    }
+
+    pub fn sign(&self, msg: &[u8]) -> Vec<u8> {
+        match self.alg {
+            SigAlg::Ed25519 => ed25519_dalek::SigningKey::from_bytes(&self.secret)
+                .sign(msg)
+                .to_bytes()
+                .to_vec(),
+            SigAlg::EcdsaP256Sha256 => {
+                use p256::ecdsa::signature::Signer as _;
+                let sig: p256::ecdsa::Signature = self.p256().sign(msg);
+                sig.to_bytes().to_vec()
+            }
+            SigAlg::EcdsaSecp256k1Sha256 => {
+                use k256::ecdsa::signature::Signer as _;
+                let sig: k256::ecdsa::Signature = self.k256().sign(msg);
+                sig.to_bytes().to_vec()
+            }
+        }
+    }
+
+    // `new` already checked the scalar
+    fn p256(&self) -> p256::ecdsa::SigningKey {
+        p256::ecdsa::SigningKey::from_slice(&self.secret).expect("valid P-256 scalar")
+    }
+
+    fn k256(&self) -> k256::ecdsa::SigningKey {
+        k256::ecdsa::SigningKey::from_slice(&self.secret).expect("valid secp256k1 scalar")
+    }
+}
+
+/// Checks `signature` over `msg` by `pubkey` under `alg`.
+pub fn verify_alg(
+    alg: SigAlg,
+    pubkey: &[u8],
+    msg: &[u8],
+    signature: &[u8],
+) -> Result<(), BytesError> {
+    if !alg.pubkey_len_ok(pubkey.len()) || signature.len() != alg.signature_len() {
+        return Err(BytesError::InvalidLength);
+    }
+    let ok = match alg {
+        SigAlg::Ed25519 => {
+            let mut key = [0u8; 32];
+            key.copy_from_slice(pubkey);
+            let mut sig = [0u8; 64];
+            sig.copy_from_slice(signature);
+            ed25519_dalek::VerifyingKey::from_bytes(&key)
+                .map(|vk| {
+                    vk.verify(msg, &ed25519_dalek::Signature::from_bytes(&sig))
+                        .is_ok()
+                })
+                .unwrap_or(false)
+        }
+        SigAlg::EcdsaP256Sha256 => {
+            use p256::ecdsa::signature::Verifier as _;
+            match (
+                p256::ecdsa::VerifyingKey::from_sec1_bytes(pubkey),
+                p256::ecdsa::Signature::from_slice(signature),
+            ) {
+                (Ok(vk), Ok(sig)) => vk.verify(msg, &sig).is_ok(),
+                _ => false,
+            }
+        }
+        SigAlg::EcdsaSecp256k1Sha256 => {
+            use k256::ecdsa::signature::Verifier as _;
+            match (
+                k256::ecdsa::VerifyingKey::from_sec1_bytes(pubkey),
+                k256::ecdsa::Signature::from_slice(signature),
+            ) {
+                (Ok(vk), Ok(sig)) => vk.verify(msg, &sig).is_ok(),
+                _ => false,
+            }
+        }
+    };
+    if ok {
+        Ok(())
+    } else {
+        Err(BytesError::InvalidLength)
+    }
+}
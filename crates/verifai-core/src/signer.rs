@@ -0,0 +1,99 @@
+//! Where artifact signatures come from.
+//!
+//! `prove_*` only ever needs a public key and signatures over
+//! `message_to_sign`, so the secret key can live in this process (raw
+//! `[u8; 32]` Ed25519 bytes or an `AlgSigningKey`) or behind an
+//! out-of-process agent.
+
+use ed25519_dalek::Signer as _;
+use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
+
+use crate::sig_alg::{verify_alg, AlgSigningKey, SigAlg};
+
+/// A signing key for one of the `SigAlg` algorithms, possibly held
+/// elsewhere.
+pub trait Signer {
+    /// The key's algorithm; Ed25519 unless the signer says otherwise.
+    fn sig_alg(&self) -> SigAlg {
+        SigAlg::Ed25519
+    }
+
+    /// The public key, encoded as `sig_alg` expects: 32 bytes for Ed25519,
+    /// a SEC1 point for ECDSA.
+    fn public_key(&self) -> Vec<u8>;
+
+    /// A signature over `msg`; `r || s` for ECDSA.
+    fn sign(&self, msg: &[u8]) -> Result<[u8; 64], SignerError>;
+}
+
+/// Why a signer could not produce a signature, e.g. an unreachable agent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignerError(pub String);
+
+/// Raw Ed25519 secret key bytes.
+impl Signer for [u8; 32] {
+    fn public_key(&self) -> Vec<u8> {
+        VerifyingKey::from(&SigningKey::from_bytes(self))
+            .to_bytes()
+            .to_vec()
+    }
+
+    fn sign(&self, msg: &[u8]) -> Result<[u8; 64], SignerError> {
+        let sig: Signature = SigningKey::from_bytes(self).sign(msg);
+        Ok(sig.to_bytes())
+    }
+}
+
+impl Signer for AlgSigningKey {
+    fn sig_alg(&self) -> SigAlg {
+        self.alg()
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        AlgSigningKey::public_key(self)
+    }
+
+    fn sign(&self, msg: &[u8]) -> Result<[u8; 64], SignerError> {
+        AlgSigningKey::sign(self, msg)
+            .try_into()
+            .map_err(|_| SignerError("signature is not 64 bytes".into()))
+    }
+}
+
+impl<S: Signer + ?Sized> Signer for &S {
+    fn sig_alg(&self) -> SigAlg {
+        (**self).sig_alg()
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        (**self).public_key()
+    }
+
+    fn sign(&self, msg: &[u8]) -> Result<[u8; 64], SignerError> {
+        (**self).sign(msg)
+    }
+}
+
+/// The signer's key for artifacts that only carry Ed25519 keys.
+pub fn ed25519_public_key<S: Signer + ?Sized>(signer: &S) -> Result<[u8; 32], SignerError> {
+    let alg = signer.sig_alg();
+    if alg != SigAlg::Ed25519 {
+        return Err(SignerError(format!(
+            "this artifact needs an ed25519 key, not {}",
+            alg.name()
+        )));
+    }
+    signer
+        .public_key()
+        .try_into()
+        .map_err(|_| SignerError("signer public key is not a valid Ed25519 key".into()))
+}
+
+/// Signs `msg` and checks the signature against `signer.public_key()`, so a
+/// misbehaving signer cannot produce an artifact that fails verification.
+pub fn sign_checked<S: Signer + ?Sized>(signer: &S, msg: &[u8]) -> Result<[u8; 64], SignerError> {
+    let signature = signer.sign(msg)?;
+    verify_alg(signer.sig_alg(), &signer.public_key(), msg, &signature)
+        .map_err(|_| SignerError("signature does not match the signer public key".into()))?;
+    Ok(signature)
+}
@@ -0,0 +1,54 @@
+use crate::bytes::{push_bytes, push_u32_le, BytesError, Reader};
+
+const SIGNER_BUNDLE_MAGIC: &[u8; 8] = b"VFAISGB0";
+
+/// An artifact packaged with the X.509 chain (DER, leaf first) certifying its
+/// `sig_pubkey`. The chain is not covered by the artifact signature; it binds
+/// an identity to the key, and the artifact signature binds the key to the
+/// claims.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignerBundleV0 {
+    pub artifact: Vec<u8>,
+    pub cert_chain: Vec<Vec<u8>>,
+}
+
+pub fn is_signer_bundle(buf: &[u8]) -> bool {
+    buf.starts_with(SIGNER_BUNDLE_MAGIC)
+}
+
+impl SignerBundleV0 {
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        push_bytes(&mut out, SIGNER_BUNDLE_MAGIC);
+        push_u32_le(&mut out, self.artifact.len() as u32);
+        push_bytes(&mut out, &self.artifact);
+        push_u32_le(&mut out, self.cert_chain.len() as u32);
+        for cert in &self.cert_chain {
+            push_u32_le(&mut out, cert.len() as u32);
+            push_bytes(&mut out, cert);
+        }
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut r = Reader::new(buf);
+        if r.read_exact(8)? != SIGNER_BUNDLE_MAGIC {
+            return Err(BytesError::InvalidMagic);
+        }
+        let artifact_len = r.read_u32_le()? as usize;
+        let artifact = r.read_exact(artifact_len)?.to_vec();
+        let count = r.read_u32_le()? as usize;
+        let mut cert_chain = Vec::new();
+        for _ in 0..count {
+            let len = r.read_u32_le()? as usize;
+            cert_chain.push(r.read_exact(len)?.to_vec());
+        }
+        if r.remaining() != 0 {
+            return Err(BytesError::InvalidLength);
+        }
+        Ok(Self {
+            artifact,
+            cert_chain,
+        })
+    }
+}
@@ -0,0 +1,98 @@
+//! RFC 3161-style timestamp tokens.
+//!
+//! A token is a time-stamping authority's signature over a message imprint
+//! (the SHA-256 of the data being stamped) and the time it saw that imprint.
+//! It mirrors the `TSTInfo` fields that matter here without the ASN.1/CMS
+//! envelope.
+
+use crate::bytes::{push_bytes, push_u16_le, push_u64_le, BytesError, Reader};
+
+use ed25519_dalek::Signer;
+use ed25519_dalek::Verifier;
+use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
+
+const SIGN_PREFIX_TIMESTAMP: &[u8; 20] = b"VERIFAI\0TIMESTAMP\0V0";
+
+pub const TIMESTAMP_TOKEN_V0_LEN: usize = 2 + 32 + 8 + 8 + 32 + 64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimestampTokenV0 {
+    pub version: u16, // must be 0
+    pub message_imprint: [u8; 32],
+    pub gen_time: u64, // unix nanos
+    pub serial: u64,
+    pub tsa_pubkey: [u8; 32],
+    pub signature: [u8; 64],
+}
+
+impl TimestampTokenV0 {
+    pub fn message_to_sign(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(20 + 2 + 32 + 8 + 8 + 32);
+        out.extend_from_slice(SIGN_PREFIX_TIMESTAMP);
+        out.extend_from_slice(&self.version.to_le_bytes());
+        out.extend_from_slice(&self.message_imprint);
+        out.extend_from_slice(&self.gen_time.to_le_bytes());
+        out.extend_from_slice(&self.serial.to_le_bytes());
+        out.extend_from_slice(&self.tsa_pubkey);
+        out
+    }
+
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(TIMESTAMP_TOKEN_V0_LEN);
+        push_u16_le(&mut out, self.version);
+        push_bytes(&mut out, &self.message_imprint);
+        push_u64_le(&mut out, self.gen_time);
+        push_u64_le(&mut out, self.serial);
+        push_bytes(&mut out, &self.tsa_pubkey);
+        push_bytes(&mut out, &self.signature);
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        if buf.len() != TIMESTAMP_TOKEN_V0_LEN {
+            return Err(BytesError::InvalidLength);
+        }
+        let mut r = Reader::new(buf);
+        let version = r.read_u16_le()?;
+        if version != 0 {
+            return Err(BytesError::InvalidMagic);
+        }
+        let message_imprint = read_32(&mut r)?;
+        let gen_time = r.read_u64_le()?;
+        let serial = r.read_u64_le()?;
+        let tsa_pubkey = read_32(&mut r)?;
+        let mut signature = [0u8; 64];
+        signature.copy_from_slice(r.read_exact(64)?);
+        Ok(Self {
+            version,
+            message_imprint,
+            gen_time,
+            serial,
+            tsa_pubkey,
+            signature,
+        })
+    }
+
+    pub fn sign_detached(&mut self, signing_key_bytes: [u8; 32]) -> Result<(), BytesError> {
+        let sk = SigningKey::from_bytes(&signing_key_bytes);
+        self.tsa_pubkey = VerifyingKey::from(&sk).to_bytes();
+        let sig: Signature = sk.sign(&self.message_to_sign());
+        self.signature = sig.to_bytes();
+        Ok(())
+    }
+
+    pub fn verify_signature(&self) -> Result<(), BytesError> {
+        let vk =
+            VerifyingKey::from_bytes(&self.tsa_pubkey).map_err(|_| BytesError::InvalidLength)?;
+        let sig = Signature::from_bytes(&self.signature);
+        vk.verify(&self.message_to_sign(), &sig)
+            .map_err(|_| BytesError::InvalidLength)
+    }
+}
+
+fn read_32(r: &mut Reader<'_>) -> Result<[u8; 32], BytesError> {
+    let b = r.read_exact(32)?;
+    let mut out = [0u8; 32];
+    out.copy_from_slice(b);
+    Ok(out)
+}
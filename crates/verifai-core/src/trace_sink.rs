@@ -0,0 +1,144 @@
+//! Streaming trace consumers.
+
+use std::io::{self, Write};
+
+use crate::event_bin::{encode_trace_record, TraceEventV0, TRACE_MAGIC};
+use crate::merkle::MerkleBuilder;
+
+/// Receives a run's trace events one at a time, in order, as the runner
+/// produces them.
+pub trait TraceSink {
+    fn record(&mut self, event: &TraceEventV0);
+
+    /// Whether the sink wants no more events. Runners check after each
+    /// event and give up on the run once it says so; only sinks that check
+    /// a trace as it is replayed should.
+    fn stopped(&self) -> bool {
+        false
+    }
+}
+
+impl<S: TraceSink + ?Sized> TraceSink for &mut S {
+    fn record(&mut self, event: &TraceEventV0) {
+        (**self).record(event);
+    }
+
+    fn stopped(&self) -> bool {
+        (**self).stopped()
+    }
+}
+
+/// Drops every event.
+impl TraceSink for () {
+    fn record(&mut self, _event: &TraceEventV0) {}
+}
+
+/// Hashes each event into the trace Merkle tree without keeping it.
+impl TraceSink for MerkleBuilder {
+    fn record(&mut self, event: &TraceEventV0) {
+        self.push(&event.encode_bin());
+    }
+}
+
+/// Keeps every event.
+impl TraceSink for Vec<TraceEventV0> {
+    fn record(&mut self, event: &TraceEventV0) {
+        self.push(event.clone());
+    }
+}
+
+/// Hands each event to both sinks, `0` first; stops once either does.
+#[derive(Debug, Clone, Default)]
+pub struct Tee<A, B>(pub A, pub B);
+
+impl<A: TraceSink, B: TraceSink> TraceSink for Tee<A, B> {
+    fn record(&mut self, event: &TraceEventV0) {
+        self.0.record(event);
+        self.1.record(event);
+    }
+
+    fn stopped(&self) -> bool {
+        self.0.stopped() || self.1.stopped()
+    }
+}
+
+/// Writes a `TraceV0` file one event at a time. Runners cannot fail on a
+/// sink, so the first write error is kept and every later event dropped;
+/// `finish` reports it.
+#[derive(Debug)]
+pub struct TraceFileWriter<W: Write> {
+    out: W,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> TraceFileWriter<W> {
+    /// Writes the magic to `out`.
+    pub fn new(mut out: W) -> Self {
+        let error = out.write_all(TRACE_MAGIC).err();
+        Self { out, error }
+    }
+
+    /// Flushes and returns the writer, or the first write error.
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+impl<W: Write> TraceSink for TraceFileWriter<W> {
+    fn record(&mut self, event: &TraceEventV0) {
+        if self.error.is_none() {
+            self.error = self.out.write_all(&encode_trace_record(event)).err();
+        }
+    }
+}
+
+/// Renders the event at a trace index as one line of JSON, without the
+/// newline.
+pub type EventJson = fn(u32, &TraceEventV0) -> io::Result<String>;
+
+/// Writes one JSON object per event and line, rendered by an `EventJson`.
+/// Like `TraceFileWriter`, the first error is kept for `finish`.
+#[derive(Debug)]
+pub struct JsonlTraceWriter<W: Write> {
+    out: W,
+    render: EventJson,
+    index: u32,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> JsonlTraceWriter<W> {
+    pub fn new(out: W, render: EventJson) -> Self {
+        Self {
+            out,
+            render,
+            index: 0,
+            error: None,
+        }
+    }
+
+    /// Flushes and returns the writer, or the first error.
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+impl<W: Write> TraceSink for JsonlTraceWriter<W> {
+    fn record(&mut self, event: &TraceEventV0) {
+        if self.error.is_none() {
+            let result = (self.render)(self.index, event).and_then(|line| {
+                self.out.write_all(line.as_bytes())?;
+                self.out.write_all(b"\n")
+            });
+            self.error = result.err();
+        }
+        self.index += 1;
+    }
+}
@@ -0,0 +1,345 @@
+//! Append-only transparency log primitives.
+//!
+//! Log entries are 32-byte artifact hashes. Leaves and inner nodes use the
+//! `merkle` hashes (`leaf_hash`, `node_hash`), but the tree shape follows
+//! RFC 6962 (split at the largest power of two below the size) instead of the
+//! trace tree's odd-node duplication, so that any earlier tree head stays a
+//! prefix of every later one and consistency proofs exist.
+
+use crate::bytes::{push_bytes, push_u16_le, push_u32_le, push_u64_le, BytesError, Reader};
+use crate::merkle::{empty_root, leaf_hash, node_hash};
+
+use ed25519_dalek::Signer;
+use ed25519_dalek::Verifier;
+use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
+
+const SIGN_PREFIX_TREE_HEAD: &[u8; 19] = b"VERIFAI\0TREEHEAD\0V0";
+
+pub const SIGNED_TREE_HEAD_V0_LEN: usize = 2 + 8 + 32 + 32 + 64;
+
+/// Largest power of two strictly below `n` (`n >= 2`).
+fn split_point(n: usize) -> usize {
+    let mut k = 1;
+    while k * 2 < n {
+        k *= 2;
+    }
+    k
+}
+
+fn subtree_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    match leaves.len() {
+        0 => empty_root(),
+        1 => leaves[0],
+        n => {
+            let k = split_point(n);
+            node_hash(subtree_root(&leaves[..k]), subtree_root(&leaves[k..]))
+        }
+    }
+}
+
+/// Log root over `entries` (artifact hashes, in append order).
+pub fn log_root(entries: &[[u8; 32]]) -> [u8; 32] {
+    let leaves: Vec<[u8; 32]> = entries.iter().map(|e| leaf_hash(e)).collect();
+    subtree_root(&leaves)
+}
+
+/// Audit path for entry `index` in the tree over all of `entries`.
+pub fn log_inclusion_proof(entries: &[[u8; 32]], index: usize) -> Option<Vec<[u8; 32]>> {
+    if index >= entries.len() {
+        return None;
+    }
+    let leaves: Vec<[u8; 32]> = entries.iter().map(|e| leaf_hash(e)).collect();
+    let mut path = Vec::new();
+    inclusion_path(&leaves, index, &mut path);
+    Some(path)
+}
+
+fn inclusion_path(leaves: &[[u8; 32]], index: usize, path: &mut Vec<[u8; 32]>) {
+    if leaves.len() <= 1 {
+        return;
+    }
+    let k = split_point(leaves.len());
+    if index < k {
+        inclusion_path(&leaves[..k], index, path);
+        path.push(subtree_root(&leaves[k..]));
+    } else {
+        inclusion_path(&leaves[k..], index - k, path);
+        path.push(subtree_root(&leaves[..k]));
+    }
+}
+
+/// Checks that `entry` is at `index` in the log of `tree_size` entries with
+/// the given `root`.
+pub fn verify_log_inclusion(
+    entry: [u8; 32],
+    index: u64,
+    tree_size: u64,
+    proof: &[[u8; 32]],
+    root: [u8; 32],
+) -> bool {
+    if index >= tree_size {
+        return false;
+    }
+
+    let mut fnode = index;
+    let mut snode = tree_size - 1;
+    let mut acc = leaf_hash(&entry);
+    for sibling in proof {
+        if snode == 0 {
+            return false;
+        }
+        if fnode & 1 == 1 || fnode == snode {
+            acc = node_hash(*sibling, acc);
+            while fnode & 1 == 0 && fnode != 0 {
+                fnode >>= 1;
+                snode >>= 1;
+            }
+        } else {
+            acc = node_hash(acc, *sibling);
+        }
+        fnode >>= 1;
+        snode >>= 1;
+    }
+
+    snode == 0 && acc == root
+}
+
+/// Proof that the log of the first `old_size` entries is a prefix of the log
+/// over all of `entries`. Empty when `old_size` is 0 or equals the full size.
+pub fn log_consistency_proof(entries: &[[u8; 32]], old_size: usize) -> Option<Vec<[u8; 32]>> {
+    if old_size > entries.len() {
+        return None;
+    }
+    let mut path = Vec::new();
+    if old_size > 0 && old_size < entries.len() {
+        let leaves: Vec<[u8; 32]> = entries.iter().map(|e| leaf_hash(e)).collect();
+        consistency_path(&leaves, old_size, true, &mut path);
+    }
+    Some(path)
+}
+
+fn consistency_path(leaves: &[[u8; 32]], m: usize, complete: bool, path: &mut Vec<[u8; 32]>) {
+    let n = leaves.len();
+    if m == n {
+        if !complete {
+            path.push(subtree_root(leaves));
+        }
+        return;
+    }
+    let k = split_point(n);
+    if m <= k {
+        consistency_path(&leaves[..k], m, complete, path);
+        path.push(subtree_root(&leaves[k..]));
+    } else {
+        consistency_path(&leaves[k..], m - k, false, path);
+        path.push(subtree_root(&leaves[..k]));
+    }
+}
+
+/// Checks that the tree with `old_root` over `old_size` entries is a prefix
+/// of the tree with `new_root` over `new_size` entries.
+pub fn verify_log_consistency(
+    old_size: u64,
+    new_size: u64,
+    old_root: [u8; 32],
+    new_root: [u8; 32],
+    proof: &[[u8; 32]],
+) -> bool {
+    if old_size > new_size {
+        return false;
+    }
+    if old_size == new_size {
+        return proof.is_empty() && old_root == new_root;
+    }
+    if old_size == 0 {
+        return proof.is_empty() && old_root == empty_root();
+    }
+    if proof.is_empty() {
+        return false;
+    }
+
+    let mut nodes = Vec::with_capacity(proof.len() + 1);
+    if old_size.is_power_of_two() {
+        nodes.push(old_root);
+    }
+    nodes.extend_from_slice(proof);
+
+    let mut fnode = old_size - 1;
+    let mut snode = new_size - 1;
+    while fnode & 1 == 1 {
+        fnode >>= 1;
+        snode >>= 1;
+    }
+
+    let mut fr = nodes[0];
+    let mut sr = nodes[0];
+    for c in &nodes[1..] {
+        if snode == 0 {
+            return false;
+        }
+        if fnode & 1 == 1 || fnode == snode {
+            fr = node_hash(*c, fr);
+            sr = node_hash(*c, sr);
+            while fnode & 1 == 0 && fnode != 0 {
+                fnode >>= 1;
+                snode >>= 1;
+            }
+        } else {
+            sr = node_hash(sr, *c);
+        }
+        fnode >>= 1;
+        snode >>= 1;
+    }
+
+    snode == 0 && fr == old_root && sr == new_root
+}
+
+/// A log root at a given size, signed by the log operator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedTreeHeadV0 {
+    pub version: u16, // must be 0
+    pub tree_size: u64,
+    pub root_hash: [u8; 32],
+    pub log_pubkey: [u8; 32],
+    pub signature: [u8; 64],
+}
+
+impl SignedTreeHeadV0 {
+    pub fn message_to_sign(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(19 + 2 + 8 + 32 * 2);
+        out.extend_from_slice(SIGN_PREFIX_TREE_HEAD);
+        out.extend_from_slice(&self.version.to_le_bytes());
+        out.extend_from_slice(&self.tree_size.to_le_bytes());
+        out.extend_from_slice(&self.root_hash);
+        out.extend_from_slice(&self.log_pubkey);
+        out
+    }
+
+    pub fn encode_bin(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(SIGNED_TREE_HEAD_V0_LEN);
+        push_u16_le(&mut out, self.version);
+        push_u64_le(&mut out, self.tree_size);
+        push_bytes(&mut out, &self.root_hash);
+        push_bytes(&mut out, &self.log_pubkey);
+        push_bytes(&mut out, &self.signature);
+        out
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        if buf.len() != SIGNED_TREE_HEAD_V0_LEN {
+            return Err(BytesError::InvalidLength);
+        }
+        let mut r = Reader::new(buf);
+        let version = r.read_u16_le()?;
+        if version != 0 {
+            return Err(BytesError::InvalidMagic);
+        }
+        let tree_size = r.read_u64_le()?;
+        let root_hash = read_32(&mut r)?;
+        let log_pubkey = read_32(&mut r)?;
+        let mut signature = [0u8; 64];
+        signature.copy_from_slice(r.read_exact(64)?);
+        Ok(Self {
+            version,
+            tree_size,
+            root_hash,
+            log_pubkey,
+            signature,
+        })
+    }
+
+    pub fn sign_detached(&mut self, signing_key_bytes: [u8; 32]) -> Result<(), BytesError> {
+        let sk = SigningKey::from_bytes(&signing_key_bytes);
+        self.log_pubkey = VerifyingKey::from(&sk).to_bytes();
+        let sig: Signature = sk.sign(&self.message_to_sign());
+        self.signature = sig.to_bytes();
+        Ok(())
+    }
+
+    pub fn verify_signature(&self) -> Result<(), BytesError> {
+        let vk =
+            VerifyingKey::from_bytes(&self.log_pubkey).map_err(|_| BytesError::InvalidLength)?;
+        let sig = Signature::from_bytes(&self.signature);
+        vk.verify(&self.message_to_sign(), &sig)
+            .map_err(|_| BytesError::InvalidLength)
+    }
+}
+
+/// Audit path for one entry, bound to the tree size it was computed for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogInclusionProofV0 {
+    pub leaf_index: u64,
+    pub tree_size: u64,
+    pub path: Vec<[u8; 32]>,
+}
+
+impl LogInclusionProofV0 {
+    pub fn encode_bin(&self) -> Vec<u8> {
+        encode_proof(self.leaf_index, self.tree_size, &self.path)
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let (leaf_index, tree_size, path) = decode_proof(buf)?;
+        Ok(Self {
+            leaf_index,
+            tree_size,
+            path,
+        })
+    }
+}
+
+/// Proof that the log at `old_size` is a prefix of the log at `new_size`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogConsistencyProofV0 {
+    pub old_size: u64,
+    pub new_size: u64,
+    pub path: Vec<[u8; 32]>,
+}
+
+impl LogConsistencyProofV0 {
+    pub fn encode_bin(&self) -> Vec<u8> {
+        encode_proof(self.old_size, self.new_size, &self.path)
+    }
+
+    pub fn decode_bin(buf: &[u8]) -> Result<Self, BytesError> {
+        let (old_size, new_size, path) = decode_proof(buf)?;
+        Ok(Self {
+            old_size,
+            new_size,
+            path,
+        })
+    }
+}
+
+fn encode_proof(a: u64, b: u64, path: &[[u8; 32]]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(8 + 8 + 4 + 32 * path.len());
+    push_u64_le(&mut out, a);
+    push_u64_le(&mut out, b);
+    push_u32_le(&mut out, path.len() as u32);
+    for node in path {
+        push_bytes(&mut out, node);
+    }
+    out
+}
+
+fn decode_proof(buf: &[u8]) -> Result<(u64, u64, Vec<[u8; 32]>), BytesError> {
+    let mut r = Reader::new(buf);
+    let a = r.read_u64_le()?;
+    let b = r.read_u64_le()?;
+    let len = r.read_u32_le()? as usize;
+    let mut path = Vec::with_capacity(len.min(64));
+    for _ in 0..len {
+        path.push(read_32(&mut r)?);
+    }
+    if r.remaining() != 0 {
+        return Err(BytesError::InvalidLength);
+    }
+    Ok((a, b, path))
+}
+
+fn read_32(r: &mut Reader<'_>) -> Result<[u8; 32], BytesError> {
+    let b = r.read_exact(32)?;
+    let mut out = [0u8; 32];
+    out.copy_from_slice(b);
+    Ok(out)
+}
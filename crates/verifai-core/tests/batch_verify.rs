@@ -0,0 +1,118 @@
+use verifai_core::artifact_bin::{CoSignature, ProofArtifactV0, ProofArtifactV4, ProofArtifactV5};
+use verifai_core::attestation::AttestationBundle;
+use verifai_core::batch_verify::{verify_batch, BatchVerify};
+use verifai_core::sig_alg::{AlgSigningKey, SigAlg};
+
+fn v0(seed: u8) -> ProofArtifactV0 {
+    let mut a = ProofArtifactV0 {
+        version: 0,
+        runtime_id: [1u8; 32],
+        model_hash: [2u8; 32],
+        input_hash: [seed; 32],
+        output_hash: [4u8; 32],
+        trace_root: [5u8; 32],
+        sig_pubkey: [0u8; 32],
+        signature: [0u8; 64],
+    };
+    a.sign_detached([seed.wrapping_add(1); 32]).unwrap();
+    a
+}
+
+fn attestation() -> AttestationBundle {
+    AttestationBundle {
+        attester_id: [0u8; 32],
+        measurement: [5u8; 32],
+        attestation: Vec::new(),
+    }
+}
+
+fn v4(keys: &[[u8; 32]]) -> ProofArtifactV4 {
+    let mut a = ProofArtifactV4 {
+        version: 4,
+        runtime_id: [1u8; 32],
+        model_hash: [2u8; 32],
+        input_hash: [3u8; 32],
+        output_hash: [4u8; 32],
+        trace_root: [5u8; 32],
+        signatures: Vec::new(),
+        attestation: attestation(),
+    };
+    for key in keys {
+        a.add_signature(*key).unwrap();
+    }
+    a
+}
+
+fn v5(alg: SigAlg) -> ProofArtifactV5 {
+    let mut a = ProofArtifactV5 {
+        version: 5,
+        runtime_id: [1u8; 32],
+        model_hash: [2u8; 32],
+        input_hash: [3u8; 32],
+        output_hash: [4u8; 32],
+        trace_root: [5u8; 32],
+        sig_alg: alg,
+        sig_pubkey: Vec::new(),
+        signature: Vec::new(),
+        attestation: attestation(),
+    };
+    a.sign_detached(&AlgSigningKey::new(alg, [9u8; 32]).unwrap());
+    a
+}
+
+fn refs<T: BatchVerify>(artifacts: &[T]) -> Vec<&dyn BatchVerify> {
+    artifacts.iter().map(|a| a as &dyn BatchVerify).collect()
+}
+
+#[test]
+fn batch_finds_exactly_the_invalid_artifacts() {
+    let mut artifacts: Vec<ProofArtifactV0> = (0..50).map(v0).collect();
+    assert!(verify_batch(&refs(&artifacts)).is_empty());
+
+    artifacts[3].signature[0] ^= 0x01;
+    artifacts[17].output_hash[0] ^= 0x01;
+    artifacts[49].sig_pubkey = artifacts[48].sig_pubkey;
+    assert_eq!(verify_batch(&refs(&artifacts)), vec![3, 17, 49]);
+
+    for a in &mut artifacts {
+        a.signature[1] ^= 0x01;
+    }
+    assert_eq!(verify_batch(&refs(&artifacts)).len(), 50);
+    assert!(verify_batch(&[]).is_empty());
+}
+
+#[test]
+fn batch_mixes_versions_and_algorithms() {
+    let good_v0 = v0(1);
+    let cosigned = v4(&[[9u8; 32], [8u8; 32]]);
+    let unsigned = v4(&[]);
+    let mut bad_cosigner = v4(&[[9u8; 32], [8u8; 32]]);
+    bad_cosigner.signatures[1] = CoSignature {
+        pubkey: bad_cosigner.signatures[1].pubkey,
+        signature: [3u8; 64],
+    };
+    let ed25519 = v5(SigAlg::Ed25519);
+    let p256 = v5(SigAlg::EcdsaP256Sha256);
+    let mut bad_k256 = v5(SigAlg::EcdsaSecp256k1Sha256);
+    bad_k256.trace_root[0] ^= 0x01;
+
+    let batch: Vec<&dyn BatchVerify> = vec![
+        &good_v0,
+        &cosigned,
+        &unsigned,
+        &bad_cosigner,
+        &ed25519,
+        &p256,
+        &bad_k256,
+    ];
+    assert_eq!(verify_batch(&batch), vec![2, 3, 6]);
+
+    // the batch verdict agrees with checking each artifact on its own
+    for (i, artifact) in batch.iter().enumerate() {
+        assert_eq!(
+            artifact.verify_alone().is_err(),
+            [2, 3, 6].contains(&i),
+            "artifact {i}"
+        );
+    }
+}
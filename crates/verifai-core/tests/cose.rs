@@ -0,0 +1,76 @@
+use coset::{CborSerializable, CoseSign1, TaggedCborSerializable};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use verifai_core::artifact_bin::ProofArtifactV1;
+use verifai_core::attestation::AttestationBundle;
+use verifai_core::cose::is_cose_sign1;
+
+fn signed_v1() -> ProofArtifactV1 {
+    let mut a = ProofArtifactV1 {
+        version: 1,
+        runtime_id: [1u8; 32],
+        model_hash: [2u8; 32],
+        input_hash: [3u8; 32],
+        output_hash: [4u8; 32],
+        trace_root: [5u8; 32],
+        sig_pubkey: [0u8; 32],
+        signature: [0u8; 64],
+        attestation: AttestationBundle {
+            attester_id: [6u8; 32],
+            measurement: [5u8; 32],
+            attestation: b"quote".to_vec(),
+        },
+    };
+    a.sign_detached([9u8; 32]).unwrap();
+    a
+}
+
+#[test]
+fn cose_roundtrip_is_lossless() {
+    let a = signed_v1();
+    let cose = a.encode_cose_sign1([9u8; 32]).unwrap();
+    assert!(is_cose_sign1(&cose));
+    assert!(!is_cose_sign1(&a.encode_bin()));
+
+    let back = ProofArtifactV1::verify_cose_sign1(&cose).unwrap();
+    assert_eq!(back, a);
+    assert_eq!(back.encode_bin(), a.encode_bin());
+    back.verify_signature().unwrap();
+
+    // deterministic: re-encoding the decoded artifact gives the same message
+    assert_eq!(back.encode_cose_sign1([9u8; 32]).unwrap(), cose);
+}
+
+#[test]
+fn cose_signature_checks_with_plain_coset() {
+    let a = signed_v1();
+    let cose = a.encode_cose_sign1([9u8; 32]).unwrap();
+
+    let sign1 = CoseSign1::from_tagged_slice(&cose).unwrap();
+    assert_eq!(sign1.protected.header.key_id, a.sig_pubkey.to_vec());
+    let vk = VerifyingKey::from_bytes(&a.sig_pubkey).unwrap();
+    sign1
+        .verify_signature(b"", |sig, tbs| {
+            vk.verify(tbs, &Signature::from_slice(sig).unwrap())
+        })
+        .unwrap();
+
+    // untagged messages decode too
+    let untagged = sign1.to_vec().unwrap();
+    assert_eq!(ProofArtifactV1::verify_cose_sign1(&untagged).unwrap(), a);
+}
+
+#[test]
+fn cose_rejects_tampering_and_foreign_keys() {
+    let a = signed_v1();
+    assert!(a.encode_cose_sign1([8u8; 32]).is_err());
+
+    let cose = a.encode_cose_sign1([9u8; 32]).unwrap();
+    let mut sign1 = CoseSign1::from_tagged_slice(&cose).unwrap();
+    let payload = sign1.payload.as_mut().unwrap();
+    let last = payload.len() - 1;
+    payload[last] ^= 1;
+    let tampered = sign1.to_tagged_vec().unwrap();
+
+    assert!(ProofArtifactV1::decode_cose_sign1(&tampered).is_ok());
+    assert!(ProofArtifactV1::verify_cose_sign1(&tampered).is_err());
+}
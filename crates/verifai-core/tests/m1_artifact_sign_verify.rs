@@ -1,4 +1,10 @@
-use verifai_core::artifact_bin::{ProofArtifactV0, PROOF_ARTIFACT_V0_LEN};
+use verifai_core::artifact_bin::{
+    ProofArtifactV0, ProofArtifactV4, ProofArtifactV5, ProofArtifactV6, PROOF_ARTIFACT_V0_LEN,
+};
+use verifai_core::attestation::AttestationBundle;
+use verifai_core::hash::HashAlg;
+use verifai_core::sig_alg::{AlgSigningKey, SigAlg};
+use verifai_core::signer::{Signer, SignerError};
 
 #[test]
 fn test_artifact_encode_has_fixed_length_258() {
@@ -57,3 +63,163 @@ fn test_artifact_verify_rejects_modified_field() {
 
     assert!(b.verify_signature().is_err());
 }
+
+#[test]
+fn test_artifact_v4_cosignatures_verify_independently() {
+    let mut a = ProofArtifactV4 {
+        version: 4,
+        runtime_id: [1u8; 32],
+        model_hash: [2u8; 32],
+        input_hash: [3u8; 32],
+        output_hash: [4u8; 32],
+        trace_root: [5u8; 32],
+        signatures: Vec::new(),
+        attestation: AttestationBundle {
+            attester_id: [0u8; 32],
+            measurement: [5u8; 32],
+            attestation: Vec::new(),
+        },
+    };
+    assert!(a.verify_signatures().is_err());
+
+    a.add_signature([9u8; 32]).unwrap();
+    let first = a.verify_signatures().unwrap();
+    a.add_signature([8u8; 32]).unwrap();
+    a.add_signature([9u8; 32]).unwrap();
+    let signers = a.verify_signatures().unwrap();
+    assert_eq!(signers.len(), 2);
+    assert_eq!(signers[0], first[0]);
+
+    let mut b = a.clone();
+    b.signatures[1].signature[0] ^= 0xFF;
+    assert!(b.verify_signatures().is_err());
+
+    let mut c = a.clone();
+    c.output_hash[0] ^= 0xFF;
+    assert!(c.verify_signatures().is_err());
+}
+
+#[test]
+fn test_artifact_v5_signs_and_verifies_with_each_algorithm() {
+    for alg in SigAlg::ALL {
+        let key = AlgSigningKey::new(alg, [9u8; 32]).unwrap();
+        let mut a = ProofArtifactV5 {
+            version: 5,
+            runtime_id: [1u8; 32],
+            model_hash: [2u8; 32],
+            input_hash: [3u8; 32],
+            output_hash: [4u8; 32],
+            trace_root: [5u8; 32],
+            sig_alg: alg,
+            sig_pubkey: Vec::new(),
+            signature: Vec::new(),
+            attestation: AttestationBundle {
+                attester_id: [0u8; 32],
+                measurement: [5u8; 32],
+                attestation: Vec::new(),
+            },
+        };
+        a.sign_detached(&key);
+        assert_eq!(
+            a.sig_pubkey.len(),
+            if alg == SigAlg::Ed25519 { 32 } else { 33 }
+        );
+        a.verify_signature().unwrap();
+        assert_eq!(ProofArtifactV5::decode_bin(&a.encode_bin()).unwrap(), a);
+
+        let mut b = a.clone();
+        b.output_hash[0] ^= 0xFF;
+        assert!(b.verify_signature().is_err());
+
+        let mut c = a.clone();
+        c.signature[10] ^= 0xFF;
+        assert!(c.verify_signature().is_err());
+    }
+
+    // the same scalar under the two curves is two different keys
+    let p256 = AlgSigningKey::new(SigAlg::EcdsaP256Sha256, [9u8; 32]).unwrap();
+    let k256 = AlgSigningKey::new(SigAlg::EcdsaSecp256k1Sha256, [9u8; 32]).unwrap();
+    assert_ne!(p256.public_key(), k256.public_key());
+}
+
+#[test]
+fn test_artifact_v6_signature_binds_the_hash_alg() {
+    let key = AlgSigningKey::new(SigAlg::EcdsaP256Sha256, [9u8; 32]).unwrap();
+    for hash_alg in HashAlg::ALL {
+        let mut a = ProofArtifactV6 {
+            version: 6,
+            runtime_id: [1u8; 32],
+            model_hash: [2u8; 32],
+            input_hash: [3u8; 32],
+            output_hash: [4u8; 32],
+            trace_root: [5u8; 32],
+            hash_alg,
+            sig_alg: SigAlg::Ed25519,
+            sig_pubkey: Vec::new(),
+            signature: Vec::new(),
+            attestation: AttestationBundle {
+                attester_id: [0u8; 32],
+                measurement: [5u8; 32],
+                attestation: Vec::new(),
+            },
+        };
+        a.sign_detached(&key);
+        assert_eq!(a.sig_alg, SigAlg::EcdsaP256Sha256);
+        a.verify_signature().unwrap();
+        assert_eq!(ProofArtifactV6::decode_bin(&a.encode_bin()).unwrap(), a);
+
+        let mut b = a.clone();
+        b.trace_root[0] ^= 0xFF;
+        assert!(b.verify_signature().is_err());
+
+        for other in HashAlg::ALL.into_iter().filter(|&o| o != hash_alg) {
+            let mut c = a.clone();
+            c.hash_alg = other;
+            assert!(c.verify_signature().is_err());
+        }
+    }
+}
+
+#[test]
+fn test_alg_signing_key_rejects_out_of_range_ecdsa_scalars() {
+    assert!(AlgSigningKey::new(SigAlg::EcdsaP256Sha256, [0u8; 32]).is_err());
+    assert!(AlgSigningKey::new(SigAlg::EcdsaSecp256k1Sha256, [0xFFu8; 32]).is_err());
+    assert!(AlgSigningKey::new(SigAlg::Ed25519, [0u8; 32]).is_ok());
+}
+
+/// Claims one key but signs with another.
+struct MismatchedSigner;
+
+impl Signer for MismatchedSigner {
+    fn public_key(&self) -> Vec<u8> {
+        [9u8; 32].public_key()
+    }
+
+    fn sign(&self, msg: &[u8]) -> Result<[u8; 64], SignerError> {
+        [8u8; 32].sign(msg)
+    }
+}
+
+#[test]
+fn test_artifact_sign_with_matches_sign_detached_and_checks_the_signer() {
+    let mut a = ProofArtifactV0 {
+        version: 0,
+        runtime_id: [1u8; 32],
+        model_hash: [2u8; 32],
+        input_hash: [3u8; 32],
+        output_hash: [4u8; 32],
+        trace_root: [5u8; 32],
+        sig_pubkey: [0u8; 32],
+        signature: [0u8; 64],
+    };
+    let mut b = a.clone();
+
+    a.sign_detached([9u8; 32]).unwrap();
+    b.sign_with(&[9u8; 32]).unwrap();
+    assert_eq!(a, b);
+
+    assert!(b.sign_with(&MismatchedSigner).is_err());
+    // v0 only carries Ed25519 keys
+    let p256 = AlgSigningKey::new(SigAlg::EcdsaP256Sha256, [9u8; 32]).unwrap();
+    assert!(b.sign_with(&p256).is_err());
+}
@@ -1,5 +1,12 @@
-use verifai_core::hash::sha256;
-use verifai_core::merkle::{empty_root, leaf_hash, node_hash, trace_root_from_event_bytes};
+use verifai_core::event_bin::TraceEventV0;
+use verifai_core::hash::{salted_sha256, sha256, HashAlg};
+use verifai_core::merkle::{
+    empty_root, empty_root_with, inclusion_proof, inclusion_proof_with, leaf_hash, leaf_hash_with,
+    multi_proof, multi_proof_with, node_hash, node_hash_with, trace_root_from_event_bytes,
+    trace_root_from_event_bytes_with, tree_depth, verify_inclusion, verify_inclusion_with,
+    verify_multi_proof, verify_multi_proof_with, MerkleBuilder,
+};
+use verifai_core::trace_sink::TraceSink;
 
 #[test]
 fn test_sha256_domain_separation_leaf_vs_node() {
@@ -56,3 +63,269 @@ fn test_merkle_root_odd_duplicates_last() {
     let root = trace_root_from_event_bytes(&[ev0, ev1, ev2]);
     assert_eq!(root, expected);
 }
+
+#[test]
+fn test_inclusion_proof_roundtrip_for_every_leaf() {
+    let events: Vec<Vec<u8>> = (0u8..5).map(|i| vec![i, i + 1]).collect();
+    let root = trace_root_from_event_bytes(&events);
+    assert_eq!(tree_depth(events.len()), 3);
+
+    for (i, ev) in events.iter().enumerate() {
+        let proof = inclusion_proof(&events, i).unwrap();
+        assert!(verify_inclusion(ev, i, events.len(), &proof, root));
+        assert!(!verify_inclusion(
+            ev,
+            (i + 1) % 5,
+            events.len(),
+            &proof,
+            root
+        ));
+    }
+    assert!(inclusion_proof(&events, 5).is_none());
+}
+
+#[test]
+fn test_inclusion_proof_rejects_wrong_depth() {
+    let events = vec![vec![0x01], vec![0x02]];
+    let root = trace_root_from_event_bytes(&events);
+    let mut proof = inclusion_proof(&events, 0).unwrap();
+    proof.push(root);
+    assert!(!verify_inclusion(&events[0], 0, 2, &proof, root));
+}
+
+fn hex(bytes: [u8; 32]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[test]
+fn test_hash_algs_match_known_answers() {
+    let expected = [
+        (
+            HashAlg::Sha256,
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        ),
+        (
+            HashAlg::Blake3,
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+        ),
+        (
+            HashAlg::Sha3_256,
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a",
+        ),
+    ];
+    for (alg, digest) in expected {
+        assert_eq!(hex(alg.digest(b"")), digest);
+        assert_eq!(HashAlg::from_id(alg.id()), Some(alg));
+        assert_eq!(HashAlg::from_name(alg.name()), Some(alg));
+    }
+    assert_eq!(HashAlg::default(), HashAlg::Sha256);
+    assert_eq!(HashAlg::from_id(0), None);
+}
+
+#[test]
+fn test_sha256_merkle_functions_are_the_sha256_instance() {
+    let events: Vec<Vec<u8>> = (0u8..3).map(|i| vec![i]).collect();
+    let alg = HashAlg::Sha256;
+    assert_eq!(empty_root_with(alg), empty_root());
+    assert_eq!(leaf_hash_with(alg, &events[0]), leaf_hash(&events[0]));
+    assert_eq!(
+        node_hash_with(alg, [1u8; 32], [2u8; 32]),
+        node_hash([1u8; 32], [2u8; 32])
+    );
+    assert_eq!(
+        trace_root_from_event_bytes_with(alg, &events),
+        trace_root_from_event_bytes(&events)
+    );
+    assert_eq!(
+        inclusion_proof_with(alg, &events, 1),
+        inclusion_proof(&events, 1)
+    );
+}
+
+#[test]
+fn test_merkle_trees_differ_per_alg_and_prove_inclusion() {
+    let events: Vec<Vec<u8>> = (0u8..5).map(|i| vec![i, i + 1]).collect();
+    let roots: Vec<[u8; 32]> = HashAlg::ALL
+        .iter()
+        .map(|&alg| trace_root_from_event_bytes_with(alg, &events))
+        .collect();
+    assert_ne!(roots[0], roots[1]);
+    assert_ne!(roots[0], roots[2]);
+    assert_ne!(roots[1], roots[2]);
+
+    for (&alg, &root) in HashAlg::ALL.iter().zip(&roots) {
+        assert_eq!(empty_root_with(alg), alg.digest(&[0x02]));
+        for (i, ev) in events.iter().enumerate() {
+            let proof = inclusion_proof_with(alg, &events, i).unwrap();
+            assert!(verify_inclusion_with(
+                alg,
+                ev,
+                i,
+                events.len(),
+                &proof,
+                root
+            ));
+            // a proof only holds under the algorithm it was built with
+            for other in HashAlg::ALL.into_iter().filter(|&o| o != alg) {
+                assert!(!verify_inclusion_with(
+                    other,
+                    ev,
+                    i,
+                    events.len(),
+                    &proof,
+                    root
+                ));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_salted_sha256_prefixes_the_salt() {
+    let salt = [0xA5u8; 32];
+    let mut buf = salt.to_vec();
+    buf.extend_from_slice(b"input");
+    assert_eq!(salted_sha256(&salt, b"input"), sha256(&buf));
+    assert_ne!(salted_sha256(&salt, b"input"), sha256(b"input"));
+    assert_ne!(
+        salted_sha256(&salt, b"input"),
+        salted_sha256(&[0x5Au8; 32], b"input")
+    );
+}
+
+fn numbered_leaves(n: usize) -> Vec<Vec<u8>> {
+    (0..n).map(|i| vec![i as u8; i % 3 + 1]).collect()
+}
+
+#[test]
+fn test_multi_proof_every_subset_of_small_trees() {
+    for n in 1..=9usize {
+        let leaves = numbered_leaves(n);
+        let root = trace_root_from_event_bytes(&leaves);
+        for mask in 1u32..(1 << n) {
+            let indices: Vec<usize> = (0..n).filter(|i| mask & (1 << i) != 0).collect();
+            let proof = multi_proof(&leaves, &indices).unwrap();
+            let disclosed: Vec<(usize, &[u8])> =
+                indices.iter().map(|&i| (i, leaves[i].as_slice())).collect();
+            assert!(
+                verify_multi_proof(&disclosed, n, &proof, root),
+                "n={n} indices={indices:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_multi_proof_single_index_matches_inclusion_proof_length() {
+    let leaves = numbered_leaves(7);
+    for i in 0..7 {
+        let multi = multi_proof(&leaves, &[i]).unwrap();
+        let single = inclusion_proof(&leaves, i).unwrap();
+        assert!(multi.len() <= single.len());
+    }
+    // disclosing everything needs no sibling hashes at all
+    assert!(multi_proof(&leaves, &[0, 1, 2, 3, 4, 5, 6])
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_multi_proof_rejects_bad_indices() {
+    let leaves = numbered_leaves(5);
+    assert!(multi_proof(&leaves, &[]).is_none());
+    assert!(multi_proof(&leaves, &[5]).is_none());
+    assert!(multi_proof(&leaves, &[2, 1]).is_none());
+    assert!(multi_proof(&leaves, &[1, 1]).is_none());
+}
+
+#[test]
+fn test_multi_proof_tamper_fails() {
+    let leaves = numbered_leaves(6);
+    let root = trace_root_from_event_bytes(&leaves);
+    let proof = multi_proof(&leaves, &[1, 4]).unwrap();
+    let disclosed: Vec<(usize, &[u8])> = vec![(1, &leaves[1]), (4, &leaves[4])];
+    assert!(verify_multi_proof(&disclosed, 6, &proof, root));
+
+    let wrong_leaf: Vec<(usize, &[u8])> = vec![(1, &leaves[1]), (4, b"forged")];
+    assert!(!verify_multi_proof(&wrong_leaf, 6, &proof, root));
+    let wrong_index: Vec<(usize, &[u8])> = vec![(1, &leaves[1]), (5, &leaves[4])];
+    assert!(!verify_multi_proof(&wrong_index, 6, &proof, root));
+    assert!(!verify_multi_proof(&disclosed, 7, &proof, root));
+    assert!(!verify_multi_proof(&disclosed, 6, &proof[1..], root));
+    let mut extra = proof.clone();
+    extra.push([0u8; 32]);
+    assert!(!verify_multi_proof(&disclosed, 6, &extra, root));
+    let mut flipped = proof;
+    flipped[0][0] ^= 1;
+    assert!(!verify_multi_proof(&disclosed, 6, &flipped, root));
+}
+
+#[test]
+fn test_multi_proof_with_other_hash_algs() {
+    let leaves = numbered_leaves(5);
+    let disclosed: Vec<(usize, &[u8])> = vec![(0, &leaves[0]), (3, &leaves[3])];
+    for alg in [HashAlg::Blake3, HashAlg::Sha3_256] {
+        let root = trace_root_from_event_bytes_with(alg, &leaves);
+        let proof = multi_proof_with(alg, &leaves, &[0, 3]).unwrap();
+        assert!(verify_multi_proof_with(alg, &disclosed, 5, &proof, root));
+        assert!(!verify_multi_proof_with(
+            HashAlg::Sha256,
+            &disclosed,
+            5,
+            &proof,
+            root
+        ));
+    }
+}
+
+#[test]
+fn test_merkle_builder_matches_batch_root_for_every_size() {
+    for alg in HashAlg::ALL {
+        for n in 0..=70usize {
+            let leaves = numbered_leaves(n);
+            let mut tree = MerkleBuilder::with_alg(alg);
+            for leaf in &leaves {
+                tree.push(leaf);
+            }
+            assert_eq!(tree.len(), n as u64);
+            assert_eq!(
+                tree.finalize(),
+                trace_root_from_event_bytes_with(alg, &leaves),
+                "{} n={n}",
+                alg.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_merkle_builder_defaults_to_sha256() {
+    let leaves = numbered_leaves(5);
+    let mut tree = MerkleBuilder::new();
+    assert!(tree.is_empty());
+    for leaf in &leaves {
+        tree.push(leaf);
+    }
+    assert_eq!(tree.finalize(), trace_root_from_event_bytes(&leaves));
+    assert_eq!(MerkleBuilder::new().finalize(), empty_root());
+}
+
+#[test]
+fn test_trace_sinks_hash_and_keep_events() {
+    let events = vec![
+        TraceEventV0::OpLinear { op_id: 0, z: 0.25 },
+        TraceEventV0::OpOutput { y: 0.5 },
+    ];
+    let mut tree = MerkleBuilder::with_alg(HashAlg::Blake3);
+    let mut kept: Vec<TraceEventV0> = Vec::new();
+    for event in &events {
+        tree.record(event);
+        kept.record(event);
+    }
+    let event_bytes: Vec<Vec<u8>> = events.iter().map(|e| e.encode_bin()).collect();
+    assert_eq!(
+        tree.finalize(),
+        trace_root_from_event_bytes_with(HashAlg::Blake3, &event_bytes)
+    );
+    assert_eq!(kept, events);
+}
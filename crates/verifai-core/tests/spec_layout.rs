@@ -1,7 +1,22 @@
-use verifai_core::artifact_bin::ProofArtifactV1;
-use verifai_core::artifact_bin::{ProofArtifactV0, PROOF_ARTIFACT_V0_LEN};
+use verifai_core::artifact_bin::{BatchProofArtifactV2, ProofArtifactV0, PROOF_ARTIFACT_V0_LEN};
+use verifai_core::artifact_bin::{CoSignature, ProofArtifactV1, ProofArtifactV3, ProofArtifactV4};
+use verifai_core::artifact_bin::{
+    ProofArtifactV10, ProofArtifactV5, ProofArtifactV6, ProofArtifactV7, ProofArtifactV8,
+    ProofArtifactV9, PROOF_ARTIFACT_V10_LEN, ZK_PROOF_LEN,
+};
 use verifai_core::attestation::AttestationBundle;
-use verifai_core::model_bin::{InputV0, LogisticModelV0, OutputV0};
+use verifai_core::disclosure::{DisclosedEvent, TraceDisclosureV0};
+use verifai_core::event_bin::{TraceEventV0, TraceLevel, TraceV0};
+use verifai_core::hash::HashAlg;
+use verifai_core::key_status::{KeyStatusEntry, KeyStatusListV0, KEY_STATUS_ENTRY_LEN};
+use verifai_core::model_bin::{
+    InputBatchV1, InputV0, LogisticModelV0, OutputBatchV1, OutputV0, QuantizedInputV0,
+    QuantizedLogisticModelV0, QuantizedOutputV0,
+};
+use verifai_core::preprocess::{FeatureSpec, PreprocessorV1, RawInputV1, RawValue};
+use verifai_core::salts::{CommitmentSaltsV0, COMMITMENT_SALTS_V0_LEN};
+use verifai_core::sig_alg::SigAlg;
+use verifai_core::timestamp::{TimestampTokenV0, TIMESTAMP_TOKEN_V0_LEN};
 
 fn sample_artifact() -> ProofArtifactV0 {
     ProofArtifactV0 {
@@ -106,3 +121,665 @@ fn proof_artifact_v1_layout_and_roundtrip() {
     let decoded = ProofArtifactV1::decode_bin(&encoded).expect("decode v1");
     assert_eq!(decoded, artifact);
 }
+
+#[test]
+fn batch_artifact_v2_layout_and_roundtrip() {
+    let att = AttestationBundle {
+        attester_id: [0u8; 32],
+        measurement: [5u8; 32],
+        attestation: vec![5u8; 32],
+    };
+    let artifact = BatchProofArtifactV2 {
+        version: 2,
+        runtime_id: [1u8; 32],
+        model_hash: [2u8; 32],
+        row_count: 3,
+        inputs_root: [3u8; 32],
+        outputs_root: [4u8; 32],
+        traces_root: [5u8; 32],
+        sig_pubkey: [6u8; 32],
+        signature: [7u8; 64],
+        attestation: att.clone(),
+    };
+    let encoded = artifact.encode_bin();
+    assert_eq!(
+        encoded.len(),
+        2 + 32 * 2 + 4 + 32 * 4 + 64 + att.encode_bin().len()
+    );
+    assert_eq!(u32::from_le_bytes(encoded[66..70].try_into().unwrap()), 3);
+    let decoded = BatchProofArtifactV2::decode_bin(&encoded).expect("decode v2");
+    assert_eq!(decoded, artifact);
+}
+
+#[test]
+fn proof_artifact_v3_layout_and_roundtrip() {
+    let att = AttestationBundle {
+        attester_id: [0u8; 32],
+        measurement: [5u8; 32],
+        attestation: vec![5u8; 32],
+    };
+    let mut artifact = ProofArtifactV3 {
+        version: 3,
+        runtime_id: [1u8; 32],
+        model_hash: [2u8; 32],
+        input_hash: [3u8; 32],
+        output_hash: [4u8; 32],
+        trace_root: [5u8; 32],
+        issued_at: 1_700_000_000_000_000_000,
+        expires_at: None,
+        nonce: [8u8; 32],
+        sig_pubkey: [6u8; 32],
+        signature: [7u8; 64],
+        timestamp: None,
+        attestation: att.clone(),
+    };
+    let encoded = artifact.encode_bin();
+    assert_eq!(
+        encoded.len(),
+        2 + 32 * 5 + 8 * 2 + 32 * 2 + 64 + 4 + att.encode_bin().len()
+    );
+    assert_eq!(
+        u64::from_le_bytes(encoded[162..170].try_into().unwrap()),
+        1_700_000_000_000_000_000
+    );
+    // absent expiry is encoded as 0
+    assert_eq!(&encoded[170..178], &[0u8; 8]);
+    assert_eq!(ProofArtifactV3::decode_bin(&encoded).unwrap(), artifact);
+
+    artifact.expires_at = Some(1_700_000_060_000_000_000);
+    artifact.timestamp = Some(TimestampTokenV0 {
+        version: 0,
+        message_imprint: [9u8; 32],
+        gen_time: 1_700_000_000_500_000_000,
+        serial: 42,
+        tsa_pubkey: [10u8; 32],
+        signature: [11u8; 64],
+    });
+    let encoded = artifact.encode_bin();
+    assert_eq!(
+        encoded.len(),
+        2 + 32 * 5 + 8 * 2 + 32 * 2 + 64 + 4 + TIMESTAMP_TOKEN_V0_LEN + att.encode_bin().len()
+    );
+    assert_eq!(ProofArtifactV3::decode_bin(&encoded).unwrap(), artifact);
+}
+
+#[test]
+fn proof_artifact_v4_layout_and_roundtrip() {
+    let att = AttestationBundle {
+        attester_id: [0u8; 32],
+        measurement: [5u8; 32],
+        attestation: vec![5u8; 32],
+    };
+    let mut artifact = ProofArtifactV4 {
+        version: 4,
+        runtime_id: [1u8; 32],
+        model_hash: [2u8; 32],
+        input_hash: [3u8; 32],
+        output_hash: [4u8; 32],
+        trace_root: [5u8; 32],
+        signatures: vec![
+            CoSignature {
+                pubkey: [6u8; 32],
+                signature: [7u8; 64],
+            },
+            CoSignature {
+                pubkey: [8u8; 32],
+                signature: [9u8; 64],
+            },
+        ],
+        attestation: att.clone(),
+    };
+    let encoded = artifact.encode_bin();
+    assert_eq!(
+        encoded.len(),
+        2 + 32 * 5 + 4 + 96 * 2 + att.encode_bin().len()
+    );
+    assert_eq!(&encoded[162..166], &2u32.to_le_bytes());
+    assert_eq!(&encoded[166..198], &[6u8; 32]);
+    assert_eq!(&encoded[262..294], &[8u8; 32]);
+    assert_eq!(ProofArtifactV4::decode_bin(&encoded).unwrap(), artifact);
+
+    // a signer key may appear only once
+    artifact.signatures[1].pubkey = [6u8; 32];
+    assert!(ProofArtifactV4::decode_bin(&artifact.encode_bin()).is_err());
+}
+
+#[test]
+fn proof_artifact_v5_layout_and_roundtrip() {
+    let att = AttestationBundle {
+        attester_id: [0u8; 32],
+        measurement: [5u8; 32],
+        attestation: Vec::new(),
+    };
+    let mut artifact = ProofArtifactV5 {
+        version: 5,
+        runtime_id: [1u8; 32],
+        model_hash: [2u8; 32],
+        input_hash: [3u8; 32],
+        output_hash: [4u8; 32],
+        trace_root: [5u8; 32],
+        sig_alg: SigAlg::EcdsaP256Sha256,
+        sig_pubkey: vec![2u8; 33],
+        signature: vec![7u8; 64],
+        attestation: att.clone(),
+    };
+    let encoded = artifact.encode_bin();
+    assert_eq!(
+        encoded.len(),
+        2 + 32 * 5 + 1 + 2 + 33 + 2 + 64 + att.encode_bin().len()
+    );
+    assert_eq!(encoded[162], 2);
+    assert_eq!(&encoded[163..165], &33u16.to_le_bytes());
+    assert_eq!(&encoded[165..198], &[2u8; 33]);
+    assert_eq!(&encoded[198..200], &64u16.to_le_bytes());
+    assert_eq!(&encoded[200..264], &[7u8; 64]);
+    assert_eq!(ProofArtifactV5::decode_bin(&encoded).unwrap(), artifact);
+
+    // unknown algorithm ids and key lengths the algorithm does not allow
+    let mut unknown = encoded.clone();
+    unknown[162] = 9;
+    assert!(ProofArtifactV5::decode_bin(&unknown).is_err());
+    artifact.sig_alg = SigAlg::Ed25519;
+    assert!(ProofArtifactV5::decode_bin(&artifact.encode_bin()).is_err());
+}
+
+#[test]
+fn proof_artifact_v6_layout_and_roundtrip() {
+    let att = AttestationBundle {
+        attester_id: [0u8; 32],
+        measurement: [5u8; 32],
+        attestation: Vec::new(),
+    };
+    let artifact = ProofArtifactV6 {
+        version: 6,
+        runtime_id: [1u8; 32],
+        model_hash: [2u8; 32],
+        input_hash: [3u8; 32],
+        output_hash: [4u8; 32],
+        trace_root: [5u8; 32],
+        hash_alg: HashAlg::Sha3_256,
+        sig_alg: SigAlg::Ed25519,
+        sig_pubkey: vec![6u8; 32],
+        signature: vec![7u8; 64],
+        attestation: att.clone(),
+    };
+    let encoded = artifact.encode_bin();
+    assert_eq!(
+        encoded.len(),
+        2 + 32 * 5 + 1 + 1 + 2 + 32 + 2 + 64 + att.encode_bin().len()
+    );
+    assert_eq!(&encoded[0..2], &6u16.to_le_bytes());
+    assert_eq!(encoded[162], 3);
+    assert_eq!(encoded[163], 1);
+    assert_eq!(&encoded[164..166], &32u16.to_le_bytes());
+    assert_eq!(&encoded[166..198], &[6u8; 32]);
+    assert_eq!(&encoded[198..200], &64u16.to_le_bytes());
+    assert_eq!(&encoded[200..264], &[7u8; 64]);
+    assert_eq!(ProofArtifactV6::decode_bin(&encoded).unwrap(), artifact);
+
+    let mut unknown = encoded.clone();
+    unknown[162] = 9;
+    assert!(ProofArtifactV6::decode_bin(&unknown).is_err());
+    let mut v5 = encoded;
+    v5[0] = 5;
+    assert!(ProofArtifactV6::decode_bin(&v5).is_err());
+}
+
+#[test]
+fn proof_artifact_v7_layout_and_roundtrip() {
+    let att = AttestationBundle {
+        attester_id: [0u8; 32],
+        measurement: [5u8; 32],
+        attestation: Vec::new(),
+    };
+    let artifact = ProofArtifactV7 {
+        version: 7,
+        runtime_id: [1u8; 32],
+        model_commitment: [2u8; 32],
+        input_commitment: [3u8; 32],
+        output_commitment: [4u8; 32],
+        trace_commitment: [5u8; 32],
+        sig_pubkey: [6u8; 32],
+        signature: [7u8; 64],
+        attestation: att.clone(),
+    };
+    let encoded = artifact.encode_bin();
+    assert_eq!(encoded.len(), 2 + 32 * 6 + 64 + att.encode_bin().len());
+    assert_eq!(&encoded[0..2], &7u16.to_le_bytes());
+    assert_eq!(&encoded[34..66], &[2u8; 32]);
+    assert_eq!(&encoded[130..162], &[5u8; 32]);
+    assert_eq!(&encoded[162..194], &[6u8; 32]);
+    assert_eq!(&encoded[194..258], &[7u8; 64]);
+    assert_eq!(ProofArtifactV7::decode_bin(&encoded).unwrap(), artifact);
+
+    let mut v1 = encoded;
+    v1[0] = 1;
+    assert!(ProofArtifactV7::decode_bin(&v1).is_err());
+}
+
+#[test]
+fn proof_artifact_v8_layout_and_roundtrip() {
+    let att = AttestationBundle {
+        attester_id: [0u8; 32],
+        measurement: [6u8; 32],
+        attestation: Vec::new(),
+    };
+    let artifact = ProofArtifactV8 {
+        version: 8,
+        runtime_id: [1u8; 32],
+        model_hash: [2u8; 32],
+        preprocessor_hash: [3u8; 32],
+        input_hash: [4u8; 32],
+        output_hash: [5u8; 32],
+        trace_root: [6u8; 32],
+        sig_pubkey: [7u8; 32],
+        signature: [8u8; 64],
+        attestation: att.clone(),
+    };
+    let encoded = artifact.encode_bin();
+    assert_eq!(encoded.len(), 2 + 32 * 7 + 64 + att.encode_bin().len());
+    assert_eq!(&encoded[0..2], &8u16.to_le_bytes());
+    assert_eq!(&encoded[66..98], &[3u8; 32]);
+    assert_eq!(&encoded[98..130], &[4u8; 32]);
+    assert_eq!(&encoded[162..194], &[6u8; 32]);
+    assert_eq!(&encoded[194..226], &[7u8; 32]);
+    assert_eq!(&encoded[226..290], &[8u8; 64]);
+    assert_eq!(ProofArtifactV8::decode_bin(&encoded).unwrap(), artifact);
+
+    let mut v7 = encoded;
+    v7[0] = 7;
+    assert!(ProofArtifactV8::decode_bin(&v7).is_err());
+}
+
+#[test]
+fn proof_artifact_v9_layout_and_roundtrip() {
+    let att = AttestationBundle {
+        attester_id: [0u8; 32],
+        measurement: [5u8; 32],
+        attestation: Vec::new(),
+    };
+    let artifact = ProofArtifactV9 {
+        version: 9,
+        runtime_id: [1u8; 32],
+        model_hash: [2u8; 32],
+        input_hash: [3u8; 32],
+        output_hash: [4u8; 32],
+        trace_level: TraceLevel::Fine,
+        trace_root: [5u8; 32],
+        sig_pubkey: [6u8; 32],
+        signature: [7u8; 64],
+        attestation: att.clone(),
+    };
+    let encoded = artifact.encode_bin();
+    assert_eq!(encoded.len(), 2 + 32 * 6 + 1 + 64 + att.encode_bin().len());
+    assert_eq!(&encoded[0..2], &9u16.to_le_bytes());
+    assert_eq!(&encoded[98..130], &[4u8; 32]);
+    assert_eq!(encoded[130], TraceLevel::Fine.id());
+    assert_eq!(&encoded[131..163], &[5u8; 32]);
+    assert_eq!(&encoded[163..195], &[6u8; 32]);
+    assert_eq!(&encoded[195..259], &[7u8; 64]);
+    assert_eq!(ProofArtifactV9::decode_bin(&encoded).unwrap(), artifact);
+
+    let mut bad_level = encoded.clone();
+    bad_level[130] = 0;
+    assert!(ProofArtifactV9::decode_bin(&bad_level).is_err());
+    let mut v8 = encoded;
+    v8[0] = 8;
+    assert!(ProofArtifactV9::decode_bin(&v8).is_err());
+}
+
+#[test]
+fn proof_artifact_v10_layout_and_roundtrip() {
+    let mut artifact = ProofArtifactV10 {
+        version: 10,
+        runtime_id: [1u8; 32],
+        model_hash: [2u8; 32],
+        input_hash: [3u8; 32],
+        output_hash: [4u8; 32],
+        circuit_hash: [5u8; 32],
+        proof: [6u8; ZK_PROOF_LEN],
+        sig_pubkey: [0u8; 32],
+        signature: [0u8; 64],
+    };
+    artifact.sign_detached([9u8; 32]).unwrap();
+    artifact.verify_signature().unwrap();
+    assert!(artifact
+        .message_to_sign()
+        .starts_with(b"VERIFAI\0ARTIFACT\0V10"));
+
+    let encoded = artifact.encode_bin();
+    assert_eq!(encoded.len(), PROOF_ARTIFACT_V10_LEN);
+    assert_eq!(&encoded[0..2], &10u16.to_le_bytes());
+    assert_eq!(&encoded[130..162], &[5u8; 32]);
+    assert_eq!(&encoded[162..290], &[6u8; ZK_PROOF_LEN]);
+    assert_eq!(&encoded[290..322], &artifact.sig_pubkey);
+    assert_eq!(&encoded[322..386], &artifact.signature);
+    assert_eq!(ProofArtifactV10::decode_bin(&encoded).unwrap(), artifact);
+
+    let mut v9 = encoded.clone();
+    v9[0] = 9;
+    assert!(ProofArtifactV10::decode_bin(&v9).is_err());
+    assert!(ProofArtifactV10::decode_bin(&encoded[..385]).is_err());
+}
+
+#[test]
+fn quantized_model_input_output_layout_and_roundtrip() {
+    let model = QuantizedLogisticModelV0::quantize(&LogisticModelV0 {
+        weights: vec![0.5, -1.0],
+        bias: -0.25,
+    })
+    .unwrap();
+    assert_eq!(model.weights, vec![1 << 15, -(1 << 16)]);
+    assert_eq!(model.bias, -(1 << 30));
+    let input = QuantizedInputV0::quantize(&InputV0 { x: vec![2.0, 0.5] }).unwrap();
+    assert_eq!(input.x, vec![1 << 17, 1 << 15]);
+    // 0.5 * 2 - 1 * 0.5 - 0.25, scaled by 2^32
+    assert_eq!(model.logit(&input), Some(1 << 30));
+
+    let model_bin = model.encode_bin();
+    assert_eq!(&model_bin[0..8], b"VFAIQMD0");
+    assert_eq!(&model_bin[8..12], &2u32.to_le_bytes());
+    assert_eq!(&model_bin[16..20], &(-(1i32 << 16)).to_le_bytes());
+    assert_eq!(&model_bin[20..28], &(-(1i64 << 30)).to_le_bytes());
+    assert_eq!(model_bin.len(), 28);
+    assert_eq!(
+        QuantizedLogisticModelV0::decode_bin(&model_bin).unwrap(),
+        model
+    );
+
+    let input_bin = input.encode_bin();
+    assert_eq!(&input_bin[0..8], b"VFAIQIN0");
+    assert_eq!(input_bin.len(), 12 + 2 * 4);
+    assert_eq!(QuantizedInputV0::decode_bin(&input_bin).unwrap(), input);
+
+    let output = QuantizedOutputV0 { logit: -7 };
+    let output_bin = output.encode_bin();
+    assert_eq!(&output_bin[0..8], b"VFAIQOU0");
+    assert_eq!(&output_bin[8..16], &(-7i64).to_le_bytes());
+    assert_eq!(QuantizedOutputV0::decode_bin(&output_bin).unwrap(), output);
+
+    // out of range, not finite, or the wrong dimension
+    let big = LogisticModelV0 {
+        weights: vec![40000.0],
+        bias: 0.0,
+    };
+    assert_eq!(QuantizedLogisticModelV0::quantize(&big), None);
+    assert_eq!(
+        QuantizedInputV0::quantize(&InputV0 { x: vec![f64::NAN] }),
+        None
+    );
+    assert_eq!(model.logit(&QuantizedInputV0 { x: vec![1] }), None);
+}
+
+#[test]
+fn granular_trace_event_layout_and_roundtrip() {
+    let layer = TraceEventV0::OpLayer {
+        layer: 1,
+        digest: [3u8; 32],
+    };
+    let encoded = layer.encode_bin();
+    assert_eq!(encoded[0], 0x05);
+    assert_eq!(&encoded[1..5], &1u32.to_le_bytes());
+    assert_eq!(&encoded[5..37], &[3u8; 32]);
+    assert_eq!(encoded.len(), 37);
+    assert_eq!(TraceEventV0::decode_bin(&encoded).unwrap(), layer);
+
+    let mac = TraceEventV0::OpMac {
+        op_id: 200,
+        index: 1,
+        acc: -0.25,
+    };
+    let encoded = mac.encode_bin();
+    assert_eq!(encoded[0], 0x06);
+    assert_eq!(&encoded[1..5], &200u32.to_le_bytes());
+    assert_eq!(&encoded[5..9], &1u32.to_le_bytes());
+    assert_eq!(&encoded[9..17], &(-0.25f64).to_le_bytes());
+    assert_eq!(TraceEventV0::decode_bin(&encoded).unwrap(), mac);
+    assert!(TraceEventV0::decode_bin(&encoded[..16]).is_err());
+
+    for level in TraceLevel::ALL {
+        assert_eq!(TraceLevel::from_id(level.id()), Some(level));
+        assert_eq!(TraceLevel::from_name(level.name()), Some(level));
+    }
+}
+
+#[test]
+fn preprocessor_layout_and_roundtrip() {
+    let preprocessor = PreprocessorV1 {
+        raw_len: 2,
+        features: vec![
+            FeatureSpec::Numeric {
+                source: 0,
+                default: 1.0,
+                clip: Some((0.0, 10.0)),
+                mean: 2.0,
+                std: 4.0,
+            },
+            FeatureSpec::Categorical {
+                source: 1,
+                categories: vec!["a".into(), "b".into()],
+                default: Some(1),
+            },
+        ],
+    };
+    assert_eq!(preprocessor.output_len(), 3);
+    let encoded = preprocessor.encode_bin();
+    assert_eq!(&encoded[0..8], b"VFAIPRE1");
+    assert_eq!(&encoded[8..12], &2u32.to_le_bytes());
+    assert_eq!(&encoded[12..16], &2u32.to_le_bytes());
+    assert_eq!(encoded[16], 1);
+    assert_eq!(&encoded[21..29], &1.0f64.to_le_bytes());
+    assert_eq!(encoded[29], 1);
+    assert_eq!(&encoded[38..46], &10.0f64.to_le_bytes());
+    assert_eq!(&encoded[54..62], &4.0f64.to_le_bytes());
+    assert_eq!(encoded[62], 2);
+    assert_eq!(&encoded[63..67], &1u32.to_le_bytes());
+    assert_eq!(&encoded[67..71], &1u32.to_le_bytes());
+    assert_eq!(&encoded[71..75], &2u32.to_le_bytes());
+    assert_eq!(&encoded[75..78], b"\x01\x00a");
+    assert_eq!(encoded.len(), 81);
+    assert_eq!(PreprocessorV1::decode_bin(&encoded).unwrap(), preprocessor);
+
+    let reject = |edit: fn(&mut PreprocessorV1)| {
+        let mut bad = preprocessor.clone();
+        edit(&mut bad);
+        assert!(PreprocessorV1::decode_bin(&bad.encode_bin()).is_err());
+    };
+    reject(|p| p.raw_len = 1);
+    reject(|p| {
+        if let FeatureSpec::Numeric { std, .. } = &mut p.features[0] {
+            *std = 0.0;
+        }
+    });
+    reject(|p| {
+        if let FeatureSpec::Numeric { clip, .. } = &mut p.features[0] {
+            *clip = Some((10.0, 0.0));
+        }
+    });
+    reject(|p| {
+        if let FeatureSpec::Categorical { categories, .. } = &mut p.features[1] {
+            categories[1] = "a".into();
+        }
+    });
+    reject(|p| {
+        if let FeatureSpec::Categorical { default, .. } = &mut p.features[1] {
+            *default = Some(2);
+        }
+    });
+    let mut unused_clip = encoded.clone();
+    unused_clip[29] = 0;
+    assert!(PreprocessorV1::decode_bin(&unused_clip).is_err());
+    let mut trailing = encoded;
+    trailing.push(0);
+    assert!(PreprocessorV1::decode_bin(&trailing).is_err());
+}
+
+#[test]
+fn raw_input_layout_and_roundtrip() {
+    let raw = RawInputV1 {
+        values: vec![
+            RawValue::Number(1.5),
+            RawValue::Missing,
+            RawValue::Category("red".into()),
+        ],
+    };
+    let encoded = raw.encode_bin();
+    assert_eq!(&encoded[0..8], b"VFAIRAW1");
+    assert_eq!(&encoded[8..12], &3u32.to_le_bytes());
+    assert_eq!(encoded[12], 1);
+    assert_eq!(&encoded[13..21], &1.5f64.to_le_bytes());
+    assert_eq!(encoded[21], 0);
+    assert_eq!(&encoded[22..28], b"\x02\x03\x00red");
+    assert_eq!(encoded.len(), 28);
+    assert_eq!(RawInputV1::decode_bin(&encoded).unwrap(), raw);
+
+    assert!(RawInputV1::decode_bin(&encoded[..27]).is_err());
+    let mut bad_tag = encoded;
+    bad_tag[21] = 3;
+    assert!(RawInputV1::decode_bin(&bad_tag).is_err());
+}
+
+#[test]
+fn commitment_salts_layout_and_roundtrip() {
+    let salts = CommitmentSaltsV0 {
+        model: [1u8; 32],
+        input: [2u8; 32],
+        output: [3u8; 32],
+        trace: [4u8; 32],
+    };
+    let encoded = salts.encode_bin();
+    assert_eq!(encoded.len(), COMMITMENT_SALTS_V0_LEN);
+    assert_eq!(&encoded[0..8], b"VFAISLT0");
+    assert_eq!(&encoded[8..40], &[1u8; 32]);
+    assert_eq!(&encoded[104..136], &[4u8; 32]);
+    assert_eq!(CommitmentSaltsV0::decode_bin(&encoded).unwrap(), salts);
+
+    assert!(CommitmentSaltsV0::decode_bin(&encoded[..135]).is_err());
+    let mut bad_magic = encoded;
+    bad_magic[0] ^= 0xFF;
+    assert!(CommitmentSaltsV0::decode_bin(&bad_magic).is_err());
+    // the secret salts stay out of debug output
+    assert!(!format!("{salts:?}").contains("1, 1"));
+}
+
+#[test]
+fn trace_disclosure_layout_and_roundtrip() {
+    let trace = vec![
+        TraceEventV0::OpLinear { op_id: 0, z: 1.5 },
+        TraceEventV0::OpOutput { y: 0.8 },
+        TraceEventV0::OpLinear { op_id: 1, z: -2.0 },
+    ];
+    let disclosure = TraceDisclosureV0::from_trace(HashAlg::Sha256, &trace, &[1, 1]).unwrap();
+    assert_eq!(
+        disclosure.events,
+        vec![DisclosedEvent {
+            index: 1,
+            event: TraceEventV0::OpOutput { y: 0.8 },
+        }]
+    );
+    let encoded = disclosure.encode_bin();
+    assert_eq!(&encoded[0..8], b"VFAITRD0");
+    assert_eq!(encoded[8], HashAlg::Sha256.id());
+    assert_eq!(&encoded[9..13], &3u32.to_le_bytes());
+    assert_eq!(&encoded[13..17], &1u32.to_le_bytes());
+    assert_eq!(&encoded[17..21], &1u32.to_le_bytes());
+    assert_eq!(&encoded[21..23], &9u16.to_le_bytes());
+    assert_eq!(&encoded[23..32], trace[1].encode_bin().as_slice());
+    let proof_count = disclosure.proof.len() as u32;
+    assert_eq!(&encoded[32..36], &proof_count.to_le_bytes());
+    assert_eq!(encoded.len(), 36 + 32 * disclosure.proof.len());
+    assert_eq!(TraceDisclosureV0::decode_bin(&encoded).unwrap(), disclosure);
+
+    assert!(TraceDisclosureV0::decode_bin(&encoded[..encoded.len() - 1]).is_err());
+    let mut trailing = encoded.clone();
+    trailing.push(0);
+    assert!(TraceDisclosureV0::decode_bin(&trailing).is_err());
+    let mut beyond = encoded.clone();
+    beyond[17] = 3;
+    assert!(TraceDisclosureV0::decode_bin(&beyond).is_err());
+    let mut bad_alg = encoded;
+    bad_alg[8] = 0;
+    assert!(TraceDisclosureV0::decode_bin(&bad_alg).is_err());
+    assert!(TraceDisclosureV0::from_trace(HashAlg::Sha256, &trace, &[3]).is_none());
+}
+
+#[test]
+fn trace_file_layout_and_roundtrip() {
+    let trace = TraceV0 {
+        events: vec![
+            TraceEventV0::OpLinear { op_id: 0, z: 1.5 },
+            TraceEventV0::OpOutput { y: 0.8 },
+        ],
+    };
+    let encoded = trace.encode_bin();
+    assert_eq!(&encoded[0..8], b"VFAITRC0");
+    assert_eq!(&encoded[8..10], &13u16.to_le_bytes());
+    assert_eq!(&encoded[10..23], trace.events[0].encode_bin().as_slice());
+    assert_eq!(&encoded[23..25], &9u16.to_le_bytes());
+    assert_eq!(encoded.len(), 34);
+    assert_eq!(TraceV0::decode_bin(&encoded).unwrap(), trace);
+    assert_eq!(
+        TraceV0::decode_bin(b"VFAITRC0").unwrap(),
+        TraceV0::default()
+    );
+
+    assert!(TraceV0::decode_bin(&encoded[..33]).is_err());
+    assert!(TraceV0::decode_bin(&encoded[..9]).is_err());
+}
+
+#[test]
+fn key_status_list_layout_and_roundtrip() {
+    let list = KeyStatusListV0 {
+        issued_at: 1_700_000_000_000_000_000,
+        entries: vec![
+            KeyStatusEntry {
+                pubkey: [1u8; 32],
+                valid_from: 10,
+                valid_until: Some(20),
+                revoked_at: None,
+            },
+            KeyStatusEntry {
+                pubkey: [2u8; 32],
+                valid_from: 20,
+                valid_until: None,
+                revoked_at: Some(30),
+            },
+        ],
+        authority_pubkey: [3u8; 32],
+        signature: [4u8; 64],
+    };
+    let encoded = list.encode_bin();
+    assert_eq!(
+        encoded.len(),
+        8 + 8 + 4 + KEY_STATUS_ENTRY_LEN * 2 + 32 + 64
+    );
+    assert_eq!(&encoded[..8], b"VFAIKSL0");
+    assert_eq!(&encoded[16..20], &2u32.to_le_bytes());
+    // absent times are encoded as 0
+    assert_eq!(&encoded[68..76], &[0u8; 8]);
+    assert_eq!(&encoded[116..124], &[0u8; 8]);
+    assert_eq!(KeyStatusListV0::decode_bin(&encoded).unwrap(), list);
+    assert!(KeyStatusListV0::decode_bin(&encoded[..encoded.len() - 1]).is_err());
+}
+
+#[test]
+fn input_output_batch_roundtrip() {
+    let inputs = InputBatchV1 {
+        rows: vec![InputV0 { x: vec![1.0, 2.0] }, InputV0 { x: vec![3.0] }],
+    };
+    let outputs = OutputBatchV1 {
+        rows: vec![OutputV0 { y: 0.25 }, OutputV0 { y: 0.75 }],
+    };
+
+    let input_bin = inputs.encode_bin();
+    assert_eq!(&input_bin[0..8], b"VFAIINB1");
+    assert_eq!(input_bin.len(), 12 + (4 + 16) + (4 + 8));
+    assert_eq!(InputBatchV1::decode_bin(&input_bin).unwrap(), inputs);
+
+    let output_bin = outputs.encode_bin();
+    assert_eq!(&output_bin[0..8], b"VFAIOUB1");
+    assert_eq!(output_bin.len(), 12 + 16);
+    assert_eq!(OutputBatchV1::decode_bin(&output_bin).unwrap(), outputs);
+
+    assert!(InputBatchV1::decode_bin(&input_bin[..input_bin.len() - 1]).is_err());
+}
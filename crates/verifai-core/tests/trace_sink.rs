@@ -0,0 +1,115 @@
+use std::io::{self, Write};
+
+use verifai_core::event_bin::{ActivationKind, TraceEventV0, TraceV0};
+use verifai_core::merkle::{trace_root_from_event_bytes, MerkleBuilder};
+use verifai_core::trace_sink::{JsonlTraceWriter, Tee, TraceFileWriter, TraceSink};
+
+fn events() -> Vec<TraceEventV0> {
+    vec![
+        TraceEventV0::OpLinear { op_id: 0, z: 0.25 },
+        TraceEventV0::OpActivation {
+            op_id: 1,
+            kind: ActivationKind::Sigmoid,
+            input: 0.25,
+            output: 0.562,
+        },
+        TraceEventV0::OpOutput { y: 0.562 },
+    ]
+}
+
+#[test]
+fn file_writer_matches_trace_v0_encoding() {
+    let mut file = TraceFileWriter::new(Vec::new());
+    for event in &events() {
+        file.record(event);
+    }
+    let bytes = file.finish().unwrap();
+    assert_eq!(bytes, TraceV0 { events: events() }.encode_bin());
+    assert_eq!(TraceV0::decode_bin(&bytes).unwrap().events, events());
+
+    let empty = TraceFileWriter::new(Vec::new()).finish().unwrap();
+    assert_eq!(empty, TraceV0::default().encode_bin());
+}
+
+#[test]
+fn tee_feeds_both_sinks_in_one_pass() {
+    let mut tree = MerkleBuilder::new();
+    let mut kept: Vec<TraceEventV0> = Vec::new();
+    let mut ignored = ();
+    let mut sink = Tee(&mut tree, Tee(&mut kept, &mut ignored));
+    for event in &events() {
+        sink.record(event);
+    }
+    let event_bytes: Vec<Vec<u8>> = events().iter().map(|e| e.encode_bin()).collect();
+    assert_eq!(tree.finalize(), trace_root_from_event_bytes(&event_bytes));
+    assert_eq!(kept, events());
+}
+
+/// Accepts `room` bytes, then fails every write.
+#[derive(Debug)]
+struct Full {
+    room: usize,
+}
+
+impl Write for Full {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.room == 0 {
+            return Err(io::Error::new(io::ErrorKind::StorageFull, "full"));
+        }
+        let n = buf.len().min(self.room);
+        self.room -= n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn file_writer_reports_the_first_write_error_on_finish() {
+    let mut file = TraceFileWriter::new(Full { room: 12 });
+    for event in &events() {
+        file.record(event);
+    }
+    assert_eq!(
+        file.finish().unwrap_err().kind(),
+        io::ErrorKind::StorageFull
+    );
+
+    let file = TraceFileWriter::new(Full { room: 0 });
+    assert!(file.finish().is_err());
+}
+
+fn kind_line(index: u32, event: &TraceEventV0) -> io::Result<String> {
+    let kind = match event {
+        TraceEventV0::OpLinear { .. } => "linear",
+        TraceEventV0::OpActivation { .. } => "activation",
+        _ => "other",
+    };
+    Ok(format!("{{\"index\":{index},\"kind\":\"{kind}\"}}"))
+}
+
+#[test]
+fn jsonl_writer_numbers_one_line_per_event() {
+    let mut file = JsonlTraceWriter::new(Vec::new(), kind_line);
+    for event in &events() {
+        file.record(event);
+    }
+    let text = String::from_utf8(file.finish().unwrap()).unwrap();
+    assert_eq!(
+        text,
+        "{\"index\":0,\"kind\":\"linear\"}\n\
+         {\"index\":1,\"kind\":\"activation\"}\n\
+         {\"index\":2,\"kind\":\"other\"}\n"
+    );
+
+    let mut file = JsonlTraceWriter::new(Full { room: 30 }, kind_line);
+    for event in &events() {
+        file.record(event);
+    }
+    assert_eq!(
+        file.finish().unwrap_err().kind(),
+        io::ErrorKind::StorageFull
+    );
+}
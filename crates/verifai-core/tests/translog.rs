@@ -0,0 +1,134 @@
+use verifai_core::hash::sha256;
+use verifai_core::merkle::{empty_root, leaf_hash, node_hash, trace_root_from_event_bytes};
+use verifai_core::translog::{
+    log_consistency_proof, log_inclusion_proof, log_root, verify_log_consistency,
+    verify_log_inclusion, LogConsistencyProofV0, LogInclusionProofV0, SignedTreeHeadV0,
+};
+
+fn entries(n: usize) -> Vec<[u8; 32]> {
+    (0..n as u32).map(|i| sha256(&i.to_le_bytes())).collect()
+}
+
+#[test]
+fn log_root_small_trees() {
+    let e = entries(3);
+    assert_eq!(log_root(&[]), empty_root());
+    assert_eq!(log_root(&e[..1]), leaf_hash(&e[0]));
+    // RFC 6962 shape: the third leaf is not duplicated
+    let expected = node_hash(
+        node_hash(leaf_hash(&e[0]), leaf_hash(&e[1])),
+        leaf_hash(&e[2]),
+    );
+    assert_eq!(log_root(&e), expected);
+}
+
+#[test]
+fn log_root_matches_trace_root_for_powers_of_two() {
+    for n in [1usize, 2, 4, 8, 16] {
+        let e = entries(n);
+        let as_bytes: Vec<Vec<u8>> = e.iter().map(|x| x.to_vec()).collect();
+        assert_eq!(log_root(&e), trace_root_from_event_bytes(&as_bytes));
+    }
+}
+
+#[test]
+fn inclusion_proofs_verify_for_every_index() {
+    for n in 1..=17usize {
+        let e = entries(n);
+        let root = log_root(&e);
+        for i in 0..n {
+            let proof = log_inclusion_proof(&e, i).unwrap();
+            assert!(verify_log_inclusion(e[i], i as u64, n as u64, &proof, root));
+            let other = (i + 1) % n;
+            if other != i {
+                assert!(!verify_log_inclusion(
+                    e[i],
+                    other as u64,
+                    n as u64,
+                    &proof,
+                    root
+                ));
+            }
+            assert!(!verify_log_inclusion(
+                sha256(b"other"),
+                i as u64,
+                n as u64,
+                &proof,
+                root
+            ));
+        }
+        assert!(log_inclusion_proof(&e, n).is_none());
+    }
+}
+
+#[test]
+fn consistency_proofs_verify_for_every_prefix() {
+    for n in 1..=17usize {
+        let e = entries(n);
+        let new_root = log_root(&e);
+        for m in 0..=n {
+            let old_root = log_root(&e[..m]);
+            let proof = log_consistency_proof(&e, m).unwrap();
+            assert!(
+                verify_log_consistency(m as u64, n as u64, old_root, new_root, &proof),
+                "m={m} n={n}"
+            );
+            if m > 0 && m < n {
+                let forked = sha256(b"forked");
+                assert!(!verify_log_consistency(
+                    m as u64, n as u64, forked, new_root, &proof
+                ));
+                assert!(!verify_log_consistency(
+                    m as u64, n as u64, old_root, forked, &proof
+                ));
+            }
+        }
+    }
+}
+
+#[test]
+fn tree_head_sign_verify_roundtrip() {
+    let e = entries(5);
+    let mut head = SignedTreeHeadV0 {
+        version: 0,
+        tree_size: 5,
+        root_hash: log_root(&e),
+        log_pubkey: [0u8; 32],
+        signature: [0u8; 64],
+    };
+    head.sign_detached([9u8; 32]).unwrap();
+    head.verify_signature().unwrap();
+
+    let decoded = SignedTreeHeadV0::decode_bin(&head.encode_bin()).unwrap();
+    assert_eq!(decoded, head);
+
+    let mut tampered = head.clone();
+    tampered.tree_size = 6;
+    assert!(tampered.verify_signature().is_err());
+}
+
+#[test]
+fn proof_encodings_roundtrip() {
+    let e = entries(6);
+    let inclusion = LogInclusionProofV0 {
+        leaf_index: 4,
+        tree_size: 6,
+        path: log_inclusion_proof(&e, 4).unwrap(),
+    };
+    assert_eq!(
+        LogInclusionProofV0::decode_bin(&inclusion.encode_bin()).unwrap(),
+        inclusion
+    );
+
+    let consistency = LogConsistencyProofV0 {
+        old_size: 3,
+        new_size: 6,
+        path: log_consistency_proof(&e, 3).unwrap(),
+    };
+    let bytes = consistency.encode_bin();
+    assert_eq!(
+        LogConsistencyProofV0::decode_bin(&bytes).unwrap(),
+        consistency
+    );
+    assert!(LogConsistencyProofV0::decode_bin(&bytes[..bytes.len() - 1]).is_err());
+}
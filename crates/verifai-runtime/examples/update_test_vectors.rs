@@ -4,8 +4,13 @@ use std::fmt;
 use std::fs;
 use std::path::Path;
 
+use verifai_core::hash::HashAlg;
 use verifai_core::model_bin::{InputV0, LogisticModelV0, MlpModelV1};
-use verifai_runtime::{prove_lr_v0, prove_mlp_v1, VerifaiError};
+use verifai_core::sig_alg::{AlgSigningKey, SigAlg};
+use verifai_runtime::{
+    prove_lr_v0, prove_lr_v5_with_attester, prove_lr_v6_with_attester, prove_mlp_v1, NoopAttester,
+    VerifaiError,
+};
 
 const RUNTIME_ID: [u8; 32] = [7u8; 32];
 const SIGNING_KEY: [u8; 32] = [9u8; 32];
@@ -48,6 +53,13 @@ fn main() -> Result<(), Box<dyn Error>> {
             update_logistic_case(&path)?;
         } else if name.starts_with("mlp-") {
             update_mlp_case(&path)?;
+        } else if let Some(alg) = name.strip_prefix("sigalg-") {
+            let alg = SigAlg::from_name(alg).ok_or_else(|| format!("unknown algorithm: {name}"))?;
+            update_sig_alg_case(&path, alg)?;
+        } else if let Some(alg) = name.strip_prefix("hashalg-") {
+            let alg =
+                HashAlg::from_name(alg).ok_or_else(|| format!("unknown algorithm: {name}"))?;
+            update_hash_alg_case(&path, alg)?;
         }
     }
 
@@ -80,6 +92,64 @@ fn update_logistic_case(dir: &Path) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Logistic case proven as a `ProofArtifactV5` signed with `alg`.
+fn update_sig_alg_case(dir: &Path, alg: SigAlg) -> Result<(), Box<dyn Error>> {
+    println!("Updating {} test vector {}", alg.name(), dir.display());
+    let model_json = read_json::<LogisticModelJson>(&dir.join("model.json"))?;
+    let input_json = read_json::<InputJson>(&dir.join("input.json"))?;
+
+    let model = LogisticModelV0 {
+        weights: model_json.weights,
+        bias: model_json.bias,
+    };
+    let input = InputV0 { x: input_json.x };
+
+    let model_bin = model.encode_bin();
+    let input_bin = input.encode_bin();
+    let key = AlgSigningKey::new(alg, SIGNING_KEY).map_err(|e| format!("{e:?}"))?;
+    let (output_bin, artifact_bin) =
+        match prove_lr_v5_with_attester::<NoopAttester>(RUNTIME_ID, &key, &model_bin, &input_bin) {
+            Ok(res) => res,
+            Err(e) => return Err(Box::new(UpdateError(e))),
+        };
+
+    fs::write(dir.join("model.bin"), &model_bin)?;
+    fs::write(dir.join("input.bin"), &input_bin)?;
+    fs::write(dir.join("expected_output.bin"), &output_bin)?;
+    fs::write(dir.join("expected_artifact.bin"), &artifact_bin)?;
+    Ok(())
+}
+
+/// Logistic case proven as an Ed25519-signed `ProofArtifactV6` hashed with
+/// `alg`.
+fn update_hash_alg_case(dir: &Path, alg: HashAlg) -> Result<(), Box<dyn Error>> {
+    println!("Updating {} test vector {}", alg.name(), dir.display());
+    let model_json = read_json::<LogisticModelJson>(&dir.join("model.json"))?;
+    let input_json = read_json::<InputJson>(&dir.join("input.json"))?;
+
+    let model = LogisticModelV0 {
+        weights: model_json.weights,
+        bias: model_json.bias,
+    };
+    let input = InputV0 { x: input_json.x };
+
+    let model_bin = model.encode_bin();
+    let input_bin = input.encode_bin();
+    let key = AlgSigningKey::new(SigAlg::Ed25519, SIGNING_KEY).map_err(|e| format!("{e:?}"))?;
+    let (output_bin, artifact_bin) = match prove_lr_v6_with_attester::<NoopAttester>(
+        RUNTIME_ID, &key, alg, &model_bin, &input_bin,
+    ) {
+        Ok(res) => res,
+        Err(e) => return Err(Box::new(UpdateError(e))),
+    };
+
+    fs::write(dir.join("model.bin"), &model_bin)?;
+    fs::write(dir.join("input.bin"), &input_bin)?;
+    fs::write(dir.join("expected_output.bin"), &output_bin)?;
+    fs::write(dir.join("expected_artifact.bin"), &artifact_bin)?;
+    Ok(())
+}
+
 fn update_mlp_case(dir: &Path) -> Result<(), Box<dyn Error>> {
     println!("Updating MLP test vector {}", dir.display());
     let model_json = read_json::<MlpModelJson>(&dir.join("model.json"))?;
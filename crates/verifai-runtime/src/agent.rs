@@ -0,0 +1,189 @@
+//! Out-of-process signing over a Unix socket.
+//!
+//! The agent holds the secret key and answers two requests, each a single
+//! op byte on a fresh connection:
+//! - `1`: public key. Reply `0 || sig_alg id (1) || pubkey`.
+//! - `2`: sign, followed by `u32_le(len) || msg`. Reply `0 || signature (64)`.
+//!
+//! Failures reply `1 || u32_le(len) || utf-8 reason`. The agent only signs
+//! artifact messages (`VERIFAI\0ARTIFACT\0V…`), so a client that reaches the
+//! socket cannot use the key to sign tree heads, timestamps, key-status lists
+//! or anything else.
+
+use std::io::{self, Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use verifai_core::artifact_bin::ARTIFACT_SIGN_PREFIXES;
+use verifai_core::sig_alg::{AlgSigningKey, SigAlg};
+use verifai_core::signer::{Signer, SignerError};
+
+use crate::VerifaiError;
+
+const OP_PUBLIC_KEY: u8 = 1;
+const OP_SIGN: u8 = 2;
+const STATUS_OK: u8 = 0;
+const STATUS_ERR: u8 = 1;
+
+/// Largest message the agent accepts, to bound its memory per request.
+pub const AGENT_MAX_MESSAGE_LEN: usize = 1 << 20;
+
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A `Signer` backed by a signing agent at `socket`.
+///
+/// The algorithm and public key are fetched once on `connect`; every
+/// signature opens a new connection.
+#[derive(Debug, Clone)]
+pub struct AgentSigner {
+    socket: PathBuf,
+    sig_alg: SigAlg,
+    public_key: Vec<u8>,
+}
+
+impl AgentSigner {
+    pub fn connect(socket: impl AsRef<Path>) -> Result<Self, VerifaiError> {
+        let socket = socket.as_ref().to_path_buf();
+        let reply = request(&socket, &[OP_PUBLIC_KEY]).map_err(VerifaiError::SignerFailed)?;
+        let malformed = || VerifaiError::SignerFailed("agent sent a malformed public key".into());
+        let (&alg_id, public_key) = reply.split_first().ok_or_else(malformed)?;
+        let sig_alg = SigAlg::from_id(alg_id).ok_or_else(malformed)?;
+        if !sig_alg.pubkey_len_ok(public_key.len()) {
+            return Err(malformed());
+        }
+        Ok(Self {
+            socket,
+            sig_alg,
+            public_key: public_key.to_vec(),
+        })
+    }
+}
+
+impl Signer for AgentSigner {
+    fn sig_alg(&self) -> SigAlg {
+        self.sig_alg
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        self.public_key.clone()
+    }
+
+    fn sign(&self, msg: &[u8]) -> Result<[u8; 64], SignerError> {
+        let mut req = Vec::with_capacity(1 + 4 + msg.len());
+        req.push(OP_SIGN);
+        req.extend_from_slice(&(msg.len() as u32).to_le_bytes());
+        req.extend_from_slice(msg);
+        let reply = request(&self.socket, &req).map_err(SignerError)?;
+        reply
+            .try_into()
+            .map_err(|_| SignerError("agent sent a malformed signature".into()))
+    }
+}
+
+fn request(socket: &Path, req: &[u8]) -> Result<Vec<u8>, String> {
+    let io_failed = |e: io::Error| format!("agent {}: {e}", socket.display());
+    let mut stream = UnixStream::connect(socket).map_err(io_failed)?;
+    stream
+        .set_read_timeout(Some(IO_TIMEOUT))
+        .map_err(io_failed)?;
+    stream.write_all(req).map_err(io_failed)?;
+    let mut reply = Vec::new();
+    stream.read_to_end(&mut reply).map_err(io_failed)?;
+    match reply.split_first() {
+        Some((&STATUS_OK, body)) => Ok(body.to_vec()),
+        Some((&STATUS_ERR, body)) if body.len() >= 4 => Err(format!(
+            "agent refused: {}",
+            String::from_utf8_lossy(&body[4..])
+        )),
+        _ => Err("agent sent a malformed reply".into()),
+    }
+}
+
+/// The agent side: holds the key and serves requests on a listener.
+pub struct SigningAgent {
+    signing_key: AlgSigningKey,
+}
+
+impl SigningAgent {
+    /// An agent for an Ed25519 key.
+    pub fn new(signing_key: [u8; 32]) -> Self {
+        Self::with_key(
+            AlgSigningKey::new(SigAlg::Ed25519, signing_key)
+                .expect("every 32 bytes are an Ed25519 key"),
+        )
+    }
+
+    pub fn with_key(signing_key: AlgSigningKey) -> Self {
+        Self { signing_key }
+    }
+
+    pub fn sig_alg(&self) -> SigAlg {
+        self.signing_key.alg()
+    }
+
+    pub fn public_key(&self) -> Vec<u8> {
+        self.signing_key.public_key()
+    }
+
+    /// Answers connections one at a time until accepting fails. A client
+    /// that stalls is dropped after a few seconds.
+    pub fn serve(&self, listener: &UnixListener) -> io::Result<()> {
+        loop {
+            let (mut stream, _) = listener.accept()?;
+            // a misbehaving client only loses its own request
+            let _ = self.handle(&mut stream);
+        }
+    }
+
+    fn handle(&self, stream: &mut UnixStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(IO_TIMEOUT))?;
+        stream.set_write_timeout(Some(IO_TIMEOUT))?;
+        let mut op = [0u8; 1];
+        stream.read_exact(&mut op)?;
+        let reply = match op[0] {
+            OP_PUBLIC_KEY => {
+                let mut body = vec![self.sig_alg().id()];
+                body.extend_from_slice(&self.public_key());
+                Ok(body)
+            }
+            OP_SIGN => {
+                let mut len = [0u8; 4];
+                stream.read_exact(&mut len)?;
+                let len = u32::from_le_bytes(len) as usize;
+                if len > AGENT_MAX_MESSAGE_LEN {
+                    Err("message too long".to_string())
+                } else {
+                    let mut msg = vec![0u8; len];
+                    stream.read_exact(&mut msg)?;
+                    self.sign_message(&msg)
+                }
+            }
+            _ => Err("unknown request".to_string()),
+        };
+
+        let mut out = Vec::new();
+        match reply {
+            Ok(body) => {
+                out.push(STATUS_OK);
+                out.extend_from_slice(&body);
+            }
+            Err(reason) => {
+                out.push(STATUS_ERR);
+                out.extend_from_slice(&(reason.len() as u32).to_le_bytes());
+                out.extend_from_slice(reason.as_bytes());
+            }
+        }
+        stream.write_all(&out)
+    }
+
+    fn sign_message(&self, msg: &[u8]) -> Result<Vec<u8>, String> {
+        if !ARTIFACT_SIGN_PREFIXES
+            .iter()
+            .any(|prefix| msg.starts_with(prefix))
+        {
+            return Err("message is not a VERIFAI artifact signing message".into());
+        }
+        Ok(self.signing_key.sign(msg))
+    }
+}
@@ -0,0 +1,332 @@
+use verifai_core::artifact_bin::BatchProofArtifactV2;
+use verifai_core::batch_bin::BatchRowProofV2;
+use verifai_core::event_bin::TraceEventV0;
+use verifai_core::hash::{sha256, HashAlg};
+use verifai_core::merkle::{
+    inclusion_proof, trace_root_from_event_bytes, verify_inclusion, MerkleBuilder,
+};
+use verifai_core::model_bin::{InputBatchV1, OutputBatchV1, OutputV0};
+use verifai_core::signer::Signer;
+use verifai_core::trace_sink::{Tee, TraceSink};
+
+use crate::attester::{Attester, NoopAttester};
+pub(crate) use crate::lr::run_lr_v0_into as run_lr_row;
+pub(crate) use crate::mlp::run_mlp_v1_into as run_mlp_row;
+use crate::VerifaiError;
+
+/// Runs one input, streaming its trace events into the sink.
+pub(crate) type RowRunner = fn(&[u8], &[u8], &mut dyn TraceSink) -> Result<OutputV0, VerifaiError>;
+
+/// Runs one input and returns its output and the root of its trace, hashed
+/// with `hash_alg` as the events are produced.
+pub(crate) fn run_row_hashed(
+    run_row: RowRunner,
+    hash_alg: HashAlg,
+    model_bin: &[u8],
+    input_bin: &[u8],
+) -> Result<(OutputV0, [u8; 32]), VerifaiError> {
+    run_row_hashed_into(run_row, hash_alg, model_bin, input_bin, &mut ())
+}
+
+/// `run_row_hashed`, also handing each event to `sink`.
+pub(crate) fn run_row_hashed_into(
+    run_row: RowRunner,
+    hash_alg: HashAlg,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    sink: &mut dyn TraceSink,
+) -> Result<(OutputV0, [u8; 32]), VerifaiError> {
+    let mut tree = MerkleBuilder::with_alg(hash_alg);
+    let output = run_row(model_bin, input_bin, &mut Tee(&mut tree, sink))?;
+    Ok((output, tree.finalize()))
+}
+
+/// Runs one input and keeps its trace events.
+pub(crate) fn run_row_events(
+    run_row: RowRunner,
+    model_bin: &[u8],
+    input_bin: &[u8],
+) -> Result<(OutputV0, Vec<TraceEventV0>), VerifaiError> {
+    let mut events = Vec::new();
+    let output = run_row(model_bin, input_bin, &mut events)?;
+    Ok((output, events))
+}
+
+/// Per-row Merkle leaves for the three batch trees.
+struct BatchLeaves {
+    inputs: Vec<Vec<u8>>,
+    outputs: Vec<Vec<u8>>,
+    traces: Vec<Vec<u8>>,
+}
+
+impl BatchLeaves {
+    fn roots(&self) -> ([u8; 32], [u8; 32], [u8; 32]) {
+        (
+            trace_root_from_event_bytes(&self.inputs),
+            trace_root_from_event_bytes(&self.outputs),
+            trace_root_from_event_bytes(&self.traces),
+        )
+    }
+}
+
+fn run_batch(
+    run_row: RowRunner,
+    model_bin: &[u8],
+    input_batch_bin: &[u8],
+) -> Result<(OutputBatchV1, BatchLeaves), VerifaiError> {
+    let batch = InputBatchV1::decode_bin(input_batch_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    if batch.rows.is_empty() {
+        return Err(VerifaiError::DimensionMismatch);
+    }
+
+    let mut outputs = OutputBatchV1 {
+        rows: Vec::with_capacity(batch.rows.len()),
+    };
+    let mut leaves = BatchLeaves {
+        inputs: Vec::with_capacity(batch.rows.len()),
+        outputs: Vec::with_capacity(batch.rows.len()),
+        traces: Vec::with_capacity(batch.rows.len()),
+    };
+
+    for row in &batch.rows {
+        let input_bin = row.encode_bin();
+        let (output, trace_root) = run_row_hashed(run_row, HashAlg::Sha256, model_bin, &input_bin)?;
+
+        leaves.inputs.push(input_bin);
+        leaves.outputs.push(output.encode_bin());
+        leaves.traces.push(trace_root.to_vec());
+        outputs.rows.push(output);
+    }
+
+    Ok((outputs, leaves))
+}
+
+fn prove_batch_v2(
+    run_row: RowRunner,
+    runtime_id: [u8; 32],
+    signer: &dyn Signer,
+    model_bin: &[u8],
+    input_batch_bin: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    let (outputs, leaves) = run_batch(run_row, model_bin, input_batch_bin)?;
+    let (inputs_root, outputs_root, traces_root) = leaves.roots();
+
+    let mut artifact = BatchProofArtifactV2 {
+        version: 2,
+        runtime_id,
+        model_hash: sha256(model_bin),
+        row_count: outputs.rows.len() as u32,
+        inputs_root,
+        outputs_root,
+        traces_root,
+        sig_pubkey: [0u8; 32],
+        signature: [0u8; 64],
+        attestation: NoopAttester::attest(traces_root),
+    };
+    artifact
+        .sign_with(signer)
+        .map_err(|e| VerifaiError::SignerFailed(e.0))?;
+
+    Ok((outputs.encode_bin(), artifact.encode_bin()))
+}
+
+fn decode_batch_artifact(artifact_bin: &[u8]) -> Result<BatchProofArtifactV2, VerifaiError> {
+    let artifact =
+        BatchProofArtifactV2::decode_bin(artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    artifact
+        .verify_signature()
+        .map_err(|_| VerifaiError::SignatureInvalid)?;
+    Ok(artifact)
+}
+
+fn verify_batch_v2(
+    run_row: RowRunner,
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_batch_bin: &[u8],
+    output_batch_bin: &[u8],
+) -> Result<(), VerifaiError> {
+    let artifact = decode_batch_artifact(artifact_bin)?;
+    if artifact.model_hash != sha256(model_bin) {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    let (outputs, leaves) = run_batch(run_row, model_bin, input_batch_bin)?;
+    if outputs.rows.len() != artifact.row_count as usize {
+        return Err(VerifaiError::HashMismatch);
+    }
+    if outputs.encode_bin() != output_batch_bin {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    let (inputs_root, outputs_root, traces_root) = leaves.roots();
+    if artifact.inputs_root != inputs_root || artifact.outputs_root != outputs_root {
+        return Err(VerifaiError::HashMismatch);
+    }
+    if artifact.traces_root != traces_root || artifact.attestation.measurement != traces_root {
+        return Err(VerifaiError::TraceMismatch);
+    }
+
+    Ok(())
+}
+
+fn batch_row_proof_v2(
+    run_row: RowRunner,
+    model_bin: &[u8],
+    input_batch_bin: &[u8],
+    index: u32,
+) -> Result<Vec<u8>, VerifaiError> {
+    let (_, leaves) = run_batch(run_row, model_bin, input_batch_bin)?;
+    let i = index as usize;
+    let input_path = inclusion_proof(&leaves.inputs, i).ok_or(VerifaiError::DimensionMismatch)?;
+    let output_path = inclusion_proof(&leaves.outputs, i).ok_or(VerifaiError::DimensionMismatch)?;
+    let trace_path = inclusion_proof(&leaves.traces, i).ok_or(VerifaiError::DimensionMismatch)?;
+
+    let mut trace_root = [0u8; 32];
+    trace_root.copy_from_slice(&leaves.traces[i]);
+
+    let proof = BatchRowProofV2 {
+        index,
+        input_bin: leaves.inputs[i].clone(),
+        output_bin: leaves.outputs[i].clone(),
+        trace_root,
+        input_path,
+        output_path,
+        trace_path,
+    };
+    Ok(proof.encode_bin())
+}
+
+fn verify_batch_row_v2(
+    run_row: RowRunner,
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    row_proof_bin: &[u8],
+) -> Result<(), VerifaiError> {
+    let artifact = decode_batch_artifact(artifact_bin)?;
+    let proof = BatchRowProofV2::decode_bin(row_proof_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    if artifact.model_hash != sha256(model_bin) {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    let index = proof.index as usize;
+    let row_count = artifact.row_count as usize;
+    if !verify_inclusion(
+        &proof.input_bin,
+        index,
+        row_count,
+        &proof.input_path,
+        artifact.inputs_root,
+    ) || !verify_inclusion(
+        &proof.output_bin,
+        index,
+        row_count,
+        &proof.output_path,
+        artifact.outputs_root,
+    ) || !verify_inclusion(
+        &proof.trace_root,
+        index,
+        row_count,
+        &proof.trace_path,
+        artifact.traces_root,
+    ) {
+        return Err(VerifaiError::InclusionProofInvalid);
+    }
+
+    let (output, trace_root) =
+        run_row_hashed(run_row, HashAlg::Sha256, model_bin, &proof.input_bin)?;
+    if output.encode_bin() != proof.output_bin {
+        return Err(VerifaiError::HashMismatch);
+    }
+    if trace_root != proof.trace_root {
+        return Err(VerifaiError::TraceMismatch);
+    }
+
+    Ok(())
+}
+
+pub fn prove_lr_batch_v2(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_batch_bin: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_batch_v2(run_lr_row, runtime_id, &signer, model_bin, input_batch_bin)
+}
+
+pub fn prove_mlp_batch_v2(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_batch_bin: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_batch_v2(run_mlp_row, runtime_id, &signer, model_bin, input_batch_bin)
+}
+
+pub fn verify_lr_batch_v2(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_batch_bin: &[u8],
+    output_batch_bin: &[u8],
+) -> Result<(), VerifaiError> {
+    verify_batch_v2(
+        run_lr_row,
+        artifact_bin,
+        model_bin,
+        input_batch_bin,
+        output_batch_bin,
+    )
+}
+
+pub fn verify_mlp_batch_v2(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_batch_bin: &[u8],
+    output_batch_bin: &[u8],
+) -> Result<(), VerifaiError> {
+    verify_batch_v2(
+        run_mlp_row,
+        artifact_bin,
+        model_bin,
+        input_batch_bin,
+        output_batch_bin,
+    )
+}
+
+/// Builds a `BatchRowProofV2` for row `index` by replaying the batch.
+pub fn batch_row_proof_lr_v2(
+    model_bin: &[u8],
+    input_batch_bin: &[u8],
+    index: u32,
+) -> Result<Vec<u8>, VerifaiError> {
+    batch_row_proof_v2(run_lr_row, model_bin, input_batch_bin, index)
+}
+
+/// Builds a `BatchRowProofV2` for row `index` by replaying the batch.
+pub fn batch_row_proof_mlp_v2(
+    model_bin: &[u8],
+    input_batch_bin: &[u8],
+    index: u32,
+) -> Result<Vec<u8>, VerifaiError> {
+    batch_row_proof_v2(run_mlp_row, model_bin, input_batch_bin, index)
+}
+
+/// Verifies a single row against a batch artifact: signature, the three
+/// inclusion proofs, and a replay of that row alone.
+pub fn verify_batch_row_lr_v2(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    row_proof_bin: &[u8],
+) -> Result<(), VerifaiError> {
+    verify_batch_row_v2(run_lr_row, artifact_bin, model_bin, row_proof_bin)
+}
+
+/// Verifies a single row against a batch artifact: signature, the three
+/// inclusion proofs, and a replay of that row alone.
+pub fn verify_batch_row_mlp_v2(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    row_proof_bin: &[u8],
+) -> Result<(), VerifaiError> {
+    verify_batch_row_v2(run_mlp_row, artifact_bin, model_bin, row_proof_bin)
+}
@@ -0,0 +1,203 @@
+use verifai_core::artifact_bin::{
+    ProofArtifactV0, ProofArtifactV1, ProofArtifactV3, ProofArtifactV4, ProofArtifactV5,
+    ProofArtifactV6,
+};
+use verifai_core::batch_verify::{verify_batch, BatchVerify};
+use verifai_core::hash::HashAlg;
+
+use crate::batch::{run_lr_row, run_mlp_row, run_row_hashed, RowRunner};
+use crate::freshness::{check_freshness_v3, FreshnessPolicy};
+use crate::prove::{artifact_version, native_artifact_bin};
+use crate::VerifaiError;
+
+/// One single-input artifact to check in `verify_*_bulk`, in any encoding
+/// `native_artifact_bin` accepts.
+#[derive(Debug, Clone, Copy)]
+pub struct BulkRecord<'a> {
+    pub artifact_bin: &'a [u8],
+    pub input_bin: &'a [u8],
+    pub output_bin: &'a [u8],
+}
+
+/// Every artifact version with plain (unsalted) commitments, decoded.
+pub(crate) enum Decoded {
+    V0(ProofArtifactV0),
+    V1(ProofArtifactV1),
+    // timestamp tokens make v3 by far the largest
+    V3(Box<ProofArtifactV3>),
+    V4(ProofArtifactV4),
+    V5(ProofArtifactV5),
+    V6(ProofArtifactV6),
+}
+
+impl Decoded {
+    pub(crate) fn decode(artifact_bin: &[u8]) -> Result<Self, VerifaiError> {
+        let artifact_bin = native_artifact_bin(artifact_bin)?;
+        let decoded = match artifact_version(&artifact_bin) {
+            Some(0) => ProofArtifactV0::decode_bin(&artifact_bin).map(Decoded::V0),
+            Some(1) => ProofArtifactV1::decode_bin(&artifact_bin).map(Decoded::V1),
+            Some(3) => ProofArtifactV3::decode_bin(&artifact_bin).map(|a| Decoded::V3(Box::new(a))),
+            Some(4) => ProofArtifactV4::decode_bin(&artifact_bin).map(Decoded::V4),
+            Some(5) => ProofArtifactV5::decode_bin(&artifact_bin).map(Decoded::V5),
+            Some(6) => ProofArtifactV6::decode_bin(&artifact_bin).map(Decoded::V6),
+            _ => return Err(VerifaiError::CoreDecode),
+        };
+        decoded.map_err(|_| VerifaiError::CoreDecode)
+    }
+
+    pub(crate) fn as_batch(&self) -> &dyn BatchVerify {
+        match self {
+            Decoded::V0(a) => a,
+            Decoded::V1(a) => a,
+            Decoded::V3(a) => a.as_ref(),
+            Decoded::V4(a) => a,
+            Decoded::V5(a) => a,
+            Decoded::V6(a) => a,
+        }
+    }
+
+    /// `[model_hash, input_hash, output_hash]`, `trace_root` and, from v1
+    /// on, the attestation measurement.
+    pub(crate) fn commitments(&self) -> ([[u8; 32]; 3], [u8; 32], Option<[u8; 32]>) {
+        match self {
+            Decoded::V0(a) => (
+                [a.model_hash, a.input_hash, a.output_hash],
+                a.trace_root,
+                None,
+            ),
+            Decoded::V1(a) => (
+                [a.model_hash, a.input_hash, a.output_hash],
+                a.trace_root,
+                Some(a.attestation.measurement),
+            ),
+            Decoded::V3(a) => (
+                [a.model_hash, a.input_hash, a.output_hash],
+                a.trace_root,
+                Some(a.attestation.measurement),
+            ),
+            Decoded::V4(a) => (
+                [a.model_hash, a.input_hash, a.output_hash],
+                a.trace_root,
+                Some(a.attestation.measurement),
+            ),
+            Decoded::V5(a) => (
+                [a.model_hash, a.input_hash, a.output_hash],
+                a.trace_root,
+                Some(a.attestation.measurement),
+            ),
+            Decoded::V6(a) => (
+                [a.model_hash, a.input_hash, a.output_hash],
+                a.trace_root,
+                Some(a.attestation.measurement),
+            ),
+        }
+    }
+
+    /// Only v6 names its hash algorithm; everything before it is SHA-256.
+    pub(crate) fn hash_alg(&self) -> HashAlg {
+        match self {
+            Decoded::V6(a) => a.hash_alg,
+            _ => HashAlg::Sha256,
+        }
+    }
+}
+
+fn verify_bulk(
+    run_row: RowRunner,
+    model_bin: &[u8],
+    records: &[BulkRecord<'_>],
+    policy: &FreshnessPolicy,
+) -> Vec<Result<(), VerifaiError>> {
+    let decoded: Vec<Result<Decoded, VerifaiError>> = records
+        .iter()
+        .map(|r| Decoded::decode(r.artifact_bin))
+        .collect();
+
+    // one batch over every artifact that decoded
+    let (positions, batch): (Vec<usize>, Vec<&dyn BatchVerify>) = decoded
+        .iter()
+        .enumerate()
+        .filter_map(|(i, d)| d.as_ref().ok().map(|d| (i, d.as_batch())))
+        .unzip();
+    let mut signature_ok = vec![true; records.len()];
+    for bad in verify_batch(&batch) {
+        signature_ok[positions[bad]] = false;
+    }
+
+    // the model is shared, so hash it once per algorithm in use
+    let model_hashes: Vec<(HashAlg, [u8; 32])> = HashAlg::ALL
+        .into_iter()
+        .filter(|alg| decoded.iter().flatten().any(|d| d.hash_alg() == *alg))
+        .map(|alg| (alg, alg.digest(model_bin)))
+        .collect();
+    decoded
+        .into_iter()
+        .zip(records)
+        .zip(signature_ok)
+        .map(|((decoded, record), signature_ok)| {
+            let decoded = decoded?;
+            if !signature_ok {
+                return Err(VerifaiError::SignatureInvalid);
+            }
+            if let Decoded::V3(a) = &decoded {
+                check_freshness_v3(a, policy)?;
+            }
+            let model_hash = model_hashes
+                .iter()
+                .find(|(alg, _)| *alg == decoded.hash_alg())
+                .map(|(_, hash)| *hash)
+                .expect("model hashed for every algorithm in use");
+            replay(run_row, &decoded, model_hash, model_bin, record)
+        })
+        .collect()
+}
+
+pub(crate) fn replay(
+    run_row: RowRunner,
+    decoded: &Decoded,
+    model_hash: [u8; 32],
+    model_bin: &[u8],
+    record: &BulkRecord<'_>,
+) -> Result<(), VerifaiError> {
+    let ([model, input, output], trace_root, measurement) = decoded.commitments();
+    let hash_alg = decoded.hash_alg();
+    if model != model_hash
+        || input != hash_alg.digest(record.input_bin)
+        || output != hash_alg.digest(record.output_bin)
+    {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    let (recomputed, recomputed_root) =
+        run_row_hashed(run_row, hash_alg, model_bin, record.input_bin)?;
+    if hash_alg.digest(&recomputed.encode_bin()) != output {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    if trace_root != recomputed_root || measurement.is_some_and(|m| m != recomputed_root) {
+        return Err(VerifaiError::TraceMismatch);
+    }
+    Ok(())
+}
+
+/// Verifies many LR artifacts against one model, checking all their
+/// signatures in a single batch. Returns one result per record, the same as
+/// the matching `verify_lr_v*` would give (v4 artifacts need every
+/// co-signature to be valid; thresholds are up to the caller). v7 artifacts
+/// need their salts and fail with `CoreDecode`.
+pub fn verify_lr_bulk(
+    model_bin: &[u8],
+    records: &[BulkRecord<'_>],
+    policy: &FreshnessPolicy,
+) -> Vec<Result<(), VerifaiError>> {
+    verify_bulk(run_lr_row, model_bin, records, policy)
+}
+
+/// MLP counterpart of `verify_lr_bulk`.
+pub fn verify_mlp_bulk(
+    model_bin: &[u8],
+    records: &[BulkRecord<'_>],
+    policy: &FreshnessPolicy,
+) -> Vec<Result<(), VerifaiError>> {
+    verify_bulk(run_mlp_row, model_bin, records, policy)
+}
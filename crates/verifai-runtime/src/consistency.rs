@@ -0,0 +1,95 @@
+use verifai_core::event_bin::{ActivationKind, TraceEventV0};
+use verifai_core::merkle::trace_root_from_event_bytes_with;
+use verifai_core::model_bin::OutputV0;
+
+use crate::batch::{run_lr_row, run_mlp_row, run_row_events};
+use crate::bulk::Decoded;
+use crate::lr::sigmoid;
+use crate::mlp::relu;
+use crate::VerifaiError;
+
+/// The trace `prove_lr_*` commits to, for handing to verifiers who may not
+/// see the model.
+pub fn lr_trace(model_bin: &[u8], input_bin: &[u8]) -> Result<Vec<TraceEventV0>, VerifaiError> {
+    run_row_events(run_lr_row, model_bin, input_bin).map(|(_, events)| events)
+}
+
+/// MLP counterpart of `lr_trace`.
+pub fn mlp_trace(model_bin: &[u8], input_bin: &[u8]) -> Result<Vec<TraceEventV0>, VerifaiError> {
+    run_row_events(run_mlp_row, model_bin, input_bin).map(|(_, events)| events)
+}
+
+/// Checks an artifact against its trace and output without the model or
+/// input: the signature, `output_hash`, that the events hash to
+/// `trace_root`, and that the trace is well formed. Every activation must
+/// follow the linear op it reads, with its input equal to that op's `z` and
+/// its output equal to sigmoid or ReLU of it (bit for bit); the trace must end
+/// with a single `OpOutput` whose `y` is the last activation's output and
+/// the `y` in `output_bin`.
+///
+/// This does not show that the linear ops used the committed model; only
+/// replay does. Batch (v2) and salted (v7) artifacts fail with `CoreDecode`.
+pub fn check_trace_consistency(
+    events: &[TraceEventV0],
+    artifact_bin: &[u8],
+    output_bin: &[u8],
+) -> Result<(), VerifaiError> {
+    let decoded = Decoded::decode(artifact_bin)?;
+    decoded
+        .as_batch()
+        .verify_alone()
+        .map_err(|_| VerifaiError::SignatureInvalid)?;
+    let ([_, _, output_hash], trace_root, measurement) = decoded.commitments();
+    let hash_alg = decoded.hash_alg();
+    if hash_alg.digest(output_bin) != output_hash {
+        return Err(VerifaiError::HashMismatch);
+    }
+    let output = OutputV0::decode_bin(output_bin).map_err(|_| VerifaiError::CoreDecode)?;
+
+    let event_bytes: Vec<Vec<u8>> = events.iter().map(|e| e.encode_bin()).collect();
+    let recomputed_root = trace_root_from_event_bytes_with(hash_alg, &event_bytes);
+    if trace_root != recomputed_root || measurement.is_some_and(|m| m != recomputed_root) {
+        return Err(VerifaiError::TraceMismatch);
+    }
+
+    if check_structure(events, output.y) {
+        Ok(())
+    } else {
+        Err(VerifaiError::TraceMismatch)
+    }
+}
+
+fn check_structure(events: &[TraceEventV0], y: f64) -> bool {
+    let Some((TraceEventV0::OpOutput { y: traced_y }, body)) = events.split_last() else {
+        return false;
+    };
+    let Some(TraceEventV0::OpActivation { output: last, .. }) = body.last() else {
+        return false;
+    };
+    if traced_y.to_bits() != last.to_bits() || traced_y.to_bits() != y.to_bits() {
+        return false;
+    }
+
+    body.iter().enumerate().all(|(i, event)| match *event {
+        TraceEventV0::OpLinear { .. } | TraceEventV0::OpFeature { .. } => true,
+        // check-trace takes op-level traces only
+        TraceEventV0::OpOutput { .. }
+        | TraceEventV0::OpLayer { .. }
+        | TraceEventV0::OpMac { .. } => false,
+        TraceEventV0::OpActivation {
+            kind,
+            input,
+            output,
+            ..
+        } => {
+            let Some(&TraceEventV0::OpLinear { z, .. }) = i.checked_sub(1).map(|j| &body[j]) else {
+                return false;
+            };
+            let expected = match kind {
+                ActivationKind::Sigmoid => sigmoid(input),
+                ActivationKind::Relu => relu(input),
+            };
+            input.to_bits() == z.to_bits() && output.to_bits() == expected.to_bits()
+        }
+    })
+}
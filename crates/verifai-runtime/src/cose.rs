@@ -0,0 +1,23 @@
+use verifai_core::artifact_bin::ProofArtifactV1;
+
+use crate::VerifaiError;
+
+/// Re-encodes a native `ProofArtifactV1` as COSE_Sign1, countersigning with
+/// the same key that made the artifact.
+pub fn encode_cose_v1(
+    artifact_bin: &[u8],
+    signing_key_bytes: [u8; 32],
+) -> Result<Vec<u8>, VerifaiError> {
+    let artifact =
+        ProofArtifactV1::decode_bin(artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    artifact
+        .encode_cose_sign1(signing_key_bytes)
+        .map_err(|_| VerifaiError::SignatureInvalid)
+}
+
+/// Checks the COSE signature and returns the native artifact bytes.
+pub fn decode_cose_v1(cose_bin: &[u8]) -> Result<Vec<u8>, VerifaiError> {
+    let artifact =
+        ProofArtifactV1::verify_cose_sign1(cose_bin).map_err(|_| VerifaiError::SignatureInvalid)?;
+    Ok(artifact.encode_bin())
+}
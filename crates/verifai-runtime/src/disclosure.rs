@@ -0,0 +1,83 @@
+use verifai_core::disclosure::TraceDisclosureV0;
+use verifai_core::event_bin::TraceEventV0;
+use verifai_core::merkle::trace_root_from_event_bytes_with;
+
+use crate::batch::{run_lr_row, run_mlp_row, run_row_events, RowRunner};
+use crate::bulk::Decoded;
+use crate::VerifaiError;
+
+fn disclose(
+    run_row: RowRunner,
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    select: &dyn Fn(usize, &TraceEventV0) -> bool,
+) -> Result<TraceDisclosureV0, VerifaiError> {
+    let decoded = Decoded::decode(artifact_bin)?;
+    let ([model_hash, input_hash, _], trace_root, _) = decoded.commitments();
+    let hash_alg = decoded.hash_alg();
+    if hash_alg.digest(model_bin) != model_hash || hash_alg.digest(input_bin) != input_hash {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    let (_, events) = run_row_events(run_row, model_bin, input_bin)?;
+    let event_bytes: Vec<Vec<u8>> = events.iter().map(|e| e.encode_bin()).collect();
+    if trace_root_from_event_bytes_with(hash_alg, &event_bytes) != trace_root {
+        return Err(VerifaiError::TraceMismatch);
+    }
+    let indices: Vec<usize> = events
+        .iter()
+        .enumerate()
+        .filter(|(i, e)| select(*i, e))
+        .map(|(i, _)| i)
+        .collect();
+    TraceDisclosureV0::from_trace(hash_alg, &events, &indices)
+        .ok_or(VerifaiError::DimensionMismatch)
+}
+
+/// Replays an LR artifact's trace and discloses the events `select` picks
+/// by leaf index and event (`DimensionMismatch` if it picks none). The
+/// artifact must be one `verify_trace_disclosure` accepts.
+pub fn disclose_lr_trace(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    select: impl Fn(usize, &TraceEventV0) -> bool,
+) -> Result<TraceDisclosureV0, VerifaiError> {
+    disclose(run_lr_row, artifact_bin, model_bin, input_bin, &select)
+}
+
+/// MLP counterpart of `disclose_lr_trace`.
+pub fn disclose_mlp_trace(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    select: impl Fn(usize, &TraceEventV0) -> bool,
+) -> Result<TraceDisclosureV0, VerifaiError> {
+    disclose(run_mlp_row, artifact_bin, model_bin, input_bin, &select)
+}
+
+/// Checks the artifact's signature and that the disclosed events are leaves
+/// of its signed `trace_root`. Needs neither the model nor the input. Batch
+/// (v2) and salted (v7) artifacts fail with `CoreDecode`.
+///
+/// Only the events' contents are checked: their indices and the trace length
+/// are not bound by the root.
+pub fn verify_trace_disclosure(
+    artifact_bin: &[u8],
+    disclosure: &TraceDisclosureV0,
+) -> Result<(), VerifaiError> {
+    let decoded = Decoded::decode(artifact_bin)?;
+    decoded
+        .as_batch()
+        .verify_alone()
+        .map_err(|_| VerifaiError::SignatureInvalid)?;
+    let (_, trace_root, measurement) = decoded.commitments();
+    if disclosure.hash_alg != decoded.hash_alg() || measurement.is_some_and(|m| m != trace_root) {
+        return Err(VerifaiError::TraceMismatch);
+    }
+    if !disclosure.verify_root(trace_root) {
+        return Err(VerifaiError::InclusionProofInvalid);
+    }
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use verifai_core::event_bin::TraceEventV0;
+use verifai_core::hash::HashAlg;
+use verifai_core::merkle::{leaf_hash_with, MerkleBuilder};
+use verifai_core::trace_sink::TraceSink;
+
+use crate::batch::{run_lr_row, run_mlp_row, RowRunner};
+use crate::bulk::{BulkRecord, Decoded};
+use crate::freshness::{check_freshness_v3, FreshnessPolicy};
+use crate::VerifaiError;
+
+/// The first event where a replay departs from a claimed trace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceDivergence {
+    /// Leaf index of the event.
+    pub index: usize,
+    /// `op_id` of the replayed event, or of the claimed one when the replay
+    /// ended first; `None` for kinds without one.
+    pub op_id: Option<u32>,
+    /// The claimed event, `None` if the claimed trace ended first.
+    pub expected: Option<TraceEventV0>,
+    /// The replayed event, `None` if the replay ended first.
+    pub actual: Option<TraceEventV0>,
+}
+
+/// Compares each replayed event's leaf hash with the claimed one and stops
+/// the run at the first that differs.
+struct DivergenceSink<'a> {
+    hash_alg: HashAlg,
+    claimed: &'a [TraceEventV0],
+    leaves: &'a [[u8; 32]],
+    next: usize,
+    divergence: Option<TraceDivergence>,
+}
+
+impl TraceSink for DivergenceSink<'_> {
+    fn record(&mut self, event: &TraceEventV0) {
+        if self.divergence.is_some() {
+            return;
+        }
+        let leaf = leaf_hash_with(self.hash_alg, &event.encode_bin());
+        if self.leaves.get(self.next) != Some(&leaf) {
+            self.divergence = Some(TraceDivergence {
+                index: self.next,
+                op_id: event.op_id(),
+                expected: self.claimed.get(self.next).cloned(),
+                actual: Some(event.clone()),
+            });
+        }
+        self.next += 1;
+    }
+
+    fn stopped(&self) -> bool {
+        self.divergence.is_some()
+    }
+}
+
+fn verify_with_trace(
+    run_row: RowRunner,
+    model_bin: &[u8],
+    record: &BulkRecord<'_>,
+    claimed: &[TraceEventV0],
+    policy: &FreshnessPolicy,
+) -> Result<Option<TraceDivergence>, VerifaiError> {
+    let decoded = Decoded::decode(record.artifact_bin)?;
+    decoded
+        .as_batch()
+        .verify_alone()
+        .map_err(|_| VerifaiError::SignatureInvalid)?;
+    if let Decoded::V3(a) = &decoded {
+        check_freshness_v3(a, policy)?;
+    }
+
+    let ([model_hash, input_hash, output_hash], trace_root, measurement) = decoded.commitments();
+    let hash_alg = decoded.hash_alg();
+    if hash_alg.digest(model_bin) != model_hash
+        || hash_alg.digest(record.input_bin) != input_hash
+        || hash_alg.digest(record.output_bin) != output_hash
+    {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    // the claim must be the trace the artifact signed before it is worth replaying
+    let mut tree = MerkleBuilder::with_alg(hash_alg);
+    let leaves: Vec<[u8; 32]> = claimed
+        .iter()
+        .map(|event| {
+            let bytes = event.encode_bin();
+            tree.push(&bytes);
+            leaf_hash_with(hash_alg, &bytes)
+        })
+        .collect();
+    let claimed_root = tree.finalize();
+    if claimed_root != trace_root || measurement.is_some_and(|m| m != claimed_root) {
+        return Err(VerifaiError::TraceMismatch);
+    }
+
+    let mut sink = DivergenceSink {
+        hash_alg,
+        claimed,
+        leaves: &leaves,
+        next: 0,
+        divergence: None,
+    };
+    let result = run_row(model_bin, record.input_bin, &mut sink);
+    if let Some(divergence) = sink.divergence {
+        return Ok(Some(divergence));
+    }
+    let output = result?;
+    if let Some(event) = claimed.get(sink.next) {
+        return Ok(Some(TraceDivergence {
+            index: sink.next,
+            op_id: event.op_id(),
+            expected: Some(event.clone()),
+            actual: None,
+        }));
+    }
+    if hash_alg.digest(&output.encode_bin()) != output_hash {
+        return Err(VerifaiError::HashMismatch);
+    }
+    Ok(None)
+}
+
+/// Verifies an LR artifact against the trace its prover claims (as
+/// `prove --out-trace` writes it), replaying event by event and stopping at
+/// the first one whose leaf hash differs from the claim. The claimed trace
+/// must hash to the artifact's `trace_root` (`TraceMismatch` otherwise).
+/// Returns `None` if the replay matches it throughout and gives the
+/// committed output, and the divergence if not.
+///
+/// Takes the artifact versions `verify_lr_bulk` does, with the same checks.
+pub fn verify_lr_with_trace(
+    model_bin: &[u8],
+    record: &BulkRecord<'_>,
+    claimed: &[TraceEventV0],
+    policy: &FreshnessPolicy,
+) -> Result<Option<TraceDivergence>, VerifaiError> {
+    verify_with_trace(run_lr_row, model_bin, record, claimed, policy)
+}
+
+/// MLP counterpart of `verify_lr_with_trace`.
+pub fn verify_mlp_with_trace(
+    model_bin: &[u8],
+    record: &BulkRecord<'_>,
+    claimed: &[TraceEventV0],
+    policy: &FreshnessPolicy,
+) -> Result<Option<TraceDivergence>, VerifaiError> {
+    verify_with_trace(run_mlp_row, model_bin, record, claimed, policy)
+}
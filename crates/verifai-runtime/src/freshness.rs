@@ -0,0 +1,284 @@
+use verifai_core::artifact_bin::ProofArtifactV3;
+use verifai_core::hash::{sha256, HashAlg};
+use verifai_core::signer::Signer;
+use verifai_core::trace_sink::TraceSink;
+
+use crate::attester::{Attester, NoopAttester};
+use crate::batch::{run_lr_row, run_mlp_row, run_row_hashed, run_row_hashed_into, RowRunner};
+use crate::tsa::LocalTsa;
+use crate::VerifaiError;
+
+/// Freshness data signed into a `ProofArtifactV3`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssuanceV3 {
+    /// Unix nanos.
+    pub issued_at: u64,
+    /// Unix nanos; must be after `issued_at` when present.
+    pub expires_at: Option<u64>,
+    /// Caller-supplied challenge, echoed back in the artifact.
+    pub nonce: [u8; 32],
+}
+
+/// What `verify_*_v3` requires of an artifact's freshness fields, evaluated at
+/// `now` (unix nanos). Expiry is always enforced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FreshnessPolicy {
+    pub now: u64,
+    pub max_age_nanos: Option<u64>,
+    pub expected_nonce: Option<[u8; 32]>,
+    /// Requires a timestamp token from this TSA key. A token checked only
+    /// against the key it carries proves nothing, since the prover can sign
+    /// one with a key of their own.
+    pub trusted_tsa: Option<[u8; 32]>,
+}
+
+impl FreshnessPolicy {
+    /// Expiry checks only.
+    pub fn at(now: u64) -> Self {
+        Self {
+            now,
+            max_age_nanos: None,
+            expected_nonce: None,
+            trusted_tsa: None,
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prove_v3<A: Attester>(
+    run_row: RowRunner,
+    runtime_id: [u8; 32],
+    signer: &dyn Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    issuance: &IssuanceV3,
+    tsa: Option<&LocalTsa>,
+    sink: &mut dyn TraceSink,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    if issuance.expires_at.is_some_and(|e| e <= issuance.issued_at) {
+        return Err(VerifaiError::Expired);
+    }
+
+    let (output, trace_root) =
+        run_row_hashed_into(run_row, HashAlg::Sha256, model_bin, input_bin, sink)?;
+    let output_bin = output.encode_bin();
+
+    let mut artifact = ProofArtifactV3 {
+        version: 3,
+        runtime_id,
+        model_hash: sha256(model_bin),
+        input_hash: sha256(input_bin),
+        output_hash: sha256(&output_bin),
+        trace_root,
+        issued_at: issuance.issued_at,
+        expires_at: issuance.expires_at,
+        nonce: issuance.nonce,
+        sig_pubkey: [0u8; 32],
+        signature: [0u8; 64],
+        timestamp: None,
+        attestation: A::attest(trace_root),
+    };
+    artifact
+        .sign_with(signer)
+        .map_err(|e| VerifaiError::SignerFailed(e.0))?;
+    if let Some(tsa) = tsa {
+        artifact.timestamp = Some(tsa.stamp(artifact.timestamp_imprint())?);
+    }
+
+    Ok((output_bin, artifact.encode_bin()))
+}
+
+/// Checks signature and freshness of a v3 artifact without replaying it.
+pub fn verify_freshness_v3(
+    artifact_bin: &[u8],
+    policy: &FreshnessPolicy,
+) -> Result<ProofArtifactV3, VerifaiError> {
+    let artifact =
+        ProofArtifactV3::decode_bin(artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    artifact
+        .verify_signature()
+        .map_err(|_| VerifaiError::SignatureInvalid)?;
+    check_freshness_v3(&artifact, policy)?;
+    Ok(artifact)
+}
+
+/// The freshness half of `verify_freshness_v3`, for callers that checked the
+/// artifact signature some other way.
+pub(crate) fn check_freshness_v3(
+    artifact: &ProofArtifactV3,
+    policy: &FreshnessPolicy,
+) -> Result<(), VerifaiError> {
+    if artifact.issued_at > policy.now {
+        return Err(VerifaiError::NotYetValid);
+    }
+    if artifact.expires_at.is_some_and(|e| policy.now > e) {
+        return Err(VerifaiError::Expired);
+    }
+    if policy
+        .max_age_nanos
+        .is_some_and(|max| policy.now - artifact.issued_at > max)
+    {
+        return Err(VerifaiError::Stale);
+    }
+    if policy.expected_nonce.is_some_and(|n| n != artifact.nonce) {
+        return Err(VerifaiError::NonceMismatch);
+    }
+
+    match &artifact.timestamp {
+        Some(token) => {
+            let in_window = token.gen_time >= artifact.issued_at
+                && artifact.expires_at.is_none_or(|e| token.gen_time <= e);
+            if token.verify_signature().is_err()
+                || token.message_imprint != artifact.timestamp_imprint()
+                || !in_window
+                || policy.trusted_tsa.is_some_and(|k| k != token.tsa_pubkey)
+            {
+                return Err(VerifaiError::TimestampInvalid);
+            }
+        }
+        None if policy.trusted_tsa.is_some() => {
+            return Err(VerifaiError::TimestampInvalid);
+        }
+        None => {}
+    }
+
+    Ok(())
+}
+
+fn verify_v3(
+    run_row: RowRunner,
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+    policy: &FreshnessPolicy,
+) -> Result<(), VerifaiError> {
+    let artifact = verify_freshness_v3(artifact_bin, policy)?;
+
+    if artifact.model_hash != sha256(model_bin)
+        || artifact.input_hash != sha256(input_bin)
+        || artifact.output_hash != sha256(output_bin)
+    {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    let (output, trace_root) = run_row_hashed(run_row, HashAlg::Sha256, model_bin, input_bin)?;
+    if sha256(&output.encode_bin()) != artifact.output_hash {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    if artifact.trace_root != trace_root || artifact.attestation.measurement != trace_root {
+        return Err(VerifaiError::TraceMismatch);
+    }
+
+    Ok(())
+}
+
+pub fn prove_lr_v3_with_attester<A: Attester>(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    issuance: &IssuanceV3,
+    tsa: Option<&LocalTsa>,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_lr_v3_with_sink::<A>(
+        runtime_id,
+        signer,
+        model_bin,
+        input_bin,
+        issuance,
+        tsa,
+        &mut (),
+    )
+}
+
+/// `prove_lr_v3_with_attester`, also handing each trace event to `sink`.
+pub fn prove_lr_v3_with_sink<A: Attester>(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    issuance: &IssuanceV3,
+    tsa: Option<&LocalTsa>,
+    sink: &mut dyn TraceSink,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_v3::<A>(
+        run_lr_row, runtime_id, &signer, model_bin, input_bin, issuance, tsa, sink,
+    )
+}
+
+pub fn prove_mlp_v3(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    issuance: &IssuanceV3,
+    tsa: Option<&LocalTsa>,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_mlp_v3_with_sink(
+        runtime_id,
+        signer,
+        model_bin,
+        input_bin,
+        issuance,
+        tsa,
+        &mut (),
+    )
+}
+
+/// `prove_mlp_v3`, also handing each trace event to `sink`.
+pub fn prove_mlp_v3_with_sink(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    issuance: &IssuanceV3,
+    tsa: Option<&LocalTsa>,
+    sink: &mut dyn TraceSink,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_v3::<NoopAttester>(
+        run_mlp_row,
+        runtime_id,
+        &signer,
+        model_bin,
+        input_bin,
+        issuance,
+        tsa,
+        sink,
+    )
+}
+
+pub fn verify_lr_v3(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+    policy: &FreshnessPolicy,
+) -> Result<(), VerifaiError> {
+    verify_v3(
+        run_lr_row,
+        artifact_bin,
+        model_bin,
+        input_bin,
+        output_bin,
+        policy,
+    )
+}
+
+pub fn verify_mlp_v3(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+    policy: &FreshnessPolicy,
+) -> Result<(), VerifaiError> {
+    verify_v3(
+        run_mlp_row,
+        artifact_bin,
+        model_bin,
+        input_bin,
+        output_bin,
+        policy,
+    )
+}
@@ -0,0 +1,149 @@
+use verifai_core::artifact_bin::ProofArtifactV6;
+use verifai_core::hash::HashAlg;
+use verifai_core::signer::Signer;
+use verifai_core::trace_sink::TraceSink;
+
+use crate::attester::{Attester, NoopAttester};
+use crate::batch::{run_lr_row, run_mlp_row, run_row_hashed, run_row_hashed_into, RowRunner};
+use crate::VerifaiError;
+
+fn prove_v6<A: Attester>(
+    run_row: RowRunner,
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    hash_alg: HashAlg,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    sink: &mut dyn TraceSink,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    let (output, trace_root) = run_row_hashed_into(run_row, hash_alg, model_bin, input_bin, sink)?;
+    let output_bin = output.encode_bin();
+
+    let mut artifact = ProofArtifactV6 {
+        version: 6,
+        runtime_id,
+        model_hash: hash_alg.digest(model_bin),
+        input_hash: hash_alg.digest(input_bin),
+        output_hash: hash_alg.digest(&output_bin),
+        trace_root,
+        hash_alg,
+        sig_alg: signer.sig_alg(),
+        sig_pubkey: Vec::new(),
+        signature: Vec::new(),
+        attestation: A::attest(trace_root),
+    };
+    artifact
+        .sign_with(&signer)
+        .map_err(|e| VerifaiError::SignerFailed(e.0))?;
+
+    Ok((output_bin, artifact.encode_bin()))
+}
+
+fn verify_v6(
+    run_row: RowRunner,
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+) -> Result<ProofArtifactV6, VerifaiError> {
+    let artifact =
+        ProofArtifactV6::decode_bin(artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    artifact
+        .verify_signature()
+        .map_err(|_| VerifaiError::SignatureInvalid)?;
+
+    let hash_alg = artifact.hash_alg;
+    if artifact.model_hash != hash_alg.digest(model_bin)
+        || artifact.input_hash != hash_alg.digest(input_bin)
+        || artifact.output_hash != hash_alg.digest(output_bin)
+    {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    let (output, trace_root) = run_row_hashed(run_row, hash_alg, model_bin, input_bin)?;
+    if hash_alg.digest(&output.encode_bin()) != artifact.output_hash {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    if artifact.trace_root != trace_root || artifact.attestation.measurement != trace_root {
+        return Err(VerifaiError::TraceMismatch);
+    }
+
+    Ok(artifact)
+}
+
+/// Proves into a v6 artifact hashed with `hash_alg` and signed with whatever
+/// algorithm `signer` reports.
+pub fn prove_lr_v6_with_attester<A: Attester>(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    hash_alg: HashAlg,
+    model_bin: &[u8],
+    input_bin: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_lr_v6_with_sink::<A>(runtime_id, signer, hash_alg, model_bin, input_bin, &mut ())
+}
+
+/// `prove_lr_v6_with_attester`, also handing each trace event to `sink`.
+pub fn prove_lr_v6_with_sink<A: Attester>(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    hash_alg: HashAlg,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    sink: &mut dyn TraceSink,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_v6::<A>(
+        run_lr_row, runtime_id, signer, hash_alg, model_bin, input_bin, sink,
+    )
+}
+
+pub fn prove_mlp_v6(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    hash_alg: HashAlg,
+    model_bin: &[u8],
+    input_bin: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_mlp_v6_with_sink(runtime_id, signer, hash_alg, model_bin, input_bin, &mut ())
+}
+
+/// `prove_mlp_v6`, also handing each trace event to `sink`.
+pub fn prove_mlp_v6_with_sink(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    hash_alg: HashAlg,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    sink: &mut dyn TraceSink,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_v6::<NoopAttester>(
+        run_mlp_row,
+        runtime_id,
+        signer,
+        hash_alg,
+        model_bin,
+        input_bin,
+        sink,
+    )
+}
+
+/// Replays a v6 LR artifact with the hash algorithm it names. Returns it so
+/// callers can pin `hash_alg`, `sig_alg` and `sig_pubkey`.
+pub fn verify_lr_v6(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+) -> Result<ProofArtifactV6, VerifaiError> {
+    verify_v6(run_lr_row, artifact_bin, model_bin, input_bin, output_bin)
+}
+
+pub fn verify_mlp_v6(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+) -> Result<ProofArtifactV6, VerifaiError> {
+    verify_v6(run_mlp_row, artifact_bin, model_bin, input_bin, output_bin)
+}
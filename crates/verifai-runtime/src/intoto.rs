@@ -0,0 +1,246 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use verifai_core::artifact_bin::{ProofArtifactV0, ProofArtifactV1};
+use verifai_core::attestation::AttestationBundle;
+use verifai_core::dsse::{sign_dsse, verify_dsse};
+
+use crate::prove::artifact_version;
+use crate::VerifaiError;
+
+pub const INTOTO_PAYLOAD_TYPE: &str = "application/vnd.in-toto+json";
+pub const INTOTO_STATEMENT_TYPE: &str = "https://in-toto.io/Statement/v1";
+pub const VERIFAI_PREDICATE_TYPE: &str = "urn:verifai:predicate:proof-artifact:v1";
+
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    #[serde(rename = "payloadType")]
+    payload_type: String,
+    payload: String,
+    signatures: Vec<EnvelopeSignature>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EnvelopeSignature {
+    keyid: String,
+    sig: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Statement {
+    #[serde(rename = "_type")]
+    statement_type: String,
+    subject: Vec<Subject>,
+    #[serde(rename = "predicateType")]
+    predicate_type: String,
+    predicate: Predicate,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Subject {
+    name: String,
+    digest: Digest,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Digest {
+    sha256: String,
+}
+
+/// Every native artifact field except `output_hash`, which is the subject
+/// digest. All byte fields are lowercase hex.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Predicate {
+    version: u16,
+    runtime_id: String,
+    model_hash: String,
+    input_hash: String,
+    trace_root: String,
+    sig_pubkey: String,
+    artifact_signature: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attestation: Option<PredicateAttestation>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PredicateAttestation {
+    attester_id: String,
+    measurement: String,
+    evidence: String,
+}
+
+/// Wraps a native v0/v1 artifact in an in-toto Statement whose subject is the
+/// output (named `subject_name`), signed as a DSSE envelope with the
+/// artifact's own key. Returns the envelope JSON.
+pub fn encode_intoto_envelope(
+    artifact_bin: &[u8],
+    signing_key_bytes: [u8; 32],
+    subject_name: &str,
+) -> Result<Vec<u8>, VerifaiError> {
+    let (output_hash, predicate) = match artifact_version(artifact_bin) {
+        Some(0) => {
+            let a =
+                ProofArtifactV0::decode_bin(artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
+            let predicate = Predicate {
+                version: a.version,
+                runtime_id: hex(&a.runtime_id),
+                model_hash: hex(&a.model_hash),
+                input_hash: hex(&a.input_hash),
+                trace_root: hex(&a.trace_root),
+                sig_pubkey: hex(&a.sig_pubkey),
+                artifact_signature: hex(&a.signature),
+                attestation: None,
+            };
+            (a.output_hash, predicate)
+        }
+        Some(1) => {
+            let a =
+                ProofArtifactV1::decode_bin(artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
+            let predicate = Predicate {
+                version: a.version,
+                runtime_id: hex(&a.runtime_id),
+                model_hash: hex(&a.model_hash),
+                input_hash: hex(&a.input_hash),
+                trace_root: hex(&a.trace_root),
+                sig_pubkey: hex(&a.sig_pubkey),
+                artifact_signature: hex(&a.signature),
+                attestation: Some(PredicateAttestation {
+                    attester_id: hex(&a.attestation.attester_id),
+                    measurement: hex(&a.attestation.measurement),
+                    evidence: hex(&a.attestation.attestation),
+                }),
+            };
+            (a.output_hash, predicate)
+        }
+        _ => return Err(VerifaiError::CoreDecode),
+    };
+    let sig_pubkey = predicate.sig_pubkey.clone();
+
+    let statement = Statement {
+        statement_type: INTOTO_STATEMENT_TYPE.into(),
+        subject: vec![Subject {
+            name: subject_name.into(),
+            digest: Digest {
+                sha256: hex(&output_hash),
+            },
+        }],
+        predicate_type: VERIFAI_PREDICATE_TYPE.into(),
+        predicate,
+    };
+    let payload = serde_json::to_vec(&statement).map_err(|_| VerifaiError::CoreDecode)?;
+
+    let (pubkey, sig) = sign_dsse(signing_key_bytes, INTOTO_PAYLOAD_TYPE, &payload);
+    if hex(&pubkey) != sig_pubkey {
+        return Err(VerifaiError::SignatureInvalid);
+    }
+    let envelope = Envelope {
+        payload_type: INTOTO_PAYLOAD_TYPE.into(),
+        payload: BASE64.encode(&payload),
+        signatures: vec![EnvelopeSignature {
+            keyid: hex(&pubkey),
+            sig: BASE64.encode(sig),
+        }],
+    };
+    serde_json::to_vec_pretty(&envelope).map_err(|_| VerifaiError::CoreDecode)
+}
+
+/// Checks the envelope signature by the predicate's `sig_pubkey` and returns
+/// the native artifact bytes it describes.
+pub fn decode_intoto_envelope(envelope_json: &[u8]) -> Result<Vec<u8>, VerifaiError> {
+    let envelope: Envelope =
+        serde_json::from_slice(envelope_json).map_err(|_| VerifaiError::CoreDecode)?;
+    if envelope.payload_type != INTOTO_PAYLOAD_TYPE {
+        return Err(VerifaiError::CoreDecode);
+    }
+    let payload = BASE64
+        .decode(&envelope.payload)
+        .map_err(|_| VerifaiError::CoreDecode)?;
+    let statement: Statement =
+        serde_json::from_slice(&payload).map_err(|_| VerifaiError::CoreDecode)?;
+    if statement.statement_type != INTOTO_STATEMENT_TYPE
+        || statement.predicate_type != VERIFAI_PREDICATE_TYPE
+        || statement.subject.len() != 1
+    {
+        return Err(VerifaiError::CoreDecode);
+    }
+
+    let p = &statement.predicate;
+    let sig_pubkey = unhex_32(&p.sig_pubkey)?;
+    let signed = envelope.signatures.iter().any(|s| {
+        unhex_32(&s.keyid).ok() == Some(sig_pubkey)
+            && BASE64.decode(&s.sig).is_ok_and(|sig| {
+                verify_dsse(sig_pubkey, INTOTO_PAYLOAD_TYPE, &payload, &sig).is_ok()
+            })
+    });
+    if !signed {
+        return Err(VerifaiError::SignatureInvalid);
+    }
+
+    let runtime_id = unhex_32(&p.runtime_id)?;
+    let model_hash = unhex_32(&p.model_hash)?;
+    let input_hash = unhex_32(&p.input_hash)?;
+    let output_hash = unhex_32(&statement.subject[0].digest.sha256)?;
+    let trace_root = unhex_32(&p.trace_root)?;
+    let signature: [u8; 64] = unhex(&p.artifact_signature)?
+        .try_into()
+        .map_err(|_| VerifaiError::CoreDecode)?;
+    match (p.version, &p.attestation) {
+        (0, None) => Ok(ProofArtifactV0 {
+            version: 0,
+            runtime_id,
+            model_hash,
+            input_hash,
+            output_hash,
+            trace_root,
+            sig_pubkey,
+            signature,
+        }
+        .encode_bin()),
+        (1, Some(a)) => Ok(ProofArtifactV1 {
+            version: 1,
+            runtime_id,
+            model_hash,
+            input_hash,
+            output_hash,
+            trace_root,
+            sig_pubkey,
+            signature,
+            attestation: AttestationBundle {
+                attester_id: unhex_32(&a.attester_id)?,
+                measurement: unhex_32(&a.measurement)?,
+                attestation: unhex(&a.evidence)?,
+            },
+        }
+        .encode_bin()),
+        _ => Err(VerifaiError::CoreDecode),
+    }
+}
+
+/// DSSE envelopes are JSON objects; native artifacts and COSE never start with `{`.
+pub fn is_intoto_envelope(buf: &[u8]) -> bool {
+    buf.iter()
+        .find(|b| !b.is_ascii_whitespace())
+        .is_some_and(|b| *b == b'{')
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn unhex(s: &str) -> Result<Vec<u8>, VerifaiError> {
+    if s.len() & 1 == 1 || !s.is_ascii() {
+        return Err(VerifaiError::CoreDecode);
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).map_err(|_| VerifaiError::CoreDecode))
+        .collect()
+}
+
+fn unhex_32(s: &str) -> Result<[u8; 32], VerifaiError> {
+    unhex(s)?.try_into().map_err(|_| VerifaiError::CoreDecode)
+}
@@ -0,0 +1,135 @@
+use verifai_core::artifact_bin::{
+    BatchProofArtifactV2, ProofArtifactV0, ProofArtifactV1, ProofArtifactV3, ProofArtifactV4,
+    ProofArtifactV5, ProofArtifactV6, ProofArtifactV7, ProofArtifactV8, ProofArtifactV9,
+};
+use verifai_core::key_status::KeyStatusListV0;
+use verifai_core::sig_alg::SigAlg;
+
+use crate::prove::artifact_version;
+use crate::VerifaiError;
+
+/// When an artifact was signed, as far as a verifier can tell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigningTime {
+    /// `issued_at` as signed by the artifact key itself (v3), or the
+    /// verifier's clock for versions that carry no signed time.
+    Claimed(u64),
+    /// `gen_time` of a timestamp token over the artifact signature, signed by
+    /// a TSA the verifier trusts (v3).
+    Timestamped(u64),
+}
+
+/// Decodes a key-status list and checks that `authority` signed it.
+pub fn verify_key_status_list(
+    list_bin: &[u8],
+    authority: [u8; 32],
+) -> Result<KeyStatusListV0, VerifaiError> {
+    let list = KeyStatusListV0::decode_bin(list_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    if list.authority_pubkey != authority || list.verify_signature().is_err() {
+        return Err(VerifaiError::SignatureInvalid);
+    }
+    Ok(list)
+}
+
+/// Whether `list` vouches for `sig_pubkey` having signed at `time`.
+///
+/// The key must be listed and the signing time must fall inside its validity
+/// window. A revoked key is only accepted for artifacts timestamped before the
+/// revocation, since a claimed time can be backdated by whoever holds the
+/// leaked key.
+pub fn check_key_status(
+    list: &KeyStatusListV0,
+    sig_pubkey: &[u8; 32],
+    time: SigningTime,
+) -> Result<(), VerifaiError> {
+    let entry = list.entry(sig_pubkey).ok_or(VerifaiError::KeyUnknown)?;
+    let (SigningTime::Claimed(t) | SigningTime::Timestamped(t)) = time;
+    if t < entry.valid_from || entry.valid_until.is_some_and(|until| t > until) {
+        return Err(VerifaiError::KeyNotValid);
+    }
+    match (entry.revoked_at, time) {
+        (None, _) => Ok(()),
+        (Some(revoked_at), SigningTime::Timestamped(t)) if t < revoked_at => Ok(()),
+        (Some(_), _) => Err(VerifaiError::KeyRevoked),
+    }
+}
+
+/// Checks every signing key of an artifact of any version against `list`.
+///
+/// A v3 timestamp token only counts when `trusted_tsa` signed it; anyone can
+/// sign a token of their own. Versions without a signed time are judged at
+/// `now` (unix nanos). Only the timestamp token is verified here; call this
+/// after the artifact itself has been verified.
+pub fn check_artifact_key_status(
+    artifact_bin: &[u8],
+    list: &KeyStatusListV0,
+    trusted_tsa: Option<[u8; 32]>,
+    now: u64,
+) -> Result<(), VerifaiError> {
+    let decode_failed = |_| VerifaiError::CoreDecode;
+    let untimed = SigningTime::Claimed(now);
+    match artifact_version(artifact_bin) {
+        Some(0) => {
+            let a = ProofArtifactV0::decode_bin(artifact_bin).map_err(decode_failed)?;
+            check_key_status(list, &a.sig_pubkey, untimed)
+        }
+        Some(1) => {
+            let a = ProofArtifactV1::decode_bin(artifact_bin).map_err(decode_failed)?;
+            check_key_status(list, &a.sig_pubkey, untimed)
+        }
+        Some(2) => {
+            let a = BatchProofArtifactV2::decode_bin(artifact_bin).map_err(decode_failed)?;
+            check_key_status(list, &a.sig_pubkey, untimed)
+        }
+        Some(3) => {
+            let a = ProofArtifactV3::decode_bin(artifact_bin).map_err(decode_failed)?;
+            let time = match &a.timestamp {
+                Some(token)
+                    if trusted_tsa == Some(token.tsa_pubkey)
+                        && token.verify_signature().is_ok()
+                        && token.message_imprint == a.timestamp_imprint() =>
+                {
+                    SigningTime::Timestamped(token.gen_time)
+                }
+                _ => SigningTime::Claimed(a.issued_at),
+            };
+            check_key_status(list, &a.sig_pubkey, time)
+        }
+        Some(4) => {
+            let a = ProofArtifactV4::decode_bin(artifact_bin).map_err(decode_failed)?;
+            a.signatures
+                .iter()
+                .try_for_each(|s| check_key_status(list, &s.pubkey, untimed))
+        }
+        Some(5) => {
+            let a = ProofArtifactV5::decode_bin(artifact_bin).map_err(decode_failed)?;
+            // key-status lists only carry Ed25519 keys
+            let pubkey: [u8; 32] = match (a.sig_alg, a.sig_pubkey.as_slice().try_into()) {
+                (SigAlg::Ed25519, Ok(pubkey)) => pubkey,
+                _ => return Err(VerifaiError::KeyUnknown),
+            };
+            check_key_status(list, &pubkey, untimed)
+        }
+        Some(6) => {
+            let a = ProofArtifactV6::decode_bin(artifact_bin).map_err(decode_failed)?;
+            let pubkey: [u8; 32] = match (a.sig_alg, a.sig_pubkey.as_slice().try_into()) {
+                (SigAlg::Ed25519, Ok(pubkey)) => pubkey,
+                _ => return Err(VerifaiError::KeyUnknown),
+            };
+            check_key_status(list, &pubkey, untimed)
+        }
+        Some(7) => {
+            let a = ProofArtifactV7::decode_bin(artifact_bin).map_err(decode_failed)?;
+            check_key_status(list, &a.sig_pubkey, untimed)
+        }
+        Some(8) => {
+            let a = ProofArtifactV8::decode_bin(artifact_bin).map_err(decode_failed)?;
+            check_key_status(list, &a.sig_pubkey, untimed)
+        }
+        Some(9) => {
+            let a = ProofArtifactV9::decode_bin(artifact_bin).map_err(decode_failed)?;
+            check_key_status(list, &a.sig_pubkey, untimed)
+        }
+        _ => Err(VerifaiError::CoreDecode),
+    }
+}
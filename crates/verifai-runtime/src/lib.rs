@@ -1,13 +1,92 @@
+#[cfg(unix)]
+mod agent;
 mod attester;
+mod batch;
+mod bulk;
+mod consistency;
+mod cose;
+mod disclosure;
+mod divergence;
+mod freshness;
+mod hashalg;
+mod intoto;
+mod keystatus;
 mod lr;
 mod mlp;
+mod multisig;
+mod pipeline;
+mod preprocess;
 mod prove;
+mod salted;
+mod sigalg;
+mod trace_level;
+mod translog;
+mod tsa;
+mod weights;
+mod x509;
+#[cfg(feature = "zk")]
+mod zk;
 
+#[cfg(unix)]
+pub use agent::{AgentSigner, SigningAgent, AGENT_MAX_MESSAGE_LEN};
 pub use attester::{Attester, NoopAttester};
+pub use batch::{
+    batch_row_proof_lr_v2, batch_row_proof_mlp_v2, prove_lr_batch_v2, prove_mlp_batch_v2,
+    verify_batch_row_lr_v2, verify_batch_row_mlp_v2, verify_lr_batch_v2, verify_mlp_batch_v2,
+};
+pub use bulk::{verify_lr_bulk, verify_mlp_bulk, BulkRecord};
+pub use consistency::{check_trace_consistency, lr_trace, mlp_trace};
+pub use cose::{decode_cose_v1, encode_cose_v1};
+pub use disclosure::{disclose_lr_trace, disclose_mlp_trace, verify_trace_disclosure};
+pub use divergence::{verify_lr_with_trace, verify_mlp_with_trace, TraceDivergence};
+pub use freshness::{
+    prove_lr_v3_with_attester, prove_lr_v3_with_sink, prove_mlp_v3, prove_mlp_v3_with_sink,
+    verify_freshness_v3, verify_lr_v3, verify_mlp_v3, FreshnessPolicy, IssuanceV3,
+};
+pub use hashalg::{
+    prove_lr_v6_with_attester, prove_lr_v6_with_sink, prove_mlp_v6, prove_mlp_v6_with_sink,
+    verify_lr_v6, verify_mlp_v6,
+};
+pub use intoto::{
+    decode_intoto_envelope, encode_intoto_envelope, is_intoto_envelope, INTOTO_PAYLOAD_TYPE,
+    INTOTO_STATEMENT_TYPE, VERIFAI_PREDICATE_TYPE,
+};
+pub use keystatus::{
+    check_artifact_key_status, check_key_status, verify_key_status_list, SigningTime,
+};
+pub use lr::run_lr_v0_into;
+pub use mlp::run_mlp_v1_into;
+pub use multisig::{
+    cosign_lr_v4, cosign_mlp_v4, prove_lr_v4_with_attester, prove_lr_v4_with_sink, prove_mlp_v4,
+    prove_mlp_v4_with_sink, verify_lr_v4, verify_mlp_v4, verify_threshold_v4, ThresholdPolicy,
+};
+pub use pipeline::{sign_pipeline_v0, verify_pipeline_replay_v0, verify_pipeline_v0, StageModel};
+pub use preprocess::{
+    preprocess_v1, prove_lr_v8_with_attester, prove_mlp_v8, verify_lr_v8, verify_mlp_v8,
+};
 pub use prove::{
-    artifact_version, prove_lr_v0, prove_lr_v1_with_attester, prove_mlp_v1, verify_lr_v0,
-    verify_lr_v1, verify_mlp_v1,
+    artifact_version, native_artifact_bin, prove_lr_v0, prove_lr_v0_with_sink,
+    prove_lr_v1_with_attester, prove_lr_v1_with_sink, prove_mlp_v1, prove_mlp_v1_with_sink,
+    verify_lr_v0, verify_lr_v1, verify_mlp_v1,
+};
+pub use salted::{
+    prove_lr_v7_with_attester, prove_lr_v7_with_sink, prove_mlp_v7, prove_mlp_v7_with_sink,
+    random_salts, verify_lr_v7, verify_mlp_v7,
+};
+pub use sigalg::{
+    prove_lr_v5_with_attester, prove_lr_v5_with_sink, prove_mlp_v5, prove_mlp_v5_with_sink,
+    verify_lr_v5, verify_mlp_v5,
+};
+pub use trace_level::{
+    lr_trace_at, lr_trace_into, mlp_trace_at, mlp_trace_into, prove_lr_v9_with_attester,
+    prove_mlp_v9, verify_lr_v9, verify_mlp_v9,
 };
+pub use translog::{verify_log_consistency_v0, verify_log_inclusion_v0, TransparencyLog};
+pub use tsa::{unix_nanos_now, LocalTsa};
+pub use weights::{load_npy, load_npz, load_safetensors, Tensor, TensorSet};
+pub use x509::{pem_certificates, verify_signer_chain, SignerIdentity};
+#[cfg(feature = "zk")]
+pub use zk::{prove_lr_v10, verify_lr_v10, zk_lr_setup};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum VerifaiError {
@@ -16,4 +95,29 @@ pub enum VerifaiError {
     SignatureInvalid,
     HashMismatch,
     TraceMismatch,
+    WeightsDecode,
+    InclusionProofInvalid,
+    ConsistencyProofInvalid,
+    NotInLog,
+    Io(String),
+    Expired,
+    Stale,
+    NotYetValid,
+    NonceMismatch,
+    TimestampInvalid,
+    CertificateInvalid,
+    CertificateExpired,
+    KeyUsageInvalid,
+    UntrustedChain,
+    ThresholdNotMet,
+    KeyUnknown,
+    KeyNotValid,
+    KeyRevoked,
+    SignerFailed(String),
+    PipelineLinkBroken,
+    RawInputInvalid,
+    OutOfRange,
+    CircuitMismatch,
+    ZkProofInvalid,
+    Zk(String),
 }
@@ -1,15 +1,17 @@
 use verifai_core::bytes::BytesError;
 use verifai_core::event_bin::{ActivationKind, TraceEventV0};
 use verifai_core::model_bin::{InputV0, LogisticModelV0, OutputV0};
+use verifai_core::trace_sink::TraceSink;
 
 use crate::VerifaiError;
 
-pub struct LrRun {
-    pub output: OutputV0,
-    pub events: Vec<TraceEventV0>,
-}
-
-pub fn run_lr_v0(model_bin: &[u8], input_bin: &[u8]) -> Result<LrRun, VerifaiError> {
+/// Runs the LR model on one input, handing each trace event to `sink` as it
+/// is produced. Stops with `TraceMismatch` if the sink does.
+pub fn run_lr_v0_into(
+    model_bin: &[u8],
+    input_bin: &[u8],
+    sink: &mut dyn TraceSink,
+) -> Result<OutputV0, VerifaiError> {
     let model = LogisticModelV0::decode_bin(model_bin).map_err(map_core)?;
     let input = InputV0::decode_bin(input_bin).map_err(map_core)?;
     if model.weights.len() != input.x.len() {
@@ -21,23 +23,36 @@ pub fn run_lr_v0(model_bin: &[u8], input_bin: &[u8]) -> Result<LrRun, VerifaiErr
         z += w * x;
     }
 
-    let y = 1.0_f64 / (1.0_f64 + (-z).exp());
-
-    let events = vec![
-        TraceEventV0::OpLinear { op_id: 0, z },
+    emit(sink, TraceEventV0::OpLinear { op_id: 0, z })?;
+    let y = sigmoid(z);
+    emit(
+        sink,
         TraceEventV0::OpActivation {
             op_id: 1,
             kind: ActivationKind::Sigmoid,
             input: z,
             output: y,
         },
-        TraceEventV0::OpOutput { y },
-    ];
+    )?;
+    emit(sink, TraceEventV0::OpOutput { y })?;
+
+    Ok(OutputV0 { y })
+}
+
+/// Hands `event` to `sink`, failing with `TraceMismatch` once the sink has
+/// stopped.
+pub(crate) fn emit(sink: &mut dyn TraceSink, event: TraceEventV0) -> Result<(), VerifaiError> {
+    sink.record(&event);
+    if sink.stopped() {
+        return Err(VerifaiError::TraceMismatch);
+    }
+    Ok(())
+}
 
-    Ok(LrRun {
-        output: OutputV0 { y },
-        events,
-    })
+/// The logistic function exactly as inference computes it; trace checks
+/// compare against it bit for bit.
+pub(crate) fn sigmoid(z: f64) -> f64 {
+    1.0_f64 / (1.0_f64 + (-z).exp())
 }
 
 fn map_core(_e: BytesError) -> VerifaiError {
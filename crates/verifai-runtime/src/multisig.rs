@@ -0,0 +1,255 @@
+use verifai_core::artifact_bin::ProofArtifactV4;
+use verifai_core::hash::{sha256, HashAlg};
+use verifai_core::signer::Signer;
+use verifai_core::trace_sink::TraceSink;
+
+use crate::attester::{Attester, NoopAttester};
+use crate::batch::{run_lr_row, run_mlp_row, run_row_hashed, run_row_hashed_into, RowRunner};
+use crate::VerifaiError;
+
+/// How many co-signatures `verify_*_v4` requires, and by whom.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThresholdPolicy {
+    /// Keys whose signatures count; `None` counts every valid signer.
+    pub trusted: Option<Vec<[u8; 32]>>,
+    pub threshold: usize,
+}
+
+impl ThresholdPolicy {
+    /// At least `threshold` distinct keys out of `trusted`.
+    pub fn k_of_n(threshold: usize, trusted: Vec<[u8; 32]>) -> Self {
+        Self {
+            trusted: Some(trusted),
+            threshold,
+        }
+    }
+
+    /// One valid signature by any key.
+    pub fn any_signer() -> Self {
+        Self {
+            trusted: None,
+            threshold: 1,
+        }
+    }
+
+    /// Signers out of `signers` (assumed distinct) that count towards the
+    /// threshold.
+    pub fn count(&self, signers: &[[u8; 32]]) -> usize {
+        match &self.trusted {
+            Some(trusted) => signers.iter().filter(|s| trusted.contains(s)).count(),
+            None => signers.len(),
+        }
+    }
+}
+
+fn prove_v4<A: Attester>(
+    run_row: RowRunner,
+    runtime_id: [u8; 32],
+    signer: &dyn Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    sink: &mut dyn TraceSink,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    let (output, trace_root) =
+        run_row_hashed_into(run_row, HashAlg::Sha256, model_bin, input_bin, sink)?;
+    let output_bin = output.encode_bin();
+
+    let mut artifact = ProofArtifactV4 {
+        version: 4,
+        runtime_id,
+        model_hash: sha256(model_bin),
+        input_hash: sha256(input_bin),
+        output_hash: sha256(&output_bin),
+        trace_root,
+        signatures: Vec::new(),
+        attestation: A::attest(trace_root),
+    };
+    artifact
+        .add_signature_with(signer)
+        .map_err(|e| VerifaiError::SignerFailed(e.0))?;
+
+    Ok((output_bin, artifact.encode_bin()))
+}
+
+/// Checks every co-signature of a v4 artifact against `policy` without
+/// replaying it. Returns the artifact and its signer keys.
+pub fn verify_threshold_v4(
+    artifact_bin: &[u8],
+    policy: &ThresholdPolicy,
+) -> Result<(ProofArtifactV4, Vec<[u8; 32]>), VerifaiError> {
+    let artifact =
+        ProofArtifactV4::decode_bin(artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    let signers = artifact
+        .verify_signatures()
+        .map_err(|_| VerifaiError::SignatureInvalid)?;
+    if policy.count(&signers) < policy.threshold {
+        return Err(VerifaiError::ThresholdNotMet);
+    }
+    Ok((artifact, signers))
+}
+
+fn verify_v4(
+    run_row: RowRunner,
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+    policy: &ThresholdPolicy,
+) -> Result<(ProofArtifactV4, Vec<[u8; 32]>), VerifaiError> {
+    let (artifact, signers) = verify_threshold_v4(artifact_bin, policy)?;
+
+    if artifact.model_hash != sha256(model_bin)
+        || artifact.input_hash != sha256(input_bin)
+        || artifact.output_hash != sha256(output_bin)
+    {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    let (output, trace_root) = run_row_hashed(run_row, HashAlg::Sha256, model_bin, input_bin)?;
+    if sha256(&output.encode_bin()) != artifact.output_hash {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    if artifact.trace_root != trace_root || artifact.attestation.measurement != trace_root {
+        return Err(VerifaiError::TraceMismatch);
+    }
+
+    Ok((artifact, signers))
+}
+
+fn cosign_v4(
+    run_row: RowRunner,
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+    signer: &dyn Signer,
+) -> Result<Vec<u8>, VerifaiError> {
+    let (mut artifact, _) = verify_v4(
+        run_row,
+        artifact_bin,
+        model_bin,
+        input_bin,
+        output_bin,
+        &ThresholdPolicy::any_signer(),
+    )?;
+    artifact
+        .add_signature_with(signer)
+        .map_err(|e| VerifaiError::SignerFailed(e.0))?;
+    Ok(artifact.encode_bin())
+}
+
+pub fn prove_lr_v4_with_attester<A: Attester>(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_lr_v4_with_sink::<A>(runtime_id, signer, model_bin, input_bin, &mut ())
+}
+
+/// `prove_lr_v4_with_attester`, also handing each trace event to `sink`.
+pub fn prove_lr_v4_with_sink<A: Attester>(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    sink: &mut dyn TraceSink,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_v4::<A>(run_lr_row, runtime_id, &signer, model_bin, input_bin, sink)
+}
+
+pub fn prove_mlp_v4(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_mlp_v4_with_sink(runtime_id, signer, model_bin, input_bin, &mut ())
+}
+
+/// `prove_mlp_v4`, also handing each trace event to `sink`.
+pub fn prove_mlp_v4_with_sink(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    sink: &mut dyn TraceSink,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_v4::<NoopAttester>(run_mlp_row, runtime_id, &signer, model_bin, input_bin, sink)
+}
+
+/// Replays a v4 LR artifact and, if it verifies, adds a signature by
+/// `signer`. Returns the updated artifact.
+pub fn cosign_lr_v4(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+    signer: impl Signer,
+) -> Result<Vec<u8>, VerifaiError> {
+    cosign_v4(
+        run_lr_row,
+        artifact_bin,
+        model_bin,
+        input_bin,
+        output_bin,
+        &signer,
+    )
+}
+
+/// MLP counterpart of `cosign_lr_v4`.
+pub fn cosign_mlp_v4(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+    signer: impl Signer,
+) -> Result<Vec<u8>, VerifaiError> {
+    cosign_v4(
+        run_mlp_row,
+        artifact_bin,
+        model_bin,
+        input_bin,
+        output_bin,
+        &signer,
+    )
+}
+
+/// Replays a v4 LR artifact and checks its co-signatures against `policy`.
+/// Returns every signer key.
+pub fn verify_lr_v4(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+    policy: &ThresholdPolicy,
+) -> Result<Vec<[u8; 32]>, VerifaiError> {
+    verify_v4(
+        run_lr_row,
+        artifact_bin,
+        model_bin,
+        input_bin,
+        output_bin,
+        policy,
+    )
+    .map(|(_, signers)| signers)
+}
+
+pub fn verify_mlp_v4(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+    policy: &ThresholdPolicy,
+) -> Result<Vec<[u8; 32]>, VerifaiError> {
+    verify_v4(
+        run_mlp_row,
+        artifact_bin,
+        model_bin,
+        input_bin,
+        output_bin,
+        policy,
+    )
+    .map(|(_, signers)| signers)
+}
@@ -0,0 +1,135 @@
+use verifai_core::pipeline::{PipelineProofV0, PipelineStageV0};
+use verifai_core::signer::Signer;
+
+use crate::batch::{run_lr_row, run_mlp_row, RowRunner};
+use crate::bulk::{replay, BulkRecord, Decoded};
+use crate::freshness::{check_freshness_v3, FreshnessPolicy};
+use crate::VerifaiError;
+
+/// The model of one pipeline stage, as `model.bin`.
+#[derive(Debug, Clone, Copy)]
+pub enum StageModel<'a> {
+    Lr(&'a [u8]),
+    Mlp(&'a [u8]),
+}
+
+/// Checks the stages' links and signs the pipeline, so an operator never
+/// signs a chain that `verify_pipeline_v0` would reject. A pipeline too
+/// large to encode fails with `DimensionMismatch`.
+pub fn sign_pipeline_v0(
+    stages: Vec<PipelineStageV0>,
+    signer: impl Signer,
+    policy: &FreshnessPolicy,
+) -> Result<PipelineProofV0, VerifaiError> {
+    check_stages(&stages, policy)?;
+    let mut pipeline = PipelineProofV0 {
+        stages,
+        sig_pubkey: [0u8; 32],
+        signature: [0u8; 64],
+    };
+    pipeline
+        .message_to_sign()
+        .map_err(|_| VerifaiError::DimensionMismatch)?;
+    pipeline
+        .sign_with(&signer)
+        .map_err(|e| VerifaiError::SignerFailed(e.0))?;
+    Ok(pipeline)
+}
+
+/// Checks the outer signature and every stage: its artifact's signature
+/// (and freshness for v3), that `output_hash` matches the stage output,
+/// and that `input_hash` matches the previous output under the declared
+/// link (`PipelineLinkBroken` if not). Batch (v2) and salted (v7)
+/// artifacts fail with `CoreDecode`. Nothing is replayed; see
+/// `verify_pipeline_replay_v0`.
+pub fn verify_pipeline_v0(
+    pipeline: &PipelineProofV0,
+    policy: &FreshnessPolicy,
+) -> Result<(), VerifaiError> {
+    pipeline
+        .verify_signature()
+        .map_err(|_| VerifaiError::SignatureInvalid)?;
+    check_stages(&pipeline.stages, policy).map(|_| ())
+}
+
+/// `verify_pipeline_v0`, then replays every stage from the first stage's
+/// `input_bin` with one model per stage, end to end.
+pub fn verify_pipeline_replay_v0(
+    pipeline: &PipelineProofV0,
+    models: &[StageModel<'_>],
+    input_bin: &[u8],
+    policy: &FreshnessPolicy,
+) -> Result<(), VerifaiError> {
+    pipeline
+        .verify_signature()
+        .map_err(|_| VerifaiError::SignatureInvalid)?;
+    let decoded = check_stages(&pipeline.stages, policy)?;
+    if models.len() != pipeline.stages.len() {
+        return Err(VerifaiError::DimensionMismatch);
+    }
+
+    let mut previous_output: Option<&[u8]> = None;
+    for ((stage, decoded), model) in pipeline.stages.iter().zip(&decoded).zip(models) {
+        let stage_input = match (stage.link, previous_output) {
+            (Some(link), Some(output)) => link
+                .apply(output)
+                .map_err(|_| VerifaiError::PipelineLinkBroken)?,
+            _ => input_bin.to_vec(),
+        };
+        let (run_row, model_bin): (RowRunner, &[u8]) = match *model {
+            StageModel::Lr(m) => (run_lr_row, m),
+            StageModel::Mlp(m) => (run_mlp_row, m),
+        };
+        let record = BulkRecord {
+            artifact_bin: &stage.artifact,
+            input_bin: &stage_input,
+            output_bin: &stage.output,
+        };
+        let model_hash = decoded.hash_alg().digest(model_bin);
+        replay(run_row, decoded, model_hash, model_bin, &record)?;
+        previous_output = Some(&stage.output);
+    }
+    Ok(())
+}
+
+/// Decodes and checks every stage, and each link to the stage before it.
+fn check_stages(
+    stages: &[PipelineStageV0],
+    policy: &FreshnessPolicy,
+) -> Result<Vec<Decoded>, VerifaiError> {
+    let mut decoded: Vec<Decoded> = Vec::with_capacity(stages.len());
+    for stage in stages {
+        let artifact = Decoded::decode(&stage.artifact)?;
+        artifact
+            .as_batch()
+            .verify_alone()
+            .map_err(|_| VerifaiError::SignatureInvalid)?;
+        if let Decoded::V3(a) = &artifact {
+            check_freshness_v3(a, policy)?;
+        }
+        let ([_, input_hash, output_hash], _, _) = artifact.commitments();
+        let hash_alg = artifact.hash_alg();
+        if hash_alg.digest(&stage.output) != output_hash {
+            return Err(VerifaiError::HashMismatch);
+        }
+
+        match (stage.link, decoded.len()) {
+            (None, 0) => {}
+            (Some(link), i) if i > 0 => {
+                let input_bin = link
+                    .apply(&stages[i - 1].output)
+                    .map_err(|_| VerifaiError::PipelineLinkBroken)?;
+                if hash_alg.digest(&input_bin) != input_hash {
+                    return Err(VerifaiError::PipelineLinkBroken);
+                }
+            }
+            // only the first stage has no link; decode_bin enforces it too
+            _ => return Err(VerifaiError::PipelineLinkBroken),
+        }
+        decoded.push(artifact);
+    }
+    if decoded.is_empty() {
+        return Err(VerifaiError::DimensionMismatch);
+    }
+    Ok(decoded)
+}
@@ -0,0 +1,239 @@
+use verifai_core::artifact_bin::ProofArtifactV8;
+use verifai_core::event_bin::TraceEventV0;
+use verifai_core::hash::sha256;
+use verifai_core::merkle::MerkleBuilder;
+use verifai_core::model_bin::InputV0;
+use verifai_core::preprocess::{FeatureSpec, PreprocessorV1, RawInputV1, RawValue};
+use verifai_core::signer::Signer;
+use verifai_core::trace_sink::TraceSink;
+
+use crate::attester::{Attester, NoopAttester};
+use crate::batch::{run_lr_row, run_mlp_row, RowRunner};
+use crate::VerifaiError;
+
+/// Applies the preprocessor to the raw input, recording one `OpFeature`
+/// event per model feature. `DimensionMismatch` if the raw input has the
+/// wrong number of fields, `RawInputInvalid` if a field has the wrong type.
+fn run_preprocessor_v1(
+    preprocessor: &PreprocessorV1,
+    raw: &RawInputV1,
+) -> Result<(InputV0, Vec<TraceEventV0>), VerifaiError> {
+    if raw.values.len() != preprocessor.raw_len as usize {
+        return Err(VerifaiError::DimensionMismatch);
+    }
+    let mut x = Vec::with_capacity(preprocessor.output_len());
+    for feature in &preprocessor.features {
+        let value = &raw.values[feature.source() as usize];
+        match feature {
+            FeatureSpec::Numeric {
+                default,
+                clip,
+                mean,
+                std,
+                ..
+            } => {
+                let mut v = match value {
+                    RawValue::Number(v) if !v.is_nan() => *v,
+                    RawValue::Number(_) | RawValue::Missing => *default,
+                    RawValue::Category(_) => return Err(VerifaiError::RawInputInvalid),
+                };
+                if let Some((lo, hi)) = clip {
+                    v = v.clamp(*lo, *hi);
+                }
+                x.push((v - mean) / std);
+            }
+            FeatureSpec::Categorical {
+                categories,
+                default,
+                ..
+            } => {
+                let hot = match value {
+                    RawValue::Category(c) => categories.iter().position(|k| k == c),
+                    RawValue::Missing => None,
+                    RawValue::Number(_) => return Err(VerifaiError::RawInputInvalid),
+                }
+                .or(default.map(|d| d as usize));
+                x.extend((0..categories.len()).map(|i| if Some(i) == hot { 1.0 } else { 0.0 }));
+            }
+        }
+    }
+    let events = x
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| TraceEventV0::OpFeature {
+            index: i as u32,
+            value,
+        })
+        .collect();
+    Ok((InputV0 { x }, events))
+}
+
+/// The model input `preprocessor_bin` derives from `raw_input_bin`.
+pub fn preprocess_v1(
+    preprocessor_bin: &[u8],
+    raw_input_bin: &[u8],
+) -> Result<Vec<u8>, VerifaiError> {
+    let (preprocessor, raw) = decode(preprocessor_bin, raw_input_bin)?;
+    run_preprocessor_v1(&preprocessor, &raw).map(|(input, _)| input.encode_bin())
+}
+
+fn decode(
+    preprocessor_bin: &[u8],
+    raw_input_bin: &[u8],
+) -> Result<(PreprocessorV1, RawInputV1), VerifaiError> {
+    let preprocessor =
+        PreprocessorV1::decode_bin(preprocessor_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    let raw = RawInputV1::decode_bin(raw_input_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    Ok((preprocessor, raw))
+}
+
+/// Output and trace root (feature events, then the model's) of one run.
+fn run_v8(
+    run_row: RowRunner,
+    preprocessor_bin: &[u8],
+    model_bin: &[u8],
+    raw_input_bin: &[u8],
+) -> Result<(Vec<u8>, [u8; 32]), VerifaiError> {
+    let (preprocessor, raw) = decode(preprocessor_bin, raw_input_bin)?;
+    let (input, events) = run_preprocessor_v1(&preprocessor, &raw)?;
+    let mut tree = MerkleBuilder::new();
+    for event in &events {
+        tree.record(event);
+    }
+    let output = run_row(model_bin, &input.encode_bin(), &mut tree)?;
+    Ok((output.encode_bin(), tree.finalize()))
+}
+
+fn prove_v8<A: Attester>(
+    run_row: RowRunner,
+    runtime_id: [u8; 32],
+    signer: &dyn Signer,
+    preprocessor_bin: &[u8],
+    model_bin: &[u8],
+    raw_input_bin: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    let (output_bin, trace_root) = run_v8(run_row, preprocessor_bin, model_bin, raw_input_bin)?;
+
+    let mut artifact = ProofArtifactV8 {
+        version: 8,
+        runtime_id,
+        model_hash: sha256(model_bin),
+        preprocessor_hash: sha256(preprocessor_bin),
+        input_hash: sha256(raw_input_bin),
+        output_hash: sha256(&output_bin),
+        trace_root,
+        sig_pubkey: [0u8; 32],
+        signature: [0u8; 64],
+        attestation: A::attest(trace_root),
+    };
+    artifact
+        .sign_with(signer)
+        .map_err(|e| VerifaiError::SignerFailed(e.0))?;
+
+    Ok((output_bin, artifact.encode_bin()))
+}
+
+fn verify_v8(
+    run_row: RowRunner,
+    artifact_bin: &[u8],
+    preprocessor_bin: &[u8],
+    model_bin: &[u8],
+    raw_input_bin: &[u8],
+    output_bin: &[u8],
+) -> Result<ProofArtifactV8, VerifaiError> {
+    let artifact =
+        ProofArtifactV8::decode_bin(artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    artifact
+        .verify_signature()
+        .map_err(|_| VerifaiError::SignatureInvalid)?;
+
+    if artifact.model_hash != sha256(model_bin)
+        || artifact.preprocessor_hash != sha256(preprocessor_bin)
+        || artifact.input_hash != sha256(raw_input_bin)
+        || artifact.output_hash != sha256(output_bin)
+    {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    let (recomputed, trace_root) = run_v8(run_row, preprocessor_bin, model_bin, raw_input_bin)?;
+    if sha256(&recomputed) != artifact.output_hash {
+        return Err(VerifaiError::HashMismatch);
+    }
+    if artifact.trace_root != trace_root || artifact.attestation.measurement != trace_root {
+        return Err(VerifaiError::TraceMismatch);
+    }
+
+    Ok(artifact)
+}
+
+/// Preprocesses `raw_input_bin` with `preprocessor_bin` and proves the LR
+/// model on the result, committing to the preprocessor and the raw input.
+pub fn prove_lr_v8_with_attester<A: Attester>(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    preprocessor_bin: &[u8],
+    model_bin: &[u8],
+    raw_input_bin: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_v8::<A>(
+        run_lr_row,
+        runtime_id,
+        &signer,
+        preprocessor_bin,
+        model_bin,
+        raw_input_bin,
+    )
+}
+
+pub fn prove_mlp_v8(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    preprocessor_bin: &[u8],
+    model_bin: &[u8],
+    raw_input_bin: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_v8::<NoopAttester>(
+        run_mlp_row,
+        runtime_id,
+        &signer,
+        preprocessor_bin,
+        model_bin,
+        raw_input_bin,
+    )
+}
+
+/// Replays a v8 LR artifact from the raw input through preprocessing and
+/// the model.
+pub fn verify_lr_v8(
+    artifact_bin: &[u8],
+    preprocessor_bin: &[u8],
+    model_bin: &[u8],
+    raw_input_bin: &[u8],
+    output_bin: &[u8],
+) -> Result<ProofArtifactV8, VerifaiError> {
+    verify_v8(
+        run_lr_row,
+        artifact_bin,
+        preprocessor_bin,
+        model_bin,
+        raw_input_bin,
+        output_bin,
+    )
+}
+
+pub fn verify_mlp_v8(
+    artifact_bin: &[u8],
+    preprocessor_bin: &[u8],
+    model_bin: &[u8],
+    raw_input_bin: &[u8],
+    output_bin: &[u8],
+) -> Result<ProofArtifactV8, VerifaiError> {
+    verify_v8(
+        run_mlp_row,
+        artifact_bin,
+        preprocessor_bin,
+        model_bin,
+        raw_input_bin,
+        output_bin,
+    )
+}
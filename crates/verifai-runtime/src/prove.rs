@@ -1,29 +1,45 @@
+use std::borrow::Cow;
+
 use verifai_core::artifact_bin::{ProofArtifactV0, ProofArtifactV1, PROOF_ARTIFACT_V0_LEN};
-use verifai_core::hash::sha256;
-use verifai_core::merkle::trace_root_from_event_bytes;
+use verifai_core::cose::is_cose_sign1;
+use verifai_core::hash::{sha256, HashAlg};
+use verifai_core::signer::Signer;
+use verifai_core::signer_bundle::{is_signer_bundle, SignerBundleV0};
+use verifai_core::trace_sink::TraceSink;
 
 use crate::attester::{Attester, NoopAttester};
-use crate::lr::run_lr_v0;
-use crate::mlp::run_mlp_v1;
+use crate::batch::{run_lr_row, run_mlp_row, run_row_hashed, run_row_hashed_into};
+use crate::cose::decode_cose_v1;
+use crate::intoto::{decode_intoto_envelope, is_intoto_envelope};
 use crate::VerifaiError;
 
 pub fn prove_lr_v0(
     runtime_id: [u8; 32],
-    signing_key_bytes: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_lr_v0_with_sink(runtime_id, signer, model_bin, input_bin, &mut ())
+}
+
+/// `prove_lr_v0`, also handing each trace event to `sink` as the proving
+/// run produces it.
+pub fn prove_lr_v0_with_sink(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
     model_bin: &[u8],
     input_bin: &[u8],
+    sink: &mut dyn TraceSink,
 ) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
-    let run = run_lr_v0(model_bin, input_bin)?;
+    let (output, trace_root) =
+        run_row_hashed_into(run_lr_row, HashAlg::Sha256, model_bin, input_bin, sink)?;
 
-    let output_bin = run.output.encode_bin();
+    let output_bin = output.encode_bin();
 
     let model_hash = sha256(model_bin);
     let input_hash = sha256(input_bin);
     let output_hash = sha256(&output_bin);
 
-    let event_bytes: Vec<Vec<u8>> = run.events.iter().map(|e| e.encode_bin()).collect();
-    let trace_root = trace_root_from_event_bytes(&event_bytes);
-
     let mut artifact = ProofArtifactV0 {
         version: 0,
         runtime_id,
@@ -36,8 +52,8 @@ pub fn prove_lr_v0(
     };
 
     artifact
-        .sign_detached(signing_key_bytes)
-        .map_err(|_| VerifaiError::CoreDecode)?;
+        .sign_with(&signer)
+        .map_err(|e| VerifaiError::SignerFailed(e.0))?;
 
     let artifact_bin = artifact.encode_bin();
     if artifact_bin.len() != PROOF_ARTIFACT_V0_LEN {
@@ -49,7 +65,7 @@ pub fn prove_lr_v0(
 
 fn build_artifact_v1(
     runtime_id: [u8; 32],
-    signing_key_bytes: [u8; 32],
+    signer: &dyn Signer,
     model_hash: [u8; 32],
     input_hash: [u8; 32],
     output_hash: [u8; 32],
@@ -68,33 +84,42 @@ fn build_artifact_v1(
         attestation,
     };
     artifact
-        .sign_detached(signing_key_bytes)
-        .map_err(|_| VerifaiError::CoreDecode)?;
+        .sign_with(signer)
+        .map_err(|e| VerifaiError::SignerFailed(e.0))?;
 
     Ok(artifact.encode_bin())
 }
 
 pub fn prove_lr_v1_with_attester<A: Attester>(
     runtime_id: [u8; 32],
-    signing_key_bytes: [u8; 32],
+    signer: impl Signer,
     model_bin: &[u8],
     input_bin: &[u8],
 ) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
-    let run = run_lr_v0(model_bin, input_bin)?;
+    prove_lr_v1_with_sink::<A>(runtime_id, signer, model_bin, input_bin, &mut ())
+}
+
+/// `prove_lr_v1_with_attester`, also handing each trace event to `sink`.
+pub fn prove_lr_v1_with_sink<A: Attester>(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    sink: &mut dyn TraceSink,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    let (output, trace_root) =
+        run_row_hashed_into(run_lr_row, HashAlg::Sha256, model_bin, input_bin, sink)?;
 
-    let output_bin = run.output.encode_bin();
+    let output_bin = output.encode_bin();
 
     let model_hash = sha256(model_bin);
     let input_hash = sha256(input_bin);
     let output_hash = sha256(&output_bin);
 
-    let event_bytes: Vec<Vec<u8>> = run.events.iter().map(|e| e.encode_bin()).collect();
-    let trace_root = trace_root_from_event_bytes(&event_bytes);
-
     let attestation = A::attest(trace_root);
     let artifact_bin = build_artifact_v1(
         runtime_id,
-        signing_key_bytes,
+        &signer,
         model_hash,
         input_hash,
         output_hash,
@@ -110,8 +135,9 @@ pub fn verify_lr_v0(
     input_bin: &[u8],
     output_bin: &[u8],
 ) -> Result<(), VerifaiError> {
+    let artifact_bin = native_artifact_bin(artifact_bin)?;
     let artifact =
-        ProofArtifactV0::decode_bin(artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
+        ProofArtifactV0::decode_bin(&artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
     if artifact.version != 0 {
         return Err(VerifaiError::CoreDecode);
     }
@@ -131,15 +157,12 @@ pub fn verify_lr_v0(
         return Err(VerifaiError::HashMismatch);
     }
 
-    let run = run_lr_v0(model_bin, input_bin)?;
-    let recomputed_output_bin = run.output.encode_bin();
+    let (output, trace_root) = run_row_hashed(run_lr_row, HashAlg::Sha256, model_bin, input_bin)?;
+    let recomputed_output_bin = output.encode_bin();
     if sha256(&recomputed_output_bin) != output_hash {
         return Err(VerifaiError::HashMismatch);
     }
 
-    let event_bytes: Vec<Vec<u8>> = run.events.iter().map(|e| e.encode_bin()).collect();
-    let trace_root = trace_root_from_event_bytes(&event_bytes);
-
     if artifact.trace_root != trace_root {
         return Err(VerifaiError::TraceMismatch);
     }
@@ -153,8 +176,9 @@ pub fn verify_lr_v1(
     input_bin: &[u8],
     output_bin: &[u8],
 ) -> Result<(), VerifaiError> {
+    let artifact_bin = native_artifact_bin(artifact_bin)?;
     let artifact =
-        ProofArtifactV1::decode_bin(artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
+        ProofArtifactV1::decode_bin(&artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
     if artifact.version != 1 {
         return Err(VerifaiError::CoreDecode);
     }
@@ -174,15 +198,12 @@ pub fn verify_lr_v1(
         return Err(VerifaiError::HashMismatch);
     }
 
-    let run = run_lr_v0(model_bin, input_bin)?;
-    let recomputed_output_bin = run.output.encode_bin();
+    let (output, trace_root) = run_row_hashed(run_lr_row, HashAlg::Sha256, model_bin, input_bin)?;
+    let recomputed_output_bin = output.encode_bin();
     if sha256(&recomputed_output_bin) != output_hash {
         return Err(VerifaiError::HashMismatch);
     }
 
-    let event_bytes: Vec<Vec<u8>> = run.events.iter().map(|e| e.encode_bin()).collect();
-    let trace_root = trace_root_from_event_bytes(&event_bytes);
-
     if artifact.trace_root != trace_root {
         return Err(VerifaiError::TraceMismatch);
     }
@@ -196,25 +217,34 @@ pub fn verify_lr_v1(
 
 pub fn prove_mlp_v1(
     runtime_id: [u8; 32],
-    signing_key_bytes: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_mlp_v1_with_sink(runtime_id, signer, model_bin, input_bin, &mut ())
+}
+
+/// `prove_mlp_v1`, also handing each trace event to `sink`.
+pub fn prove_mlp_v1_with_sink(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
     model_bin: &[u8],
     input_bin: &[u8],
+    sink: &mut dyn TraceSink,
 ) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
-    let run = run_mlp_v1(model_bin, input_bin)?;
+    let (output, trace_root) =
+        run_row_hashed_into(run_mlp_row, HashAlg::Sha256, model_bin, input_bin, sink)?;
 
-    let output_bin = run.output.encode_bin();
+    let output_bin = output.encode_bin();
 
     let model_hash = sha256(model_bin);
     let input_hash = sha256(input_bin);
     let output_hash = sha256(&output_bin);
 
-    let event_bytes: Vec<Vec<u8>> = run.events.iter().map(|e| e.encode_bin()).collect();
-    let trace_root = trace_root_from_event_bytes(&event_bytes);
-
     let attestation = NoopAttester::attest(trace_root);
     let artifact_bin = build_artifact_v1(
         runtime_id,
-        signing_key_bytes,
+        &signer,
         model_hash,
         input_hash,
         output_hash,
@@ -231,8 +261,9 @@ pub fn verify_mlp_v1(
     input_bin: &[u8],
     output_bin: &[u8],
 ) -> Result<(), VerifaiError> {
+    let artifact_bin = native_artifact_bin(artifact_bin)?;
     let artifact =
-        ProofArtifactV1::decode_bin(artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
+        ProofArtifactV1::decode_bin(&artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
     if artifact.version != 1 {
         return Err(VerifaiError::CoreDecode);
     }
@@ -252,15 +283,12 @@ pub fn verify_mlp_v1(
         return Err(VerifaiError::HashMismatch);
     }
 
-    let run = run_mlp_v1(model_bin, input_bin)?;
-    let recomputed_output_bin = run.output.encode_bin();
+    let (output, trace_root) = run_row_hashed(run_mlp_row, HashAlg::Sha256, model_bin, input_bin)?;
+    let recomputed_output_bin = output.encode_bin();
     if sha256(&recomputed_output_bin) != output_hash {
         return Err(VerifaiError::HashMismatch);
     }
 
-    let event_bytes: Vec<Vec<u8>> = run.events.iter().map(|e| e.encode_bin()).collect();
-    let trace_root = trace_root_from_event_bytes(&event_bytes);
-
     if artifact.trace_root != trace_root {
         return Err(VerifaiError::TraceMismatch);
     }
@@ -278,3 +306,30 @@ pub fn artifact_version(artifact_bin: &[u8]) -> Option<u16> {
     }
     Some(u16::from_le_bytes([artifact_bin[0], artifact_bin[1]]))
 }
+
+/// Native artifact bytes for any accepted encoding: native `.bin`, COSE_Sign1,
+/// an in-toto DSSE envelope or a signer bundle wrapping one of those.
+/// Envelope signatures are checked on the way; bundle chains are not. A
+/// bundle wrapping another bundle is rejected, so decoding is one level deep.
+pub fn native_artifact_bin(artifact_bin: &[u8]) -> Result<Cow<'_, [u8]>, VerifaiError> {
+    if is_signer_bundle(artifact_bin) {
+        let bundle =
+            SignerBundleV0::decode_bin(artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
+        if is_signer_bundle(&bundle.artifact) {
+            return Err(VerifaiError::CoreDecode);
+        }
+        Ok(Cow::Owned(unwrap_envelope(&bundle.artifact)?.into_owned()))
+    } else {
+        unwrap_envelope(artifact_bin)
+    }
+}
+
+fn unwrap_envelope(artifact_bin: &[u8]) -> Result<Cow<'_, [u8]>, VerifaiError> {
+    if is_cose_sign1(artifact_bin) {
+        decode_cose_v1(artifact_bin).map(Cow::Owned)
+    } else if is_intoto_envelope(artifact_bin) {
+        decode_intoto_envelope(artifact_bin).map(Cow::Owned)
+    } else {
+        Ok(Cow::Borrowed(artifact_bin))
+    }
+}
@@ -0,0 +1,186 @@
+use verifai_core::artifact_bin::ProofArtifactV7;
+use verifai_core::hash::HashAlg;
+use verifai_core::salts::CommitmentSaltsV0;
+use verifai_core::signer::Signer;
+use verifai_core::trace_sink::TraceSink;
+
+use crate::attester::{Attester, NoopAttester};
+use crate::batch::{run_lr_row, run_mlp_row, run_row_hashed, run_row_hashed_into, RowRunner};
+use crate::VerifaiError;
+
+/// Four fresh salts from the OS random number generator.
+pub fn random_salts() -> Result<CommitmentSaltsV0, VerifaiError> {
+    let mut bytes = [0u8; 32 * 4];
+    getrandom::getrandom(&mut bytes).map_err(|e| VerifaiError::Io(e.to_string()))?;
+    let salt = |i: usize| {
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&bytes[32 * i..32 * (i + 1)]);
+        out
+    };
+    Ok(CommitmentSaltsV0 {
+        model: salt(0),
+        input: salt(1),
+        output: salt(2),
+        trace: salt(3),
+    })
+}
+
+fn prove_v7<A: Attester>(
+    run_row: RowRunner,
+    runtime_id: [u8; 32],
+    signer: &dyn Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    salts: &CommitmentSaltsV0,
+    sink: &mut dyn TraceSink,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    let (output, trace_root) =
+        run_row_hashed_into(run_row, HashAlg::Sha256, model_bin, input_bin, sink)?;
+    let output_bin = output.encode_bin();
+    let trace_commitment = salts.commit_trace(trace_root);
+
+    let mut artifact = ProofArtifactV7 {
+        version: 7,
+        runtime_id,
+        model_commitment: salts.commit_model(model_bin),
+        input_commitment: salts.commit_input(input_bin),
+        output_commitment: salts.commit_output(&output_bin),
+        trace_commitment,
+        sig_pubkey: [0u8; 32],
+        signature: [0u8; 64],
+        attestation: A::attest(trace_commitment),
+    };
+    artifact
+        .sign_with(signer)
+        .map_err(|e| VerifaiError::SignerFailed(e.0))?;
+
+    Ok((output_bin, artifact.encode_bin()))
+}
+
+fn verify_v7(
+    run_row: RowRunner,
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+    salts: &CommitmentSaltsV0,
+) -> Result<ProofArtifactV7, VerifaiError> {
+    let artifact =
+        ProofArtifactV7::decode_bin(artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    artifact
+        .verify_signature()
+        .map_err(|_| VerifaiError::SignatureInvalid)?;
+
+    if artifact.model_commitment != salts.commit_model(model_bin)
+        || artifact.input_commitment != salts.commit_input(input_bin)
+        || artifact.output_commitment != salts.commit_output(output_bin)
+    {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    let (output, trace_root) = run_row_hashed(run_row, HashAlg::Sha256, model_bin, input_bin)?;
+    if salts.commit_output(&output.encode_bin()) != artifact.output_commitment {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    let trace_commitment = salts.commit_trace(trace_root);
+    if artifact.trace_commitment != trace_commitment
+        || artifact.attestation.measurement != trace_commitment
+    {
+        return Err(VerifaiError::TraceMismatch);
+    }
+
+    Ok(artifact)
+}
+
+/// Proves into a v7 artifact whose commitments are salted with `salts`;
+/// draw them with `random_salts` for each artifact.
+pub fn prove_lr_v7_with_attester<A: Attester>(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    salts: &CommitmentSaltsV0,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_lr_v7_with_sink::<A>(runtime_id, signer, model_bin, input_bin, salts, &mut ())
+}
+
+/// `prove_lr_v7_with_attester`, also handing each trace event to `sink`.
+pub fn prove_lr_v7_with_sink<A: Attester>(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    salts: &CommitmentSaltsV0,
+    sink: &mut dyn TraceSink,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_v7::<A>(
+        run_lr_row, runtime_id, &signer, model_bin, input_bin, salts, sink,
+    )
+}
+
+pub fn prove_mlp_v7(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    salts: &CommitmentSaltsV0,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_mlp_v7_with_sink(runtime_id, signer, model_bin, input_bin, salts, &mut ())
+}
+
+/// `prove_mlp_v7`, also handing each trace event to `sink`.
+pub fn prove_mlp_v7_with_sink(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    salts: &CommitmentSaltsV0,
+    sink: &mut dyn TraceSink,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_v7::<NoopAttester>(
+        run_mlp_row,
+        runtime_id,
+        &signer,
+        model_bin,
+        input_bin,
+        salts,
+        sink,
+    )
+}
+
+/// Replays a v7 LR artifact, opening its commitments with `salts`. Wrong
+/// salts fail the same way as wrong data (`HashMismatch`).
+pub fn verify_lr_v7(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+    salts: &CommitmentSaltsV0,
+) -> Result<ProofArtifactV7, VerifaiError> {
+    verify_v7(
+        run_lr_row,
+        artifact_bin,
+        model_bin,
+        input_bin,
+        output_bin,
+        salts,
+    )
+}
+
+pub fn verify_mlp_v7(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+    salts: &CommitmentSaltsV0,
+) -> Result<ProofArtifactV7, VerifaiError> {
+    verify_v7(
+        run_mlp_row,
+        artifact_bin,
+        model_bin,
+        input_bin,
+        output_bin,
+        salts,
+    )
+}
@@ -0,0 +1,132 @@
+use verifai_core::artifact_bin::ProofArtifactV5;
+use verifai_core::hash::{sha256, HashAlg};
+use verifai_core::signer::Signer;
+use verifai_core::trace_sink::TraceSink;
+
+use crate::attester::{Attester, NoopAttester};
+use crate::batch::{run_lr_row, run_mlp_row, run_row_hashed, run_row_hashed_into, RowRunner};
+use crate::VerifaiError;
+
+fn prove_v5<A: Attester>(
+    run_row: RowRunner,
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    sink: &mut dyn TraceSink,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    let (output, trace_root) =
+        run_row_hashed_into(run_row, HashAlg::Sha256, model_bin, input_bin, sink)?;
+    let output_bin = output.encode_bin();
+
+    let mut artifact = ProofArtifactV5 {
+        version: 5,
+        runtime_id,
+        model_hash: sha256(model_bin),
+        input_hash: sha256(input_bin),
+        output_hash: sha256(&output_bin),
+        trace_root,
+        sig_alg: signer.sig_alg(),
+        sig_pubkey: Vec::new(),
+        signature: Vec::new(),
+        attestation: A::attest(trace_root),
+    };
+    artifact
+        .sign_with(&signer)
+        .map_err(|e| VerifaiError::SignerFailed(e.0))?;
+
+    Ok((output_bin, artifact.encode_bin()))
+}
+
+fn verify_v5(
+    run_row: RowRunner,
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+) -> Result<ProofArtifactV5, VerifaiError> {
+    let artifact =
+        ProofArtifactV5::decode_bin(artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    artifact
+        .verify_signature()
+        .map_err(|_| VerifaiError::SignatureInvalid)?;
+
+    if artifact.model_hash != sha256(model_bin)
+        || artifact.input_hash != sha256(input_bin)
+        || artifact.output_hash != sha256(output_bin)
+    {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    let (output, trace_root) = run_row_hashed(run_row, HashAlg::Sha256, model_bin, input_bin)?;
+    if sha256(&output.encode_bin()) != artifact.output_hash {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    if artifact.trace_root != trace_root || artifact.attestation.measurement != trace_root {
+        return Err(VerifaiError::TraceMismatch);
+    }
+
+    Ok(artifact)
+}
+
+/// Proves into a v5 artifact signed with whatever algorithm `signer` reports.
+pub fn prove_lr_v5_with_attester<A: Attester>(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_lr_v5_with_sink::<A>(runtime_id, signer, model_bin, input_bin, &mut ())
+}
+
+/// `prove_lr_v5_with_attester`, also handing each trace event to `sink`.
+pub fn prove_lr_v5_with_sink<A: Attester>(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    sink: &mut dyn TraceSink,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_v5::<A>(run_lr_row, runtime_id, signer, model_bin, input_bin, sink)
+}
+
+pub fn prove_mlp_v5(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_mlp_v5_with_sink(runtime_id, signer, model_bin, input_bin, &mut ())
+}
+
+/// `prove_mlp_v5`, also handing each trace event to `sink`.
+pub fn prove_mlp_v5_with_sink(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    sink: &mut dyn TraceSink,
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_v5::<NoopAttester>(run_mlp_row, runtime_id, signer, model_bin, input_bin, sink)
+}
+
+/// Replays a v5 LR artifact. Returns it so callers can pin `sig_alg` and
+/// `sig_pubkey`.
+pub fn verify_lr_v5(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+) -> Result<ProofArtifactV5, VerifaiError> {
+    verify_v5(run_lr_row, artifact_bin, model_bin, input_bin, output_bin)
+}
+
+pub fn verify_mlp_v5(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+) -> Result<ProofArtifactV5, VerifaiError> {
+    verify_v5(run_mlp_row, artifact_bin, model_bin, input_bin, output_bin)
+}
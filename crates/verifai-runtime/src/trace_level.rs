@@ -0,0 +1,325 @@
+use verifai_core::artifact_bin::ProofArtifactV9;
+use verifai_core::event_bin::{ActivationKind, TraceEventV0, TraceLevel};
+use verifai_core::hash::sha256;
+use verifai_core::merkle::MerkleBuilder;
+use verifai_core::model_bin::{InputV0, LogisticModelV0, MlpModelV1, OutputV0};
+use verifai_core::signer::Signer;
+use verifai_core::trace_sink::TraceSink;
+
+use crate::attester::{Attester, NoopAttester};
+use crate::batch::{run_lr_row, run_mlp_row};
+use crate::lr::emit;
+use crate::VerifaiError;
+
+/// Runs one input, streaming its trace at the given level into the sink.
+type LevelRunner =
+    fn(TraceLevel, &[u8], &[u8], &mut dyn TraceSink) -> Result<OutputV0, VerifaiError>;
+
+/// SHA-256 of a layer's output vector as little-endian f64s.
+fn layer_digest(values: &[f64]) -> [u8; 32] {
+    let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+    sha256(&bytes)
+}
+
+/// Hands each event to `before`, which may record events of its own ahead
+/// of it, and then to `sink`.
+struct Interleave<S, F> {
+    sink: S,
+    before: F,
+}
+
+impl<S: TraceSink, F: FnMut(&TraceEventV0, &mut S)> TraceSink for Interleave<S, F> {
+    fn record(&mut self, event: &TraceEventV0) {
+        (self.before)(event, &mut self.sink);
+        self.sink.record(event);
+    }
+
+    fn stopped(&self) -> bool {
+        self.sink.stopped()
+    }
+}
+
+/// Records one `OpMac` per term of `bias + Σ weights[i] * inputs[i]`, summed
+/// in inference order so the last `acc` equals the op's `z` bit for bit.
+fn record_macs(sink: &mut dyn TraceSink, op_id: u32, bias: f64, weights: &[f64], inputs: &[f64]) {
+    let mut acc = bias;
+    for (i, (w, x)) in weights.iter().zip(inputs).enumerate() {
+        acc += w * x;
+        sink.record(&TraceEventV0::OpMac {
+            op_id,
+            index: i as u32,
+            acc,
+        });
+    }
+}
+
+/// Records the summary and layer levels' events once the run has produced
+/// `y` and the digests of its `layers`.
+fn record_layers(
+    level: TraceLevel,
+    layers: &[[u8; 32]],
+    y: f64,
+    sink: &mut dyn TraceSink,
+) -> Result<(), VerifaiError> {
+    if level == TraceLevel::Layer {
+        for (layer, &digest) in layers.iter().enumerate() {
+            emit(
+                sink,
+                TraceEventV0::OpLayer {
+                    layer: layer as u32,
+                    digest,
+                },
+            )?;
+        }
+    }
+    emit(sink, TraceEventV0::OpOutput { y })
+}
+
+/// The LR output and its trace at `level`. `Op` is the trace of every
+/// earlier artifact version.
+pub fn lr_trace_at(
+    level: TraceLevel,
+    model_bin: &[u8],
+    input_bin: &[u8],
+) -> Result<(OutputV0, Vec<TraceEventV0>), VerifaiError> {
+    let mut events = Vec::new();
+    let output = lr_trace_into(level, model_bin, input_bin, &mut events)?;
+    Ok((output, events))
+}
+
+/// `lr_trace_at`, handing each event to `sink` as it is produced.
+pub fn lr_trace_into(
+    level: TraceLevel,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    sink: &mut dyn TraceSink,
+) -> Result<OutputV0, VerifaiError> {
+    match level {
+        TraceLevel::Summary | TraceLevel::Layer => {
+            let output = run_lr_row(model_bin, input_bin, &mut ())?;
+            record_layers(level, &[layer_digest(&[output.y])], output.y, sink)?;
+            Ok(output)
+        }
+        TraceLevel::Op => run_lr_row(model_bin, input_bin, sink),
+        TraceLevel::Fine => {
+            let model =
+                LogisticModelV0::decode_bin(model_bin).map_err(|_| VerifaiError::CoreDecode)?;
+            let input = InputV0::decode_bin(input_bin).map_err(|_| VerifaiError::CoreDecode)?;
+            let before = |event: &TraceEventV0, sink: &mut &mut dyn TraceSink| {
+                if let TraceEventV0::OpLinear { op_id, .. } = *event {
+                    record_macs(*sink, op_id, model.bias, &model.weights, &input.x);
+                }
+            };
+            run_lr_row(model_bin, input_bin, &mut Interleave { sink, before })
+        }
+    }
+}
+
+/// MLP counterpart of `lr_trace_at`. Its two layers are the hidden ReLU
+/// activations and the output.
+pub fn mlp_trace_at(
+    level: TraceLevel,
+    model_bin: &[u8],
+    input_bin: &[u8],
+) -> Result<(OutputV0, Vec<TraceEventV0>), VerifaiError> {
+    let mut events = Vec::new();
+    let output = mlp_trace_into(level, model_bin, input_bin, &mut events)?;
+    Ok((output, events))
+}
+
+/// `mlp_trace_at`, handing each event to `sink` as it is produced.
+pub fn mlp_trace_into(
+    level: TraceLevel,
+    model_bin: &[u8],
+    input_bin: &[u8],
+    sink: &mut dyn TraceSink,
+) -> Result<OutputV0, VerifaiError> {
+    match level {
+        TraceLevel::Summary | TraceLevel::Layer => {
+            let mut hidden = Vec::new();
+            let before = |event: &TraceEventV0, _: &mut ()| {
+                if let TraceEventV0::OpActivation {
+                    kind: ActivationKind::Relu,
+                    output,
+                    ..
+                } = *event
+                {
+                    hidden.push(output);
+                }
+            };
+            let output = run_mlp_row(model_bin, input_bin, &mut Interleave { sink: (), before })?;
+            let layers = [layer_digest(&hidden), layer_digest(&[output.y])];
+            record_layers(level, &layers, output.y, sink)?;
+            Ok(output)
+        }
+        TraceLevel::Op => run_mlp_row(model_bin, input_bin, sink),
+        TraceLevel::Fine => {
+            let model = MlpModelV1::decode_bin(model_bin).map_err(|_| VerifaiError::CoreDecode)?;
+            let input = InputV0::decode_bin(input_bin).map_err(|_| VerifaiError::CoreDecode)?;
+            let (input_dim, hidden_size) = (model.input_dim as usize, model.hidden_size as usize);
+            // by position: op ids overlap once there are more than 200
+            // hidden units, and the last linear op is the output
+            let mut linear = 0;
+            let mut hidden = Vec::with_capacity(hidden_size);
+            let before = |event: &TraceEventV0, sink: &mut &mut dyn TraceSink| match *event {
+                TraceEventV0::OpActivation {
+                    kind: ActivationKind::Relu,
+                    output,
+                    ..
+                } => hidden.push(output),
+                TraceEventV0::OpLinear { op_id, .. } => {
+                    match linear {
+                        h if h < hidden_size => record_macs(
+                            *sink,
+                            op_id,
+                            model.b1[h],
+                            &model.w1[h * input_dim..(h + 1) * input_dim],
+                            &input.x,
+                        ),
+                        _ => record_macs(*sink, op_id, model.b2, &model.w2, &hidden),
+                    }
+                    linear += 1;
+                }
+                _ => {}
+            };
+            run_mlp_row(model_bin, input_bin, &mut Interleave { sink, before })
+        }
+    }
+}
+
+fn run_v9(
+    run: LevelRunner,
+    level: TraceLevel,
+    model_bin: &[u8],
+    input_bin: &[u8],
+) -> Result<(Vec<u8>, [u8; 32]), VerifaiError> {
+    let mut tree = MerkleBuilder::new();
+    let output = run(level, model_bin, input_bin, &mut tree)?;
+    Ok((output.encode_bin(), tree.finalize()))
+}
+
+fn prove_v9<A: Attester>(
+    run: LevelRunner,
+    runtime_id: [u8; 32],
+    signer: &dyn Signer,
+    level: TraceLevel,
+    model_bin: &[u8],
+    input_bin: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    let (output_bin, trace_root) = run_v9(run, level, model_bin, input_bin)?;
+
+    let mut artifact = ProofArtifactV9 {
+        version: 9,
+        runtime_id,
+        model_hash: sha256(model_bin),
+        input_hash: sha256(input_bin),
+        output_hash: sha256(&output_bin),
+        trace_level: level,
+        trace_root,
+        sig_pubkey: [0u8; 32],
+        signature: [0u8; 64],
+        attestation: A::attest(trace_root),
+    };
+    artifact
+        .sign_with(signer)
+        .map_err(|e| VerifaiError::SignerFailed(e.0))?;
+
+    Ok((output_bin, artifact.encode_bin()))
+}
+
+fn verify_v9(
+    run: LevelRunner,
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+) -> Result<ProofArtifactV9, VerifaiError> {
+    let artifact =
+        ProofArtifactV9::decode_bin(artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    artifact
+        .verify_signature()
+        .map_err(|_| VerifaiError::SignatureInvalid)?;
+
+    if artifact.model_hash != sha256(model_bin)
+        || artifact.input_hash != sha256(input_bin)
+        || artifact.output_hash != sha256(output_bin)
+    {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    let (recomputed, trace_root) = run_v9(run, artifact.trace_level, model_bin, input_bin)?;
+    if sha256(&recomputed) != artifact.output_hash {
+        return Err(VerifaiError::HashMismatch);
+    }
+    if artifact.trace_root != trace_root || artifact.attestation.measurement != trace_root {
+        return Err(VerifaiError::TraceMismatch);
+    }
+
+    Ok(artifact)
+}
+
+/// Proves the LR model with its trace recorded at `level`.
+pub fn prove_lr_v9_with_attester<A: Attester>(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    level: TraceLevel,
+    model_bin: &[u8],
+    input_bin: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_v9::<A>(
+        lr_trace_into,
+        runtime_id,
+        &signer,
+        level,
+        model_bin,
+        input_bin,
+    )
+}
+
+pub fn prove_mlp_v9(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    level: TraceLevel,
+    model_bin: &[u8],
+    input_bin: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    prove_v9::<NoopAttester>(
+        mlp_trace_into,
+        runtime_id,
+        &signer,
+        level,
+        model_bin,
+        input_bin,
+    )
+}
+
+/// Replays a v9 LR artifact at its declared trace level.
+pub fn verify_lr_v9(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+) -> Result<ProofArtifactV9, VerifaiError> {
+    verify_v9(
+        lr_trace_into,
+        artifact_bin,
+        model_bin,
+        input_bin,
+        output_bin,
+    )
+}
+
+pub fn verify_mlp_v9(
+    artifact_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+) -> Result<ProofArtifactV9, VerifaiError> {
+    verify_v9(
+        mlp_trace_into,
+        artifact_bin,
+        model_bin,
+        input_bin,
+        output_bin,
+    )
+}
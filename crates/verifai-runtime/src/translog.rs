@@ -0,0 +1,221 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use verifai_core::hash::sha256;
+use verifai_core::translog::{
+    log_consistency_proof, log_inclusion_proof, log_root, verify_log_consistency,
+    verify_log_inclusion, LogConsistencyProofV0, LogInclusionProofV0, SignedTreeHeadV0,
+};
+
+use crate::VerifaiError;
+
+const ENTRIES_FILE: &str = "entries.bin";
+
+/// File-backed append-only log of artifact hashes.
+///
+/// The log directory holds `entries.bin`, the concatenated 32-byte SHA-256
+/// hashes of every appended artifact in order. Existing bytes are never
+/// rewritten; tree heads, inclusion and consistency proofs are recomputed from
+/// the entries on demand.
+pub struct TransparencyLog {
+    entries_path: PathBuf,
+    entries: Vec<[u8; 32]>,
+}
+
+fn io_err(path: &Path, e: std::io::Error) -> VerifaiError {
+    VerifaiError::Io(format!("{}: {e}", path.display()))
+}
+
+impl TransparencyLog {
+    /// Opens the log in `dir`, creating an empty one if needed.
+    pub fn open(dir: &Path) -> Result<Self, VerifaiError> {
+        fs::create_dir_all(dir).map_err(|e| io_err(dir, e))?;
+        let entries_path = dir.join(ENTRIES_FILE);
+        let bytes = match fs::read(&entries_path) {
+            Ok(b) => b,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(io_err(&entries_path, e)),
+        };
+        if bytes.len() % 32 != 0 {
+            return Err(VerifaiError::CoreDecode);
+        }
+        let entries = bytes
+            .chunks_exact(32)
+            .map(|c| {
+                let mut h = [0u8; 32];
+                h.copy_from_slice(c);
+                h
+            })
+            .collect();
+        Ok(Self {
+            entries_path,
+            entries,
+        })
+    }
+
+    pub fn size(&self) -> u64 {
+        self.entries.len() as u64
+    }
+
+    pub fn entries(&self) -> &[[u8; 32]] {
+        &self.entries
+    }
+
+    /// Appends `sha256(artifact_bin)` and returns its index. An artifact that
+    /// is already logged keeps its original index.
+    pub fn append(&mut self, artifact_bin: &[u8]) -> Result<u64, VerifaiError> {
+        let entry = sha256(artifact_bin);
+        if let Some(index) = self.index_of(entry) {
+            return Ok(index);
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.entries_path)
+            .map_err(|e| io_err(&self.entries_path, e))?;
+        file.write_all(&entry)
+            .and_then(|_| file.sync_data())
+            .map_err(|e| io_err(&self.entries_path, e))?;
+
+        self.entries.push(entry);
+        Ok(self.size() - 1)
+    }
+
+    pub fn index_of(&self, entry: [u8; 32]) -> Option<u64> {
+        self.entries
+            .iter()
+            .position(|e| *e == entry)
+            .map(|i| i as u64)
+    }
+
+    /// Root of the log over its first `tree_size` entries.
+    pub fn root_at(&self, tree_size: u64) -> Result<[u8; 32], VerifaiError> {
+        Ok(log_root(self.prefix(tree_size)?))
+    }
+
+    /// Signs the current root with the log operator's key.
+    pub fn tree_head(&self, signing_key_bytes: [u8; 32]) -> Result<SignedTreeHeadV0, VerifaiError> {
+        let mut head = SignedTreeHeadV0 {
+            version: 0,
+            tree_size: self.size(),
+            root_hash: log_root(&self.entries),
+            log_pubkey: [0u8; 32],
+            signature: [0u8; 64],
+        };
+        head.sign_detached(signing_key_bytes)
+            .map_err(|_| VerifaiError::CoreDecode)?;
+        Ok(head)
+    }
+
+    /// Inclusion proof for `artifact_bin` in the log as of `tree_size`.
+    pub fn inclusion_proof(
+        &self,
+        artifact_bin: &[u8],
+        tree_size: u64,
+    ) -> Result<LogInclusionProofV0, VerifaiError> {
+        let prefix = self.prefix(tree_size)?;
+        let leaf_index = prefix
+            .iter()
+            .position(|e| *e == sha256(artifact_bin))
+            .ok_or(VerifaiError::NotInLog)?;
+        let path = log_inclusion_proof(prefix, leaf_index).ok_or(VerifaiError::NotInLog)?;
+        Ok(LogInclusionProofV0 {
+            leaf_index: leaf_index as u64,
+            tree_size,
+            path,
+        })
+    }
+
+    /// Consistency proof from the log at `old_size` to the log at `new_size`.
+    pub fn consistency_proof(
+        &self,
+        old_size: u64,
+        new_size: u64,
+    ) -> Result<LogConsistencyProofV0, VerifaiError> {
+        let prefix = self.prefix(new_size)?;
+        let path = log_consistency_proof(prefix, old_size as usize)
+            .ok_or(VerifaiError::DimensionMismatch)?;
+        Ok(LogConsistencyProofV0 {
+            old_size,
+            new_size,
+            path,
+        })
+    }
+
+    fn prefix(&self, tree_size: u64) -> Result<&[[u8; 32]], VerifaiError> {
+        self.entries
+            .get(..tree_size as usize)
+            .ok_or(VerifaiError::DimensionMismatch)
+    }
+}
+
+/// Checks a tree head's signature and, when pinned, its signer.
+fn check_tree_head(
+    head: &SignedTreeHeadV0,
+    log_pubkey: Option<[u8; 32]>,
+) -> Result<(), VerifaiError> {
+    head.verify_signature()
+        .map_err(|_| VerifaiError::SignatureInvalid)?;
+    if log_pubkey.is_some_and(|k| k != head.log_pubkey) {
+        return Err(VerifaiError::SignatureInvalid);
+    }
+    Ok(())
+}
+
+/// Verifies that `artifact_bin` is included in the log described by the
+/// pinned tree head. The head must be signed by `log_pubkey`: anyone can sign
+/// a head of their own that includes their artifact.
+pub fn verify_log_inclusion_v0(
+    artifact_bin: &[u8],
+    proof_bin: &[u8],
+    tree_head_bin: &[u8],
+    log_pubkey: [u8; 32],
+) -> Result<u64, VerifaiError> {
+    let head = SignedTreeHeadV0::decode_bin(tree_head_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    let proof = LogInclusionProofV0::decode_bin(proof_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    check_tree_head(&head, Some(log_pubkey))?;
+
+    if proof.tree_size != head.tree_size
+        || !verify_log_inclusion(
+            sha256(artifact_bin),
+            proof.leaf_index,
+            proof.tree_size,
+            &proof.path,
+            head.root_hash,
+        )
+    {
+        return Err(VerifaiError::InclusionProofInvalid);
+    }
+    Ok(proof.leaf_index)
+}
+
+/// Verifies that `new_head` extends `old_head` without rewriting history.
+pub fn verify_log_consistency_v0(
+    old_head_bin: &[u8],
+    new_head_bin: &[u8],
+    proof_bin: &[u8],
+    log_pubkey: Option<[u8; 32]>,
+) -> Result<(), VerifaiError> {
+    let old = SignedTreeHeadV0::decode_bin(old_head_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    let new = SignedTreeHeadV0::decode_bin(new_head_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    let proof =
+        LogConsistencyProofV0::decode_bin(proof_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    check_tree_head(&old, log_pubkey)?;
+    check_tree_head(&new, log_pubkey.or(Some(old.log_pubkey)))?;
+
+    if proof.old_size != old.tree_size
+        || proof.new_size != new.tree_size
+        || !verify_log_consistency(
+            old.tree_size,
+            new.tree_size,
+            old.root_hash,
+            new.root_hash,
+            &proof.path,
+        )
+    {
+        return Err(VerifaiError::ConsistencyProofInvalid);
+    }
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use verifai_core::timestamp::TimestampTokenV0;
+
+use crate::VerifaiError;
+
+/// Current wall-clock time as unix nanos.
+pub fn unix_nanos_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+/// In-process stand-in for an RFC 3161 time-stamping authority.
+///
+/// Signs `(message_imprint, gen_time, serial)` with its own Ed25519 key.
+/// Serial numbers increase per authority instance.
+pub struct LocalTsa {
+    signing_key: [u8; 32],
+    fixed_time: Option<u64>,
+    next_serial: AtomicU64,
+}
+
+impl LocalTsa {
+    /// A TSA that stamps with the system clock.
+    pub fn new(signing_key: [u8; 32]) -> Self {
+        Self {
+            signing_key,
+            fixed_time: None,
+            next_serial: AtomicU64::new(1),
+        }
+    }
+
+    /// A TSA whose clock always reads `gen_time`, for reproducible tokens.
+    pub fn with_fixed_time(signing_key: [u8; 32], gen_time: u64) -> Self {
+        Self {
+            fixed_time: Some(gen_time),
+            ..Self::new(signing_key)
+        }
+    }
+
+    pub fn stamp(&self, message_imprint: [u8; 32]) -> Result<TimestampTokenV0, VerifaiError> {
+        let mut token = TimestampTokenV0 {
+            version: 0,
+            message_imprint,
+            gen_time: self.fixed_time.unwrap_or_else(unix_nanos_now),
+            serial: self.next_serial.fetch_add(1, Ordering::Relaxed),
+            tsa_pubkey: [0u8; 32],
+            signature: [0u8; 64],
+        };
+        token
+            .sign_detached(self.signing_key)
+            .map_err(|_| VerifaiError::CoreDecode)?;
+        Ok(token)
+    }
+}
@@ -0,0 +1,311 @@
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read};
+
+use serde_json::Value;
+use verifai_core::model_bin::{LogisticModelV0, MlpModelV1};
+
+use crate::VerifaiError;
+
+const NPY_MAGIC: &[u8; 6] = b"\x93NUMPY";
+
+/// Deflate expands data at most about 1032 to 1, so no honest `.npz` member
+/// is larger than this multiple of the whole archive.
+const NPZ_MAX_INFLATION: u64 = 1032;
+
+/// A dense little-endian tensor widened to f64, in C (row-major) order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tensor {
+    pub shape: Vec<usize>,
+    pub data: Vec<f64>,
+}
+
+/// Named tensors loaded from a safetensors file, an `.npz` archive or
+/// individual `.npy` files.
+///
+/// Tensor names follow the model field names: `weights`/`bias` for
+/// `LogisticModelV0` and `w1`/`b1`/`w2`/`b2` for `MlpModelV1`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TensorSet {
+    tensors: BTreeMap<String, Tensor>,
+}
+
+impl TensorSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, tensor: Tensor) {
+        self.tensors.insert(name.into(), tensor);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Tensor> {
+        self.tensors.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.tensors.contains_key(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.tensors.keys().map(String::as_str)
+    }
+
+    /// Maps `weights` (`[n]` or `[1, n]`) and `bias` (scalar or `[1]`).
+    pub fn to_logistic_v0(&self) -> Result<LogisticModelV0, VerifaiError> {
+        let weights = self.require("weights")?;
+        let n = match weights.shape.as_slice() {
+            [n] | [1, n] => *n,
+            _ => return Err(VerifaiError::DimensionMismatch),
+        };
+        if weights.data.len() != n {
+            return Err(VerifaiError::DimensionMismatch);
+        }
+        let bias = scalar(self.require("bias")?)?;
+        Ok(LogisticModelV0 {
+            weights: weights.data.clone(),
+            bias,
+        })
+    }
+
+    /// Maps `w1` (`[hidden_size, input_dim]`), `b1` (`[hidden_size]`),
+    /// `w2` (`[hidden_size]` or `[1, hidden_size]`) and `b2` (scalar or `[1]`).
+    ///
+    /// `input_dim` and `hidden_size` are taken from the shape of `w1`; every
+    /// other tensor must agree with them.
+    pub fn to_mlp_v1(&self) -> Result<MlpModelV1, VerifaiError> {
+        let w1 = self.require("w1")?;
+        let (hidden_size, input_dim) = match w1.shape.as_slice() {
+            [h, d] => (*h, *d),
+            _ => return Err(VerifaiError::DimensionMismatch),
+        };
+        let b1 = self.require("b1")?;
+        if !matches!(b1.shape.as_slice(), [h] if *h == hidden_size) {
+            return Err(VerifaiError::DimensionMismatch);
+        }
+        let w2 = self.require("w2")?;
+        if !matches!(w2.shape.as_slice(), [h] | [1, h] if *h == hidden_size) {
+            return Err(VerifaiError::DimensionMismatch);
+        }
+        let b2 = scalar(self.require("b2")?)?;
+
+        let input_dim = u32::try_from(input_dim).map_err(|_| VerifaiError::DimensionMismatch)?;
+        let hidden_size =
+            u32::try_from(hidden_size).map_err(|_| VerifaiError::DimensionMismatch)?;
+        Ok(MlpModelV1 {
+            input_dim,
+            hidden_size,
+            w1: w1.data.clone(),
+            b1: b1.data.clone(),
+            w2: w2.data.clone(),
+            b2,
+        })
+    }
+
+    fn require(&self, name: &str) -> Result<&Tensor, VerifaiError> {
+        self.tensors.get(name).ok_or(VerifaiError::WeightsDecode)
+    }
+}
+
+fn scalar(t: &Tensor) -> Result<f64, VerifaiError> {
+    match (t.shape.as_slice(), t.data.as_slice()) {
+        ([] | [1], [v]) => Ok(*v),
+        _ => Err(VerifaiError::DimensionMismatch),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Dtype {
+    F32,
+    F64,
+}
+
+impl Dtype {
+    fn size(self) -> usize {
+        match self {
+            Self::F32 => 4,
+            Self::F64 => 8,
+        }
+    }
+}
+
+fn element_count(shape: &[usize]) -> Result<usize, VerifaiError> {
+    shape
+        .iter()
+        .try_fold(1usize, |acc, &d| acc.checked_mul(d))
+        .ok_or(VerifaiError::WeightsDecode)
+}
+
+fn decode_le(dtype: Dtype, shape: Vec<usize>, raw: &[u8]) -> Result<Tensor, VerifaiError> {
+    let count = element_count(&shape)?;
+    let expected = count
+        .checked_mul(dtype.size())
+        .ok_or(VerifaiError::WeightsDecode)?;
+    if raw.len() != expected {
+        return Err(VerifaiError::WeightsDecode);
+    }
+    let data = match dtype {
+        Dtype::F32 => raw
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64)
+            .collect(),
+        Dtype::F64 => raw
+            .chunks_exact(8)
+            .map(|b| f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+            .collect(),
+    };
+    Ok(Tensor { shape, data })
+}
+
+/* ----------------------------- safetensors ----------------------------- */
+
+/// Reads every F32/F64 tensor from a safetensors buffer.
+pub fn load_safetensors(buf: &[u8]) -> Result<TensorSet, VerifaiError> {
+    if buf.len() < 8 {
+        return Err(VerifaiError::WeightsDecode);
+    }
+    let header_len = u64::from_le_bytes([
+        buf[0], buf[1], buf[2], buf[3], buf[4], buf[5], buf[6], buf[7],
+    ]);
+    let header_end = usize::try_from(header_len)
+        .ok()
+        .and_then(|n| n.checked_add(8))
+        .filter(|&end| end <= buf.len())
+        .ok_or(VerifaiError::WeightsDecode)?;
+    let header: BTreeMap<String, Value> =
+        serde_json::from_slice(&buf[8..header_end]).map_err(|_| VerifaiError::WeightsDecode)?;
+    let data = &buf[header_end..];
+
+    let mut set = TensorSet::new();
+    for (name, info) in header {
+        if name == "__metadata__" {
+            continue;
+        }
+        let dtype = match info.get("dtype").and_then(Value::as_str) {
+            Some("F32") => Dtype::F32,
+            Some("F64") => Dtype::F64,
+            _ => return Err(VerifaiError::WeightsDecode),
+        };
+        let shape = json_usizes(info.get("shape"))?;
+        let offsets = json_usizes(info.get("data_offsets"))?;
+        let (begin, end) = match offsets.as_slice() {
+            [b, e] if b <= e && *e <= data.len() => (*b, *e),
+            _ => return Err(VerifaiError::WeightsDecode),
+        };
+        set.insert(name, decode_le(dtype, shape, &data[begin..end])?);
+    }
+    Ok(set)
+}
+
+fn json_usizes(v: Option<&Value>) -> Result<Vec<usize>, VerifaiError> {
+    v.and_then(Value::as_array)
+        .ok_or(VerifaiError::WeightsDecode)?
+        .iter()
+        .map(|d| {
+            d.as_u64()
+                .and_then(|d| usize::try_from(d).ok())
+                .ok_or(VerifaiError::WeightsDecode)
+        })
+        .collect()
+}
+
+/* -------------------------------- NumPy -------------------------------- */
+
+/// Reads a single `.npy` array (`<f4` or `<f8`, C order).
+pub fn load_npy(buf: &[u8]) -> Result<Tensor, VerifaiError> {
+    if buf.len() < 10 || &buf[0..6] != NPY_MAGIC {
+        return Err(VerifaiError::WeightsDecode);
+    }
+    let (header_len, header_start) = match buf[6] {
+        1 => (u16::from_le_bytes([buf[8], buf[9]]) as usize, 10usize),
+        2 | 3 => {
+            if buf.len() < 12 {
+                return Err(VerifaiError::WeightsDecode);
+            }
+            let n = u32::from_le_bytes([buf[8], buf[9], buf[10], buf[11]]) as usize;
+            (n, 12)
+        }
+        _ => return Err(VerifaiError::WeightsDecode),
+    };
+    let header_end = header_start
+        .checked_add(header_len)
+        .filter(|&end| end <= buf.len())
+        .ok_or(VerifaiError::WeightsDecode)?;
+    let header = std::str::from_utf8(&buf[header_start..header_end])
+        .map_err(|_| VerifaiError::WeightsDecode)?;
+
+    let dtype = match npy_header_value(header, "descr") {
+        Some("'<f4'") | Some("\"<f4\"") => Dtype::F32,
+        Some("'<f8'") | Some("\"<f8\"") => Dtype::F64,
+        _ => return Err(VerifaiError::WeightsDecode),
+    };
+    if npy_header_value(header, "fortran_order") != Some("False") {
+        return Err(VerifaiError::WeightsDecode);
+    }
+    let shape = npy_header_value(header, "shape")
+        .and_then(parse_npy_shape)
+        .ok_or(VerifaiError::WeightsDecode)?;
+
+    decode_le(dtype, shape, &buf[header_end..])
+}
+
+/// Reads every `.npy` member of an `.npz` archive (stored or deflated), keyed
+/// by the member name without its `.npy` suffix. Member sizes come from the
+/// untrusted zip headers; one larger than deflate could produce from the
+/// archive, or not matching the data, is rejected.
+pub fn load_npz(buf: &[u8]) -> Result<TensorSet, VerifaiError> {
+    let size_limit = (buf.len() as u64).saturating_mul(NPZ_MAX_INFLATION);
+    let mut archive =
+        zip::ZipArchive::new(Cursor::new(buf)).map_err(|_| VerifaiError::WeightsDecode)?;
+    let mut set = TensorSet::new();
+    for i in 0..archive.len() {
+        let mut member = archive
+            .by_index(i)
+            .map_err(|_| VerifaiError::WeightsDecode)?;
+        let name = match member.name().strip_suffix(".npy") {
+            Some(n) => n.to_string(),
+            None => continue,
+        };
+        let size = member.size();
+        if size > size_limit {
+            return Err(VerifaiError::WeightsDecode);
+        }
+        let mut bytes = Vec::with_capacity(size as usize);
+        (&mut member)
+            .take(size + 1)
+            .read_to_end(&mut bytes)
+            .map_err(|_| VerifaiError::WeightsDecode)?;
+        if bytes.len() as u64 != size {
+            return Err(VerifaiError::WeightsDecode);
+        }
+        set.insert(name, load_npy(&bytes)?);
+    }
+    Ok(set)
+}
+
+// The .npy header is a Python dict literal, e.g.
+// `{'descr': '<f8', 'fortran_order': False, 'shape': (2, 3), }`.
+fn npy_header_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    let quoted_single = format!("'{key}'");
+    let quoted_double = format!("\"{key}\"");
+    let start = header
+        .find(&quoted_single)
+        .map(|i| i + quoted_single.len())
+        .or_else(|| header.find(&quoted_double).map(|i| i + quoted_double.len()))?;
+    let rest = header[start..].trim_start().strip_prefix(':')?.trim_start();
+    let end = if rest.starts_with('(') {
+        rest.find(')')? + 1
+    } else {
+        rest.find([',', '}'])?
+    };
+    Some(rest[..end].trim())
+}
+
+fn parse_npy_shape(s: &str) -> Option<Vec<usize>> {
+    let inner = s.strip_prefix('(')?.strip_suffix(')')?;
+    inner
+        .split(',')
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(|d| d.parse().ok())
+        .collect()
+}
@@ -0,0 +1,199 @@
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use x509_parser::certificate::X509Certificate;
+use x509_parser::oid_registry::{
+    OID_EC_P256, OID_KEY_TYPE_EC_PUBLIC_KEY, OID_SIG_ECDSA_WITH_SHA256, OID_SIG_ED25519,
+    OID_X509_EXT_BASIC_CONSTRAINTS, OID_X509_EXT_KEY_USAGE,
+};
+use x509_parser::pem::Pem;
+use x509_parser::prelude::FromDer;
+
+use crate::VerifaiError;
+
+/// Who a verified chain says signed an artifact.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignerIdentity {
+    /// Leaf certificate subject, RFC 4514 style.
+    pub subject: String,
+    /// Subject of the trusted root the chain ends at.
+    pub root_subject: String,
+}
+
+/// DER bodies of every `CERTIFICATE` block in a PEM file, in file order.
+pub fn pem_certificates(pem: &[u8]) -> Result<Vec<Vec<u8>>, VerifaiError> {
+    let mut out = Vec::new();
+    for block in Pem::iter_from_buffer(pem) {
+        let block = block.map_err(|_| VerifaiError::CertificateInvalid)?;
+        if block.label == "CERTIFICATE" {
+            out.push(block.contents);
+        }
+    }
+    if out.is_empty() {
+        return Err(VerifaiError::CertificateInvalid);
+    }
+    Ok(out)
+}
+
+/// Validates `chain` (DER, leaf first) up to one of `trusted_roots` at
+/// `now_unix_secs`, entirely offline.
+///
+/// The leaf must hold the Ed25519 key `sig_pubkey`, allow `digitalSignature`
+/// and not be a CA; every issuer must be a CA allowed to sign certificates
+/// within its path length; every certificate, the root included, must be
+/// within its validity period. Certificates in `chain` may mark only
+/// `basicConstraints` and `keyUsage` critical. Issuer signatures may be
+/// Ed25519 or ECDSA P-256 with SHA-256.
+pub fn verify_signer_chain(
+    chain: &[Vec<u8>],
+    trusted_roots: &[Vec<u8>],
+    sig_pubkey: [u8; 32],
+    now_unix_secs: i64,
+) -> Result<SignerIdentity, VerifaiError> {
+    let certs = chain
+        .iter()
+        .map(|der| parse(der))
+        .collect::<Result<Vec<_>, _>>()?;
+    let roots = trusted_roots
+        .iter()
+        .map(|der| parse(der))
+        .collect::<Result<Vec<_>, _>>()?;
+    let leaf = certs.first().ok_or(VerifaiError::CertificateInvalid)?;
+
+    let leaf_key = leaf.public_key();
+    if leaf_key.algorithm.algorithm != OID_SIG_ED25519
+        || leaf_key.subject_public_key.data.as_ref() != sig_pubkey
+    {
+        return Err(VerifaiError::KeyUsageInvalid);
+    }
+    match leaf.key_usage() {
+        Ok(Some(ku)) if ku.value.digital_signature() => {}
+        _ => return Err(VerifaiError::KeyUsageInvalid),
+    }
+    match leaf.basic_constraints() {
+        Ok(None) => {}
+        Ok(Some(bc)) if !bc.value.ca => {}
+        _ => return Err(VerifaiError::KeyUsageInvalid),
+    }
+
+    for cert in &certs {
+        check_critical_extensions(cert)?;
+        check_validity(cert, now_unix_secs)?;
+    }
+    for (depth, pair) in certs.windows(2).enumerate() {
+        check_issued_by(&pair[0], &pair[1], depth)?;
+    }
+
+    let top = certs.last().expect("chain is non-empty");
+    let root = roots
+        .iter()
+        .find(|root| root.as_raw() == top.as_raw())
+        .or_else(|| {
+            roots.iter().find(|root| {
+                check_issued_by(top, root, certs.len() - 1).is_ok()
+                    && check_validity(root, now_unix_secs).is_ok()
+            })
+        })
+        .ok_or(VerifaiError::UntrustedChain)?;
+
+    Ok(SignerIdentity {
+        subject: leaf.subject().to_string(),
+        root_subject: root.subject().to_string(),
+    })
+}
+
+fn parse(der: &[u8]) -> Result<X509Certificate<'_>, VerifaiError> {
+    match X509Certificate::from_der(der) {
+        Ok(([], cert)) => Ok(cert),
+        _ => Err(VerifaiError::CertificateInvalid),
+    }
+}
+
+/// Every critical extension of `cert` is one this module enforces.
+fn check_critical_extensions(cert: &X509Certificate<'_>) -> Result<(), VerifaiError> {
+    let understood = [OID_X509_EXT_BASIC_CONSTRAINTS, OID_X509_EXT_KEY_USAGE];
+    if cert
+        .extensions()
+        .iter()
+        .any(|ext| ext.critical && !understood.contains(&ext.oid))
+    {
+        return Err(VerifaiError::CertificateInvalid);
+    }
+    Ok(())
+}
+
+fn check_validity(cert: &X509Certificate<'_>, now_unix_secs: i64) -> Result<(), VerifaiError> {
+    let validity = cert.validity();
+    if now_unix_secs < validity.not_before.timestamp()
+        || now_unix_secs > validity.not_after.timestamp()
+    {
+        return Err(VerifaiError::CertificateExpired);
+    }
+    Ok(())
+}
+
+/// `issuer` signed `cert`, which sits `depth` CA certificates below it
+/// counting from the leaf (so `depth` intermediates lie between them).
+fn check_issued_by(
+    cert: &X509Certificate<'_>,
+    issuer: &X509Certificate<'_>,
+    depth: usize,
+) -> Result<(), VerifaiError> {
+    if cert.issuer().as_raw() != issuer.subject().as_raw() {
+        return Err(VerifaiError::UntrustedChain);
+    }
+    match issuer.basic_constraints() {
+        Ok(Some(bc)) if bc.value.ca => {
+            if bc
+                .value
+                .path_len_constraint
+                .is_some_and(|max| depth > max as usize)
+            {
+                return Err(VerifaiError::KeyUsageInvalid);
+            }
+        }
+        _ => return Err(VerifaiError::KeyUsageInvalid),
+    }
+    match issuer.key_usage() {
+        Ok(Some(ku)) if ku.value.key_cert_sign() => {}
+        _ => return Err(VerifaiError::KeyUsageInvalid),
+    }
+    verify_cert_signature(cert, issuer)
+}
+
+fn verify_cert_signature(
+    cert: &X509Certificate<'_>,
+    issuer: &X509Certificate<'_>,
+) -> Result<(), VerifaiError> {
+    let tbs = cert.tbs_certificate.as_ref();
+    let sig = cert.signature_value.data.as_ref();
+    let spki = issuer.public_key();
+    let key = spki.subject_public_key.data.as_ref();
+    let alg = &cert.signature_algorithm.algorithm;
+
+    let ok = if *alg == OID_SIG_ED25519 && spki.algorithm.algorithm == OID_SIG_ED25519 {
+        let vk = key
+            .try_into()
+            .ok()
+            .and_then(|k| VerifyingKey::from_bytes(k).ok());
+        let sig = Signature::from_slice(sig).ok();
+        matches!((vk, sig), (Some(vk), Some(sig)) if vk.verify(tbs, &sig).is_ok())
+    } else if *alg == OID_SIG_ECDSA_WITH_SHA256
+        && spki.algorithm.algorithm == OID_KEY_TYPE_EC_PUBLIC_KEY
+        && spki
+            .algorithm
+            .parameters
+            .as_ref()
+            .and_then(|p| p.as_oid().ok())
+            .is_some_and(|curve| curve == OID_EC_P256)
+    {
+        let vk = p256::ecdsa::VerifyingKey::from_sec1_bytes(key).ok();
+        let sig = p256::ecdsa::Signature::from_der(sig).ok();
+        matches!((vk, sig), (Some(vk), Some(sig)) if vk.verify(tbs, &sig).is_ok())
+    } else {
+        false
+    };
+    if ok {
+        Ok(())
+    } else {
+        Err(VerifaiError::CertificateInvalid)
+    }
+}
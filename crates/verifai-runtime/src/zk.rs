@@ -0,0 +1,287 @@
+//! Zero-knowledge proofs of fixed-point logistic regression.
+//!
+//! The circuit hashes the quantized model, input and output encodings with
+//! SHA-256 and checks that the output is the model's logit for the input,
+//! so a `ProofArtifactV10` convinces a verifier holding neither the model
+//! nor the input. Proofs are Groth16 over BN254, which needs a setup per
+//! input dimension; whoever runs `zk_lr_setup` can forge proofs for its
+//! keys, so verifiers pin a verifying key they trust.
+
+use ark_bn254::{Bn254, Fr};
+use ark_crypto_primitives::crh::sha256::constraints::Sha256Gadget;
+use ark_ff::ToConstraintField;
+use ark_groth16::{Groth16, Proof, ProvingKey, VerifyingKey};
+use ark_r1cs_std::alloc::AllocVar;
+use ark_r1cs_std::boolean::Boolean;
+use ark_r1cs_std::eq::EqGadget;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::fields::FieldVar;
+use ark_r1cs_std::uint8::UInt8;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_snark::{CircuitSpecificSetupSNARK, SNARK};
+use ark_std::rand::rngs::StdRng;
+use ark_std::rand::SeedableRng;
+use verifai_core::artifact_bin::{ProofArtifactV10, ZK_PROOF_LEN};
+use verifai_core::bytes::{push_bytes, push_u32_le, Reader};
+use verifai_core::hash::sha256;
+use verifai_core::model_bin::{QuantizedInputV0, QuantizedLogisticModelV0, QuantizedOutputV0};
+use verifai_core::signer::Signer;
+
+use crate::VerifaiError;
+
+const PROVING_KEY_MAGIC: &[u8; 8] = b"VFAIZPK0";
+const VERIFYING_KEY_MAGIC: &[u8; 8] = b"VFAIZVK0";
+
+/// The circuit for `dim`-feature models. `witness` is `None` during setup.
+struct LrCircuit {
+    dim: u32,
+    witness: Option<(QuantizedLogisticModelV0, QuantizedInputV0, i64)>,
+    /// Model, input and output hashes, the public inputs.
+    hashes: [u8; 96],
+}
+
+/// Allocates `value` as `bits` witness bits and returns it as a field
+/// element together with its little-endian two's complement bytes. The
+/// bits are of `value + 2^(bits - 1)`, which also range-checks it.
+fn alloc_signed(
+    cs: &ConstraintSystemRef<Fr>,
+    value: Option<i64>,
+    bits: usize,
+) -> Result<(FpVar<Fr>, Vec<UInt8<Fr>>), SynthesisError> {
+    let offset = 1u64 << (bits - 1);
+    let biased = value.map(|v| (i128::from(v) + i128::from(offset)) as u64);
+    let mut le = (0..bits)
+        .map(|i| {
+            Boolean::new_witness(cs.clone(), || {
+                biased
+                    .map(|u| (u >> i) & 1 == 1)
+                    .ok_or(SynthesisError::AssignmentMissing)
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let var = Boolean::le_bits_to_fp_var(&le)? - FpVar::constant(Fr::from(offset));
+    // flipping the top bit of the biased value gives two's complement
+    le[bits - 1] = le[bits - 1].not();
+    let bytes = le.chunks(8).map(UInt8::from_bits_le).collect();
+    Ok((var, bytes))
+}
+
+fn constant_bytes(bytes: &[u8]) -> Vec<UInt8<Fr>> {
+    bytes.iter().map(|&b| UInt8::constant(b)).collect()
+}
+
+impl ConstraintSynthesizer<Fr> for LrCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let dim = self.dim as usize;
+        let hashes = UInt8::new_input_vec(cs.clone(), &self.hashes)?;
+
+        // the encodings' fixed prefixes, taken from empty ones
+        let mut model_bin = constant_bytes(&model_header(self.dim));
+        let mut input_bin = constant_bytes(&input_header(self.dim));
+        let mut output_bin = constant_bytes(&QuantizedOutputV0 { logit: 0 }.encode_bin()[..8]);
+
+        let (weights, bias, x, logit) = match &self.witness {
+            Some((model, input, logit)) => (
+                model.weights.iter().map(|&w| Some(i64::from(w))).collect(),
+                Some(model.bias),
+                input.x.iter().map(|&v| Some(i64::from(v))).collect(),
+                Some(*logit),
+            ),
+            None => (vec![None; dim], None, vec![None; dim], None),
+        };
+
+        let (bias, bias_bytes) = alloc_signed(&cs, bias, 64)?;
+        let mut acc = bias;
+        for (w, v) in weights.into_iter().zip(x) {
+            let (w, w_bytes) = alloc_signed(&cs, w, 32)?;
+            let (v, v_bytes) = alloc_signed(&cs, v, 32)?;
+            model_bin.extend(w_bytes);
+            input_bin.extend(v_bytes);
+            acc += w * v;
+        }
+        model_bin.extend(bias_bytes);
+        let (logit, logit_bytes) = alloc_signed(&cs, logit, 64)?;
+        output_bin.extend(logit_bytes);
+        // |acc| < (dim + 1) * 2^63, far below the field size, so the 64-bit
+        // logit equals it exactly
+        logit.enforce_equal(&acc)?;
+
+        for (i, bin) in [model_bin, input_bin, output_bin].iter().enumerate() {
+            let digest = Sha256Gadget::digest(bin)?;
+            digest.0.enforce_equal(&hashes[32 * i..32 * (i + 1)])?;
+        }
+        Ok(())
+    }
+}
+
+/// Magic and length of a `dim`-feature quantized model encoding.
+fn model_header(dim: u32) -> Vec<u8> {
+    let empty = QuantizedLogisticModelV0 {
+        weights: Vec::new(),
+        bias: 0,
+    };
+    let mut out = empty.encode_bin()[..8].to_vec();
+    push_u32_le(&mut out, dim);
+    out
+}
+
+fn input_header(dim: u32) -> Vec<u8> {
+    let mut out = QuantizedInputV0 { x: Vec::new() }.encode_bin()[..8].to_vec();
+    push_u32_le(&mut out, dim);
+    out
+}
+
+fn os_rng() -> Result<StdRng, VerifaiError> {
+    let mut seed = [0u8; 32];
+    getrandom::getrandom(&mut seed).map_err(|e| VerifaiError::Io(e.to_string()))?;
+    Ok(StdRng::from_seed(seed))
+}
+
+fn public_hashes(model_hash: &[u8; 32], input_hash: &[u8; 32], output_hash: &[u8; 32]) -> [u8; 96] {
+    let mut out = [0u8; 96];
+    out[..32].copy_from_slice(model_hash);
+    out[32..64].copy_from_slice(input_hash);
+    out[64..].copy_from_slice(output_hash);
+    out
+}
+
+/// Generates Groth16 keys for `dim`-feature models, returning the proving
+/// and verifying key encodings. The setup randomness is dropped on return.
+pub fn zk_lr_setup(dim: u32) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    let circuit = LrCircuit {
+        dim,
+        witness: None,
+        hashes: [0u8; 96],
+    };
+    let (pk, vk) = Groth16::<Bn254>::setup(circuit, &mut os_rng()?)
+        .map_err(|e| VerifaiError::Zk(e.to_string()))?;
+
+    let mut pk_bin = PROVING_KEY_MAGIC.to_vec();
+    push_u32_le(&mut pk_bin, dim);
+    pk.serialize_uncompressed(&mut pk_bin)
+        .map_err(|e| VerifaiError::Zk(e.to_string()))?;
+    Ok((pk_bin, encode_verifying_key(&vk)?))
+}
+
+fn encode_verifying_key(vk: &VerifyingKey<Bn254>) -> Result<Vec<u8>, VerifaiError> {
+    let mut out = Vec::new();
+    push_bytes(&mut out, VERIFYING_KEY_MAGIC);
+    vk.serialize_compressed(&mut out)
+        .map_err(|e| VerifaiError::Zk(e.to_string()))?;
+    Ok(out)
+}
+
+fn decode_proving_key(pk_bin: &[u8]) -> Result<(u32, ProvingKey<Bn254>), VerifaiError> {
+    let mut r = Reader::new(pk_bin);
+    let magic = r.read_exact(8).map_err(|_| VerifaiError::CoreDecode)?;
+    if magic != PROVING_KEY_MAGIC {
+        return Err(VerifaiError::CoreDecode);
+    }
+    let dim = r.read_u32_le().map_err(|_| VerifaiError::CoreDecode)?;
+    let rest = r
+        .read_exact(r.remaining())
+        .map_err(|_| VerifaiError::CoreDecode)?;
+    // the prover's own key; checking every point would dominate proving
+    let pk = ProvingKey::deserialize_uncompressed_unchecked(rest)
+        .map_err(|_| VerifaiError::CoreDecode)?;
+    Ok((dim, pk))
+}
+
+fn decode_verifying_key(vk_bin: &[u8]) -> Result<VerifyingKey<Bn254>, VerifaiError> {
+    let rest = vk_bin
+        .strip_prefix(VERIFYING_KEY_MAGIC)
+        .ok_or(VerifaiError::CoreDecode)?;
+    VerifyingKey::deserialize_compressed(rest).map_err(|_| VerifaiError::CoreDecode)
+}
+
+/// Proves a quantized LR model on a quantized input, returning the
+/// `QuantizedOutputV0` and the signed `ProofArtifactV10`. Fails with
+/// `DimensionMismatch` if the input or the proving key is for another
+/// dimension, and `OutOfRange` if the logit overflows.
+pub fn prove_lr_v10(
+    runtime_id: [u8; 32],
+    signer: impl Signer,
+    proving_key_bin: &[u8],
+    model_bin: &[u8],
+    input_bin: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), VerifaiError> {
+    let model =
+        QuantizedLogisticModelV0::decode_bin(model_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    let input = QuantizedInputV0::decode_bin(input_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    let (dim, pk) = decode_proving_key(proving_key_bin)?;
+    if model.weights.len() != dim as usize || input.x.len() != dim as usize {
+        return Err(VerifaiError::DimensionMismatch);
+    }
+    let logit = model.logit(&input).ok_or(VerifaiError::OutOfRange)?;
+    let output_bin = QuantizedOutputV0 { logit }.encode_bin();
+
+    let (model_hash, input_hash, output_hash) =
+        (sha256(model_bin), sha256(input_bin), sha256(&output_bin));
+    let circuit = LrCircuit {
+        dim,
+        witness: Some((model, input, logit)),
+        hashes: public_hashes(&model_hash, &input_hash, &output_hash),
+    };
+    let proof = Groth16::<Bn254>::prove(&pk, circuit, &mut os_rng()?)
+        .map_err(|e| VerifaiError::Zk(e.to_string()))?;
+    let mut proof_bytes = [0u8; ZK_PROOF_LEN];
+    proof
+        .serialize_compressed(&mut proof_bytes[..])
+        .map_err(|e| VerifaiError::Zk(e.to_string()))?;
+
+    let mut artifact = ProofArtifactV10 {
+        version: 10,
+        runtime_id,
+        model_hash,
+        input_hash,
+        output_hash,
+        circuit_hash: sha256(&encode_verifying_key(&pk.vk)?),
+        proof: proof_bytes,
+        sig_pubkey: [0u8; 32],
+        signature: [0u8; 64],
+    };
+    artifact
+        .sign_with(&signer)
+        .map_err(|e| VerifaiError::SignerFailed(e.0))?;
+
+    Ok((output_bin, artifact.encode_bin()))
+}
+
+/// Checks a `ProofArtifactV10` against a pinned verifying key without the
+/// model or the input: the signature, that `circuit_hash` names the key
+/// (`CircuitMismatch`), the output hash, and the proof (`ZkProofInvalid`).
+pub fn verify_lr_v10(
+    artifact_bin: &[u8],
+    verifying_key_bin: &[u8],
+    output_bin: &[u8],
+) -> Result<ProofArtifactV10, VerifaiError> {
+    let artifact =
+        ProofArtifactV10::decode_bin(artifact_bin).map_err(|_| VerifaiError::CoreDecode)?;
+    artifact
+        .verify_signature()
+        .map_err(|_| VerifaiError::SignatureInvalid)?;
+    if artifact.circuit_hash != sha256(verifying_key_bin) {
+        return Err(VerifaiError::CircuitMismatch);
+    }
+    if artifact.output_hash != sha256(output_bin) {
+        return Err(VerifaiError::HashMismatch);
+    }
+
+    let vk = decode_verifying_key(verifying_key_bin)?;
+    let proof = Proof::<Bn254>::deserialize_compressed(&artifact.proof[..])
+        .map_err(|_| VerifaiError::ZkProofInvalid)?;
+    let hashes = public_hashes(
+        &artifact.model_hash,
+        &artifact.input_hash,
+        &artifact.output_hash,
+    );
+    let public_inputs: Vec<Fr> = hashes[..]
+        .to_field_elements()
+        .ok_or(VerifaiError::ZkProofInvalid)?;
+    let pvk = Groth16::<Bn254>::process_vk(&vk).map_err(|_| VerifaiError::CoreDecode)?;
+    match Groth16::<Bn254>::verify_with_processed_vk(&pvk, &public_inputs, &proof) {
+        Ok(true) => Ok(artifact),
+        _ => Err(VerifaiError::ZkProofInvalid),
+    }
+}
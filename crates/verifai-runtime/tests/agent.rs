@@ -0,0 +1,123 @@
+#![cfg(unix)]
+
+use std::os::unix::net::UnixListener;
+use std::path::PathBuf;
+use std::thread;
+
+use verifai_core::model_bin::{InputV0, LogisticModelV0};
+use verifai_core::sig_alg::{AlgSigningKey, SigAlg};
+use verifai_core::signer::Signer;
+use verifai_runtime::{
+    prove_lr_v0, prove_lr_v3_with_attester, prove_lr_v5_with_attester, verify_lr_v0, verify_lr_v5,
+    AgentSigner, IssuanceV3, NoopAttester, SigningAgent, VerifaiError,
+};
+
+fn bins() -> (Vec<u8>, Vec<u8>) {
+    let model = LogisticModelV0 {
+        weights: vec![0.1, -0.2, 0.3, 0.4],
+        bias: -0.05,
+    };
+    let input = InputV0 {
+        x: vec![1.0, 2.0, 3.0, 4.0],
+    };
+    (model.encode_bin(), input.encode_bin())
+}
+
+/// Starts an agent for `key` on a fresh socket and returns its path.
+fn spawn_agent(name: &str, key: [u8; 32]) -> PathBuf {
+    spawn_agent_with(name, SigningAgent::new(key))
+}
+
+fn spawn_agent_with(name: &str, agent: SigningAgent) -> PathBuf {
+    let socket =
+        std::env::temp_dir().join(format!("verifai-agent-{}-{name}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&socket);
+    let listener = UnixListener::bind(&socket).unwrap();
+    thread::spawn(move || agent.serve(&listener));
+    socket
+}
+
+#[test]
+fn agent_signatures_match_in_process_keys() {
+    let socket = spawn_agent("match", [9u8; 32]);
+    let agent = AgentSigner::connect(&socket).unwrap();
+    assert_eq!(agent.public_key(), [9u8; 32].public_key());
+
+    let (model_bin, input_bin) = bins();
+    let local = prove_lr_v0([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap();
+    let remote = prove_lr_v0([7u8; 32], &agent, &model_bin, &input_bin).unwrap();
+    assert_eq!(remote, local);
+    assert_eq!(
+        verify_lr_v0(&remote.1, &model_bin, &input_bin, &remote.0),
+        Ok(())
+    );
+
+    let issuance = IssuanceV3 {
+        issued_at: 1,
+        expires_at: None,
+        nonce: [0u8; 32],
+    };
+    let local = prove_lr_v3_with_attester::<NoopAttester>(
+        [7u8; 32], [9u8; 32], &model_bin, &input_bin, &issuance, None,
+    )
+    .unwrap();
+    let remote = prove_lr_v3_with_attester::<NoopAttester>(
+        [7u8; 32], agent, &model_bin, &input_bin, &issuance, None,
+    )
+    .unwrap();
+    assert_eq!(remote, local);
+}
+
+#[test]
+fn agent_reports_its_algorithm_and_signs_v5_with_it() {
+    let key = AlgSigningKey::new(SigAlg::EcdsaP256Sha256, [9u8; 32]).unwrap();
+    let socket = spawn_agent_with("p256", SigningAgent::with_key(key.clone()));
+    let agent = AgentSigner::connect(&socket).unwrap();
+    assert_eq!(agent.sig_alg(), SigAlg::EcdsaP256Sha256);
+    assert_eq!(agent.public_key(), key.public_key());
+
+    let (model_bin, input_bin) = bins();
+    let local =
+        prove_lr_v5_with_attester::<NoopAttester>([7u8; 32], &key, &model_bin, &input_bin).unwrap();
+    let remote =
+        prove_lr_v5_with_attester::<NoopAttester>([7u8; 32], &agent, &model_bin, &input_bin)
+            .unwrap();
+    assert_eq!(remote, local);
+    let artifact = verify_lr_v5(&remote.1, &model_bin, &input_bin, &remote.0).unwrap();
+    assert_eq!(artifact.sig_alg, SigAlg::EcdsaP256Sha256);
+
+    // artifacts that only carry Ed25519 keys refuse it
+    assert!(matches!(
+        prove_lr_v0([7u8; 32], &agent, &model_bin, &input_bin),
+        Err(VerifaiError::SignerFailed(_))
+    ));
+}
+
+#[test]
+fn agent_refuses_foreign_messages_and_missing_sockets() {
+    let socket = spawn_agent("refuse", [9u8; 32]);
+    let agent = AgentSigner::connect(&socket).unwrap();
+    assert!(agent.sign(b"VERIFAI\0ARTIFACT\0V0").is_ok());
+    let refused = agent.sign(b"transfer 100 coins").unwrap_err();
+    assert!(refused.0.contains("not a VERIFAI artifact signing message"));
+    // other VERIFAI documents could vouch for keys or logs; those are not the agent's to sign
+    for other in [
+        &b"VERIFAI\0TREEHEAD\0V0"[..],
+        b"VERIFAI\0KEYSTATUS\0V0",
+        b"VERIFAI\0TIMESTAMP\0V0",
+        b"VERIFAI\0PIPELINE\0V0",
+    ] {
+        assert!(agent.sign(other).is_err());
+    }
+
+    let _ = std::fs::remove_file(&socket);
+    let (model_bin, input_bin) = bins();
+    assert!(matches!(
+        prove_lr_v0([7u8; 32], &agent, &model_bin, &input_bin),
+        Err(VerifaiError::SignerFailed(_))
+    ));
+    assert!(matches!(
+        AgentSigner::connect(&socket),
+        Err(VerifaiError::SignerFailed(_))
+    ));
+}
@@ -0,0 +1,136 @@
+use verifai_core::model_bin::{InputBatchV1, InputV0, LogisticModelV0, MlpModelV1, OutputBatchV1};
+use verifai_runtime::{
+    batch_row_proof_lr_v2, batch_row_proof_mlp_v2, prove_lr_batch_v2, prove_lr_v0,
+    prove_mlp_batch_v2, verify_batch_row_lr_v2, verify_batch_row_mlp_v2, verify_lr_batch_v2,
+    verify_mlp_batch_v2, VerifaiError,
+};
+
+fn lr_model() -> LogisticModelV0 {
+    LogisticModelV0 {
+        weights: vec![0.1, -0.2, 0.3, 0.4],
+        bias: -0.05,
+    }
+}
+
+fn batch() -> InputBatchV1 {
+    InputBatchV1 {
+        rows: vec![
+            InputV0 {
+                x: vec![1.0, 2.0, 3.0, 4.0],
+            },
+            InputV0 {
+                x: vec![0.0, 0.0, 0.0, 0.0],
+            },
+            InputV0 {
+                x: vec![-1.0, 0.5, 2.0, -3.0],
+            },
+        ],
+    }
+}
+
+#[test]
+fn lr_batch_outputs_match_single_row_proofs() {
+    let model_bin = lr_model().encode_bin();
+    let batch = batch();
+    let (output_batch_bin, artifact_bin) =
+        prove_lr_batch_v2([7u8; 32], [9u8; 32], &model_bin, &batch.encode_bin()).unwrap();
+
+    let outputs = OutputBatchV1::decode_bin(&output_batch_bin).unwrap();
+    for (row, out) in batch.rows.iter().zip(outputs.rows.iter()) {
+        let (single, _) = prove_lr_v0([7u8; 32], [9u8; 32], &model_bin, &row.encode_bin()).unwrap();
+        assert_eq!(single, out.encode_bin());
+    }
+
+    verify_lr_batch_v2(
+        &artifact_bin,
+        &model_bin,
+        &batch.encode_bin(),
+        &output_batch_bin,
+    )
+    .unwrap();
+}
+
+#[test]
+fn lr_batch_verify_rejects_tampered_output_row() {
+    let model_bin = lr_model().encode_bin();
+    let input_batch_bin = batch().encode_bin();
+    let (mut output_batch_bin, artifact_bin) =
+        prove_lr_batch_v2([7u8; 32], [9u8; 32], &model_bin, &input_batch_bin).unwrap();
+
+    output_batch_bin[12 + 8] ^= 0x01;
+    assert_eq!(
+        verify_lr_batch_v2(
+            &artifact_bin,
+            &model_bin,
+            &input_batch_bin,
+            &output_batch_bin
+        ),
+        Err(VerifaiError::HashMismatch)
+    );
+}
+
+#[test]
+fn lr_batch_row_verifies_in_isolation() {
+    let model_bin = lr_model().encode_bin();
+    let input_batch_bin = batch().encode_bin();
+    let (_, artifact_bin) =
+        prove_lr_batch_v2([7u8; 32], [9u8; 32], &model_bin, &input_batch_bin).unwrap();
+
+    for index in 0..3 {
+        let row_proof = batch_row_proof_lr_v2(&model_bin, &input_batch_bin, index).unwrap();
+        verify_batch_row_lr_v2(&artifact_bin, &model_bin, &row_proof).unwrap();
+    }
+    assert_eq!(
+        batch_row_proof_lr_v2(&model_bin, &input_batch_bin, 3),
+        Err(VerifaiError::DimensionMismatch)
+    );
+}
+
+#[test]
+fn lr_batch_row_rejects_tampered_path() {
+    let model_bin = lr_model().encode_bin();
+    let input_batch_bin = batch().encode_bin();
+    let (_, artifact_bin) =
+        prove_lr_batch_v2([7u8; 32], [9u8; 32], &model_bin, &input_batch_bin).unwrap();
+
+    let mut row_proof = batch_row_proof_lr_v2(&model_bin, &input_batch_bin, 1).unwrap();
+    let last = row_proof.len() - 1;
+    row_proof[last] ^= 0x01;
+    assert_eq!(
+        verify_batch_row_lr_v2(&artifact_bin, &model_bin, &row_proof),
+        Err(VerifaiError::InclusionProofInvalid)
+    );
+}
+
+#[test]
+fn mlp_batch_prove_verify_and_row_proof() {
+    let model = MlpModelV1 {
+        input_dim: 4,
+        hidden_size: 2,
+        w1: vec![0.1, -0.2, 0.3, 0.4, -0.1, 0.5, 0.2, -0.3],
+        b1: vec![0.0, -0.1],
+        w2: vec![0.2, -0.4],
+        b2: 0.05,
+    };
+    let model_bin = model.encode_bin();
+    let input_batch_bin = batch().encode_bin();
+
+    let (output_batch_bin, artifact_bin) =
+        prove_mlp_batch_v2([7u8; 32], [9u8; 32], &model_bin, &input_batch_bin).unwrap();
+    verify_mlp_batch_v2(
+        &artifact_bin,
+        &model_bin,
+        &input_batch_bin,
+        &output_batch_bin,
+    )
+    .unwrap();
+
+    let row_proof = batch_row_proof_mlp_v2(&model_bin, &input_batch_bin, 2).unwrap();
+    verify_batch_row_mlp_v2(&artifact_bin, &model_bin, &row_proof).unwrap();
+
+    let lr_model_bin = lr_model().encode_bin();
+    assert_eq!(
+        verify_batch_row_mlp_v2(&artifact_bin, &lr_model_bin, &row_proof),
+        Err(VerifaiError::HashMismatch)
+    );
+}
@@ -0,0 +1,105 @@
+use verifai_core::model_bin::{InputV0, LogisticModelV0};
+use verifai_core::sig_alg::{AlgSigningKey, SigAlg};
+use verifai_runtime::{
+    prove_lr_v0, prove_lr_v1_with_attester, prove_lr_v3_with_attester, prove_lr_v4_with_attester,
+    prove_lr_v5_with_attester, verify_lr_bulk, verify_lr_v0, BulkRecord, FreshnessPolicy,
+    IssuanceV3, NoopAttester, VerifaiError,
+};
+
+fn model_bin() -> Vec<u8> {
+    LogisticModelV0 {
+        weights: vec![0.1, -0.2, 0.3, 0.4],
+        bias: -0.05,
+    }
+    .encode_bin()
+}
+
+fn input_bin(i: usize) -> Vec<u8> {
+    InputV0 {
+        x: vec![i as f64, 2.0, 3.0, 4.0],
+    }
+    .encode_bin()
+}
+
+/// (artifact, input, output) for every single-input artifact version.
+fn proven() -> Vec<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+    let model_bin = model_bin();
+    let issuance = IssuanceV3 {
+        issued_at: 10,
+        expires_at: Some(100),
+        nonce: [0u8; 32],
+    };
+    let p256 = AlgSigningKey::new(SigAlg::EcdsaP256Sha256, [9u8; 32]).unwrap();
+    (0..6)
+        .map(|i| {
+            let input_bin = input_bin(i);
+            let (output, artifact) = match i {
+                0 => prove_lr_v0([7u8; 32], [9u8; 32], &model_bin, &input_bin),
+                1 => prove_lr_v1_with_attester::<NoopAttester>(
+                    [7u8; 32], [9u8; 32], &model_bin, &input_bin,
+                ),
+                2 => prove_lr_v3_with_attester::<NoopAttester>(
+                    [7u8; 32], [9u8; 32], &model_bin, &input_bin, &issuance, None,
+                ),
+                3 => prove_lr_v4_with_attester::<NoopAttester>(
+                    [7u8; 32], [9u8; 32], &model_bin, &input_bin,
+                ),
+                4 => prove_lr_v5_with_attester::<NoopAttester>(
+                    [7u8; 32], &p256, &model_bin, &input_bin,
+                ),
+                _ => prove_lr_v0([7u8; 32], [8u8; 32], &model_bin, &input_bin),
+            }
+            .unwrap();
+            (artifact, input_bin, output)
+        })
+        .collect()
+}
+
+fn records(proven: &[(Vec<u8>, Vec<u8>, Vec<u8>)]) -> Vec<BulkRecord<'_>> {
+    proven
+        .iter()
+        .map(|(artifact_bin, input_bin, output_bin)| BulkRecord {
+            artifact_bin,
+            input_bin,
+            output_bin,
+        })
+        .collect()
+}
+
+#[test]
+fn bulk_verifies_every_single_input_version() {
+    let proven = proven();
+    let results = verify_lr_bulk(&model_bin(), &records(&proven), &FreshnessPolicy::at(50));
+    assert_eq!(results, vec![Ok(()); 6]);
+
+    // v3 freshness is still enforced
+    let results = verify_lr_bulk(&model_bin(), &records(&proven), &FreshnessPolicy::at(500));
+    assert_eq!(results[2], Err(VerifaiError::Expired));
+}
+
+#[test]
+fn bulk_reports_each_failure_like_single_verification() {
+    let mut proven = proven();
+    proven[0].0[200] ^= 0x01; // v0 signature
+    proven[3].0[170] ^= 0x01; // v4 co-signer key
+    *proven[4].2.last_mut().unwrap() ^= 0x01; // v5 output
+    proven[5].0.truncate(10);
+
+    let results = verify_lr_bulk(&model_bin(), &records(&proven), &FreshnessPolicy::at(50));
+    assert_eq!(
+        results,
+        vec![
+            Err(VerifaiError::SignatureInvalid),
+            Ok(()),
+            Ok(()),
+            Err(VerifaiError::SignatureInvalid),
+            Err(VerifaiError::HashMismatch),
+            Err(VerifaiError::CoreDecode),
+        ]
+    );
+    let (artifact, input, output) = &proven[0];
+    assert_eq!(
+        verify_lr_v0(artifact, &model_bin(), input, output),
+        Err(VerifaiError::SignatureInvalid)
+    );
+}
@@ -0,0 +1,176 @@
+use std::fs;
+use std::path::PathBuf;
+
+use verifai_core::artifact_bin::ProofArtifactV0;
+use verifai_core::event_bin::{ActivationKind, TraceEventV0, TraceV0};
+use verifai_core::hash::sha256;
+use verifai_core::merkle::trace_root_from_event_bytes;
+use verifai_core::model_bin::{InputV0, LogisticModelV0, MlpModelV1, OutputV0};
+use verifai_runtime::{
+    check_trace_consistency, lr_trace, mlp_trace, prove_lr_v0, prove_mlp_v1, VerifaiError,
+};
+
+fn lr_fixture() -> (Vec<u8>, Vec<u8>) {
+    let model = LogisticModelV0 {
+        weights: vec![0.1, -0.2, 0.3, 0.4],
+        bias: -0.05,
+    };
+    let input = InputV0 {
+        x: vec![1.0, 2.0, 3.0, 4.0],
+    };
+    (model.encode_bin(), input.encode_bin())
+}
+
+/// A correctly signed v0 artifact over an arbitrary trace and output.
+fn signed_over(events: &[TraceEventV0], y: f64) -> (Vec<u8>, Vec<u8>) {
+    let output_bin = OutputV0 { y }.encode_bin();
+    let event_bytes: Vec<Vec<u8>> = events.iter().map(|e| e.encode_bin()).collect();
+    let mut artifact = ProofArtifactV0 {
+        version: 0,
+        runtime_id: [7u8; 32],
+        model_hash: [1u8; 32],
+        input_hash: [2u8; 32],
+        output_hash: sha256(&output_bin),
+        trace_root: trace_root_from_event_bytes(&event_bytes),
+        sig_pubkey: [0u8; 32],
+        signature: [0u8; 64],
+    };
+    artifact.sign_detached([9u8; 32]).unwrap();
+    (artifact.encode_bin(), output_bin)
+}
+
+#[test]
+fn lr_and_mlp_traces_are_consistent() {
+    let (model_bin, input_bin) = lr_fixture();
+    let (output_bin, artifact_bin) =
+        prove_lr_v0([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap();
+    let events = lr_trace(&model_bin, &input_bin).unwrap();
+    assert_eq!(
+        check_trace_consistency(&events, &artifact_bin, &output_bin),
+        Ok(())
+    );
+
+    let model = MlpModelV1 {
+        input_dim: 4,
+        hidden_size: 2,
+        w1: vec![0.1, -0.2, 0.3, 0.4, -0.1, 0.5, 0.2, -0.3],
+        b1: vec![0.0, -0.1],
+        w2: vec![0.2, -0.4],
+        b2: 0.05,
+    };
+    let input = InputV0 {
+        x: vec![1.0, 0.5, -0.5, 0.25],
+    };
+    let (model_bin, input_bin) = (model.encode_bin(), input.encode_bin());
+    let (output_bin, artifact_bin) =
+        prove_mlp_v1([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap();
+    let events = mlp_trace(&model_bin, &input_bin).unwrap();
+    let trace = TraceV0::decode_bin(&TraceV0 { events }.encode_bin()).unwrap();
+    assert_eq!(
+        check_trace_consistency(&trace.events, &artifact_bin, &output_bin),
+        Ok(())
+    );
+}
+
+#[test]
+fn v6_traces_use_the_artifact_hash_alg() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../test-vectors/hashalg-sha3-256");
+    let read = |file: &str| fs::read(dir.join(file)).unwrap();
+    let events = lr_trace(&read("model.bin"), &read("input.bin")).unwrap();
+    assert_eq!(
+        check_trace_consistency(
+            &events,
+            &read("expected_artifact.bin"),
+            &read("expected_output.bin")
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn mismatched_trace_output_or_signature_fails() {
+    let (model_bin, input_bin) = lr_fixture();
+    let (output_bin, artifact_bin) =
+        prove_lr_v0([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap();
+    let events = lr_trace(&model_bin, &input_bin).unwrap();
+
+    let mut other_events = events.clone();
+    other_events[0] = TraceEventV0::OpLinear { op_id: 0, z: 0.0 };
+    assert_eq!(
+        check_trace_consistency(&other_events, &artifact_bin, &output_bin),
+        Err(VerifaiError::TraceMismatch)
+    );
+
+    let other_output = OutputV0 { y: 0.5 }.encode_bin();
+    assert_eq!(
+        check_trace_consistency(&events, &artifact_bin, &other_output),
+        Err(VerifaiError::HashMismatch)
+    );
+
+    let mut bad_sig = artifact_bin;
+    *bad_sig.last_mut().unwrap() ^= 0x01;
+    assert_eq!(
+        check_trace_consistency(&events, &bad_sig, &output_bin),
+        Err(VerifaiError::SignatureInvalid)
+    );
+}
+
+#[test]
+fn signed_but_malformed_traces_fail() {
+    let good = |z: f64| {
+        let y = 1.0 / (1.0 + (-z).exp());
+        vec![
+            TraceEventV0::OpLinear { op_id: 0, z },
+            TraceEventV0::OpActivation {
+                op_id: 1,
+                kind: ActivationKind::Sigmoid,
+                input: z,
+                output: y,
+            },
+            TraceEventV0::OpOutput { y },
+        ]
+    };
+    let check = |events: &[TraceEventV0], y: f64| {
+        let (artifact_bin, output_bin) = signed_over(events, y);
+        check_trace_consistency(events, &artifact_bin, &output_bin)
+    };
+    let events = good(0.7);
+    let y = 1.0 / (1.0 + (-0.7f64).exp());
+    assert_eq!(check(&events, y), Ok(()));
+
+    // sigmoid output that is not sigmoid of its input
+    let mut wrong_sigmoid = events.clone();
+    wrong_sigmoid[1] = TraceEventV0::OpActivation {
+        op_id: 1,
+        kind: ActivationKind::Sigmoid,
+        input: 0.7,
+        output: 0.9,
+    };
+    wrong_sigmoid[2] = TraceEventV0::OpOutput { y: 0.9 };
+    assert_eq!(check(&wrong_sigmoid, 0.9), Err(VerifaiError::TraceMismatch));
+
+    // activation reading something other than the preceding z
+    let mut detached = events.clone();
+    detached[0] = TraceEventV0::OpLinear { op_id: 0, z: 0.6 };
+    assert_eq!(check(&detached, y), Err(VerifaiError::TraceMismatch));
+
+    // negative ReLU output
+    let relu = vec![
+        TraceEventV0::OpLinear { op_id: 0, z: -1.0 },
+        TraceEventV0::OpActivation {
+            op_id: 100,
+            kind: ActivationKind::Relu,
+            input: -1.0,
+            output: -1.0,
+        },
+        TraceEventV0::OpOutput { y: -1.0 },
+    ];
+    assert_eq!(check(&relu, -1.0), Err(VerifaiError::TraceMismatch));
+
+    // OpOutput disagreeing with the last activation or missing
+    let mut other_y = events.clone();
+    other_y[2] = TraceEventV0::OpOutput { y: 0.25 };
+    assert_eq!(check(&other_y, 0.25), Err(VerifaiError::TraceMismatch));
+    assert_eq!(check(&events[..2], y), Err(VerifaiError::TraceMismatch));
+    assert_eq!(check(&[], y), Err(VerifaiError::TraceMismatch));
+}
@@ -0,0 +1,173 @@
+use std::fs;
+use std::path::PathBuf;
+
+use verifai_core::disclosure::TraceDisclosureV0;
+use verifai_core::event_bin::TraceEventV0;
+use verifai_core::hash::HashAlg;
+use verifai_core::model_bin::{InputV0, LogisticModelV0, MlpModelV1};
+use verifai_core::salts::CommitmentSaltsV0;
+use verifai_runtime::{
+    disclose_lr_trace, disclose_mlp_trace, prove_lr_v0, prove_lr_v7_with_attester, prove_mlp_v1,
+    verify_trace_disclosure, NoopAttester, VerifaiError,
+};
+
+fn lr_fixture() -> (Vec<u8>, Vec<u8>) {
+    let model = LogisticModelV0 {
+        weights: vec![0.1, -0.2, 0.3, 0.4],
+        bias: -0.05,
+    };
+    let input = InputV0 {
+        x: vec![1.0, 2.0, 3.0, 4.0],
+    };
+    (model.encode_bin(), input.encode_bin())
+}
+
+fn mlp_fixture() -> (Vec<u8>, Vec<u8>) {
+    let model = MlpModelV1 {
+        input_dim: 4,
+        hidden_size: 2,
+        w1: vec![0.1, -0.2, 0.3, 0.4, -0.1, 0.5, 0.2, -0.3],
+        b1: vec![0.0, -0.1],
+        w2: vec![0.2, -0.4],
+        b2: 0.05,
+    };
+    let input = InputV0 {
+        x: vec![1.0, 0.5, -0.5, 0.25],
+    };
+    (model.encode_bin(), input.encode_bin())
+}
+
+#[test]
+fn lr_disclosure_verifies_without_model_or_input() {
+    let (model_bin, input_bin) = lr_fixture();
+    let (_, artifact_bin) = prove_lr_v0([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap();
+
+    // the score and the decision, not the sigmoid step
+    let disclosure = disclose_lr_trace(&artifact_bin, &model_bin, &input_bin, |i, _| {
+        i == 0 || i == 2
+    })
+    .unwrap();
+    assert_eq!(disclosure.leaf_count, 3);
+    assert_eq!(disclosure.events.len(), 2);
+    assert!(matches!(
+        disclosure.events[0].event,
+        TraceEventV0::OpLinear { op_id: 0, .. }
+    ));
+    assert!(matches!(
+        disclosure.events[1].event,
+        TraceEventV0::OpOutput { .. }
+    ));
+
+    let decoded = TraceDisclosureV0::decode_bin(&disclosure.encode_bin()).unwrap();
+    assert_eq!(verify_trace_disclosure(&artifact_bin, &decoded), Ok(()));
+}
+
+#[test]
+fn mlp_disclosure_hides_hidden_activations() {
+    let (model_bin, input_bin) = mlp_fixture();
+    let (_, artifact_bin) = prove_mlp_v1([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap();
+
+    let disclosure = disclose_mlp_trace(&artifact_bin, &model_bin, &input_bin, |_, e| {
+        matches!(
+            e,
+            TraceEventV0::OpLinear { op_id: 200, .. } | TraceEventV0::OpOutput { .. }
+        )
+    })
+    .unwrap();
+    assert_eq!(
+        disclosure
+            .events
+            .iter()
+            .map(|d| d.index)
+            .collect::<Vec<_>>(),
+        vec![4, 6]
+    );
+    assert_eq!(verify_trace_disclosure(&artifact_bin, &disclosure), Ok(()));
+}
+
+#[test]
+fn disclosure_rejects_tampering() {
+    let (model_bin, input_bin) = lr_fixture();
+    let (_, artifact_bin) = prove_lr_v0([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap();
+    let disclosure =
+        disclose_lr_trace(&artifact_bin, &model_bin, &input_bin, |i, _| i == 0).unwrap();
+
+    let mut forged = disclosure.clone();
+    forged.events[0].event = TraceEventV0::OpLinear { op_id: 0, z: 9.0 };
+    assert_eq!(
+        verify_trace_disclosure(&artifact_bin, &forged),
+        Err(VerifaiError::InclusionProofInvalid)
+    );
+
+    let mut other_alg = disclosure.clone();
+    other_alg.hash_alg = HashAlg::Blake3;
+    assert_eq!(
+        verify_trace_disclosure(&artifact_bin, &other_alg),
+        Err(VerifaiError::TraceMismatch)
+    );
+
+    let mut bad_sig = artifact_bin.clone();
+    *bad_sig.last_mut().unwrap() ^= 0x01;
+    assert_eq!(
+        verify_trace_disclosure(&bad_sig, &disclosure),
+        Err(VerifaiError::SignatureInvalid)
+    );
+
+    // a disclosure is only made for the artifact's own model and input
+    let mut other_input = input_bin.clone();
+    *other_input.last_mut().unwrap() ^= 0x01;
+    assert_eq!(
+        disclose_lr_trace(&artifact_bin, &model_bin, &other_input, |i, _| i == 0),
+        Err(VerifaiError::HashMismatch)
+    );
+    assert_eq!(
+        disclose_lr_trace(&artifact_bin, &model_bin, &input_bin, |i, _| i == 3),
+        Err(VerifaiError::DimensionMismatch)
+    );
+}
+
+#[test]
+fn disclosure_uses_the_v6_hash_alg() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../test-vectors/hashalg-blake3");
+    let model_bin = fs::read(dir.join("model.bin")).unwrap();
+    let input_bin = fs::read(dir.join("input.bin")).unwrap();
+    let artifact_bin = fs::read(dir.join("expected_artifact.bin")).unwrap();
+
+    let disclosure =
+        disclose_lr_trace(&artifact_bin, &model_bin, &input_bin, |i, _| i == 2).unwrap();
+    assert_eq!(disclosure.hash_alg, HashAlg::Blake3);
+    assert_eq!(verify_trace_disclosure(&artifact_bin, &disclosure), Ok(()));
+}
+
+#[test]
+fn salted_artifacts_are_not_disclosable() {
+    let (model_bin, input_bin) = lr_fixture();
+    let salts = CommitmentSaltsV0 {
+        model: [1u8; 32],
+        input: [2u8; 32],
+        output: [3u8; 32],
+        trace: [4u8; 32],
+    };
+    let (_, artifact_bin) = prove_lr_v7_with_attester::<NoopAttester>(
+        [7u8; 32], [9u8; 32], &model_bin, &input_bin, &salts,
+    )
+    .unwrap();
+    assert_eq!(
+        disclose_lr_trace(&artifact_bin, &model_bin, &input_bin, |i, _| i == 0),
+        Err(VerifaiError::CoreDecode)
+    );
+}
+
+#[test]
+fn disclosure_does_not_prove_positions() {
+    let (model_bin, input_bin) = lr_fixture();
+    let (_, artifact_bin) = prove_lr_v0([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap();
+    let full = disclose_lr_trace(&artifact_bin, &model_bin, &input_bin, |_, _| true).unwrap();
+    let mut padded: Vec<TraceEventV0> = full.events.iter().map(|d| d.event.clone()).collect();
+    padded.push(padded[2].clone());
+
+    // odd levels duplicate their last node, so [a, b, c, c] has the root of [a, b, c]
+    let disclosure = TraceDisclosureV0::from_trace(HashAlg::Sha256, &padded, &[3]).unwrap();
+    assert_eq!(disclosure.leaf_count, 4);
+    assert_eq!(verify_trace_disclosure(&artifact_bin, &disclosure), Ok(()));
+}
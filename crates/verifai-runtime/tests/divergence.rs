@@ -0,0 +1,217 @@
+use verifai_core::artifact_bin::ProofArtifactV0;
+use verifai_core::event_bin::TraceEventV0;
+use verifai_core::hash::sha256;
+use verifai_core::merkle::trace_root_from_event_bytes;
+use verifai_core::model_bin::{InputV0, LogisticModelV0, MlpModelV1};
+use verifai_core::trace_sink::TraceSink;
+use verifai_runtime::{
+    lr_trace, mlp_trace, prove_lr_v0, prove_mlp_v1, run_mlp_v1_into, verify_lr_with_trace,
+    verify_mlp_with_trace, BulkRecord, FreshnessPolicy, VerifaiError,
+};
+
+fn lr_fixture() -> (Vec<u8>, Vec<u8>) {
+    let model = LogisticModelV0 {
+        weights: vec![0.1, -0.2, 0.3, 0.4],
+        bias: -0.05,
+    };
+    let input = InputV0 {
+        x: vec![1.0, 2.0, 3.0, 4.0],
+    };
+    (model.encode_bin(), input.encode_bin())
+}
+
+fn mlp_fixture() -> (Vec<u8>, Vec<u8>) {
+    let model = MlpModelV1 {
+        input_dim: 4,
+        hidden_size: 2,
+        w1: vec![0.1, -0.2, 0.3, 0.4, -0.1, 0.5, 0.2, -0.3],
+        b1: vec![0.0, -0.1],
+        w2: vec![0.2, -0.4],
+        b2: 0.05,
+    };
+    let input = InputV0 {
+        x: vec![1.0, 0.5, -0.5, 0.25],
+    };
+    (model.encode_bin(), input.encode_bin())
+}
+
+fn policy() -> FreshnessPolicy {
+    FreshnessPolicy {
+        now: 0,
+        max_age_nanos: None,
+        expected_nonce: None,
+        trusted_tsa: None,
+    }
+}
+
+/// A correctly signed v0 artifact over the real model, input and output but
+/// the given trace, as a dishonest prover would make it.
+fn signed_over(
+    events: &[TraceEventV0],
+    model_bin: &[u8],
+    input_bin: &[u8],
+    output_bin: &[u8],
+) -> Vec<u8> {
+    let event_bytes: Vec<Vec<u8>> = events.iter().map(|e| e.encode_bin()).collect();
+    let mut artifact = ProofArtifactV0 {
+        version: 0,
+        runtime_id: [7u8; 32],
+        model_hash: sha256(model_bin),
+        input_hash: sha256(input_bin),
+        output_hash: sha256(output_bin),
+        trace_root: trace_root_from_event_bytes(&event_bytes),
+        sig_pubkey: [0u8; 32],
+        signature: [0u8; 64],
+    };
+    artifact.sign_detached([9u8; 32]).unwrap();
+    artifact.encode_bin()
+}
+
+#[test]
+fn honest_traces_verify() {
+    let (model_bin, input_bin) = lr_fixture();
+    let (output_bin, artifact_bin) =
+        prove_lr_v0([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap();
+    let record = BulkRecord {
+        artifact_bin: &artifact_bin,
+        input_bin: &input_bin,
+        output_bin: &output_bin,
+    };
+    let events = lr_trace(&model_bin, &input_bin).unwrap();
+    assert_eq!(
+        verify_lr_with_trace(&model_bin, &record, &events, &policy()),
+        Ok(None)
+    );
+
+    let (model_bin, input_bin) = mlp_fixture();
+    let (output_bin, artifact_bin) =
+        prove_mlp_v1([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap();
+    let record = BulkRecord {
+        artifact_bin: &artifact_bin,
+        input_bin: &input_bin,
+        output_bin: &output_bin,
+    };
+    let events = mlp_trace(&model_bin, &input_bin).unwrap();
+    assert_eq!(
+        verify_mlp_with_trace(&model_bin, &record, &events, &policy()),
+        Ok(None)
+    );
+}
+
+#[test]
+fn reports_the_first_divergent_event() {
+    let (model_bin, input_bin) = mlp_fixture();
+    let (output_bin, _) = prove_mlp_v1([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap();
+    let honest = mlp_trace(&model_bin, &input_bin).unwrap();
+
+    let mut claimed = honest.clone();
+    claimed[2] = TraceEventV0::OpLinear { op_id: 1, z: 0.75 };
+    let artifact_bin = signed_over(&claimed, &model_bin, &input_bin, &output_bin);
+    let record = BulkRecord {
+        artifact_bin: &artifact_bin,
+        input_bin: &input_bin,
+        output_bin: &output_bin,
+    };
+    let divergence = verify_mlp_with_trace(&model_bin, &record, &claimed, &policy())
+        .unwrap()
+        .unwrap();
+    assert_eq!(divergence.index, 2);
+    assert_eq!(divergence.op_id, Some(1));
+    assert_eq!(divergence.expected, Some(claimed[2].clone()));
+    assert_eq!(divergence.actual, Some(honest[2].clone()));
+}
+
+#[test]
+fn reports_a_claim_that_is_too_short_or_too_long() {
+    let (model_bin, input_bin) = lr_fixture();
+    let (output_bin, _) = prove_lr_v0([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap();
+    let honest = lr_trace(&model_bin, &input_bin).unwrap();
+
+    let short = &honest[..2];
+    let artifact_bin = signed_over(short, &model_bin, &input_bin, &output_bin);
+    let record = BulkRecord {
+        artifact_bin: &artifact_bin,
+        input_bin: &input_bin,
+        output_bin: &output_bin,
+    };
+    let divergence = verify_lr_with_trace(&model_bin, &record, short, &policy())
+        .unwrap()
+        .unwrap();
+    assert_eq!(divergence.index, 2);
+    assert_eq!(divergence.op_id, None);
+    assert_eq!(divergence.expected, None);
+    assert_eq!(divergence.actual, Some(honest[2].clone()));
+
+    let mut long = honest.clone();
+    long.push(TraceEventV0::OpLinear { op_id: 9, z: 1.0 });
+    let artifact_bin = signed_over(&long, &model_bin, &input_bin, &output_bin);
+    let record = BulkRecord {
+        artifact_bin: &artifact_bin,
+        input_bin: &input_bin,
+        output_bin: &output_bin,
+    };
+    let divergence = verify_lr_with_trace(&model_bin, &record, &long, &policy())
+        .unwrap()
+        .unwrap();
+    assert_eq!(divergence.index, 3);
+    assert_eq!(divergence.op_id, Some(9));
+    assert_eq!(divergence.expected, Some(long[3].clone()));
+    assert_eq!(divergence.actual, None);
+}
+
+#[test]
+fn rejects_a_claim_the_artifact_does_not_commit_to() {
+    let (model_bin, input_bin) = lr_fixture();
+    let (output_bin, artifact_bin) =
+        prove_lr_v0([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap();
+    let record = BulkRecord {
+        artifact_bin: &artifact_bin,
+        input_bin: &input_bin,
+        output_bin: &output_bin,
+    };
+    let mut claimed = lr_trace(&model_bin, &input_bin).unwrap();
+    claimed.pop();
+    assert_eq!(
+        verify_lr_with_trace(&model_bin, &record, &claimed, &policy()),
+        Err(VerifaiError::TraceMismatch)
+    );
+
+    let mut tampered = artifact_bin.clone();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    let record = BulkRecord {
+        artifact_bin: &tampered,
+        ..record
+    };
+    assert_eq!(
+        verify_lr_with_trace(&model_bin, &record, &claimed, &policy()),
+        Err(VerifaiError::SignatureInvalid)
+    );
+}
+
+/// Takes `limit` events, then stops.
+struct StopAfter {
+    limit: usize,
+    seen: usize,
+}
+
+impl TraceSink for StopAfter {
+    fn record(&mut self, _event: &TraceEventV0) {
+        self.seen += 1;
+    }
+
+    fn stopped(&self) -> bool {
+        self.seen >= self.limit
+    }
+}
+
+#[test]
+fn runners_stop_when_the_sink_does() {
+    let (model_bin, input_bin) = mlp_fixture();
+    let mut sink = StopAfter { limit: 2, seen: 0 };
+    assert_eq!(
+        run_mlp_v1_into(&model_bin, &input_bin, &mut sink),
+        Err(VerifaiError::TraceMismatch)
+    );
+    assert_eq!(sink.seen, 2);
+}
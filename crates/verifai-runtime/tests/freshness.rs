@@ -0,0 +1,143 @@
+use verifai_core::artifact_bin::ProofArtifactV3;
+use verifai_core::model_bin::{InputV0, LogisticModelV0};
+use verifai_runtime::{
+    prove_lr_v3_with_attester, verify_lr_v3, FreshnessPolicy, IssuanceV3, LocalTsa, NoopAttester,
+    VerifaiError,
+};
+
+const SEC: u64 = 1_000_000_000;
+const T0: u64 = 1_700_000_000 * SEC;
+const TSA_KEY: [u8; 32] = [5u8; 32];
+
+fn bins() -> (Vec<u8>, Vec<u8>) {
+    let model = LogisticModelV0 {
+        weights: vec![0.1, -0.2, 0.3, 0.4],
+        bias: -0.05,
+    };
+    let input = InputV0 {
+        x: vec![1.0, 2.0, 3.0, 4.0],
+    };
+    (model.encode_bin(), input.encode_bin())
+}
+
+fn prove(issuance: &IssuanceV3, tsa: Option<&LocalTsa>) -> (Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>) {
+    let (model_bin, input_bin) = bins();
+    let (output_bin, artifact_bin) = prove_lr_v3_with_attester::<NoopAttester>(
+        [7u8; 32], [9u8; 32], &model_bin, &input_bin, issuance, tsa,
+    )
+    .unwrap();
+    (model_bin, input_bin, output_bin, artifact_bin)
+}
+
+fn issuance() -> IssuanceV3 {
+    IssuanceV3 {
+        issued_at: T0,
+        expires_at: Some(T0 + 60 * SEC),
+        nonce: [4u8; 32],
+    }
+}
+
+#[test]
+fn v3_roundtrip_and_freshness_checks() {
+    let (model_bin, input_bin, output_bin, artifact_bin) = prove(&issuance(), None);
+    let check = |policy: &FreshnessPolicy| {
+        verify_lr_v3(&artifact_bin, &model_bin, &input_bin, &output_bin, policy)
+    };
+
+    assert_eq!(check(&FreshnessPolicy::at(T0 + 10 * SEC)), Ok(()));
+    assert_eq!(
+        check(&FreshnessPolicy::at(T0 - 1)),
+        Err(VerifaiError::NotYetValid)
+    );
+    assert_eq!(
+        check(&FreshnessPolicy::at(T0 + 61 * SEC)),
+        Err(VerifaiError::Expired)
+    );
+
+    let mut policy = FreshnessPolicy::at(T0 + 10 * SEC);
+    policy.max_age_nanos = Some(5 * SEC);
+    assert_eq!(check(&policy), Err(VerifaiError::Stale));
+
+    let mut policy = FreshnessPolicy::at(T0 + 10 * SEC);
+    policy.expected_nonce = Some([4u8; 32]);
+    assert_eq!(check(&policy), Ok(()));
+    policy.expected_nonce = Some([3u8; 32]);
+    assert_eq!(check(&policy), Err(VerifaiError::NonceMismatch));
+
+    let mut policy = FreshnessPolicy::at(T0 + 10 * SEC);
+    policy.trusted_tsa = Some([5u8; 32]);
+    assert_eq!(check(&policy), Err(VerifaiError::TimestampInvalid));
+}
+
+#[test]
+fn freshness_fields_are_signed() {
+    let (model_bin, input_bin, output_bin, artifact_bin) = prove(&issuance(), None);
+    let mut artifact = ProofArtifactV3::decode_bin(&artifact_bin).unwrap();
+    artifact.issued_at += SEC;
+    assert_eq!(
+        verify_lr_v3(
+            &artifact.encode_bin(),
+            &model_bin,
+            &input_bin,
+            &output_bin,
+            &FreshnessPolicy::at(T0 + 10 * SEC)
+        ),
+        Err(VerifaiError::SignatureInvalid)
+    );
+}
+
+#[test]
+fn timestamp_token_is_checked() {
+    let tsa = LocalTsa::with_fixed_time(TSA_KEY, T0 + SEC);
+    let (model_bin, input_bin, output_bin, artifact_bin) = prove(&issuance(), Some(&tsa));
+    let artifact = ProofArtifactV3::decode_bin(&artifact_bin).unwrap();
+    let token = artifact.timestamp.clone().unwrap();
+    assert_eq!(token.message_imprint, artifact.timestamp_imprint());
+    assert_eq!(token.gen_time, T0 + SEC);
+
+    let mut policy = FreshnessPolicy::at(T0 + 10 * SEC);
+    policy.trusted_tsa = Some(token.tsa_pubkey);
+    let check = |artifact_bin: &[u8], policy: &FreshnessPolicy| {
+        verify_lr_v3(artifact_bin, &model_bin, &input_bin, &output_bin, policy)
+    };
+    assert_eq!(check(&artifact_bin, &policy), Ok(()));
+
+    policy.trusted_tsa = Some([0u8; 32]);
+    assert_eq!(
+        check(&artifact_bin, &policy),
+        Err(VerifaiError::TimestampInvalid)
+    );
+
+    // a token from before issuance does not vouch for this artifact
+    let early = LocalTsa::with_fixed_time(TSA_KEY, T0 - SEC);
+    let mut tampered = artifact.clone();
+    tampered.timestamp = Some(early.stamp(artifact.timestamp_imprint()).unwrap());
+    assert_eq!(
+        check(&tampered.encode_bin(), &FreshnessPolicy::at(T0 + 10 * SEC)),
+        Err(VerifaiError::TimestampInvalid)
+    );
+
+    // nor does a token over some other imprint
+    let mut tampered = artifact;
+    tampered.timestamp = Some(tsa.stamp([1u8; 32]).unwrap());
+    assert_eq!(
+        check(&tampered.encode_bin(), &FreshnessPolicy::at(T0 + 10 * SEC)),
+        Err(VerifaiError::TimestampInvalid)
+    );
+}
+
+#[test]
+fn prove_rejects_expiry_before_issuance() {
+    let (model_bin, input_bin) = bins();
+    let issuance = IssuanceV3 {
+        issued_at: T0,
+        expires_at: Some(T0),
+        nonce: [0u8; 32],
+    };
+    assert_eq!(
+        prove_lr_v3_with_attester::<NoopAttester>(
+            [7u8; 32], [9u8; 32], &model_bin, &input_bin, &issuance, None
+        ),
+        Err(VerifaiError::Expired)
+    );
+}
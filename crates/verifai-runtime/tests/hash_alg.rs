@@ -0,0 +1,99 @@
+use std::fs;
+use std::path::PathBuf;
+
+use verifai_core::artifact_bin::ProofArtifactV6;
+use verifai_core::hash::HashAlg;
+use verifai_core::sig_alg::{AlgSigningKey, SigAlg};
+use verifai_runtime::{
+    prove_lr_v0, prove_lr_v6_with_attester, verify_lr_bulk, verify_lr_v6, BulkRecord,
+    FreshnessPolicy, NoopAttester, VerifaiError,
+};
+
+fn vector(alg: HashAlg, file: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(format!("hashalg-{}", alg.name()))
+        .join(file);
+    fs::read(path).unwrap()
+}
+
+#[test]
+fn hash_alg_vectors_verify_and_regenerate() {
+    let key = AlgSigningKey::new(SigAlg::Ed25519, [9u8; 32]).unwrap();
+    for alg in HashAlg::ALL {
+        let model_bin = vector(alg, "model.bin");
+        let input_bin = vector(alg, "input.bin");
+        let output_bin = vector(alg, "expected_output.bin");
+        let artifact_bin = vector(alg, "expected_artifact.bin");
+
+        let artifact = verify_lr_v6(&artifact_bin, &model_bin, &input_bin, &output_bin).unwrap();
+        assert_eq!(artifact.hash_alg, alg);
+        assert_eq!(artifact.model_hash, alg.digest(&model_bin));
+
+        let (output, proved) =
+            prove_lr_v6_with_attester::<NoopAttester>([7u8; 32], &key, alg, &model_bin, &input_bin)
+                .unwrap();
+        assert_eq!(output, output_bin);
+        assert_eq!(proved, artifact_bin);
+    }
+
+    // the hash algorithm never changes the inference itself
+    let outputs: Vec<Vec<u8>> = HashAlg::ALL
+        .into_iter()
+        .map(|alg| vector(alg, "expected_output.bin"))
+        .collect();
+    assert!(outputs.iter().all(|o| *o == outputs[0]));
+}
+
+#[test]
+fn hash_alg_vectors_reject_tampering_and_hash_swaps() {
+    for alg in HashAlg::ALL {
+        let model_bin = vector(alg, "model.bin");
+        let input_bin = vector(alg, "input.bin");
+        let output_bin = vector(alg, "expected_output.bin");
+        let artifact_bin = vector(alg, "expected_artifact.bin");
+
+        let mut bad_input = input_bin.clone();
+        *bad_input.last_mut().unwrap() ^= 0x01;
+        assert_eq!(
+            verify_lr_v6(&artifact_bin, &model_bin, &bad_input, &output_bin),
+            Err(VerifaiError::HashMismatch)
+        );
+
+        // relabelling the hash algorithm breaks the signature
+        for other in HashAlg::ALL.into_iter().filter(|&o| o != alg) {
+            let mut swapped = ProofArtifactV6::decode_bin(&artifact_bin).unwrap();
+            swapped.hash_alg = other;
+            assert_eq!(
+                verify_lr_v6(&swapped.encode_bin(), &model_bin, &input_bin, &output_bin),
+                Err(VerifaiError::SignatureInvalid)
+            );
+        }
+    }
+}
+
+#[test]
+fn bulk_verifies_v6_artifacts_next_to_sha256_ones() {
+    let model_bin = vector(HashAlg::Sha256, "model.bin");
+    let input_bin = vector(HashAlg::Sha256, "input.bin");
+    let output_bin = vector(HashAlg::Sha256, "expected_output.bin");
+    let v0 = prove_lr_v0([7u8; 32], [9u8; 32], &model_bin, &input_bin)
+        .unwrap()
+        .1;
+    let artifacts: Vec<Vec<u8>> = HashAlg::ALL
+        .into_iter()
+        .map(|alg| vector(alg, "expected_artifact.bin"))
+        .chain([v0])
+        .collect();
+    let records: Vec<BulkRecord<'_>> = artifacts
+        .iter()
+        .map(|artifact_bin| BulkRecord {
+            artifact_bin,
+            input_bin: &input_bin,
+            output_bin: &output_bin,
+        })
+        .collect();
+
+    let results = verify_lr_bulk(&model_bin, &records, &FreshnessPolicy::at(0));
+    assert_eq!(results, vec![Ok(()); 4]);
+}
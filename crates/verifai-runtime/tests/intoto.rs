@@ -0,0 +1,103 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde_json::Value;
+use verifai_core::hash::sha256;
+use verifai_core::model_bin::{InputV0, LogisticModelV0};
+use verifai_runtime::{
+    decode_intoto_envelope, encode_intoto_envelope, prove_lr_v0, prove_lr_v1_with_attester,
+    verify_lr_v0, verify_lr_v1, NoopAttester, VerifaiError, INTOTO_PAYLOAD_TYPE,
+    VERIFAI_PREDICATE_TYPE,
+};
+
+fn bins() -> (Vec<u8>, Vec<u8>) {
+    let model = LogisticModelV0 {
+        weights: vec![0.1, -0.2, 0.3, 0.4],
+        bias: -0.05,
+    };
+    let input = InputV0 {
+        x: vec![1.0, 2.0, 3.0, 4.0],
+    };
+    (model.encode_bin(), input.encode_bin())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn statement(envelope: &[u8]) -> Value {
+    let envelope: Value = serde_json::from_slice(envelope).unwrap();
+    assert_eq!(envelope["payloadType"], INTOTO_PAYLOAD_TYPE);
+    let payload = BASE64
+        .decode(envelope["payload"].as_str().unwrap())
+        .unwrap();
+    serde_json::from_slice(&payload).unwrap()
+}
+
+/// Replaces the payload without re-signing the envelope.
+fn with_statement(envelope: &[u8], statement: &Value) -> Vec<u8> {
+    let mut envelope: Value = serde_json::from_slice(envelope).unwrap();
+    envelope["payload"] = BASE64.encode(serde_json::to_vec(statement).unwrap()).into();
+    serde_json::to_vec(&envelope).unwrap()
+}
+
+#[test]
+fn v1_envelope_roundtrips_and_verifies() {
+    let (model_bin, input_bin) = bins();
+    let (output_bin, artifact_bin) =
+        prove_lr_v1_with_attester::<NoopAttester>([7u8; 32], [9u8; 32], &model_bin, &input_bin)
+            .unwrap();
+
+    let envelope = encode_intoto_envelope(&artifact_bin, [9u8; 32], "output.bin").unwrap();
+    let st = statement(&envelope);
+    assert_eq!(st["predicateType"], VERIFAI_PREDICATE_TYPE);
+    assert_eq!(st["subject"][0]["name"], "output.bin");
+    assert_eq!(
+        st["subject"][0]["digest"]["sha256"],
+        hex(&sha256(&output_bin))
+    );
+    assert_eq!(st["predicate"]["model_hash"], hex(&sha256(&model_bin)));
+    assert_eq!(st["predicate"]["runtime_id"], hex(&[7u8; 32]));
+    assert!(st["predicate"]["attestation"].is_object());
+
+    assert_eq!(decode_intoto_envelope(&envelope).unwrap(), artifact_bin);
+    assert_eq!(
+        verify_lr_v1(&envelope, &model_bin, &input_bin, &output_bin),
+        Ok(())
+    );
+}
+
+#[test]
+fn v0_envelope_roundtrips_and_verifies() {
+    let (model_bin, input_bin) = bins();
+    let (output_bin, artifact_bin) =
+        prove_lr_v0([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap();
+
+    let envelope = encode_intoto_envelope(&artifact_bin, [9u8; 32], "output.bin").unwrap();
+    assert!(statement(&envelope)["predicate"]
+        .get("attestation")
+        .is_none());
+    assert_eq!(decode_intoto_envelope(&envelope).unwrap(), artifact_bin);
+    assert_eq!(
+        verify_lr_v0(&envelope, &model_bin, &input_bin, &output_bin),
+        Ok(())
+    );
+}
+
+#[test]
+fn envelope_rejects_tampering_and_foreign_keys() {
+    let (model_bin, input_bin) = bins();
+    let (_, artifact_bin) = prove_lr_v0([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap();
+
+    assert_eq!(
+        encode_intoto_envelope(&artifact_bin, [8u8; 32], "output.bin"),
+        Err(VerifaiError::SignatureInvalid)
+    );
+
+    let envelope = encode_intoto_envelope(&artifact_bin, [9u8; 32], "output.bin").unwrap();
+    let mut st = statement(&envelope);
+    st["subject"][0]["digest"]["sha256"] = hex(&[0u8; 32]).into();
+    assert_eq!(
+        decode_intoto_envelope(&with_statement(&envelope, &st)),
+        Err(VerifaiError::SignatureInvalid)
+    );
+}
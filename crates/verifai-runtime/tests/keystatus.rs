@@ -0,0 +1,163 @@
+use ed25519_dalek::SigningKey;
+use verifai_core::key_status::{KeyStatusEntry, KeyStatusListV0};
+use verifai_core::model_bin::{InputV0, LogisticModelV0};
+use verifai_runtime::{
+    check_artifact_key_status, check_key_status, prove_lr_v0, prove_lr_v3_with_attester,
+    verify_key_status_list, IssuanceV3, LocalTsa, NoopAttester, SigningTime, VerifaiError,
+};
+
+const SEC: u64 = 1_000_000_000;
+const T0: u64 = 1_700_000_000 * SEC;
+const OLD_KEY: [u8; 32] = [9u8; 32];
+const NEW_KEY: [u8; 32] = [8u8; 32];
+const AUTHORITY: [u8; 32] = [3u8; 32];
+const TSA_KEY: [u8; 32] = [5u8; 32];
+
+fn pubkey(sk: [u8; 32]) -> [u8; 32] {
+    SigningKey::from_bytes(&sk).verifying_key().to_bytes()
+}
+
+fn bins() -> (Vec<u8>, Vec<u8>) {
+    let model = LogisticModelV0 {
+        weights: vec![0.1, -0.2, 0.3, 0.4],
+        bias: -0.05,
+    };
+    let input = InputV0 {
+        x: vec![1.0, 2.0, 3.0, 4.0],
+    };
+    (model.encode_bin(), input.encode_bin())
+}
+
+/// OLD_KEY was valid from T0 and rotated out at T0+100s; NEW_KEY took over.
+fn rotated(old_revoked_at: Option<u64>) -> KeyStatusListV0 {
+    let mut list = KeyStatusListV0 {
+        issued_at: T0 + 200 * SEC,
+        entries: vec![
+            KeyStatusEntry {
+                pubkey: pubkey(OLD_KEY),
+                valid_from: T0,
+                valid_until: Some(T0 + 100 * SEC),
+                revoked_at: old_revoked_at,
+            },
+            KeyStatusEntry {
+                pubkey: pubkey(NEW_KEY),
+                valid_from: T0 + 100 * SEC,
+                valid_until: None,
+                revoked_at: None,
+            },
+        ],
+        authority_pubkey: [0u8; 32],
+        signature: [0u8; 64],
+    };
+    list.sign_detached(AUTHORITY).unwrap();
+    list
+}
+
+fn prove_v3(key: [u8; 32], issued_at: u64, tsa_time: Option<u64>) -> Vec<u8> {
+    let (model_bin, input_bin) = bins();
+    let issuance = IssuanceV3 {
+        issued_at,
+        expires_at: None,
+        nonce: [0u8; 32],
+    };
+    let tsa = tsa_time.map(|t| LocalTsa::with_fixed_time(TSA_KEY, t));
+    prove_lr_v3_with_attester::<NoopAttester>(
+        [7u8; 32],
+        key,
+        &model_bin,
+        &input_bin,
+        &issuance,
+        tsa.as_ref(),
+    )
+    .unwrap()
+    .1
+}
+
+/// Checks `artifact` trusting TSA_KEY, with the verifier's clock at `now`.
+fn check_at(artifact: &[u8], list: &KeyStatusListV0, now: u64) -> Result<(), VerifaiError> {
+    check_artifact_key_status(artifact, list, Some(pubkey(TSA_KEY)), now)
+}
+
+#[test]
+fn list_roundtrips_and_pins_the_authority() {
+    let list = rotated(None);
+    let bin = list.encode_bin();
+    assert_eq!(verify_key_status_list(&bin, pubkey(AUTHORITY)), Ok(list));
+    assert_eq!(
+        verify_key_status_list(&bin, pubkey(NEW_KEY)),
+        Err(VerifaiError::SignatureInvalid)
+    );
+
+    let mut forged = KeyStatusListV0::decode_bin(&bin).unwrap();
+    forged.entries[0].revoked_at = None;
+    forged.entries[0].valid_until = None;
+    assert_eq!(
+        verify_key_status_list(&forged.encode_bin(), pubkey(AUTHORITY)),
+        Err(VerifaiError::SignatureInvalid)
+    );
+}
+
+#[test]
+fn rotation_keeps_old_proofs_and_enforces_windows() {
+    let list = rotated(None);
+
+    let old = prove_v3(OLD_KEY, T0 + 50 * SEC, None);
+    assert_eq!(check_at(&old, &list, T0 + 300 * SEC), Ok(()));
+    let late = prove_v3(OLD_KEY, T0 + 150 * SEC, None);
+    assert_eq!(
+        check_at(&late, &list, T0 + 300 * SEC),
+        Err(VerifaiError::KeyNotValid)
+    );
+    let early = prove_v3(NEW_KEY, T0 + 50 * SEC, None);
+    assert_eq!(
+        check_at(&early, &list, T0 + 300 * SEC),
+        Err(VerifaiError::KeyNotValid)
+    );
+
+    // untimed artifacts are held to the window at the verifier's clock
+    let (model_bin, input_bin) = bins();
+    let (_, v0) = prove_lr_v0([7u8; 32], OLD_KEY, &model_bin, &input_bin).unwrap();
+    assert_eq!(check_at(&v0, &list, T0 + 50 * SEC), Ok(()));
+    assert_eq!(
+        check_at(&v0, &list, T0 + 150 * SEC),
+        Err(VerifaiError::KeyNotValid)
+    );
+    let (_, unknown) = prove_lr_v0([7u8; 32], [6u8; 32], &model_bin, &input_bin).unwrap();
+    assert_eq!(
+        check_at(&unknown, &list, T0 + 50 * SEC),
+        Err(VerifaiError::KeyUnknown)
+    );
+}
+
+#[test]
+fn revocation_needs_a_timestamp_before_it() {
+    let list = rotated(Some(T0 + 60 * SEC));
+
+    let stamped = prove_v3(OLD_KEY, T0 + 50 * SEC, Some(T0 + 51 * SEC));
+    assert_eq!(check_at(&stamped, &list, T0 + 300 * SEC), Ok(()));
+    // a token from a TSA the verifier does not trust is no better than a claim
+    assert_eq!(
+        check_artifact_key_status(&stamped, &list, None, T0 + 300 * SEC),
+        Err(VerifaiError::KeyRevoked)
+    );
+    assert_eq!(
+        check_artifact_key_status(&stamped, &list, Some(pubkey(OLD_KEY)), T0 + 300 * SEC),
+        Err(VerifaiError::KeyRevoked)
+    );
+
+    // a backdated claim alone is not enough once the key has leaked
+    let claimed = prove_v3(OLD_KEY, T0 + 50 * SEC, None);
+    assert_eq!(
+        check_at(&claimed, &list, T0 + 300 * SEC),
+        Err(VerifaiError::KeyRevoked)
+    );
+    let stamped_late = prove_v3(OLD_KEY, T0 + 50 * SEC, Some(T0 + 61 * SEC));
+    assert_eq!(
+        check_at(&stamped_late, &list, T0 + 300 * SEC),
+        Err(VerifaiError::KeyRevoked)
+    );
+    assert_eq!(
+        check_key_status(&list, &pubkey(OLD_KEY), SigningTime::Claimed(T0 + 50 * SEC)),
+        Err(VerifaiError::KeyRevoked)
+    );
+}
@@ -0,0 +1,103 @@
+use ed25519_dalek::SigningKey;
+use verifai_core::artifact_bin::ProofArtifactV4;
+use verifai_core::model_bin::{InputV0, LogisticModelV0};
+use verifai_runtime::{
+    cosign_lr_v4, prove_lr_v4_with_attester, verify_lr_v4, NoopAttester, ThresholdPolicy,
+    VerifaiError,
+};
+
+const PROVER: [u8; 32] = [9u8; 32];
+const AUDITOR: [u8; 32] = [8u8; 32];
+const OUTSIDER: [u8; 32] = [6u8; 32];
+
+fn pubkey(sk: [u8; 32]) -> [u8; 32] {
+    SigningKey::from_bytes(&sk).verifying_key().to_bytes()
+}
+
+fn bins() -> (Vec<u8>, Vec<u8>) {
+    let model = LogisticModelV0 {
+        weights: vec![0.1, -0.2, 0.3, 0.4],
+        bias: -0.05,
+    };
+    let input = InputV0 {
+        x: vec![1.0, 2.0, 3.0, 4.0],
+    };
+    (model.encode_bin(), input.encode_bin())
+}
+
+#[test]
+fn cosigned_v4_meets_k_of_n() {
+    let (model_bin, input_bin) = bins();
+    let (output_bin, artifact_bin) =
+        prove_lr_v4_with_attester::<NoopAttester>([7u8; 32], PROVER, &model_bin, &input_bin)
+            .unwrap();
+    let trusted = vec![pubkey(PROVER), pubkey(AUDITOR)];
+    let check = |artifact: &[u8], policy: &ThresholdPolicy| {
+        verify_lr_v4(artifact, &model_bin, &input_bin, &output_bin, policy)
+    };
+
+    assert_eq!(
+        check(&artifact_bin, &ThresholdPolicy::any_signer()),
+        Ok(vec![pubkey(PROVER)])
+    );
+    assert_eq!(
+        check(&artifact_bin, &ThresholdPolicy::k_of_n(2, trusted.clone())),
+        Err(VerifaiError::ThresholdNotMet)
+    );
+
+    let cosigned =
+        cosign_lr_v4(&artifact_bin, &model_bin, &input_bin, &output_bin, AUDITOR).unwrap();
+    assert_eq!(
+        check(&cosigned, &ThresholdPolicy::k_of_n(2, trusted.clone())),
+        Ok(vec![pubkey(PROVER), pubkey(AUDITOR)])
+    );
+
+    // an untrusted co-signer does not count, and signing twice adds nothing
+    let outsider =
+        cosign_lr_v4(&artifact_bin, &model_bin, &input_bin, &output_bin, OUTSIDER).unwrap();
+    assert_eq!(
+        check(&outsider, &ThresholdPolicy::k_of_n(2, trusted.clone())),
+        Err(VerifaiError::ThresholdNotMet)
+    );
+    let again = cosign_lr_v4(&cosigned, &model_bin, &input_bin, &output_bin, AUDITOR).unwrap();
+    assert_eq!(again, cosigned);
+}
+
+#[test]
+fn cosign_refuses_a_replay_that_does_not_match() {
+    let (model_bin, input_bin) = bins();
+    let (output_bin, artifact_bin) =
+        prove_lr_v4_with_attester::<NoopAttester>([7u8; 32], PROVER, &model_bin, &input_bin)
+            .unwrap();
+
+    let other_input = InputV0 {
+        x: vec![4.0, 3.0, 2.0, 1.0],
+    }
+    .encode_bin();
+    assert_eq!(
+        cosign_lr_v4(
+            &artifact_bin,
+            &model_bin,
+            &other_input,
+            &output_bin,
+            AUDITOR
+        ),
+        Err(VerifaiError::HashMismatch)
+    );
+
+    // one bad co-signature invalidates the artifact
+    let cosigned =
+        cosign_lr_v4(&artifact_bin, &model_bin, &input_bin, &output_bin, AUDITOR).unwrap();
+    let mut artifact = ProofArtifactV4::decode_bin(&cosigned).unwrap();
+    artifact.signatures[1].signature[0] ^= 0xFF;
+    assert_eq!(
+        verify_lr_v4(
+            &artifact.encode_bin(),
+            &model_bin,
+            &input_bin,
+            &output_bin,
+            &ThresholdPolicy::any_signer()
+        ),
+        Err(VerifaiError::SignatureInvalid)
+    );
+}
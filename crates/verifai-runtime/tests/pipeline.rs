@@ -0,0 +1,196 @@
+use verifai_core::hash::HashAlg;
+use verifai_core::model_bin::{InputV0, LogisticModelV0, MlpModelV1};
+use verifai_core::pipeline::{PipelineProofV0, PipelineStageV0, StageLink};
+use verifai_core::sig_alg::{AlgSigningKey, SigAlg};
+use verifai_runtime::{
+    prove_lr_v0, prove_lr_v6_with_attester, prove_mlp_v1, sign_pipeline_v0,
+    verify_pipeline_replay_v0, verify_pipeline_v0, FreshnessPolicy, NoopAttester, StageModel,
+    VerifaiError,
+};
+
+const OPERATOR_KEY: [u8; 32] = [5u8; 32];
+
+struct Fixture {
+    embed_bin: Vec<u8>,
+    score_bin: Vec<u8>,
+    input_bin: Vec<u8>,
+    stages: Vec<PipelineStageV0>,
+}
+
+/// An MLP stage feeding an LR stage; the second stage is a BLAKE3 v6
+/// artifact to show stages need not share a format.
+fn fixture() -> Fixture {
+    let embed = MlpModelV1 {
+        input_dim: 4,
+        hidden_size: 2,
+        w1: vec![0.1, -0.2, 0.3, 0.4, -0.1, 0.5, 0.2, -0.3],
+        b1: vec![0.0, -0.1],
+        w2: vec![0.2, -0.4],
+        b2: 0.05,
+    };
+    let score = LogisticModelV0 {
+        weights: vec![2.0],
+        bias: -1.0,
+    };
+    let input = InputV0 {
+        x: vec![1.0, 0.5, -0.5, 0.25],
+    };
+    let (embed_bin, score_bin, input_bin) =
+        (embed.encode_bin(), score.encode_bin(), input.encode_bin());
+
+    let (embed_out, embed_artifact) =
+        prove_mlp_v1([7u8; 32], [9u8; 32], &embed_bin, &input_bin).unwrap();
+    let score_input = StageLink::OutputAsInput.apply(&embed_out).unwrap();
+    let key = AlgSigningKey::new(SigAlg::Ed25519, [9u8; 32]).unwrap();
+    let (score_out, score_artifact) = prove_lr_v6_with_attester::<NoopAttester>(
+        [7u8; 32],
+        &key,
+        HashAlg::Blake3,
+        &score_bin,
+        &score_input,
+    )
+    .unwrap();
+
+    Fixture {
+        embed_bin,
+        score_bin,
+        input_bin,
+        stages: vec![
+            PipelineStageV0 {
+                link: None,
+                artifact: embed_artifact,
+                output: embed_out,
+            },
+            PipelineStageV0 {
+                link: Some(StageLink::OutputAsInput),
+                artifact: score_artifact,
+                output: score_out,
+            },
+        ],
+    }
+}
+
+fn policy() -> FreshnessPolicy {
+    FreshnessPolicy::at(0)
+}
+
+#[test]
+fn pipeline_verifies_and_replays_end_to_end() {
+    let f = fixture();
+    let pipeline = sign_pipeline_v0(f.stages, OPERATOR_KEY, &policy()).unwrap();
+    let decoded = PipelineProofV0::decode_bin(&pipeline.encode_bin().unwrap()).unwrap();
+    assert_eq!(decoded, pipeline);
+
+    assert_eq!(verify_pipeline_v0(&decoded, &policy()), Ok(()));
+    let models = [StageModel::Mlp(&f.embed_bin), StageModel::Lr(&f.score_bin)];
+    assert_eq!(
+        verify_pipeline_replay_v0(&decoded, &models, &f.input_bin, &policy()),
+        Ok(())
+    );
+}
+
+#[test]
+fn broken_links_are_rejected() {
+    let f = fixture();
+    let (other_out, other_artifact) = prove_lr_v0(
+        [7u8; 32],
+        [9u8; 32],
+        &f.score_bin,
+        &InputV0 { x: vec![0.5] }.encode_bin(),
+    )
+    .unwrap();
+    let mut stages = f.stages;
+    stages[1] = PipelineStageV0 {
+        link: Some(StageLink::OutputAsInput),
+        artifact: other_artifact,
+        output: other_out,
+    };
+    assert_eq!(
+        sign_pipeline_v0(stages.clone(), OPERATOR_KEY, &policy()),
+        Err(VerifaiError::PipelineLinkBroken)
+    );
+
+    // signed anyway, bypassing sign_pipeline_v0
+    let mut pipeline = PipelineProofV0 {
+        stages,
+        sig_pubkey: [0u8; 32],
+        signature: [0u8; 64],
+    };
+    pipeline.sign_detached(OPERATOR_KEY).unwrap();
+    assert_eq!(
+        verify_pipeline_v0(&pipeline, &policy()),
+        Err(VerifaiError::PipelineLinkBroken)
+    );
+}
+
+#[test]
+fn tampered_pipelines_are_rejected() {
+    let f = fixture();
+    let pipeline = sign_pipeline_v0(f.stages, OPERATOR_KEY, &policy()).unwrap();
+
+    let mut bad_sig = pipeline.clone();
+    bad_sig.signature[0] ^= 0x01;
+    assert_eq!(
+        verify_pipeline_v0(&bad_sig, &policy()),
+        Err(VerifaiError::SignatureInvalid)
+    );
+
+    // swapping in another output breaks the stage hash before the link
+    let mut swapped = pipeline.clone();
+    swapped.stages[0].output = swapped.stages[1].output.clone();
+    swapped.sign_detached(OPERATOR_KEY).unwrap();
+    assert_eq!(
+        verify_pipeline_v0(&swapped, &policy()),
+        Err(VerifaiError::HashMismatch)
+    );
+
+    let models = [StageModel::Mlp(&f.embed_bin), StageModel::Lr(&f.score_bin)];
+    let other_input = InputV0 {
+        x: vec![1.0, 0.5, -0.5, 0.5],
+    }
+    .encode_bin();
+    assert_eq!(
+        verify_pipeline_replay_v0(&pipeline, &models, &other_input, &policy()),
+        Err(VerifaiError::HashMismatch)
+    );
+    assert_eq!(
+        verify_pipeline_replay_v0(&pipeline, &models[..1], &f.input_bin, &policy()),
+        Err(VerifaiError::DimensionMismatch)
+    );
+    let swapped_models = [StageModel::Mlp(&f.embed_bin), StageModel::Lr(&f.embed_bin)];
+    assert_eq!(
+        verify_pipeline_replay_v0(&pipeline, &swapped_models, &f.input_bin, &policy()),
+        Err(VerifaiError::HashMismatch)
+    );
+}
+
+#[test]
+fn pipeline_layout_rejects_misplaced_links() {
+    let f = fixture();
+    let pipeline = sign_pipeline_v0(f.stages, OPERATOR_KEY, &policy()).unwrap();
+    let encoded = pipeline.encode_bin().unwrap();
+    assert_eq!(&encoded[0..8], b"VFAIPPL0");
+    assert_eq!(&encoded[8..10], &2u16.to_le_bytes());
+    assert_eq!(encoded[10], 0);
+
+    let mut first_linked = encoded.clone();
+    first_linked[10] = StageLink::OutputAsInput.id();
+    assert!(PipelineProofV0::decode_bin(&first_linked).is_err());
+    let second_link = 10 + 1 + 4 + pipeline.stages[0].artifact.len() + 2 + 16;
+    assert_eq!(encoded[second_link], 1);
+    let mut unknown_link = encoded.clone();
+    unknown_link[second_link] = 9;
+    assert!(PipelineProofV0::decode_bin(&unknown_link).is_err());
+    let mut trailing = encoded;
+    trailing.push(0);
+    assert!(PipelineProofV0::decode_bin(&trailing).is_err());
+
+    // lengths that overflow their fields are refused rather than truncated
+    let mut long_output = pipeline.clone();
+    long_output.stages[1].output = vec![0u8; usize::from(u16::MAX) + 1];
+    assert!(long_output.encode_bin().is_err());
+    assert!(long_output.sign_detached(OPERATOR_KEY).is_err());
+    let mut many_stages = pipeline;
+    many_stages.stages = vec![many_stages.stages[1].clone(); usize::from(u16::MAX) + 1];
+    assert!(many_stages.encode_bin().is_err());
+}
@@ -0,0 +1,175 @@
+use verifai_core::artifact_bin::ProofArtifactV8;
+use verifai_core::hash::sha256;
+use verifai_core::model_bin::{InputV0, LogisticModelV0, MlpModelV1};
+use verifai_core::preprocess::{FeatureSpec, PreprocessorV1, RawInputV1, RawValue};
+use verifai_runtime::{
+    preprocess_v1, prove_lr_v0, prove_lr_v8_with_attester, prove_mlp_v8, verify_lr_v8,
+    verify_mlp_v8, NoopAttester, VerifaiError,
+};
+
+fn model_bin() -> Vec<u8> {
+    LogisticModelV0 {
+        weights: vec![0.1, -0.2, 0.3, 0.4],
+        bias: -0.05,
+    }
+    .encode_bin()
+}
+
+fn preprocessor() -> PreprocessorV1 {
+    PreprocessorV1 {
+        raw_len: 3,
+        features: vec![
+            FeatureSpec::Numeric {
+                source: 0,
+                default: 1.0,
+                clip: Some((-5.0, 5.0)),
+                mean: 1.0,
+                std: 2.0,
+            },
+            FeatureSpec::Categorical {
+                source: 2,
+                categories: vec!["red".into(), "green".into(), "blue".into()],
+                default: None,
+            },
+        ],
+    }
+}
+
+fn raw(first: RawValue, third: RawValue) -> Vec<u8> {
+    RawInputV1 {
+        values: vec![first, RawValue::Missing, third],
+    }
+    .encode_bin()
+}
+
+fn features(first: RawValue, third: RawValue) -> Vec<f64> {
+    let input_bin = preprocess_v1(&preprocessor().encode_bin(), &raw(first, third)).unwrap();
+    InputV0::decode_bin(&input_bin).unwrap().x
+}
+
+#[test]
+fn preprocessing_imputes_clips_standardizes_and_one_hot_encodes() {
+    let green = || RawValue::Category("green".into());
+    assert_eq!(
+        features(RawValue::Number(3.0), green()),
+        vec![1.0, 0.0, 1.0, 0.0]
+    );
+    assert_eq!(
+        features(RawValue::Missing, green())[0],
+        0.0,
+        "missing takes the default"
+    );
+    assert_eq!(features(RawValue::Number(f64::NAN), green())[0], 0.0);
+    assert_eq!(features(RawValue::Number(100.0), green())[0], 2.0);
+    assert_eq!(
+        features(RawValue::Number(3.0), RawValue::Category("mauve".into())),
+        vec![1.0, 0.0, 0.0, 0.0]
+    );
+
+    let pre = preprocessor().encode_bin();
+    assert_eq!(
+        preprocess_v1(&pre, &raw(green(), green())),
+        Err(VerifaiError::RawInputInvalid)
+    );
+    assert_eq!(
+        preprocess_v1(&pre, &raw(RawValue::Number(3.0), RawValue::Number(1.0))),
+        Err(VerifaiError::RawInputInvalid)
+    );
+    let short = RawInputV1 {
+        values: vec![RawValue::Number(3.0)],
+    }
+    .encode_bin();
+    assert_eq!(
+        preprocess_v1(&pre, &short),
+        Err(VerifaiError::DimensionMismatch)
+    );
+}
+
+#[test]
+fn v8_artifact_covers_raw_input_to_score() {
+    let (model_bin, pre) = (model_bin(), preprocessor().encode_bin());
+    let raw_bin = raw(RawValue::Number(3.0), RawValue::Category("green".into()));
+    let (output_bin, artifact_bin) =
+        prove_lr_v8_with_attester::<NoopAttester>([7u8; 32], [9u8; 32], &pre, &model_bin, &raw_bin)
+            .unwrap();
+
+    // the score is the model's score on the preprocessed features
+    let input_bin = preprocess_v1(&pre, &raw_bin).unwrap();
+    let (plain_output, _) = prove_lr_v0([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap();
+    assert_eq!(output_bin, plain_output);
+
+    let artifact = verify_lr_v8(&artifact_bin, &pre, &model_bin, &raw_bin, &output_bin).unwrap();
+    assert_eq!(artifact.preprocessor_hash, sha256(&pre));
+    assert_eq!(artifact.input_hash, sha256(&raw_bin));
+    assert_eq!(artifact.attestation.measurement, artifact.trace_root);
+
+    let other_raw = raw(RawValue::Number(3.0), RawValue::Category("blue".into()));
+    assert_eq!(
+        verify_lr_v8(&artifact_bin, &pre, &model_bin, &other_raw, &output_bin),
+        Err(VerifaiError::HashMismatch)
+    );
+    let mut other_pre = preprocessor();
+    other_pre.features.swap(0, 1);
+    assert_eq!(
+        verify_lr_v8(
+            &artifact_bin,
+            &other_pre.encode_bin(),
+            &model_bin,
+            &raw_bin,
+            &output_bin
+        ),
+        Err(VerifaiError::HashMismatch)
+    );
+
+    let mut bad_sig = ProofArtifactV8::decode_bin(&artifact_bin).unwrap();
+    bad_sig.preprocessor_hash[0] ^= 0x01;
+    assert_eq!(
+        verify_lr_v8(
+            &bad_sig.encode_bin(),
+            &pre,
+            &model_bin,
+            &raw_bin,
+            &output_bin
+        ),
+        Err(VerifaiError::SignatureInvalid)
+    );
+}
+
+#[test]
+fn v8_rejects_preprocessor_model_dimension_mismatch() {
+    let mut pre = preprocessor();
+    pre.features.pop();
+    let raw_bin = raw(RawValue::Number(3.0), RawValue::Missing);
+    assert_eq!(
+        prove_lr_v8_with_attester::<NoopAttester>(
+            [7u8; 32],
+            [9u8; 32],
+            &pre.encode_bin(),
+            &model_bin(),
+            &raw_bin
+        ),
+        Err(VerifaiError::DimensionMismatch)
+    );
+}
+
+#[test]
+fn mlp_v8_roundtrip() {
+    let model_bin = MlpModelV1 {
+        input_dim: 4,
+        hidden_size: 2,
+        w1: vec![0.1, -0.2, 0.3, 0.4, -0.1, 0.5, 0.2, -0.3],
+        b1: vec![0.0, -0.1],
+        w2: vec![0.2, -0.4],
+        b2: 0.05,
+    }
+    .encode_bin();
+    let pre = preprocessor().encode_bin();
+    let raw_bin = raw(RawValue::Missing, RawValue::Category("red".into()));
+    let (output_bin, artifact_bin) =
+        prove_mlp_v8([7u8; 32], [9u8; 32], &pre, &model_bin, &raw_bin).unwrap();
+    verify_mlp_v8(&artifact_bin, &pre, &model_bin, &raw_bin, &output_bin).unwrap();
+    assert_eq!(
+        verify_lr_v8(&artifact_bin, &pre, &model_bin, &raw_bin, &output_bin),
+        Err(VerifaiError::CoreDecode)
+    );
+}
@@ -0,0 +1,147 @@
+use verifai_core::artifact_bin::ProofArtifactV7;
+use verifai_core::hash::sha256;
+use verifai_core::model_bin::{InputV0, LogisticModelV0};
+use verifai_core::salts::CommitmentSaltsV0;
+use verifai_runtime::{
+    prove_lr_v0, prove_lr_v7_with_attester, random_salts, verify_lr_bulk, verify_lr_v7, BulkRecord,
+    FreshnessPolicy, NoopAttester, VerifaiError,
+};
+
+fn model_bin() -> Vec<u8> {
+    LogisticModelV0 {
+        weights: vec![0.1, -0.2, 0.3, 0.4],
+        bias: -0.05,
+    }
+    .encode_bin()
+}
+
+fn input_bin() -> Vec<u8> {
+    InputV0 {
+        x: vec![1.0, 2.0, 3.0, 4.0],
+    }
+    .encode_bin()
+}
+
+fn salts(seed: u8) -> CommitmentSaltsV0 {
+    CommitmentSaltsV0 {
+        model: [seed; 32],
+        input: [seed.wrapping_add(1); 32],
+        output: [seed.wrapping_add(2); 32],
+        trace: [seed.wrapping_add(3); 32],
+    }
+}
+
+#[test]
+fn salted_artifact_verifies_only_with_its_salts() {
+    let (model_bin, input_bin) = (model_bin(), input_bin());
+    let (output_bin, artifact_bin) = prove_lr_v7_with_attester::<NoopAttester>(
+        [7u8; 32],
+        [9u8; 32],
+        &model_bin,
+        &input_bin,
+        &salts(1),
+    )
+    .unwrap();
+
+    let artifact = verify_lr_v7(
+        &artifact_bin,
+        &model_bin,
+        &input_bin,
+        &output_bin,
+        &salts(1),
+    )
+    .unwrap();
+    assert_eq!(artifact.attestation.measurement, artifact.trace_commitment);
+    assert_eq!(
+        verify_lr_v7(
+            &artifact_bin,
+            &model_bin,
+            &input_bin,
+            &output_bin,
+            &salts(2)
+        ),
+        Err(VerifaiError::HashMismatch)
+    );
+
+    // only the trace salt is wrong: the commitments open but the replay does not
+    let mut wrong_trace = salts(1);
+    wrong_trace.trace[0] ^= 0x01;
+    assert_eq!(
+        verify_lr_v7(
+            &artifact_bin,
+            &model_bin,
+            &input_bin,
+            &output_bin,
+            &wrong_trace
+        ),
+        Err(VerifaiError::TraceMismatch)
+    );
+
+    let mut bad_sig = ProofArtifactV7::decode_bin(&artifact_bin).unwrap();
+    bad_sig.signature[0] ^= 0x01;
+    assert_eq!(
+        verify_lr_v7(
+            &bad_sig.encode_bin(),
+            &model_bin,
+            &input_bin,
+            &output_bin,
+            &salts(1)
+        ),
+        Err(VerifaiError::SignatureInvalid)
+    );
+}
+
+#[test]
+fn salted_commitments_hide_the_plain_hashes() {
+    let (model_bin, input_bin) = (model_bin(), input_bin());
+    let (_, plain) = prove_lr_v0([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap();
+    let (_, salted_a) = prove_lr_v7_with_attester::<NoopAttester>(
+        [7u8; 32],
+        [9u8; 32],
+        &model_bin,
+        &input_bin,
+        &salts(1),
+    )
+    .unwrap();
+    let (_, salted_b) = prove_lr_v7_with_attester::<NoopAttester>(
+        [7u8; 32],
+        [9u8; 32],
+        &model_bin,
+        &input_bin,
+        &salts(5),
+    )
+    .unwrap();
+    let a = ProofArtifactV7::decode_bin(&salted_a).unwrap();
+    let b = ProofArtifactV7::decode_bin(&salted_b).unwrap();
+
+    // the signature still checks without any salt
+    a.verify_signature().unwrap();
+
+    assert_ne!(a.input_commitment, sha256(&input_bin));
+    assert_ne!(a.input_commitment, b.input_commitment);
+    assert_ne!(a.trace_commitment, b.trace_commitment);
+    // the plain trace root of the same run appears nowhere in the artifact
+    let plain_trace_root = &plain[130..162];
+    assert!(!salted_a.windows(32).any(|w| w == plain_trace_root));
+}
+
+#[test]
+fn random_salts_are_fresh_and_bulk_rejects_v7() {
+    let (x, y) = (random_salts().unwrap(), random_salts().unwrap());
+    assert_ne!(x, y);
+    assert_ne!(x.model, x.input);
+
+    let (model_bin, input_bin) = (model_bin(), input_bin());
+    let (output_bin, artifact_bin) =
+        prove_lr_v7_with_attester::<NoopAttester>([7u8; 32], [9u8; 32], &model_bin, &input_bin, &x)
+            .unwrap();
+    let record = BulkRecord {
+        artifact_bin: &artifact_bin,
+        input_bin: &input_bin,
+        output_bin: &output_bin,
+    };
+    assert_eq!(
+        verify_lr_bulk(&model_bin, &[record], &FreshnessPolicy::at(0)),
+        vec![Err(VerifaiError::CoreDecode)]
+    );
+}
@@ -0,0 +1,72 @@
+use std::fs;
+use std::path::PathBuf;
+
+use verifai_core::artifact_bin::ProofArtifactV5;
+use verifai_core::sig_alg::{verify_alg, AlgSigningKey, SigAlg};
+use verifai_runtime::{prove_lr_v5_with_attester, verify_lr_v5, NoopAttester, VerifaiError};
+
+fn vector(alg: SigAlg, file: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors")
+        .join(format!("sigalg-{}", alg.name()))
+        .join(file);
+    fs::read(path).unwrap()
+}
+
+#[test]
+fn sig_alg_vectors_verify_and_regenerate() {
+    for alg in SigAlg::ALL {
+        let model_bin = vector(alg, "model.bin");
+        let input_bin = vector(alg, "input.bin");
+        let output_bin = vector(alg, "expected_output.bin");
+        let artifact_bin = vector(alg, "expected_artifact.bin");
+
+        let artifact = verify_lr_v5(&artifact_bin, &model_bin, &input_bin, &output_bin).unwrap();
+        assert_eq!(artifact.sig_alg, alg);
+
+        // deterministic signatures (Ed25519, RFC 6979 ECDSA) reproduce the vector
+        let key = AlgSigningKey::new(alg, [9u8; 32]).unwrap();
+        assert_eq!(artifact.sig_pubkey, key.public_key());
+        let (output, proved) =
+            prove_lr_v5_with_attester::<NoopAttester>([7u8; 32], &key, &model_bin, &input_bin)
+                .unwrap();
+        assert_eq!(output, output_bin);
+        assert_eq!(proved, artifact_bin);
+    }
+}
+
+#[test]
+fn sig_alg_vectors_reject_tampering_and_algorithm_swaps() {
+    for alg in SigAlg::ALL {
+        let model_bin = vector(alg, "model.bin");
+        let input_bin = vector(alg, "input.bin");
+        let output_bin = vector(alg, "expected_output.bin");
+        let artifact_bin = vector(alg, "expected_artifact.bin");
+        let artifact = ProofArtifactV5::decode_bin(&artifact_bin).unwrap();
+
+        let mut bad_sig = artifact.clone();
+        bad_sig.signature[0] ^= 0x01;
+        assert_eq!(
+            verify_lr_v5(&bad_sig.encode_bin(), &model_bin, &input_bin, &output_bin),
+            Err(VerifaiError::SignatureInvalid)
+        );
+
+        let mut bad_output = output_bin.clone();
+        *bad_output.last_mut().unwrap() ^= 0x01;
+        assert_eq!(
+            verify_lr_v5(&artifact_bin, &model_bin, &input_bin, &bad_output),
+            Err(VerifaiError::HashMismatch)
+        );
+
+        // the signature only verifies under the algorithm it was made with
+        for other in SigAlg::ALL.into_iter().filter(|&o| o != alg) {
+            assert!(verify_alg(
+                other,
+                &artifact.sig_pubkey,
+                &artifact.message_to_sign(),
+                &artifact.signature
+            )
+            .is_err());
+        }
+    }
+}
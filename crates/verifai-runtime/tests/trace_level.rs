@@ -0,0 +1,189 @@
+use verifai_core::artifact_bin::ProofArtifactV9;
+use verifai_core::event_bin::{TraceEventV0, TraceLevel};
+use verifai_core::merkle::{trace_root_from_event_bytes, MerkleBuilder};
+use verifai_core::model_bin::{InputV0, LogisticModelV0, MlpModelV1};
+use verifai_runtime::{
+    lr_trace, lr_trace_at, mlp_trace_at, mlp_trace_into, prove_lr_v9_with_attester, prove_mlp_v9,
+    verify_lr_v9, verify_mlp_v9, NoopAttester, VerifaiError,
+};
+
+fn model_bin() -> Vec<u8> {
+    LogisticModelV0 {
+        weights: vec![0.1, -0.2, 0.3, 0.4],
+        bias: -0.05,
+    }
+    .encode_bin()
+}
+
+fn mlp_model_bin() -> Vec<u8> {
+    MlpModelV1 {
+        input_dim: 4,
+        hidden_size: 2,
+        w1: vec![0.1, -0.2, 0.3, 0.4, -0.1, 0.5, 0.2, -0.3],
+        b1: vec![0.0, -0.1],
+        w2: vec![0.2, -0.4],
+        b2: 0.05,
+    }
+    .encode_bin()
+}
+
+fn input_bin(x: Vec<f64>) -> Vec<u8> {
+    InputV0 { x }.encode_bin()
+}
+
+#[test]
+fn every_level_proves_and_verifies_at_its_declared_level() {
+    let (model_bin, input_bin) = (model_bin(), input_bin(vec![1.0, 2.0, 3.0, 4.0]));
+    let mut roots = Vec::new();
+    for level in TraceLevel::ALL {
+        let (output_bin, artifact_bin) = prove_lr_v9_with_attester::<NoopAttester>(
+            [7u8; 32], [9u8; 32], level, &model_bin, &input_bin,
+        )
+        .unwrap();
+        let artifact = verify_lr_v9(&artifact_bin, &model_bin, &input_bin, &output_bin).unwrap();
+        assert_eq!(artifact.trace_level, level);
+        assert_eq!(artifact.attestation.measurement, artifact.trace_root);
+        roots.push(artifact.trace_root);
+    }
+    roots.dedup();
+    assert_eq!(roots.len(), TraceLevel::ALL.len());
+}
+
+#[test]
+fn levels_record_what_they_declare() {
+    let (model_bin, input_bin) = (model_bin(), input_bin(vec![1.0, 2.0, 3.0, 4.0]));
+    let events = |level| lr_trace_at(level, &model_bin, &input_bin).unwrap().1;
+
+    let (output, summary) = lr_trace_at(TraceLevel::Summary, &model_bin, &input_bin).unwrap();
+    assert_eq!(summary, vec![TraceEventV0::OpOutput { y: output.y }]);
+    assert_eq!(
+        events(TraceLevel::Op),
+        lr_trace(&model_bin, &input_bin).unwrap()
+    );
+    assert!(matches!(
+        events(TraceLevel::Layer)[..],
+        [
+            TraceEventV0::OpLayer { layer: 0, .. },
+            TraceEventV0::OpOutput { .. }
+        ]
+    ));
+
+    // four MACs, the last one summing to the linear op's z bit for bit
+    let fine = events(TraceLevel::Fine);
+    assert_eq!(fine.len(), 4 + 3);
+    let (TraceEventV0::OpMac { index: 3, acc, .. }, TraceEventV0::OpLinear { z, .. }) =
+        (&fine[3], &fine[4])
+    else {
+        panic!("unexpected fine trace: {fine:?}");
+    };
+    assert_eq!(acc.to_bits(), z.to_bits());
+    assert_eq!(fine[4..], events(TraceLevel::Op)[..]);
+}
+
+#[test]
+fn mlp_levels_cover_both_layers() {
+    let (model_bin, input_bin) = (mlp_model_bin(), input_bin(vec![1.0, 0.5, -0.5, 0.25]));
+    let events = |level| mlp_trace_at(level, &model_bin, &input_bin).unwrap().1;
+    let op = events(TraceLevel::Op);
+    assert_eq!(events(TraceLevel::Layer).len(), 3);
+    // 2 hidden units × 4 inputs, plus 2 output terms
+    assert_eq!(events(TraceLevel::Fine).len(), op.len() + 2 * 4 + 2);
+
+    for level in TraceLevel::ALL {
+        let (output_bin, artifact_bin) =
+            prove_mlp_v9([7u8; 32], [9u8; 32], level, &model_bin, &input_bin).unwrap();
+        verify_mlp_v9(&artifact_bin, &model_bin, &input_bin, &output_bin).unwrap();
+    }
+}
+
+#[test]
+fn streamed_trace_root_matches_the_recorded_trace() {
+    let (model_bin, input_bin) = (mlp_model_bin(), input_bin(vec![1.0, 0.5, -0.5, 0.25]));
+    for level in TraceLevel::ALL {
+        let (_, events) = mlp_trace_at(level, &model_bin, &input_bin).unwrap();
+        let event_bytes: Vec<Vec<u8>> = events.iter().map(|e| e.encode_bin()).collect();
+
+        let mut tree = MerkleBuilder::new();
+        mlp_trace_into(level, &model_bin, &input_bin, &mut tree).unwrap();
+        assert_eq!(tree.len(), events.len() as u64);
+        let root = tree.finalize();
+        assert_eq!(root, trace_root_from_event_bytes(&event_bytes));
+
+        let (output_bin, artifact_bin) =
+            prove_mlp_v9([7u8; 32], [9u8; 32], level, &model_bin, &input_bin).unwrap();
+        let artifact = verify_mlp_v9(&artifact_bin, &model_bin, &input_bin, &output_bin).unwrap();
+        assert_eq!(artifact.trace_root, root);
+    }
+}
+
+#[test]
+fn fine_mlp_macs_follow_op_position_past_200_hidden_units() {
+    // hidden op 200 and the output op share an op id
+    let hidden_size = 201;
+    let model_bin = MlpModelV1 {
+        input_dim: 2,
+        hidden_size: hidden_size as u32,
+        w1: (0..2 * hidden_size)
+            .map(|i| (i % 7) as f64 * 0.1 - 0.3)
+            .collect(),
+        b1: (0..hidden_size).map(|h| (h % 3) as f64 * 0.05).collect(),
+        w2: (0..hidden_size)
+            .map(|h| (h % 5) as f64 * 0.02 - 0.04)
+            .collect(),
+        b2: 0.05,
+    }
+    .encode_bin();
+    let input_bin = input_bin(vec![1.0, -0.5]);
+
+    let (_, op) = mlp_trace_at(TraceLevel::Op, &model_bin, &input_bin).unwrap();
+    let (_, fine) = mlp_trace_at(TraceLevel::Fine, &model_bin, &input_bin).unwrap();
+    assert_eq!(fine.len(), op.len() + hidden_size * 2 + hidden_size);
+
+    let mut macs = Vec::new();
+    for event in &fine {
+        match *event {
+            TraceEventV0::OpMac { acc, .. } => macs.push(acc),
+            TraceEventV0::OpLinear { z, .. } => {
+                assert_eq!(macs.last().map(|acc| acc.to_bits()), Some(z.to_bits()));
+                macs.clear();
+            }
+            _ => {}
+        }
+    }
+
+    let (output_bin, artifact_bin) = prove_mlp_v9(
+        [7u8; 32],
+        [9u8; 32],
+        TraceLevel::Fine,
+        &model_bin,
+        &input_bin,
+    )
+    .unwrap();
+    verify_mlp_v9(&artifact_bin, &model_bin, &input_bin, &output_bin).unwrap();
+}
+
+#[test]
+fn declared_level_is_signed_and_replayed() {
+    let (model_bin, input_bin) = (model_bin(), input_bin(vec![1.0, 2.0, 3.0, 4.0]));
+    let (output_bin, artifact_bin) = prove_lr_v9_with_attester::<NoopAttester>(
+        [7u8; 32],
+        [9u8; 32],
+        TraceLevel::Fine,
+        &model_bin,
+        &input_bin,
+    )
+    .unwrap();
+
+    let mut relabeled = ProofArtifactV9::decode_bin(&artifact_bin).unwrap();
+    relabeled.trace_level = TraceLevel::Summary;
+    assert_eq!(
+        verify_lr_v9(&relabeled.encode_bin(), &model_bin, &input_bin, &output_bin),
+        Err(VerifaiError::SignatureInvalid)
+    );
+    // re-signed, the fine trace root no longer matches a summary replay
+    relabeled.sign_detached([9u8; 32]).unwrap();
+    assert_eq!(
+        verify_lr_v9(&relabeled.encode_bin(), &model_bin, &input_bin, &output_bin),
+        Err(VerifaiError::TraceMismatch)
+    );
+}
@@ -0,0 +1,225 @@
+use verifai_core::artifact_bin::ProofArtifactV1;
+use verifai_core::event_bin::{TraceEventV0, TraceV0};
+use verifai_core::hash::HashAlg;
+use verifai_core::model_bin::{InputV0, LogisticModelV0, MlpModelV1};
+use verifai_core::salts::CommitmentSaltsV0;
+use verifai_core::trace_sink::{Tee, TraceFileWriter};
+use verifai_runtime::{
+    lr_trace, mlp_trace, prove_lr_v0, prove_lr_v0_with_sink, prove_lr_v1_with_attester,
+    prove_lr_v1_with_sink, prove_lr_v3_with_attester, prove_lr_v3_with_sink,
+    prove_lr_v4_with_attester, prove_lr_v4_with_sink, prove_lr_v5_with_attester,
+    prove_lr_v5_with_sink, prove_lr_v6_with_attester, prove_lr_v6_with_sink,
+    prove_lr_v7_with_attester, prove_lr_v7_with_sink, prove_mlp_v1, prove_mlp_v1_with_sink,
+    prove_mlp_v3, prove_mlp_v3_with_sink, prove_mlp_v4, prove_mlp_v4_with_sink, prove_mlp_v5,
+    prove_mlp_v5_with_sink, prove_mlp_v6, prove_mlp_v6_with_sink, prove_mlp_v7,
+    prove_mlp_v7_with_sink, run_lr_v0_into, IssuanceV3, NoopAttester,
+};
+
+fn lr_fixture() -> (Vec<u8>, Vec<u8>) {
+    let model = LogisticModelV0 {
+        weights: vec![0.1, -0.2, 0.3, 0.4],
+        bias: -0.05,
+    };
+    let input = InputV0 {
+        x: vec![1.0, 2.0, 3.0, 4.0],
+    };
+    (model.encode_bin(), input.encode_bin())
+}
+
+fn mlp_fixture() -> (Vec<u8>, Vec<u8>) {
+    let model = MlpModelV1 {
+        input_dim: 4,
+        hidden_size: 2,
+        w1: vec![0.1, -0.2, 0.3, 0.4, -0.1, 0.5, 0.2, -0.3],
+        b1: vec![0.0, -0.1],
+        w2: vec![0.2, -0.4],
+        b2: 0.05,
+    };
+    let input = InputV0 {
+        x: vec![1.0, 0.5, -0.5, 0.25],
+    };
+    (model.encode_bin(), input.encode_bin())
+}
+
+#[test]
+fn proving_with_a_sink_signs_the_same_artifact_and_hands_over_the_trace() {
+    let (model_bin, input_bin) = lr_fixture();
+    let mut events: Vec<TraceEventV0> = Vec::new();
+    let proved =
+        prove_lr_v0_with_sink([7u8; 32], [9u8; 32], &model_bin, &input_bin, &mut events).unwrap();
+    assert_eq!(
+        proved,
+        prove_lr_v0([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap()
+    );
+    assert_eq!(events, lr_trace(&model_bin, &input_bin).unwrap());
+
+    let mut events: Vec<TraceEventV0> = Vec::new();
+    let (_, artifact_bin) = prove_lr_v1_with_sink::<NoopAttester>(
+        [7u8; 32],
+        [9u8; 32],
+        &model_bin,
+        &input_bin,
+        &mut events,
+    )
+    .unwrap();
+    let (_, expected_bin) =
+        prove_lr_v1_with_attester::<NoopAttester>([7u8; 32], [9u8; 32], &model_bin, &input_bin)
+            .unwrap();
+    assert_eq!(
+        ProofArtifactV1::decode_bin(&artifact_bin)
+            .unwrap()
+            .trace_root,
+        ProofArtifactV1::decode_bin(&expected_bin)
+            .unwrap()
+            .trace_root
+    );
+    assert_eq!(events, lr_trace(&model_bin, &input_bin).unwrap());
+
+    let (model_bin, input_bin) = mlp_fixture();
+    let mut events: Vec<TraceEventV0> = Vec::new();
+    let proved =
+        prove_mlp_v1_with_sink([7u8; 32], [9u8; 32], &model_bin, &input_bin, &mut events).unwrap();
+    assert_eq!(
+        proved,
+        prove_mlp_v1([7u8; 32], [9u8; 32], &model_bin, &input_bin).unwrap()
+    );
+    assert_eq!(events, mlp_trace(&model_bin, &input_bin).unwrap());
+}
+
+#[test]
+fn every_replayed_version_streams_its_trace_while_proving() {
+    let key = [9u8; 32];
+    let issuance = IssuanceV3 {
+        issued_at: 1_000,
+        expires_at: None,
+        nonce: [3u8; 32],
+    };
+    let salts = CommitmentSaltsV0 {
+        model: [1u8; 32],
+        input: [2u8; 32],
+        output: [3u8; 32],
+        trace: [4u8; 32],
+    };
+
+    let (model_bin, input_bin) = lr_fixture();
+    let (m, i) = (&model_bin[..], &input_bin[..]);
+    type Proved = Result<(Vec<u8>, Vec<u8>), verifai_runtime::VerifaiError>;
+    let streamed: Vec<(Proved, Proved, Vec<TraceEventV0>)> = vec![
+        {
+            let mut events = Vec::new();
+            let p = prove_lr_v3_with_sink::<NoopAttester>(
+                [7u8; 32],
+                key,
+                m,
+                i,
+                &issuance,
+                None,
+                &mut events,
+            );
+            let q =
+                prove_lr_v3_with_attester::<NoopAttester>([7u8; 32], key, m, i, &issuance, None);
+            (p, q, events)
+        },
+        {
+            let mut events = Vec::new();
+            let p = prove_lr_v4_with_sink::<NoopAttester>([7u8; 32], key, m, i, &mut events);
+            let q = prove_lr_v4_with_attester::<NoopAttester>([7u8; 32], key, m, i);
+            (p, q, events)
+        },
+        {
+            let mut events = Vec::new();
+            let p = prove_lr_v5_with_sink::<NoopAttester>([7u8; 32], key, m, i, &mut events);
+            let q = prove_lr_v5_with_attester::<NoopAttester>([7u8; 32], key, m, i);
+            (p, q, events)
+        },
+        {
+            let mut events = Vec::new();
+            let p = prove_lr_v6_with_sink::<NoopAttester>(
+                [7u8; 32],
+                key,
+                HashAlg::Blake3,
+                m,
+                i,
+                &mut events,
+            );
+            let q =
+                prove_lr_v6_with_attester::<NoopAttester>([7u8; 32], key, HashAlg::Blake3, m, i);
+            (p, q, events)
+        },
+        {
+            let mut events = Vec::new();
+            let p =
+                prove_lr_v7_with_sink::<NoopAttester>([7u8; 32], key, m, i, &salts, &mut events);
+            let q = prove_lr_v7_with_attester::<NoopAttester>([7u8; 32], key, m, i, &salts);
+            (p, q, events)
+        },
+    ];
+    for (proved, expected, events) in streamed {
+        assert_eq!(proved.unwrap(), expected.unwrap());
+        assert_eq!(events, lr_trace(m, i).unwrap());
+    }
+
+    let (model_bin, input_bin) = mlp_fixture();
+    let (m, i) = (&model_bin[..], &input_bin[..]);
+    let streamed: Vec<(Proved, Proved, Vec<TraceEventV0>)> = vec![
+        {
+            let mut events = Vec::new();
+            let p = prove_mlp_v3_with_sink([7u8; 32], key, m, i, &issuance, None, &mut events);
+            (
+                p,
+                prove_mlp_v3([7u8; 32], key, m, i, &issuance, None),
+                events,
+            )
+        },
+        {
+            let mut events = Vec::new();
+            let p = prove_mlp_v4_with_sink([7u8; 32], key, m, i, &mut events);
+            (p, prove_mlp_v4([7u8; 32], key, m, i), events)
+        },
+        {
+            let mut events = Vec::new();
+            let p = prove_mlp_v5_with_sink([7u8; 32], key, m, i, &mut events);
+            (p, prove_mlp_v5([7u8; 32], key, m, i), events)
+        },
+        {
+            let mut events = Vec::new();
+            let p = prove_mlp_v6_with_sink([7u8; 32], key, HashAlg::Blake3, m, i, &mut events);
+            (
+                p,
+                prove_mlp_v6([7u8; 32], key, HashAlg::Blake3, m, i),
+                events,
+            )
+        },
+        {
+            let mut events = Vec::new();
+            let p = prove_mlp_v7_with_sink([7u8; 32], key, m, i, &salts, &mut events);
+            (p, prove_mlp_v7([7u8; 32], key, m, i, &salts), events)
+        },
+    ];
+    for (proved, expected, events) in streamed {
+        assert_eq!(proved.unwrap(), expected.unwrap());
+        assert_eq!(events, mlp_trace(m, i).unwrap());
+    }
+}
+
+#[test]
+fn one_run_can_write_and_keep_the_trace() {
+    let (model_bin, input_bin) = mlp_fixture();
+    let mut kept: Vec<TraceEventV0> = Vec::new();
+    let mut sink = Tee(TraceFileWriter::new(Vec::new()), &mut kept);
+    prove_mlp_v1_with_sink([7u8; 32], [9u8; 32], &model_bin, &input_bin, &mut sink).unwrap();
+    let file = sink.0.finish().unwrap();
+
+    let events = mlp_trace(&model_bin, &input_bin).unwrap();
+    assert_eq!(kept, events);
+    assert_eq!(file, TraceV0 { events }.encode_bin());
+}
+
+#[test]
+fn runners_stream_into_a_sink_without_proving() {
+    let (model_bin, input_bin) = lr_fixture();
+    let mut file = TraceFileWriter::new(Vec::new());
+    run_lr_v0_into(&model_bin, &input_bin, &mut file).unwrap();
+    let trace = TraceV0::decode_bin(&file.finish().unwrap()).unwrap();
+    assert_eq!(trace.events, lr_trace(&model_bin, &input_bin).unwrap());
+}
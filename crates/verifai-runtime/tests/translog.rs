@@ -0,0 +1,121 @@
+use std::fs;
+use std::path::PathBuf;
+
+use verifai_runtime::{
+    verify_log_consistency_v0, verify_log_inclusion_v0, TransparencyLog, VerifaiError,
+};
+
+const LOG_KEY: [u8; 32] = [3u8; 32];
+
+fn fresh_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("verifai-translog-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn artifact(i: u8) -> Vec<u8> {
+    vec![i; 40]
+}
+
+#[test]
+fn append_is_persistent_and_idempotent() {
+    let dir = fresh_dir("persist");
+    let mut log = TransparencyLog::open(&dir).unwrap();
+    assert_eq!(log.append(&artifact(1)).unwrap(), 0);
+    assert_eq!(log.append(&artifact(2)).unwrap(), 1);
+    assert_eq!(log.append(&artifact(1)).unwrap(), 0);
+    let root = log.root_at(2).unwrap();
+
+    let reopened = TransparencyLog::open(&dir).unwrap();
+    assert_eq!(reopened.size(), 2);
+    assert_eq!(reopened.root_at(2).unwrap(), root);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn inclusion_against_pinned_head() {
+    let dir = fresh_dir("inclusion");
+    let mut log = TransparencyLog::open(&dir).unwrap();
+    for i in 0..5 {
+        log.append(&artifact(i)).unwrap();
+    }
+    let head = log.tree_head(LOG_KEY).unwrap();
+    let head_bin = head.encode_bin();
+
+    // entries appended after the head was pinned do not affect its proofs
+    log.append(&artifact(9)).unwrap();
+
+    let proof = log.inclusion_proof(&artifact(3), head.tree_size).unwrap();
+    let index = verify_log_inclusion_v0(
+        &artifact(3),
+        &proof.encode_bin(),
+        &head_bin,
+        head.log_pubkey,
+    )
+    .unwrap();
+    assert_eq!(index, 3);
+
+    assert_eq!(
+        verify_log_inclusion_v0(
+            &artifact(4),
+            &proof.encode_bin(),
+            &head_bin,
+            head.log_pubkey
+        ),
+        Err(VerifaiError::InclusionProofInvalid)
+    );
+    assert_eq!(
+        verify_log_inclusion_v0(&artifact(3), &proof.encode_bin(), &head_bin, [0u8; 32]),
+        Err(VerifaiError::SignatureInvalid)
+    );
+    assert_eq!(
+        log.inclusion_proof(&artifact(9), head.tree_size),
+        Err(VerifaiError::NotInLog)
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn consistency_between_heads() {
+    let dir = fresh_dir("consistency");
+    let mut log = TransparencyLog::open(&dir).unwrap();
+    for i in 0..3 {
+        log.append(&artifact(i)).unwrap();
+    }
+    let old = log.tree_head(LOG_KEY).unwrap();
+    for i in 3..7 {
+        log.append(&artifact(i)).unwrap();
+    }
+    let new = log.tree_head(LOG_KEY).unwrap();
+
+    let proof = log.consistency_proof(old.tree_size, new.tree_size).unwrap();
+    verify_log_consistency_v0(
+        &old.encode_bin(),
+        &new.encode_bin(),
+        &proof.encode_bin(),
+        None,
+    )
+    .unwrap();
+
+    // a log that rewrote entry 2 cannot prove consistency with the old head
+    let forked_dir = fresh_dir("consistency-fork");
+    let mut forked = TransparencyLog::open(&forked_dir).unwrap();
+    for i in [0, 1, 9, 3, 4, 5, 6] {
+        forked.append(&artifact(i)).unwrap();
+    }
+    let forked_new = forked.tree_head(LOG_KEY).unwrap();
+    let forked_proof = forked
+        .consistency_proof(old.tree_size, forked_new.tree_size)
+        .unwrap();
+    assert_eq!(
+        verify_log_consistency_v0(
+            &old.encode_bin(),
+            &forked_new.encode_bin(),
+            &forked_proof.encode_bin(),
+            None
+        ),
+        Err(VerifaiError::ConsistencyProofInvalid)
+    );
+    fs::remove_dir_all(&dir).unwrap();
+    fs::remove_dir_all(&forked_dir).unwrap();
+}
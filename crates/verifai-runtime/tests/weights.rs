@@ -0,0 +1,188 @@
+use std::io::{Cursor, Write};
+
+use verifai_core::model_bin::{LogisticModelV0, MlpModelV1};
+use verifai_runtime::{load_npy, load_npz, load_safetensors, VerifaiError};
+
+fn safetensors_bytes(tensors: &[(&str, &str, &[usize], Vec<u8>)]) -> Vec<u8> {
+    let mut header = serde_json::Map::new();
+    let mut data = Vec::new();
+    for (name, dtype, shape, raw) in tensors {
+        let begin = data.len();
+        data.extend_from_slice(raw);
+        header.insert(
+            name.to_string(),
+            serde_json::json!({
+                "dtype": dtype,
+                "shape": shape,
+                "data_offsets": [begin, data.len()],
+            }),
+        );
+    }
+    let header = serde_json::to_vec(&header).unwrap();
+    let mut out = Vec::new();
+    out.extend_from_slice(&(header.len() as u64).to_le_bytes());
+    out.extend_from_slice(&header);
+    out.extend_from_slice(&data);
+    out
+}
+
+fn npy_bytes(descr: &str, shape: &[usize], raw: Vec<u8>) -> Vec<u8> {
+    let dims: Vec<String> = shape.iter().map(|d| d.to_string()).collect();
+    let shape = match dims.len() {
+        1 => format!("({},)", dims[0]),
+        _ => format!("({})", dims.join(", ")),
+    };
+    let mut header = format!("{{'descr': '{descr}', 'fortran_order': False, 'shape': {shape}, }}");
+    while (10 + header.len() + 1) % 64 != 0 {
+        header.push(' ');
+    }
+    header.push('\n');
+    let mut out = Vec::new();
+    out.extend_from_slice(b"\x93NUMPY\x01\x00");
+    out.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    out.extend_from_slice(header.as_bytes());
+    out.extend_from_slice(&raw);
+    out
+}
+
+fn f64_bytes(v: &[f64]) -> Vec<u8> {
+    v.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+fn f32_bytes(v: &[f32]) -> Vec<u8> {
+    v.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+fn sample_mlp() -> MlpModelV1 {
+    MlpModelV1 {
+        input_dim: 4,
+        hidden_size: 2,
+        w1: vec![0.1, -0.2, 0.3, 0.4, -0.1, 0.5, 0.2, -0.3],
+        b1: vec![0.0, -0.1],
+        w2: vec![0.2, -0.4],
+        b2: 0.05,
+    }
+}
+
+#[test]
+fn safetensors_f64_maps_to_logistic_model() {
+    let bytes = safetensors_bytes(&[
+        ("weights", "F64", &[4], f64_bytes(&[0.1, -0.2, 0.3, 0.4])),
+        ("bias", "F64", &[], f64_bytes(&[-0.05])),
+    ]);
+    let model = load_safetensors(&bytes).unwrap().to_logistic_v0().unwrap();
+    assert_eq!(
+        model,
+        LogisticModelV0 {
+            weights: vec![0.1, -0.2, 0.3, 0.4],
+            bias: -0.05,
+        }
+    );
+}
+
+#[test]
+fn safetensors_f32_mlp_is_widened_and_shape_checked() {
+    let m = sample_mlp();
+    let w1: Vec<f32> = m.w1.iter().map(|&v| v as f32).collect();
+    let bytes = safetensors_bytes(&[
+        ("w1", "F32", &[2, 4], f32_bytes(&w1)),
+        ("b1", "F32", &[2], f32_bytes(&[0.0, -0.1])),
+        ("w2", "F32", &[1, 2], f32_bytes(&[0.2, -0.4])),
+        ("b2", "F32", &[1], f32_bytes(&[0.05])),
+    ]);
+    let model = load_safetensors(&bytes).unwrap().to_mlp_v1().unwrap();
+    assert_eq!(model.input_dim, 4);
+    assert_eq!(model.hidden_size, 2);
+    assert_eq!(model.w1[1], -0.2_f32 as f64);
+
+    let bad = safetensors_bytes(&[
+        ("w1", "F32", &[2, 4], f32_bytes(&w1)),
+        ("b1", "F32", &[3], f32_bytes(&[0.0, -0.1, 0.0])),
+        ("w2", "F32", &[2], f32_bytes(&[0.2, -0.4])),
+        ("b2", "F32", &[], f32_bytes(&[0.05])),
+    ]);
+    assert_eq!(
+        load_safetensors(&bad).unwrap().to_mlp_v1(),
+        Err(VerifaiError::DimensionMismatch)
+    );
+}
+
+#[test]
+fn safetensors_rejects_out_of_range_offsets() {
+    let mut bytes = safetensors_bytes(&[("bias", "F64", &[], f64_bytes(&[1.0]))]);
+    bytes.truncate(bytes.len() - 1);
+    assert_eq!(load_safetensors(&bytes), Err(VerifaiError::WeightsDecode));
+}
+
+#[test]
+fn npy_reads_c_order_and_rejects_fortran_order() {
+    let t = load_npy(&npy_bytes("<f8", &[2, 2], f64_bytes(&[1.0, 2.0, 3.0, 4.0]))).unwrap();
+    assert_eq!(t.shape, vec![2, 2]);
+    assert_eq!(t.data, vec![1.0, 2.0, 3.0, 4.0]);
+
+    let mut fortran = npy_bytes("<f8", &[2, 2], f64_bytes(&[1.0, 2.0, 3.0, 4.0]));
+    let pos = fortran.windows(5).position(|w| w == b"False").unwrap();
+    fortran[pos..pos + 5].copy_from_slice(b"True ");
+    assert_eq!(load_npy(&fortran), Err(VerifaiError::WeightsDecode));
+
+    let big_endian = npy_bytes(">f8", &[1], f64_bytes(&[1.0]));
+    assert_eq!(load_npy(&big_endian), Err(VerifaiError::WeightsDecode));
+}
+
+#[test]
+fn npz_maps_to_mlp_model_matching_test_vector() {
+    let m = sample_mlp();
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let members = [
+        ("w1.npy", npy_bytes("<f8", &[2, 4], f64_bytes(&m.w1))),
+        ("b1.npy", npy_bytes("<f8", &[2], f64_bytes(&m.b1))),
+        ("w2.npy", npy_bytes("<f8", &[2], f64_bytes(&m.w2))),
+        ("b2.npy", npy_bytes("<f8", &[], f64_bytes(&[m.b2]))),
+    ];
+    for (name, bytes) in members {
+        zip.start_file(name, zip::write::FileOptions::default())
+            .unwrap();
+        zip.write_all(&bytes).unwrap();
+    }
+    let archive = zip.finish().unwrap().into_inner();
+
+    let model = load_npz(&archive).unwrap().to_mlp_v1().unwrap();
+    assert_eq!(model, m);
+
+    let expected = std::fs::read(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../test-vectors/mlp-case-1/model.bin"),
+    )
+    .unwrap();
+    assert_eq!(model.encode_bin(), expected);
+}
+
+/// Patches the uncompressed size of the archive's only member in both zip
+/// headers.
+fn with_claimed_size(mut archive: Vec<u8>, size: u32) -> Vec<u8> {
+    let local = archive.windows(4).position(|w| w == b"PK\x03\x04").unwrap();
+    archive[local + 22..local + 26].copy_from_slice(&size.to_le_bytes());
+    let central = archive.windows(4).position(|w| w == b"PK\x01\x02").unwrap();
+    archive[central + 24..central + 28].copy_from_slice(&size.to_le_bytes());
+    archive
+}
+
+#[test]
+fn npz_rejects_member_sizes_the_archive_cannot_hold() {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    let member = npy_bytes("<f8", &[1], f64_bytes(&[1.0]));
+    zip.start_file("b2.npy", options).unwrap();
+    zip.write_all(&member).unwrap();
+    let archive = zip.finish().unwrap().into_inner();
+    assert!(load_npz(&archive).is_ok());
+
+    // a few hundred bytes claiming a 4 GB member must not allocate it
+    let huge = with_claimed_size(archive.clone(), 0xFFFF_FFF0);
+    assert_eq!(load_npz(&huge), Err(VerifaiError::WeightsDecode));
+
+    // nor may the header understate what is read
+    let short = with_claimed_size(archive, member.len() as u32 - 8);
+    assert_eq!(load_npz(&short), Err(VerifaiError::WeightsDecode));
+}
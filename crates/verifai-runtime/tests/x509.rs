@@ -0,0 +1,124 @@
+use ed25519_dalek::SigningKey;
+use std::path::PathBuf;
+use verifai_core::signer_bundle::SignerBundleV0;
+use verifai_runtime::{native_artifact_bin, pem_certificates, verify_signer_chain, VerifaiError};
+
+// 2024-01-01T00:00:00Z; the fixtures are valid from 2020 through 2099
+const NOW: i64 = 1_704_067_200;
+
+fn pem(name: &str) -> Vec<Vec<u8>> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test-vectors/x509")
+        .join(name);
+    pem_certificates(&std::fs::read(path).unwrap()).unwrap()
+}
+
+fn signer_pubkey() -> [u8; 32] {
+    SigningKey::from_bytes(&[9u8; 32])
+        .verifying_key()
+        .to_bytes()
+}
+
+#[test]
+fn chain_to_trusted_root_verifies() {
+    let chain = pem("chain.pem");
+    assert_eq!(chain.len(), 2);
+    let identity = verify_signer_chain(&chain, &pem("root.pem"), signer_pubkey(), NOW).unwrap();
+    assert_eq!(identity.subject, "O=VerifAI Test, CN=prover-1");
+    assert_eq!(identity.root_subject, "O=VerifAI Test, CN=Test Root CA");
+
+    // a chain that already ends in the trusted root is accepted as well
+    let mut full = chain;
+    full.extend(pem("root.pem"));
+    assert!(verify_signer_chain(&full, &pem("root.pem"), signer_pubkey(), NOW).is_ok());
+}
+
+#[test]
+fn chain_rejects_wrong_key_usage_and_validity() {
+    let chain = pem("chain.pem");
+    let roots = pem("root.pem");
+
+    assert_eq!(
+        verify_signer_chain(&chain, &roots, [1u8; 32], NOW),
+        Err(VerifaiError::KeyUsageInvalid)
+    );
+    assert_eq!(
+        verify_signer_chain(
+            &pem("chain-no-digital-signature.pem"),
+            &roots,
+            signer_pubkey(),
+            NOW
+        ),
+        Err(VerifaiError::KeyUsageInvalid)
+    );
+    // a leaf that is itself a CA could certify other signers
+    assert_eq!(
+        verify_signer_chain(&pem("chain-leaf-ca.pem"), &roots, signer_pubkey(), NOW),
+        Err(VerifaiError::KeyUsageInvalid)
+    );
+    // 2019-01-01 and 2100-01-01
+    for t in [1_546_300_800, 4_102_444_800] {
+        assert_eq!(
+            verify_signer_chain(&chain, &roots, signer_pubkey(), t),
+            Err(VerifaiError::CertificateExpired)
+        );
+    }
+}
+
+#[test]
+fn chain_rejects_untrusted_or_broken_paths() {
+    let chain = pem("chain.pem");
+
+    // same root name, different key
+    assert_eq!(
+        verify_signer_chain(&chain, &pem("other-root.pem"), signer_pubkey(), NOW),
+        Err(VerifaiError::UntrustedChain)
+    );
+    // leaf alone does not reach the root
+    assert_eq!(
+        verify_signer_chain(&chain[..1], &pem("root.pem"), signer_pubkey(), NOW),
+        Err(VerifaiError::UntrustedChain)
+    );
+
+    // a critical extension the verifier does not enforce
+    assert_eq!(
+        verify_signer_chain(
+            &pem("chain-unknown-critical.pem"),
+            &pem("root.pem"),
+            signer_pubkey(),
+            NOW
+        ),
+        Err(VerifaiError::CertificateInvalid)
+    );
+
+    // flip a byte of the leaf's signature value (the last bytes of the DER)
+    let mut tampered = chain.clone();
+    let last = tampered[0].len() - 1;
+    tampered[0][last] ^= 1;
+    assert_eq!(
+        verify_signer_chain(&tampered, &pem("root.pem"), signer_pubkey(), NOW),
+        Err(VerifaiError::CertificateInvalid)
+    );
+}
+
+#[test]
+fn bundles_unwrap_one_level_only() {
+    let artifact_bin = std::fs::read(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../test-vectors/case-1/expected_artifact.bin"),
+    )
+    .unwrap();
+    let bundle = SignerBundleV0 {
+        artifact: artifact_bin.clone(),
+        cert_chain: pem("chain.pem"),
+    }
+    .encode_bin();
+    assert_eq!(native_artifact_bin(&bundle).unwrap(), artifact_bin);
+
+    let nested = SignerBundleV0 {
+        artifact: bundle,
+        cert_chain: Vec::new(),
+    }
+    .encode_bin();
+    assert_eq!(native_artifact_bin(&nested), Err(VerifaiError::CoreDecode));
+}
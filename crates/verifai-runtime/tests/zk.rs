@@ -0,0 +1,108 @@
+#![cfg(feature = "zk")]
+
+use std::sync::OnceLock;
+
+use verifai_core::artifact_bin::ProofArtifactV10;
+use verifai_core::model_bin::{
+    InputV0, LogisticModelV0, QuantizedInputV0, QuantizedLogisticModelV0, QuantizedOutputV0,
+};
+use verifai_runtime::{prove_lr_v10, verify_lr_v10, zk_lr_setup, VerifaiError};
+
+/// Setup is slow, so every test shares one key pair for 4-feature models.
+fn keys() -> &'static (Vec<u8>, Vec<u8>) {
+    static KEYS: OnceLock<(Vec<u8>, Vec<u8>)> = OnceLock::new();
+    KEYS.get_or_init(|| zk_lr_setup(4).unwrap())
+}
+
+fn fixture() -> (Vec<u8>, Vec<u8>) {
+    let model = LogisticModelV0 {
+        weights: vec![0.1, -0.2, 0.3, 0.4],
+        bias: -0.05,
+    };
+    let input = InputV0 {
+        x: vec![1.0, 2.0, 3.0, -4.0],
+    };
+    (
+        QuantizedLogisticModelV0::quantize(&model)
+            .unwrap()
+            .encode_bin(),
+        QuantizedInputV0::quantize(&input).unwrap().encode_bin(),
+    )
+}
+
+#[test]
+fn zk_proof_verifies_without_model_or_input() {
+    let (pk, vk) = keys();
+    let (model_bin, input_bin) = fixture();
+    let (output_bin, artifact_bin) =
+        prove_lr_v10([7u8; 32], [9u8; 32], pk, &model_bin, &input_bin).unwrap();
+
+    let artifact = verify_lr_v10(&artifact_bin, vk, &output_bin).unwrap();
+    assert_eq!(artifact.model_hash, verifai_core::hash::sha256(&model_bin));
+    assert_eq!(artifact.input_hash, verifai_core::hash::sha256(&input_bin));
+
+    // 0.1 - 0.4 + 0.9 - 1.6 - 0.05, up to rounding the weights to 2^-16
+    let logit = QuantizedOutputV0::decode_bin(&output_bin).unwrap().logit;
+    let score = logit as f64 / 2f64.powi(32);
+    assert!((score - -1.05).abs() < 1e-3, "{score}");
+}
+
+#[test]
+fn zk_proof_rejects_tampering() {
+    let (pk, vk) = keys();
+    let (model_bin, input_bin) = fixture();
+    let (output_bin, artifact_bin) =
+        prove_lr_v10([7u8; 32], [9u8; 32], pk, &model_bin, &input_bin).unwrap();
+
+    // a re-signed artifact claiming another output
+    let other_output = QuantizedOutputV0 { logit: 1 << 32 }.encode_bin();
+    let mut forged = ProofArtifactV10::decode_bin(&artifact_bin).unwrap();
+    forged.output_hash = verifai_core::hash::sha256(&other_output);
+    forged.sign_detached([9u8; 32]).unwrap();
+    assert_eq!(
+        verify_lr_v10(&forged.encode_bin(), vk, &other_output),
+        Err(VerifaiError::ZkProofInvalid)
+    );
+
+    // or another model
+    let mut forged = ProofArtifactV10::decode_bin(&artifact_bin).unwrap();
+    forged.model_hash[0] ^= 0x01;
+    forged.sign_detached([9u8; 32]).unwrap();
+    assert_eq!(
+        verify_lr_v10(&forged.encode_bin(), vk, &output_bin),
+        Err(VerifaiError::ZkProofInvalid)
+    );
+
+    let mut bad_sig = artifact_bin.clone();
+    *bad_sig.last_mut().unwrap() ^= 0x01;
+    assert_eq!(
+        verify_lr_v10(&bad_sig, vk, &output_bin),
+        Err(VerifaiError::SignatureInvalid)
+    );
+    assert_eq!(
+        verify_lr_v10(&artifact_bin, vk, &other_output),
+        Err(VerifaiError::HashMismatch)
+    );
+}
+
+#[test]
+fn zk_proof_is_bound_to_its_circuit() {
+    let (pk, vk) = keys();
+    let (model_bin, input_bin) = fixture();
+    let (output_bin, artifact_bin) =
+        prove_lr_v10([7u8; 32], [9u8; 32], pk, &model_bin, &input_bin).unwrap();
+
+    let mut other_vk = vk.clone();
+    *other_vk.last_mut().unwrap() ^= 0x01;
+    assert_eq!(
+        verify_lr_v10(&artifact_bin, &other_vk, &output_bin),
+        Err(VerifaiError::CircuitMismatch)
+    );
+
+    // the proving key is for 4 features
+    let short = QuantizedInputV0 { x: vec![1, 2, 3] }.encode_bin();
+    assert_eq!(
+        prove_lr_v10([7u8; 32], [9u8; 32], pk, &model_bin, &short),
+        Err(VerifaiError::DimensionMismatch)
+    );
+}
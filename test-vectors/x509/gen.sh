@@ -0,0 +1,59 @@
+#!/bin/sh
+# Regenerates the X.509 fixtures. The leaf key is the Ed25519 key with seed
+# 0x09 * 32 used by the other vectors; CA keys are fresh on every run.
+set -e
+cd "$(dirname "$0")"
+tmp=$(mktemp -d)
+trap 'rm -rf "$tmp"' EXIT
+
+cat >"$tmp/ext.cnf" <<'CNF'
+[intermediate]
+basicConstraints = critical, CA:true, pathlen:0
+keyUsage = critical, keyCertSign, cRLSign
+[leaf]
+basicConstraints = critical, CA:false
+keyUsage = critical, digitalSignature
+[leaf_no_sign]
+basicConstraints = critical, CA:false
+keyUsage = critical, keyEncipherment
+[leaf_ca]
+basicConstraints = critical, CA:true
+keyUsage = critical, digitalSignature
+[leaf_critical]
+basicConstraints = critical, CA:false
+keyUsage = critical, digitalSignature
+1.3.6.1.4.1.55555.1 = critical, ASN1:UTF8String:must be understood
+CNF
+
+validity="-not_before 20200101000000Z -not_after 20991231235959Z"
+
+openssl genpkey -algorithm ed25519 -out "$tmp/root.key"
+openssl req -x509 -new -key "$tmp/root.key" -subj "/O=VerifAI Test/CN=Test Root CA" \
+    $validity -addext "basicConstraints=critical,CA:true" \
+    -addext "keyUsage=critical,keyCertSign,cRLSign" -out root.pem
+
+openssl genpkey -algorithm ec -pkeyopt ec_paramgen_curve:P-256 -out "$tmp/int.key"
+openssl req -new -key "$tmp/int.key" -subj "/O=VerifAI Test/CN=Test Signing CA" -out "$tmp/int.csr"
+openssl x509 -req -in "$tmp/int.csr" -CA root.pem -CAkey "$tmp/root.key" -set_serial 2 \
+    $validity -extfile "$tmp/ext.cnf" -extensions intermediate -out "$tmp/int.pem"
+
+# PKCS#8 wrapping of the raw Ed25519 seed
+printf '302e020100300506032b657004220420%s' \
+    0909090909090909090909090909090909090909090909090909090909090909 |
+    xxd -r -p | openssl pkey -inform der -out "$tmp/leaf.key"
+openssl req -new -key "$tmp/leaf.key" -subj "/O=VerifAI Test/CN=prover-1" -out "$tmp/leaf.csr"
+for ext in leaf leaf_no_sign leaf_ca leaf_critical; do
+    openssl x509 -req -in "$tmp/leaf.csr" -CA "$tmp/int.pem" -CAkey "$tmp/int.key" -set_serial 3 \
+        $validity -extfile "$tmp/ext.cnf" -extensions $ext -out "$tmp/$ext.pem"
+done
+
+cat "$tmp/leaf.pem" "$tmp/int.pem" >chain.pem
+cat "$tmp/leaf_no_sign.pem" "$tmp/int.pem" >chain-no-digital-signature.pem
+cat "$tmp/leaf_ca.pem" "$tmp/int.pem" >chain-leaf-ca.pem
+cat "$tmp/leaf_critical.pem" "$tmp/int.pem" >chain-unknown-critical.pem
+
+# an unrelated root with the same name, for negative tests
+openssl genpkey -algorithm ed25519 -out "$tmp/other.key"
+openssl req -x509 -new -key "$tmp/other.key" -subj "/O=VerifAI Test/CN=Test Root CA" \
+    $validity -addext "basicConstraints=critical,CA:true" \
+    -addext "keyUsage=critical,keyCertSign,cRLSign" -out other-root.pem